@@ -996,6 +996,8 @@ pub enum MacStmtStyle {
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct Local {
     pub id: NodeId,
+    /// The span of the `super` keyword in a `super let` statement, if any.
+    pub super_: Option<Span>,
     pub pat: P<Pat>,
     pub ty: Option<P<Ty>>,
     /// Initializer expression to set the value, if any.
@@ -1222,6 +1224,7 @@ pub fn precedence(&self) -> ExprPrecedence {
             ExprKind::Paren(..) => ExprPrecedence::Paren,
             ExprKind::Try(..) => ExprPrecedence::Try,
             ExprKind::Yield(..) => ExprPrecedence::Yield,
+            ExprKind::Yeet(..) => ExprPrecedence::Yeet,
             ExprKind::Err => ExprPrecedence::Err,
         }
     }
@@ -1396,6 +1399,10 @@ pub enum ExprKind {
     /// A `yield`, with an optional value to be yielded.
     Yield(Option<P<Expr>>),
 
+    /// A `do yeet` (aka `throw`/`fail`/`bail`/`raise`/whatever),
+    /// with an optional value to be returned.
+    Yeet(Option<P<Expr>>),
+
     /// Placeholder for an expression that wasn't syntactically well formed in some way.
     Err,
 }
@@ -1800,11 +1807,26 @@ pub struct AssocTyConstraint {
     pub span: Span,
 }
 
+impl AssocTyConstraint {
+    /// Whether this is a bound on the return type of an associated function,
+    /// e.g. `method(): Send` in `Trait<method(): Send>`.
+    pub fn is_return_type_notation(&self) -> bool {
+        match (&self.gen_args, &self.kind) {
+            (Some(GenericArgs::Parenthesized(args)), AssocTyConstraintKind::Bound { .. }) => {
+                args.inputs.is_empty() && matches!(args.output, FnRetTy::Default(_))
+            }
+            _ => false,
+        }
+    }
+}
+
 /// The kinds of an `AssocTyConstraint`.
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub enum AssocTyConstraintKind {
     /// E.g., `A = Bar` in `Foo<A = Bar>`.
     Equality { ty: P<Ty> },
+    /// E.g., `N = 3` in `Foo<N = 3>`, where `N` is an associated constant.
+    ConstEquality { value: AnonConst },
     /// E.g. `A: TraitA + TraitB` in `Foo<A: TraitA + TraitB>`.
     Bound { bounds: GenericBounds },
 }
@@ -442,6 +442,9 @@ pub fn noop_visit_ty_constraint<T: MutVisitor>(
         AssocTyConstraintKind::Equality { ref mut ty } => {
             vis.visit_ty(ty);
         }
+        AssocTyConstraintKind::ConstEquality { ref mut value } => {
+            vis.visit_anon_const(value);
+        }
         AssocTyConstraintKind::Bound { ref mut bounds } => {
             visit_bounds(bounds, vis);
         }
@@ -571,8 +574,9 @@ pub fn noop_visit_parenthesized_parameter_data<T: MutVisitor>(
 }
 
 pub fn noop_visit_local<T: MutVisitor>(local: &mut P<Local>, vis: &mut T) {
-    let Local { id, pat, ty, init, span, attrs, tokens } = local.deref_mut();
+    let Local { id, super_, pat, ty, init, span, attrs, tokens } = local.deref_mut();
     vis.visit_id(id);
+    visit_opt(super_, |sp| vis.visit_span(sp));
     vis.visit_pat(pat);
     visit_opt(ty, |ty| vis.visit_ty(ty));
     visit_opt(init, |init| vis.visit_expr(init));
@@ -1357,6 +1361,9 @@ pub fn noop_visit_expr<T: MutVisitor>(
         ExprKind::Yield(expr) => {
             visit_opt(expr, |expr| vis.visit_expr(expr));
         }
+        ExprKind::Yeet(expr) => {
+            visit_opt(expr, |expr| vis.visit_expr(expr));
+        }
         ExprKind::Try(expr) => vis.visit_expr(expr),
         ExprKind::TryBlock(body) => vis.visit_block(body),
         ExprKind::Lit(_) | ExprKind::Err => {}
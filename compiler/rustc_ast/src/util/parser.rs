@@ -247,6 +247,7 @@ pub enum ExprPrecedence {
     Continue,
     Ret,
     Yield,
+    Yeet,
 
     Range,
 
@@ -300,6 +301,7 @@ pub fn order(self) -> i8 {
             ExprPrecedence::Continue |
             ExprPrecedence::Ret |
             ExprPrecedence::Yield => PREC_JUMP,
+            ExprPrecedence::Yeet => PREC_JUMP,
 
             // `Range` claims to have higher precedence than `Assign`, but `x .. x = x` fails to
             // parse, instead of parsing as `(x .. x) = x`.  Giving `Range` a lower precedence
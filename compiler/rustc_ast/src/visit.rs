@@ -489,6 +489,9 @@ pub fn walk_assoc_ty_constraint<'a, V: Visitor<'a>>(
         AssocTyConstraintKind::Equality { ref ty } => {
             visitor.visit_ty(ty);
         }
+        AssocTyConstraintKind::ConstEquality { ref value } => {
+            visitor.visit_anon_const(value);
+        }
         AssocTyConstraintKind::Bound { ref bounds } => {
             walk_list!(visitor, visit_param_bound, bounds);
         }
@@ -869,6 +872,9 @@ pub fn walk_expr<'a, V: Visitor<'a>>(visitor: &mut V, expression: &'a Expr) {
         ExprKind::Yield(ref optional_expression) => {
             walk_list!(visitor, visit_expr, optional_expression);
         }
+        ExprKind::Yeet(ref optional_expression) => {
+            walk_list!(visitor, visit_expr, optional_expression);
+        }
         ExprKind::Try(ref subexpression) => visitor.visit_expr(subexpression),
         ExprKind::TryBlock(ref body) => visitor.visit_block(body),
         ExprKind::Lit(_) | ExprKind::Err => {}
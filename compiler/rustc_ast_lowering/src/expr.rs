@@ -2,6 +2,7 @@
 
 use rustc_ast::attr;
 use rustc_ast::ptr::P as AstP;
+use rustc_ast::visit::{self, Visitor};
 use rustc_ast::*;
 use rustc_data_structures::stack::ensure_sufficient_stack;
 use rustc_data_structures::thin_vec::ThinVec;
@@ -108,9 +109,9 @@ pub(super) fn lower_expr_mut(&mut self, e: &Expr) -> hir::Expr<'hir> {
                                 else_opt.as_deref(),
                             )
                         }
-                        _ => self.lower_expr_if(cond, then, else_opt.as_deref()),
+                        _ => self.lower_expr_if(e.span, cond, then, else_opt.as_deref()),
                     },
-                    _ => self.lower_expr_if(cond, then, else_opt.as_deref()),
+                    _ => self.lower_expr_if(e.span, cond, then, else_opt.as_deref()),
                 },
                 ExprKind::While(ref cond, ref body, opt_label) => self
                     .with_loop_scope(e.id, |this| {
@@ -250,6 +251,7 @@ pub(super) fn lower_expr_mut(&mut self, e: &Expr) -> hir::Expr<'hir> {
                 ExprKind::Yield(ref opt_expr) => self.lower_expr_yield(e.span, opt_expr.as_deref()),
                 ExprKind::Err => hir::ExprKind::Err,
                 ExprKind::Try(ref sub_expr) => self.lower_expr_try(e.span, sub_expr),
+                ExprKind::Yeet(ref sub_expr) => self.lower_expr_yeet(e.span, sub_expr.as_deref()),
                 ExprKind::Paren(ref ex) => {
                     let mut ex = self.lower_expr_mut(ex);
                     // Include parens in span, but only if it is a super-span.
@@ -438,10 +440,14 @@ fn lower_expr_let(&mut self, span: Span, pat: &Pat, scrutinee: &Expr) -> hir::Ex
 
     fn lower_expr_if(
         &mut self,
+        span: Span,
         cond: &Expr,
         then: &Block,
         else_opt: Option<&Expr>,
     ) -> hir::ExprKind<'hir> {
+        if let Some(operands) = let_chain_operands(cond) {
+            return self.lower_expr_if_let_chain(span, &operands, then, else_opt);
+        }
         macro_rules! make_if {
             ($opt:expr) => {{
                 let cond = self.lower_expr(cond);
@@ -464,8 +470,6 @@ fn lower_expr_if_let(
         then: &Block,
         else_opt: Option<&Expr>,
     ) -> hir::ExprKind<'hir> {
-        // FIXME(#53667): handle lowering of && and parens.
-
         // `_ => else_block` where `else_block` is `{}` if there's `None`:
         let else_pat = self.pat_wild(span);
         let (else_expr, contains_else_clause) = match else_opt {
@@ -485,6 +489,80 @@ fn lower_expr_if_let(
         hir::ExprKind::Match(scrutinee, arena_vec![self; then_arm, else_arm], desugar)
     }
 
+    /// Lowers an `if` expression whose condition is a `let` chain, like
+    /// `if let Some(x) = a && x > 0 && let Ok(y) = b { <then> } else { <else> }`, into:
+    /// ```rust
+    /// {
+    ///     match a {
+    ///         Some(x) => if x > 0 {
+    ///             match b {
+    ///                 Ok(y) => break { <then> },
+    ///                 _ => {}
+    ///             }
+    ///         },
+    ///         _ => {}
+    ///     }
+    ///     { <else> }
+    /// }
+    /// ```
+    /// where the `break` leaves the outer block. The bindings of each `let` are in scope in the
+    /// rest of the chain and in `<then>`, but not in `<else>`. The temporaries of a `let`
+    /// scrutinee live until the end of the `if`, while those of a `bool` operand are dropped once
+    /// it has been evaluated, like those of the condition of an `if`.
+    fn lower_expr_if_let_chain(
+        &mut self,
+        span: Span,
+        operands: &[&Expr],
+        then: &Block,
+        else_opt: Option<&Expr>,
+    ) -> hir::ExprKind<'hir> {
+        let block_hir_id = self.next_id();
+
+        let then_expr = self.lower_block_expr(then);
+        let destination = hir::Destination { label: None, target_id: Ok(block_hir_id) };
+        let expr_break = hir::ExprKind::Break(destination, Some(self.arena.alloc(then_expr)));
+        let mut chain = self.expr(then.span, expr_break, ThinVec::new());
+        for operand in operands.iter().rev() {
+            let inner = self.arena.alloc(chain);
+            let kind = match operand.kind {
+                ExprKind::Let(ref pat, ref scrutinee) => {
+                    let scrutinee = self.lower_expr(scrutinee);
+                    let then_pat = self.lower_pat(pat);
+                    let then_arm = self.arm(then_pat, inner);
+                    let else_span =
+                        self.mark_span_with_reason(DesugaringKind::LetChain, operand.span, None);
+                    let else_pat = self.pat_wild(else_span);
+                    let else_expr = self.expr_block_empty(operand.span.shrink_to_hi());
+                    let else_arm = self.arm(else_pat, else_expr);
+                    let desugar = hir::MatchSource::IfLetDesugar { contains_else_clause: false };
+                    hir::ExprKind::Match(scrutinee, arena_vec![self; then_arm, else_arm], desugar)
+                }
+                _ => {
+                    let cond = self.lower_expr(operand);
+                    let then_block = self.block_expr(inner);
+                    let then_expr = self.expr_block(then_block, ThinVec::new());
+                    hir::ExprKind::If(cond, self.arena.alloc(then_expr), None)
+                }
+            };
+            chain = self.expr(operand.span.to(then.span), kind, ThinVec::new());
+        }
+        let chain = self.stmt_expr(chain.span, chain);
+
+        let else_expr = match else_opt {
+            None => self.expr_block_empty(span.shrink_to_hi()),
+            Some(els) => self.lower_expr(els),
+        };
+        let block = hir::Block {
+            stmts: arena_vec![self; chain],
+            expr: Some(else_expr),
+            hir_id: block_hir_id,
+            rules: hir::BlockCheckMode::DefaultBlock,
+            span,
+            targeted_by_break: true,
+        };
+        hir::ExprKind::Block(self.arena.alloc(block), None)
+    }
+
     fn lower_expr_while_in_loop_scope(
         &mut self,
         span: Span,
@@ -492,11 +570,13 @@ fn lower_expr_while_in_loop_scope(
         body: &Block,
         opt_label: Option<Label>,
     ) -> hir::ExprKind<'hir> {
-        // FIXME(#53667): handle lowering of && and parens.
-
         // Note that the block AND the condition are evaluated in the loop scope.
         // This is done to allow `break` from inside the condition of the loop.
 
+        if let Some(operands) = let_chain_operands(cond) {
+            return self.lower_expr_while_let_chain(span, cond, &operands, body, opt_label);
+        }
+
         // `_ => break`:
         let else_arm = {
             let else_pat = self.pat_wild(span);
@@ -560,6 +640,71 @@ fn lower_expr_while_in_loop_scope(
         )
     }
 
+    /// Lowers a `while` loop whose condition is a `let` chain, like
+    /// `while let Some(x) = a && x > 0 && let Ok(y) = b { <body> }`, into:
+    /// ```rust
+    /// [opt_ident]: loop {
+    ///     match a {
+    ///         Some(x) => match drop-temps { x > 0 } {
+    ///             true => match b {
+    ///                 Ok(y) => <body>,
+    ///                 _ => break,
+    ///             },
+    ///             _ => break,
+    ///         },
+    ///         _ => break,
+    ///     }
+    /// }
+    /// ```
+    fn lower_expr_while_let_chain(
+        &mut self,
+        span: Span,
+        cond: &Expr,
+        operands: &[&Expr],
+        body: &Block,
+        opt_label: Option<Label>,
+    ) -> hir::ExprKind<'hir> {
+        let mut chain = self.lower_block_expr(body);
+        for operand in operands.iter().rev() {
+            let inner = self.arena.alloc(chain);
+            let (then_pat, scrutinee, desugar) = match operand.kind {
+                ExprKind::Let(ref pat, ref scrutinee) => {
+                    let scrutinee = self.with_loop_condition_scope(|t| t.lower_expr(scrutinee));
+                    let pat = self.lower_pat(pat);
+                    (pat, scrutinee, hir::MatchSource::WhileLetDesugar)
+                }
+                _ => {
+                    let cond = self.with_loop_condition_scope(|this| this.lower_expr(operand));
+                    let span_block =
+                        self.mark_span_with_reason(DesugaringKind::CondTemporary, cond.span, None);
+                    let cond = self.expr_drop_temps(span_block, cond, ThinVec::new());
+                    let pat = self.pat_bool(operand.span, true);
+                    (pat, cond, hir::MatchSource::WhileDesugar)
+                }
+            };
+            let then_arm = self.arm(then_pat, inner);
+            let else_span =
+                self.mark_span_with_reason(DesugaringKind::LetChain, operand.span, None);
+            let else_pat = self.pat_wild(else_span);
+            let else_expr = self.expr_break(operand.span, ThinVec::new());
+            let else_arm = self.arm(else_pat, else_expr);
+            chain = self.expr_match(
+                operand.span.to(body.span),
+                scrutinee,
+                arena_vec![self; then_arm, else_arm],
+                desugar,
+            );
+        }
+
+        // `[opt_ident]: loop { ... }`
+        hir::ExprKind::Loop(
+            self.block_expr(self.arena.alloc(chain)),
+            opt_label,
+            hir::LoopSource::WhileLet,
+            span.with_hi(cond.span.hi()),
+        )
+    }
+
     /// Desugar `try { <stmts>; <expr> }` into `{ <stmts>; ::std::ops::Try::from_output(<expr>) }`,
     /// `try { <stmts>; }` into `{ <stmts>; ::std::ops::Try::from_output(()) }`
     /// and save the block id to use it as a break target for desugaring of the `?` operator.
@@ -705,7 +850,7 @@ pub(super) fn make_async_expr(
 
     /// Desugar `<expr>.await` into:
     /// ```rust
-    /// match <expr> {
+    /// match ::std::future::IntoFuture::into_future(<expr>) {
     ///     mut pinned => loop {
     ///         match unsafe { ::std::future::Future::poll(
     ///             <::std::pin::Pin>::new_unchecked(&mut pinned),
@@ -741,7 +886,21 @@ fn lower_expr_await(&mut self, await_span: Span, expr: &Expr) -> hir::ExprKind<'
             await_span,
             self.allow_gen_future.clone(),
         );
-        let expr = self.lower_expr(expr);
+        let into_future_span = self.mark_span_with_reason(
+            DesugaringKind::Await,
+            await_span,
+            self.allow_into_future.clone(),
+        );
+        let expr = self.lower_expr_mut(expr);
+        let expr_hir_id = expr.hir_id;
+
+        // `::std::future::IntoFuture::into_future(<expr>)`, so that anything implementing
+        // `IntoFuture` can be awaited, not just futures.
+        let into_future_expr = self.expr_call_lang_item_fn(
+            into_future_span,
+            hir::LangItem::IntoFutureIntoFuture,
+            arena_vec![self; expr],
+        );
 
         let pinned_ident = Ident::with_dummy_span(sym::pinned);
         let (pinned_pat, pinned_pat_hid) =
@@ -821,7 +980,7 @@ fn lower_expr_await(&mut self, await_span: Span, expr: &Expr) -> hir::ExprKind<'
             let unit = self.expr_unit(span);
             let yield_expr = self.expr(
                 span,
-                hir::ExprKind::Yield(unit, hir::YieldSource::Await { expr: Some(expr.hir_id) }),
+                hir::ExprKind::Yield(unit, hir::YieldSource::Await { expr: Some(expr_hir_id) }),
                 ThinVec::new(),
             );
             let yield_expr = self.arena.alloc(yield_expr);
@@ -850,10 +1009,14 @@ fn lower_expr_await(&mut self, await_span: Span, expr: &Expr) -> hir::ExprKind<'
         // mut pinned => loop { ... }
         let pinned_arm = self.arm(pinned_pat, loop_expr);
 
-        // match <expr> {
+        // match ::std::future::IntoFuture::into_future(<expr>) {
         //     mut pinned => loop { .. }
         // }
-        hir::ExprKind::Match(expr, arena_vec![self; pinned_arm], hir::MatchSource::AwaitDesugar)
+        hir::ExprKind::Match(
+            into_future_expr,
+            arena_vec![self; pinned_arm],
+            hir::MatchSource::AwaitDesugar,
+        )
     }
 
     fn lower_expr_closure(
@@ -1453,10 +1616,26 @@ fn lower_expr_for(
             self.arm(some_pat, assign)
         };
 
-        // `::std::option::Option::None => break`
+        // `::std::option::Option::None => break`, or with `#![feature(for_loop_break_value)]`
+        // and a `break value` targeting this loop,
+        // `::std::option::Option::None => break ::std::default::Default::default()`
         let break_arm = {
-            let break_expr =
-                self.with_loop_scope(e.id, |this| this.expr_break(e.span, ThinVec::new()));
+            let break_expr = if self.sess.features_untracked().for_loop_break_value
+                && for_loop_breaks_with_value(body, opt_label)
+            {
+                let default_expr = self.expr_call_lang_item_fn(
+                    desugared_span,
+                    hir::LangItem::DefaultDefault,
+                    &[],
+                );
+                self.with_loop_scope(e.id, |this| {
+                    let kind =
+                        hir::ExprKind::Break(this.lower_loop_destination(None), Some(default_expr));
+                    &*this.arena.alloc(this.expr(e.span, kind, ThinVec::new()))
+                })
+            } else {
+                self.with_loop_scope(e.id, |this| this.expr_break(e.span, ThinVec::new()))
+            };
             let pat = self.pat_none(e.span);
             self.arm(pat, break_expr)
         };
@@ -1664,6 +1843,44 @@ fn lower_expr_try(&mut self, span: Span, sub_expr: &Expr) -> hir::ExprKind<'hir>
         )
     }
 
+    /// Desugar `ExprKind::Yeet` from: `do yeet <expr>` into:
+    /// ```rust
+    /// // If there is an enclosing `try {...}`:
+    /// break 'catch_target FromResidual::from_residual(Yeet(expr)),
+    /// // Otherwise:
+    /// return FromResidual::from_residual(Yeet(expr)),
+    /// ```
+    /// `from_residual` is called through the `from_yeet` lang item, so that a return type that
+    /// can't absorb the residual is reported at the `do yeet` rather than inside of `core`.
+    fn lower_expr_yeet(&mut self, span: Span, sub_expr: Option<&Expr>) -> hir::ExprKind<'hir> {
+        // The expression (if present) or `()` otherwise.
+        let (yeeted_span, yeeted_expr) = if let Some(sub_expr) = sub_expr {
+            (sub_expr.span, self.lower_expr(sub_expr))
+        } else {
+            (self.mark_span_with_reason(DesugaringKind::YeetExpr, span, None), self.expr_unit(span))
+        };
+
+        let unstable_span = self.mark_span_with_reason(
+            DesugaringKind::YeetExpr,
+            span,
+            self.allow_try_trait.clone(),
+        );
+
+        let from_yeet_expr = self.wrap_in_try_constructor(
+            hir::LangItem::TryTraitFromYeet,
+            unstable_span,
+            yeeted_expr,
+            yeeted_span,
+        );
+
+        if let Some(catch_node) = self.catch_scopes.last().copied() {
+            let target_id = Ok(self.lower_node_id(catch_node));
+            hir::ExprKind::Break(hir::Destination { label: None, target_id }, Some(from_yeet_expr))
+        } else {
+            hir::ExprKind::Ret(Some(from_yeet_expr))
+        }
+    }
+
     // =========================================================================
     // Helper methods for building HIR.
     // =========================================================================
@@ -1866,3 +2083,65 @@ fn arm(&mut self, pat: &'hir hir::Pat<'hir>, expr: &'hir hir::Expr<'hir>) -> hir
         hir::Arm { hir_id: self.next_id(), pat, guard: None, span: expr.span, body: expr }
     }
 }
+
+/// Returns the operands of the condition of an `if` or `while` expression if it is a `let`
+/// chain, i.e. a chain of `&&`s of which at least one operand is a `let` expression.
+fn let_chain_operands(cond: &Expr) -> Option<Vec<&Expr>> {
+    fn collect<'a>(expr: &'a Expr, operands: &mut Vec<&'a Expr>) {
+        match expr.kind {
+            ExprKind::Binary(Spanned { node: BinOpKind::And, .. }, ref lhs, ref rhs) => {
+                collect(lhs, operands);
+                collect(rhs, operands);
+            }
+            _ => operands.push(expr),
+        }
+    }
+
+    let mut operands = vec![];
+    collect(cond, &mut operands);
+    let has_let = operands.iter().any(|operand| matches!(operand.kind, ExprKind::Let(..)));
+    if operands.len() > 1 && has_let { Some(operands) } else { None }
+}
+
+/// Whether the body of a `for` loop labeled `label` contains a `break` with a value that targets
+/// the loop itself, as opposed to an inner loop, closure or item.
+fn for_loop_breaks_with_value(body: &Block, label: Option<Label>) -> bool {
+    struct BreakValueFinder {
+        label: Option<Symbol>,
+        /// Number of inner loops we're in; unlabeled `break`s inside of them don't target us.
+        depth: usize,
+        found: bool,
+    }
+
+    impl<'ast> Visitor<'ast> for BreakValueFinder {
+        fn visit_expr(&mut self, ex: &'ast Expr) {
+            match ex.kind {
+                ExprKind::Break(label, Some(_)) => {
+                    let targets_us = match label {
+                        Some(label) => Some(label.ident.name) == self.label,
+                        None => self.depth == 0,
+                    };
+                    self.found |= targets_us;
+                    visit::walk_expr(self, ex);
+                }
+                ExprKind::While(..) | ExprKind::ForLoop(..) | ExprKind::Loop(..) => {
+                    self.depth += 1;
+                    visit::walk_expr(self, ex);
+                    self.depth -= 1;
+                }
+                // `break` can't cross these boundaries.
+                ExprKind::Closure(..) | ExprKind::Async(..) => {}
+                _ => visit::walk_expr(self, ex),
+            }
+        }
+
+        fn visit_item(&mut self, _: &'ast Item) {}
+
+        fn visit_anon_const(&mut self, _: &'ast AnonConst) {}
+    }
+
+    let mut finder =
+        BreakValueFinder { label: label.map(|label| label.ident.name), depth: 0, found: false };
+    finder.visit_block(body);
+    finder.found
+}
@@ -172,6 +172,7 @@ struct LoweringContext<'a, 'hir: 'a> {
 
     allow_try_trait: Option<Lrc<[Symbol]>>,
     allow_gen_future: Option<Lrc<[Symbol]>>,
+    allow_into_future: Option<Lrc<[Symbol]>>,
 }
 
 pub trait ResolverAstLowering {
@@ -331,8 +332,11 @@ pub fn lower_crate<'a, 'hir>(
         lifetimes_to_define: Vec::new(),
         is_collecting_in_band_lifetimes: false,
         in_scope_lifetimes: Vec::new(),
-        allow_try_trait: Some([sym::try_trait_v2][..].into()),
+        allow_try_trait: Some(
+            [sym::try_trait_v2, sym::try_trait_v2_yeet, sym::yeet_desugar_details][..].into(),
+        ),
         allow_gen_future: Some([sym::gen_future][..].into()),
+        allow_into_future: Some([sym::into_future][..].into()),
     }
     .lower_crate(krate)
 }
@@ -1038,6 +1042,10 @@ fn lower_assoc_ty_constraint(
     ) -> hir::TypeBinding<'hir> {
         debug!("lower_assoc_ty_constraint(constraint={:?}, itctx={:?})", constraint, itctx);
 
+        if constraint.is_return_type_notation() {
+            return self.lower_return_type_constraint(constraint, itctx);
+        }
+
         // lower generic arguments of identifier in constraint
         let gen_args = if let Some(ref gen_args) = constraint.gen_args {
             let gen_args_ctor = match gen_args {
@@ -1073,6 +1081,9 @@ fn lower_assoc_ty_constraint(
             AssocTyConstraintKind::Equality { ref ty } => {
                 hir::TypeBindingKind::Equality { ty: self.lower_ty(ty, itctx) }
             }
+            AssocTyConstraintKind::ConstEquality { ref value } => {
+                hir::TypeBindingKind::ConstEquality { value: self.lower_anon_const(value) }
+            }
             AssocTyConstraintKind::Bound { ref bounds } => {
                 let mut capturable_lifetimes;
                 let mut parent_def_id = self.current_hir_id_owner.0;
@@ -1173,6 +1184,52 @@ fn lower_assoc_ty_constraint(
         }
     }
 
+    /// Lowers a bound on the return type of an associated function, e.g.
+    ///
+    /// ```
+    /// T: Trait<method(): Send>
+    ///          ^^^^^^^^^^^^^^
+    /// ```
+    ///
+    /// Unlike associated type bounds, these are never desugared into `impl Trait`, so they are
+    /// only accepted where they end up as where-clauses on a type parameter.
+    fn lower_return_type_constraint(
+        &mut self,
+        constraint: &AssocTyConstraint,
+        itctx: ImplTraitContext<'_, 'hir>,
+    ) -> hir::TypeBinding<'hir> {
+        let bounds = match constraint.kind {
+            AssocTyConstraintKind::Bound { ref bounds } => bounds,
+            _ => unreachable!("return type notation with non-bound constraint"),
+        };
+
+        let in_opaque_or_dyn = self.is_in_dyn_type
+            || matches!(
+                itctx,
+                ImplTraitContext::ReturnPositionOpaqueTy { .. }
+                    | ImplTraitContext::OtherOpaqueTy { .. }
+            );
+        let bounds = if in_opaque_or_dyn {
+            self.sess
+                .struct_span_err(
+                    constraint.span,
+                    "return type notation is not allowed in `impl Trait` or `dyn Trait` types",
+                )
+                .emit();
+            &[]
+        } else {
+            self.lower_param_bounds(bounds, itctx)
+        };
+
+        hir::TypeBinding {
+            hir_id: self.lower_node_id(constraint.id),
+            ident: constraint.ident,
+            gen_args: self.arena.alloc(hir::GenericArgs::none()),
+            kind: hir::TypeBindingKind::ReturnConstraint { bounds },
+            span: constraint.span,
+        }
+    }
+
     fn lower_generic_arg(
         &mut self,
         arg: &ast::GenericArg,
@@ -1747,6 +1804,7 @@ fn lower_local(&mut self, l: &Local) -> hir::Local<'hir> {
         self.lower_attrs(hir_id, &l.attrs);
         hir::Local {
             hir_id,
+            super_: l.super_,
             ty,
             pat: self.lower_pat(&l.pat),
             init,
@@ -2458,7 +2516,7 @@ fn stmt_let_pat(
             debug_assert!(!a.is_empty());
             self.attrs.insert(hir_id, a);
         }
-        let local = hir::Local { hir_id, init, pat, source, span, ty: None };
+        let local = hir::Local { hir_id, super_: None, init, pat, source, span, ty: None };
         self.stmt(span, hir::StmtKind::Local(self.arena.alloc(local)))
     }
 
@@ -120,7 +120,8 @@ fn with_bound_context(&mut self, ctx: BoundContext, f: impl FnOnce(&mut Self)) {
 
     fn visit_assoc_ty_constraint_from_generic_args(&mut self, constraint: &'a AssocTyConstraint) {
         match constraint.kind {
-            AssocTyConstraintKind::Equality { .. } => {}
+            AssocTyConstraintKind::Equality { .. }
+            | AssocTyConstraintKind::ConstEquality { .. } => {}
             AssocTyConstraintKind::Bound { .. } => {
                 if self.is_assoc_ty_bound_banned {
                     self.err_handler().span_err(
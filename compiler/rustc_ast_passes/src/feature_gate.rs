@@ -611,7 +611,11 @@ fn visit_fn(&mut self, fn_kind: FnKind<'a>, span: Span, _: NodeId) {
     }
 
     fn visit_assoc_ty_constraint(&mut self, constraint: &'a AssocTyConstraint) {
+        // Return type notation is gated separately, e.g. `Trait<method(): Send>`.
         if let AssocTyConstraintKind::Bound { .. } = constraint.kind {
+            if constraint.is_return_type_notation() {
+                return visit::walk_assoc_ty_constraint(self, constraint);
+            }
             gate_feature_post!(
                 &self,
                 associated_type_bounds,
@@ -722,6 +726,9 @@ macro_rules! gate_all {
         gate_all!(destructuring_assignment, "destructuring assignments are unstable");
     }
     gate_all!(unnamed_fields, "unnamed fields are not yet fully implemented");
+    gate_all!(associated_const_equality, "associated const equality is incomplete");
+    gate_all!(return_type_notation, "return type notation is experimental");
+    gate_all!(super_let, "`super let` is experimental");
 
     // All uses of `gate_all!` below this point were added in #65742,
     // and subsequently disabled (with the non-early gating readded).
@@ -745,6 +752,7 @@ macro_rules! gate_all {
     gate_all!(box_patterns, "box pattern syntax is experimental");
     gate_all!(exclusive_range_pattern, "exclusive range pattern syntax is experimental");
     gate_all!(try_blocks, "`try` blocks are unstable");
+    gate_all!(yeet_expr, "`do yeet` expression is experimental");
     gate_all!(label_break_value, "labels on blocks are unstable");
     gate_all!(box_syntax, "box expression syntax is experimental; you can call `Box::new` instead");
     // To avoid noise about type ascription in common syntax errors,
@@ -915,6 +915,10 @@ pub fn print_assoc_constraint(&mut self, constraint: &ast::AssocTyConstraint) {
                 self.word_space("=");
                 self.print_type(ty);
             }
+            ast::AssocTyConstraintKind::ConstEquality { value } => {
+                self.word_space("=");
+                self.print_expr(&value.value);
+            }
             ast::AssocTyConstraintKind::Bound { bounds } => {
                 self.print_type_bounds(":", &*bounds);
             }
@@ -1501,6 +1505,9 @@ fn print_poly_trait_ref(&mut self, t: &ast::PolyTraitRef) {
                 self.print_outer_attributes(&loc.attrs);
                 self.space_if_not_bol();
                 self.ibox(INDENT_UNIT);
+                if loc.super_.is_some() {
+                    self.word_nbsp("super");
+                }
                 self.word_nbsp("let");
 
                 self.ibox(INDENT_UNIT);
@@ -2163,6 +2170,16 @@ fn print_expr_outer_attr_style(&mut self, expr: &ast::Expr, is_inline: bool) {
                     self.print_expr_maybe_paren(expr, parser::PREC_JUMP);
                 }
             }
+            ast::ExprKind::Yeet(ref e) => {
+                self.s.word("do");
+                self.s.space();
+                self.s.word("yeet");
+
+                if let Some(ref expr) = *e {
+                    self.s.space();
+                    self.print_expr_maybe_paren(expr, parser::PREC_JUMP);
+                }
+            }
             ast::ExprKind::Try(ref e) => {
                 self.print_expr_maybe_paren(e, parser::PREC_POSTFIX);
                 self.s.word("?")
@@ -231,6 +231,7 @@ fn run_compiler(
         override_queries: None,
         make_codegen_backend,
         registry: diagnostics_registry(),
+        cancellation_flag: None,
     };
 
     match make_input(config.opts.error_format, &matches.free) {
@@ -4,16 +4,23 @@
 use rustc_ast_pretty::pprust;
 use rustc_errors::ErrorReported;
 use rustc_hir as hir;
+use rustc_hir::def_id::LocalDefId;
 use rustc_hir_pretty as pprust_hir;
+use rustc_macros::Encodable;
 use rustc_middle::hir::map as hir_map;
-use rustc_middle::ty::{self, TyCtxt};
+use rustc_middle::ty::adjustment::Adjustment;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::subst::SubstsRef;
+use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_mir::util::{write_mir_graphviz, write_mir_pretty};
+use rustc_serialize::json::as_json;
 use rustc_session::config::{Input, PpAstTreeMode, PpHirMode, PpMode, PpSourceMode};
 use rustc_session::Session;
 use rustc_span::symbol::Ident;
 use rustc_span::FileName;
 
 use std::cell::Cell;
+use std::fmt::{self, Write};
 use std::path::Path;
 
 pub use self::PpMode::*;
@@ -490,6 +497,24 @@ fn print_with_analysis(
             todo!()
         }
 
+        TypeckCoercions => {
+            let mut out = String::new();
+            with_no_trimmed_paths(|| write_typeck_coercions(tcx, &mut out)).unwrap();
+            out
+        }
+
+        TypeckResults => {
+            let mut out = String::new();
+            with_no_trimmed_paths(|| write_typeck_results(tcx, &mut out)).unwrap();
+            out
+        }
+
+        TypeckResultsJson => {
+            let mut out = String::new();
+            with_no_trimmed_paths(|| write_typeck_results_json(tcx, &mut out)).unwrap();
+            out
+        }
+
         _ => unreachable!(),
     };
 
@@ -497,3 +522,175 @@ fn print_with_analysis(
 
     Ok(())
 }
+
+/// The bodies that have typeck results of their own, along with those results. Closures and
+/// other inline bodies share the results of the body they're defined in.
+fn typeck_roots<'tcx>(
+    tcx: TyCtxt<'tcx>,
+) -> impl Iterator<Item = (LocalDefId, &'tcx ty::TypeckResults<'tcx>)> + 'tcx {
+    tcx.body_owners()
+        .map(move |def_id| (def_id, tcx.typeck(def_id)))
+        .filter(|&(def_id, typeck_results)| typeck_results.hir_owner == def_id)
+}
+
+/// Writes the coercion sites recorded while type checking each body, in source order.
+fn write_typeck_coercions(tcx: TyCtxt<'_>, out: &mut dyn fmt::Write) -> fmt::Result {
+    let source_map = tcx.sess.source_map();
+    for (def_id, typeck_results) in typeck_roots(tcx) {
+        let mut sites = typeck_results.coercion_sites().iter().collect::<Vec<_>>();
+        if sites.is_empty() {
+            continue;
+        }
+        sites.sort_by_key(|&(&local_id, _)| local_id);
+
+        writeln!(out, "// {}", tcx.def_path_str(def_id.to_def_id()))?;
+        for (&local_id, sites) in sites {
+            let hir_id = hir::HirId { owner: def_id, local_id };
+            let span = source_map.span_to_diagnostic_string(tcx.hir().span(hir_id));
+            for site in sites {
+                writeln!(out, "{}: {} -> {}", span, site.source, site.target)?;
+                for adjustment in site.adjustments {
+                    writeln!(out, "    adjust {:?} -> {}", adjustment.kind, adjustment.target)?;
+                }
+                for predicate in &site.obligations {
+                    writeln!(out, "    obligation {}", predicate)?;
+                }
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// What the typeck results of a body record for one of its nodes that has a type.
+struct TypeckNode<'tcx> {
+    local_id: hir::ItemLocalId,
+    span: String,
+    ty: Ty<'tcx>,
+    substs: SubstsRef<'tcx>,
+    adjustments: &'tcx [Adjustment<'tcx>],
+    /// What a method call, an overloaded operator or a type-relative path resolved to.
+    resolution: Option<String>,
+}
+
+/// Collects the nodes of the body owned by `def_id` that have a type, sorted by their
+/// `ItemLocalId`s. Both dumps of the typeck results are built from this.
+fn typeck_nodes<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    typeck_results: &'tcx ty::TypeckResults<'tcx>,
+) -> Vec<TypeckNode<'tcx>> {
+    let source_map = tcx.sess.source_map();
+    let mut nodes = typeck_results
+        .node_types()
+        .iter()
+        .map(|(&local_id, &ty)| {
+            let hir_id = hir::HirId { owner: def_id, local_id };
+            let adjustments = typeck_results.adjustments().get(hir_id);
+            let adjustments = adjustments.map_or(&[][..], |adjustments| &adjustments[..]);
+            let resolution = match typeck_results.type_dependent_defs().get(hir_id) {
+                Some(&Ok((kind, res_def_id))) => {
+                    Some(format!("{} `{}`", kind.descr(res_def_id), tcx.def_path_str(res_def_id)))
+                }
+                _ => None,
+            };
+            TypeckNode {
+                local_id,
+                span: source_map.span_to_diagnostic_string(tcx.hir().span(hir_id)),
+                ty,
+                substs: typeck_results.node_substs(hir_id),
+                adjustments,
+                resolution,
+            }
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_by_key(|node| node.local_id);
+    nodes
+}
+
+/// Writes the results of type checking each body after writeback: the type of every node along
+/// with its substitutions, adjustments and resolution, followed by the closure kinds and the
+/// types held across suspension points by generators.
+fn write_typeck_results(tcx: TyCtxt<'_>, out: &mut dyn fmt::Write) -> fmt::Result {
+    let source_map = tcx.sess.source_map();
+    for (def_id, typeck_results) in typeck_roots(tcx) {
+        writeln!(out, "// {}", tcx.def_path_str(def_id.to_def_id()))?;
+
+        for node in typeck_nodes(tcx, def_id, typeck_results) {
+            writeln!(out, "{}: {}", node.span, node.ty)?;
+
+            if !node.substs.is_empty() {
+                writeln!(out, "    substs {:?}", node.substs)?;
+            }
+            for adjustment in node.adjustments {
+                writeln!(out, "    adjust {:?} -> {}", adjustment.kind, adjustment.target)?;
+            }
+            if let Some(resolution) = &node.resolution {
+                writeln!(out, "    resolved {}", resolution)?;
+            }
+            if let ty::Closure(_, substs) = *node.ty.kind() {
+                if let Some(kind) = substs.as_closure().kind_ty().to_opt_closure_kind() {
+                    writeln!(out, "    closure kind {:?}", kind)?;
+                }
+            }
+        }
+
+        for cause in typeck_results.generator_interior_types.as_ref().skip_binder() {
+            let span = source_map.span_to_diagnostic_string(cause.span);
+            let yield_span = source_map.span_to_diagnostic_string(cause.yield_span);
+            writeln!(out, "{}: {} held across {}", span, cause.ty, yield_span)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[derive(Encodable)]
+struct TypeckBodyJson {
+    /// The path of the item owning the body, which is what the `local_id`s are relative to.
+    owner: String,
+    nodes: Vec<TypeckNodeJson>,
+}
+
+#[derive(Encodable)]
+struct TypeckNodeJson {
+    local_id: u32,
+    span: String,
+    ty: String,
+    adjustments: Vec<AdjustmentJson>,
+    resolution: Option<String>,
+}
+
+#[derive(Encodable)]
+struct AdjustmentJson {
+    kind: String,
+    target: String,
+}
+
+/// Writes the node types, adjustments and resolutions of each body as JSON, one line per body,
+/// for tools that want to inspect the results of type checking without parsing the output of
+/// `-Zunpretty=typeck-results`.
+fn write_typeck_results_json(tcx: TyCtxt<'_>, out: &mut dyn fmt::Write) -> fmt::Result {
+    for (def_id, typeck_results) in typeck_roots(tcx) {
+        let nodes = typeck_nodes(tcx, def_id, typeck_results)
+            .into_iter()
+            .map(|node| TypeckNodeJson {
+                local_id: node.local_id.as_u32(),
+                span: node.span,
+                ty: node.ty.to_string(),
+                adjustments: node
+                    .adjustments
+                    .iter()
+                    .map(|adjustment| AdjustmentJson {
+                        kind: format!("{:?}", adjustment.kind),
+                        target: adjustment.target.to_string(),
+                    })
+                    .collect(),
+                resolution: node.resolution,
+            })
+            .collect();
+        let owner = tcx.def_path_str(def_id.to_def_id());
+        writeln!(out, "{}", as_json(&TypeckBodyJson { owner, nodes }))?;
+    }
+    Ok(())
+}
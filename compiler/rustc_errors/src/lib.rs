@@ -19,7 +19,7 @@
 
 use emitter::{is_case_difference, Emitter, EmitterWriter};
 use registry::Registry;
-use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_data_structures::sync::{self, Lock, Lrc};
 use rustc_data_structures::AtomicRef;
@@ -343,8 +343,15 @@ struct HandlerInner {
     deduplicated_warn_count: usize,
 
     future_breakage_diagnostics: Vec<Diagnostic>,
+
+    /// Structured suggestions of emitted diagnostics, only collected when
+    /// `HandlerFlags::track_suggestion_stats` is set.
+    suggestion_stats: SuggestionStats,
 }
 
+/// The number of emitted structured suggestions, keyed by their message and applicability.
+pub type SuggestionStats = FxHashMap<(String, Applicability), usize>;
+
 /// A key denoting where from a diagnostic was stashed.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub enum StashKey {
@@ -375,6 +382,9 @@ pub struct HandlerFlags {
     pub macro_backtrace: bool,
     /// If true, identical diagnostics are reported only once.
     pub deduplicate_diagnostics: bool,
+    /// If true, count the structured suggestions of emitted diagnostics.
+    /// (rustc: see `-Z suggestion-stats`)
+    pub track_suggestion_stats: bool,
 }
 
 impl Drop for HandlerInner {
@@ -457,6 +467,7 @@ pub fn with_emitter_and_flags(
                 emitted_diagnostics: Default::default(),
                 stashed_diagnostics: Default::default(),
                 future_breakage_diagnostics: Vec::new(),
+                suggestion_stats: Default::default(),
             }),
         }
     }
@@ -734,6 +745,12 @@ pub fn take_future_breakage_diagnostics(&self) -> Vec<Diagnostic> {
         std::mem::take(&mut self.inner.borrow_mut().future_breakage_diagnostics)
     }
 
+    /// Returns the suggestion counts collected so far and resets them, so that callers can
+    /// measure the suggestions emitted by a single compiler pass.
+    pub fn take_suggestion_stats(&self) -> SuggestionStats {
+        std::mem::take(&mut self.inner.borrow_mut().suggestion_stats)
+    }
+
     pub fn abort_if_errors(&self) {
         self.inner.borrow_mut().abort_if_errors()
     }
@@ -829,6 +846,12 @@ fn emit_diagnostic(&mut self, diagnostic: &Diagnostic) {
         // haven't already emitted an equivalent diagnostic.
         if !(self.flags.deduplicate_diagnostics && already_emitted(self)) {
             self.emitter.emit_diagnostic(diagnostic);
+            if self.flags.track_suggestion_stats {
+                for suggestion in &diagnostic.suggestions {
+                    let key = (suggestion.msg.clone(), suggestion.applicability);
+                    *self.suggestion_stats.entry(key).or_insert(0) += 1;
+                }
+            }
             if diagnostic.is_error() {
                 self.deduplicated_err_count += 1;
             } else if diagnostic.level == Warning {
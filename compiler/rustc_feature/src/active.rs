@@ -687,6 +687,36 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows qualified paths in struct expressions, struct patterns and tuple struct patterns.
     (active, more_qualified_paths, "1.54.0", Some(80080), None),
 
+    // no-tracking-issue-start
+
+    /// Allows `break value` in `for` loops, with the loop evaluating to `Default::default()`
+    /// when the iterator is exhausted. An experiment that has no RFC or tracking issue yet.
+    (active, for_loop_break_value, "1.55.0", None, None),
+
+    // no-tracking-issue-end
+
+    /// Allows `do yeet` expressions, which exit the enclosing `try` block or function with a
+    /// residual.
+    (active, yeet_expr, "1.55.0", Some(96373), None),
+
+    // no-tracking-issue-start
+
+    /// Allows values of uninhabited types, like empty enums, to coerce to any type, like `!`.
+    /// Only types that are visibly uninhabited where the value is coerced do.
+    (active, uninhabited_coercions, "1.55.0", None, None),
+
+    // no-tracking-issue-end
+
+    /// Allows associated constants to be constrained in bounds, e.g., `T: Trait<N = 3>`.
+    (active, associated_const_equality, "1.55.0", Some(92827), None),
+
+    /// Allows bounds on the return type of trait methods, e.g., `T: Trait<method(): Send>`.
+    (incomplete, return_type_notation, "1.55.0", Some(109417), None),
+
+    /// Allows `super let` statements, whose bindings and temporaries live as long as the
+    /// enclosing block's temporaries.
+    (active, super_let, "1.55.0", Some(139076), None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
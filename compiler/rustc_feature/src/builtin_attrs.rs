@@ -524,6 +524,12 @@ macro_rules! experimental {
         overflow checking behavior of several libcore functions that are inlined \
         across crates and will never be stable",
     ),
+    rustc_attr!(
+        rustc_has_incoherent_inherent_impls, Normal, template!(Word),
+        "the `#[rustc_has_incoherent_inherent_impls]` attribute allows inherent impls of the \
+        annotated type in crates other than the one defining it, for types that are split \
+        between the standard library crates",
+    ),
     rustc_attr!(rustc_reservation_impl, Normal, template!(NameValueStr: "reservation message"),
                 "the `#[rustc_reservation_impl]` attribute is internally used \
                  for reserving for `for<T> From<!> for T` impl"
@@ -549,6 +555,11 @@ macro_rules! experimental {
         "the `#[rustc_skip_array_during_method_dispatch]` attribute is used to exclude a trait \
         from method dispatch when the receiver is an array, for compatibility in editions < 2021."
     ),
+    rustc_attr!(
+        rustc_must_implement_one_of, Normal, template!(List: "function1, function2, ..."),
+        "the `#[rustc_must_implement_one_of]` attribute is used to require impls of a trait to \
+        override at least one of a group of methods that are defined in terms of each other"
+    ),
 
     // ==========================================================================
     // Internal attributes, Testing:
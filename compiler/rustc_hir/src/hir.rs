@@ -1158,6 +1158,11 @@ pub enum StmtKind<'hir> {
 /// Represents a `let` statement (i.e., `let <pat>:<ty> = <expr>;`).
 #[derive(Debug, HashStable_Generic)]
 pub struct Local<'hir> {
+    /// The span of the `super` keyword, if this is a `super let` statement.
+    /// Bindings introduced by `super let` (and temporaries in the initializer
+    /// that would normally be extended to the end of the block) live as long
+    /// as the enclosing block's own temporaries instead.
+    pub super_: Option<Span>,
     pub pat: &'hir Pat<'hir>,
     /// Type annotation, if any (otherwise the type will be inferred).
     pub ty: Option<&'hir Ty<'hir>>,
@@ -2140,6 +2145,11 @@ pub enum TypeBindingKind<'hir> {
     Constraint { bounds: &'hir [GenericBound<'hir>] },
     /// E.g., `Foo<Bar = ()>`.
     Equality { ty: &'hir Ty<'hir> },
+    /// E.g., `Foo<N = 3>`, where `N` is an associated constant.
+    ConstEquality { value: AnonConst },
+    /// E.g., `Foo<method(): Send>`, a bound on the return type of the associated function
+    /// `method`.
+    ReturnConstraint { bounds: &'hir [GenericBound<'hir>] },
 }
 
 impl TypeBinding<'_> {
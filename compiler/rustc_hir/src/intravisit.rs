@@ -803,7 +803,10 @@ pub fn walk_assoc_type_binding<'v, V: Visitor<'v>>(
         TypeBindingKind::Equality { ref ty } => {
             visitor.visit_ty(ty);
         }
-        TypeBindingKind::Constraint { bounds } => {
+        TypeBindingKind::ConstEquality { ref value } => {
+            visitor.visit_anon_const(value);
+        }
+        TypeBindingKind::Constraint { bounds } | TypeBindingKind::ReturnConstraint { bounds } => {
             walk_list!(visitor, visit_param_bound, bounds);
         }
     }
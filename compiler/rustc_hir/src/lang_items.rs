@@ -72,6 +72,13 @@ pub fn group(self) -> Option<LangItemGroup> {
                     $( LangItem::$variant => expand_group!($($group)*), )*
                 }
             }
+
+            /// The kind of item that `#[lang = "$name"]` has to be applied to.
+            pub fn target(self) -> Target {
+                match self {
+                    $( LangItem::$variant => $target, )*
+                }
+            }
         }
 
         /// All of the language items, defined or not.
@@ -316,6 +323,7 @@ pub fn extract<'a, F>(check_name: F, attrs: &'a [ast::Attribute]) -> Option<(Sym
     TryTraitFromResidual,    sym::from_residual,       from_residual_fn,           Target::Method(MethodKind::Trait { body: false });
     TryTraitFromOutput,      sym::from_output,         from_output_fn,             Target::Method(MethodKind::Trait { body: false });
     TryTraitBranch,          sym::branch,              branch_fn,                  Target::Method(MethodKind::Trait { body: false });
+    TryTraitFromYeet,        sym::from_yeet,           from_yeet_fn,               Target::Fn;
 
     PollReady,               sym::Ready,               poll_ready_variant,         Target::Variant;
     PollPending,             sym::Pending,             poll_pending_variant,       Target::Variant;
@@ -324,6 +332,7 @@ pub fn extract<'a, F>(check_name: F, attrs: &'a [ast::Attribute]) -> Option<(Sym
     GetContext,              sym::get_context,         get_context_fn,             Target::Fn;
 
     FuturePoll,              sym::poll,                future_poll_fn,             Target::Method(MethodKind::Trait { body: false });
+    IntoFutureIntoFuture,    sym::into_future,         into_future_fn,             Target::Method(MethodKind::Trait { body: false });
 
     FromFrom,                sym::from,                from_fn,                    Target::Method(MethodKind::Trait { body: false });
 
@@ -339,6 +348,8 @@ pub fn extract<'a, F>(check_name: F, attrs: &'a [ast::Attribute]) -> Option<(Sym
     IntoIterIntoIter,        sym::into_iter,           into_iter_fn,               Target::Method(MethodKind::Trait { body: false });
     IteratorNext,            sym::next,                next_fn,                    Target::Method(MethodKind::Trait { body: false});
 
+    DefaultDefault,          sym::default_fn,          default_fn,                 Target::Method(MethodKind::Trait { body: false });
+
     PinNewUnchecked,         sym::new_unchecked,       new_unchecked_fn,           Target::Method(MethodKind::Inherent);
 
     RangeFrom,               sym::RangeFrom,           range_from_struct,          Target::Struct;
@@ -1031,6 +1031,10 @@ pub fn print_stmt(&mut self, st: &hir::Stmt<'_>) {
         self.maybe_print_comment(st.span.lo());
         match st.kind {
             hir::StmtKind::Local(ref loc) => {
+                if loc.super_.is_some() {
+                    self.space_if_not_bol();
+                    self.word("super");
+                }
                 self.print_local(loc.init.as_deref(), |this| this.print_local_decl(&loc));
             }
             hir::StmtKind::Item(item) => self.ann.nested(self, Nested::Item(item)),
@@ -1866,13 +1870,21 @@ fn print_generic_args(
                 start_or_comma(self);
                 self.print_ident(binding.ident);
                 self.print_generic_args(binding.gen_args, false, false);
+                if let hir::TypeBindingKind::ReturnConstraint { .. } = binding.kind {
+                    self.s.word("()");
+                }
                 self.s.space();
                 match generic_args.bindings[0].kind {
                     hir::TypeBindingKind::Equality { ref ty } => {
                         self.word_space("=");
                         self.print_type(ty);
                     }
-                    hir::TypeBindingKind::Constraint { bounds } => {
+                    hir::TypeBindingKind::ConstEquality { ref value } => {
+                        self.word_space("=");
+                        self.print_anon_const(value);
+                    }
+                    hir::TypeBindingKind::Constraint { bounds }
+                    | hir::TypeBindingKind::ReturnConstraint { bounds } => {
                         self.print_bounds(":", bounds);
                     }
                 }
@@ -789,6 +789,9 @@ fn note_error_origin(
                     );
                 }
             }
+            ObligationCauseCode::YieldExpression { prior_yield_span } => {
+                err.span_label(prior_yield_span, "expected because of this");
+            }
             _ => (),
         }
     }
@@ -2290,7 +2293,7 @@ fn binding_suggestion<'tcx, S: fmt::Display>(
                     sub,
                     "...",
                 );
-                if let Some(infer::RelateParamBound(_, t)) = origin {
+                if let Some(infer::RelateParamBound(_, t, _)) = origin {
                     let return_impl_trait = self
                         .in_progress_typeck_results
                         .map(|typeck_results| typeck_results.borrow().hir_owner)
@@ -2481,6 +2484,7 @@ fn as_failure_code(&self, terr: &TypeError<'tcx>) -> FailureCode {
             }
             IfExpression { .. } => Error0308("`if` and `else` have incompatible types"),
             IfExpressionWithNoElse => Error0317("`if` may be missing an `else` clause"),
+            YieldExpression { .. } => Error0308("`yield` expressions have incompatible types"),
             MainFunctionType => Error0580("`main` function has wrong type"),
             StartFunctionType => Error0308("`#[start]` function has wrong type"),
             IntrinsicType => Error0308("intrinsic has wrong type"),
@@ -2514,6 +2518,7 @@ fn as_requirement_str(&self) -> &'static str {
             },
             IfExpression { .. } => "`if` and `else` have incompatible types",
             IfExpressionWithNoElse => "`if` missing an `else` returns `()`",
+            YieldExpression { .. } => "`yield` expressions have compatible types",
             MainFunctionType => "`main` function has the correct type",
             StartFunctionType => "`#[start]` function has the correct type",
             IntrinsicType => "intrinsic has the correct type",
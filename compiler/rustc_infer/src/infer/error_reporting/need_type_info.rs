@@ -26,6 +26,7 @@ struct FindHirNodeVisitor<'a, 'tcx> {
     found_closure: Option<&'tcx Expr<'tcx>>,
     found_method_call: Option<&'tcx Expr<'tcx>>,
     found_exact_method_call: Option<&'tcx Expr<'tcx>>,
+    found_try_block: Option<&'tcx Expr<'tcx>>,
     found_use_diagnostic: Option<UseDiagnostic<'tcx>>,
 }
 
@@ -41,6 +42,7 @@ fn new(infcx: &'a InferCtxt<'a, 'tcx>, target: GenericArg<'tcx>, target_span: Sp
             found_closure: None,
             found_method_call: None,
             found_exact_method_call: None,
+            found_try_block: None,
             found_use_diagnostic: None,
         }
     }
@@ -79,6 +81,18 @@ fn is_try_conversion(&self, callee: &Expr<'tcx>) -> bool {
             .trait_def_from_hir_fn(callee.hir_id)
             .map_or(false, |def_id| self.infcx.is_try_conversion(callee.span, def_id))
     }
+
+    /// Determine whether the expression is the block a `try { .. }` block was desugared into,
+    /// whose tail expression is the ok-wrapping `Try::from_output` call.
+    fn is_try_block(&self, expr: &Expr<'tcx>) -> bool {
+        match expr.kind {
+            ExprKind::Block(hir::Block { expr: Some(tail), .. }, None) => {
+                matches!(tail.kind, ExprKind::Call(..))
+                    && tail.span.is_desugaring(DesugaringKind::TryBlock)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl<'a, 'tcx> Visitor<'tcx> for FindHirNodeVisitor<'a, 'tcx> {
@@ -134,6 +148,11 @@ fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
             match expr.kind {
                 ExprKind::Closure(..) => self.found_closure = Some(&expr),
                 ExprKind::MethodCall(..) => self.found_method_call = Some(&expr),
+                ExprKind::Block(..)
+                    if self.found_try_block.is_none() && self.is_try_block(expr) =>
+                {
+                    self.found_try_block = Some(&expr);
+                }
 
                 // If the given expression falls within the target span and is a
                 // `From::from(e)` call emitted during desugaring of the `?` operator,
@@ -541,6 +560,18 @@ pub fn emit_inference_failure_err(
             }
         }
 
+        if let Some(try_block) = local_visitor.found_try_block {
+            // Point at the `try` keyword rather than at the whole block.
+            let try_span = self.tcx.sess.source_map().span_until_whitespace(try_block.span);
+            if try_span != err_span {
+                err.span_label(try_span, "cannot infer the type of this `try` block");
+            }
+            err.note(
+                "the type of a `try` block isn't inferred from the `?` operators inside of it, \
+                 so it has to be known from where the block is used",
+            );
+        }
+
         let suffix = match local_visitor.found_node_ty {
             Some(ty) if ty.is_closure() => {
                 let substs =
@@ -2,10 +2,13 @@
 //! where one region is named and the other is anonymous.
 use crate::infer::error_reporting::nice_region_error::find_anon_type::find_anon_type;
 use crate::infer::error_reporting::nice_region_error::NiceRegionError;
+use crate::infer::lexical_region_resolve::RegionResolutionError::*;
+use crate::infer::SubregionOrigin;
 use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
+use rustc_hir as hir;
 use rustc_hir::intravisit::Visitor;
-use rustc_hir::FnRetTy;
-use rustc_middle::ty;
+use rustc_hir::{FnRetTy, Node};
+use rustc_middle::ty::{self, Ty};
 
 impl<'a, 'tcx> NiceRegionError<'a, 'tcx> {
     /// When given a `ConcreteFailure` for a function with parameters containing a named region and
@@ -123,8 +126,116 @@ pub(super) fn try_report_named_anon_conflict(&self) -> Option<DiagnosticBuilder<
                 new_ty.to_string(),
                 Applicability::Unspecified,
             );
+        } else {
+            self.explain_static_closure_capture(&mut diag, param, new_ty);
         }
 
         Some(diag)
     }
+
+    /// When a closure capturing `param` has to be `'static` because it was passed to something
+    /// like `std::thread::spawn`, point at the capture and at the bound requiring `'static`, and
+    /// suggest moving owned data into the closure instead.
+    fn explain_static_closure_capture(
+        &self,
+        diag: &mut DiagnosticBuilder<'_>,
+        param: &hir::Param<'_>,
+        param_ty: Ty<'tcx>,
+    ) {
+        let tcx = self.tcx();
+        let origin = match &self.error {
+            Some(ConcreteFailure(origin, ..)) | Some(SubSupConflict(_, _, origin, ..)) => origin,
+            _ => return,
+        };
+        let (closure_ty, bound) = match origin {
+            SubregionOrigin::RelateParamBound(_, ty, bound) => (*ty, bound),
+            _ => return,
+        };
+        let closure_def_id = match *closure_ty.kind() {
+            ty::Closure(def_id, _) => def_id,
+            _ => return,
+        };
+        let (ident, local_id) = match param.pat.kind {
+            hir::PatKind::Binding(_, local_id, ident, None) => (ident, local_id),
+            _ => return,
+        };
+        let upvar = match tcx.upvars_mentioned(closure_def_id).and_then(|u| u.get(&local_id)) {
+            Some(upvar) => upvar,
+            None => return,
+        };
+
+        diag.span_label(
+            upvar.span,
+            format!("`{}` is captured here, so the closure can't outlive it", ident),
+        );
+        if let Some(&(bound_def_id, bound_span)) = bound.as_deref() {
+            diag.span_note(
+                bound_span,
+                &format!(
+                    "the `'static` requirement is introduced by this bound on `{}`",
+                    tcx.def_path_str(bound_def_id),
+                ),
+            );
+        }
+
+        let hir = tcx.hir();
+        let closure_hir_id = match closure_def_id.as_local() {
+            Some(def_id) => hir.local_def_id_to_hir_id(def_id),
+            None => return,
+        };
+        let is_move = matches!(
+            hir.expect_expr(closure_hir_id).kind,
+            hir::ExprKind::Closure(hir::CaptureBy::Value, ..)
+        );
+        // Find the statement (or tail expression) containing the closure, so that an owned copy
+        // of the captured data can be made right before it.
+        let mut prev_span = hir.span(closure_hir_id);
+        let mut stmt_span = None;
+        for (_, node) in hir.parent_iter(closure_hir_id) {
+            match node {
+                Node::Stmt(stmt) => {
+                    stmt_span = Some(stmt.span);
+                    break;
+                }
+                Node::Block(_) => {
+                    stmt_span = Some(prev_span);
+                    break;
+                }
+                Node::Expr(expr) => prev_span = expr.span,
+                Node::Local(_) => {}
+                _ => break,
+            }
+        }
+
+        let source_map = tcx.sess.source_map();
+        match stmt_span {
+            Some(stmt_span) if param_ty.is_ref() && !stmt_span.from_expansion() => {
+                let indent = " ".repeat(source_map.span_to_margin(stmt_span).unwrap_or(0));
+                let owned = format!("let {0} = {0}.to_owned();\n{1}", ident, indent);
+                let mut sugg = vec![(stmt_span.shrink_to_lo(), owned)];
+                if !is_move {
+                    sugg.push((hir.span(closure_hir_id).shrink_to_lo(), "move ".to_string()));
+                }
+                diag.multipart_suggestion(
+                    &format!("consider moving an owned copy of `{}` into the closure", ident),
+                    sugg,
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            _ if !is_move => {
+                diag.span_suggestion_verbose(
+                    hir.span(closure_hir_id).shrink_to_lo(),
+                    "consider moving owned data into the closure with a `move` closure",
+                    "move ".to_string(),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            _ => {}
+        }
+        diag.help(&format!(
+            "if `{}` can't be cloned, consider sharing it through a `std::sync::Arc` and moving a \
+             clone of the `Arc` into the closure",
+            ident,
+        ));
+    }
 }
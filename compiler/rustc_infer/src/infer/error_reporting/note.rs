@@ -1,9 +1,11 @@
 use crate::infer::error_reporting::{note_and_explain_region, ObligationCauseExt};
 use crate::infer::{self, InferCtxt, SubregionOrigin};
 use rustc_errors::{struct_span_err, DiagnosticBuilder};
+use rustc_hir::def_id::DefId;
 use rustc_middle::traits::ObligationCauseCode;
 use rustc_middle::ty::error::TypeError;
 use rustc_middle::ty::{self, Region};
+use rustc_span::Span;
 
 impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
     pub(super) fn note_region_origin(
@@ -74,7 +76,7 @@ pub(super) fn note_region_origin(
                     ),
                 );
             }
-            infer::RelateParamBound(span, t) => {
+            infer::RelateParamBound(span, t, ref bound) => {
                 label_or_note(
                     span,
                     &format!(
@@ -82,6 +84,9 @@ pub(super) fn note_region_origin(
                         self.ty_to_string(t)
                     ),
                 );
+                if let Some(&(def_id, bound_span)) = bound.as_deref() {
+                    self.note_inferred_outlives_field(err, def_id, bound_span);
+                }
             }
             infer::RelateRegionParamBound(span) => {
                 label_or_note(
@@ -98,6 +103,52 @@ pub(super) fn note_region_origin(
         }
     }
 
+    /// Points at the field an outlives requirement of `def_id` was inferred from, when the
+    /// bound at `bound_span` wasn't written by the user but inferred from the fields of a
+    /// local type (RFC 2093).
+    fn note_inferred_outlives_field(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        def_id: DefId,
+        bound_span: Span,
+    ) {
+        let local_def_id = match def_id.as_local() {
+            Some(local_def_id) => local_def_id,
+            None => return,
+        };
+        let predicate = match self
+            .tcx
+            .inferred_outlives_of(def_id)
+            .iter()
+            .find(|&&(_, span)| span == bound_span)
+        {
+            Some(&(predicate, _)) => predicate,
+            None => return,
+        };
+        // Predicates written by the user are already pointed at by the bound's span.
+        if self.tcx.explicit_predicates_of(def_id).predicates.iter().any(|&(p, _)| p == predicate) {
+            return;
+        }
+        let field = match self
+            .tcx
+            .inferred_outlives_fields_of(local_def_id)
+            .iter()
+            .find(|&&(p, _)| p == predicate)
+        {
+            Some(&(_, field)) => field,
+            None => return,
+        };
+        err.span_note(
+            self.tcx.def_span(field),
+            &format!(
+                "this requirement was inferred from field `{}: {}` of `{}`",
+                self.tcx.item_name(field),
+                self.tcx.type_of(field),
+                self.tcx.def_path_str(def_id),
+            ),
+        );
+    }
+
     pub(super) fn report_concrete_failure(
         &self,
         origin: SubregionOrigin<'tcx>,
@@ -207,7 +258,7 @@ pub(super) fn report_concrete_failure(
                 );
                 err
             }
-            infer::RelateParamBound(span, ty) => {
+            infer::RelateParamBound(span, ty, _) => {
                 let mut err = struct_span_err!(
                     self.tcx.sess,
                     span,
@@ -28,6 +28,7 @@
 use rustc_middle::ty::{self, GenericParamDefKind, InferConst, Ty, TyCtxt};
 use rustc_middle::ty::{ConstVid, FloatVid, IntVid, TyVid};
 use rustc_session::config::BorrowckMode;
+use rustc_session::InferVarStats;
 use rustc_span::symbol::Symbol;
 use rustc_span::Span;
 
@@ -374,8 +375,9 @@ pub enum SubregionOrigin<'tcx> {
     RelateObjectBound(Span),
 
     /// Some type parameter was instantiated with the given type,
-    /// and that type must outlive some region.
-    RelateParamBound(Span, Ty<'tcx>),
+    /// and that type must outlive some region. The last field is the
+    /// item and span of the bound that required it, if known.
+    RelateParamBound(Span, Ty<'tcx>, Option<Box<(DefId, Span)>>),
 
     /// The given region parameter was instantiated with a region
     /// that must outlive some other region.
@@ -1074,6 +1076,25 @@ pub fn num_region_vars(&self) -> usize {
         self.inner.borrow_mut().unwrap_region_constraints().num_region_vars()
     }
 
+    /// Counts the inference variables created so far, for `-Z typeck-stats`. This must be called
+    /// before the region constraints are solved.
+    pub fn infer_var_stats(&self) -> InferVarStats {
+        let mut inner = self.inner.borrow_mut();
+        let type_vars = inner.type_variables().num_vars();
+        let mut unified_set_sizes = FxHashMap::default();
+        for index in 0..type_vars {
+            let root = inner.type_variables().root_var(ty::TyVid { index: index as u32 });
+            *unified_set_sizes.entry(root).or_insert(0) += 1;
+        }
+        InferVarStats {
+            type_vars,
+            int_vars: inner.int_unification_table().len(),
+            float_vars: inner.float_unification_table().len(),
+            region_vars: inner.unwrap_region_constraints().num_region_vars(),
+            largest_unified_set: unified_set_sizes.values().copied().max().unwrap_or(0),
+        }
+    }
+
     /// Just a convenient wrapper of `next_region_var` for using during NLL.
     pub fn next_nll_region_var(&self, origin: NllRegionVariableOrigin) -> ty::Region<'tcx> {
         self.next_region_var(RegionVariableOrigin::Nll(origin))
@@ -1700,7 +1721,7 @@ pub fn span(&self) -> Span {
         match *self {
             Subtype(ref a) => a.span(),
             RelateObjectBound(a) => a,
-            RelateParamBound(a, _) => a,
+            RelateParamBound(a, ..) => a,
             RelateRegionParamBound(a) => a,
             Reborrow(a) => a,
             ReborrowUpvar(a, _) => a,
@@ -64,7 +64,7 @@
 use crate::infer::{
     self, GenericKind, InferCtxt, RegionObligation, SubregionOrigin, UndoLog, VerifyBound,
 };
-use crate::traits::ObligationCause;
+use crate::traits::{ObligationCause, ObligationCauseCode};
 use rustc_middle::ty::outlives::Component;
 use rustc_middle::ty::subst::GenericArgKind;
 use rustc_middle::ty::{self, Region, Ty, TyCtxt, TypeFoldable};
@@ -99,7 +99,13 @@ pub fn register_region_obligation_with_cause(
         cause: &ObligationCause<'tcx>,
     ) {
         let origin = SubregionOrigin::from_obligation_cause(cause, || {
-            infer::RelateParamBound(cause.span, sup_type)
+            let bound = match cause.code {
+                ObligationCauseCode::BindingObligation(def_id, span) => {
+                    Some(Box::new((def_id, span)))
+                }
+                _ => None,
+            };
+            infer::RelateParamBound(cause.span, sup_type, bound)
         });
 
         self.register_region_obligation(
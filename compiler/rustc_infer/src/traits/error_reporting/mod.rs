@@ -5,7 +5,7 @@
 use rustc_errors::{struct_span_err, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{self, TyCtxt};
 use rustc_span::symbol::Symbol;
 use rustc_span::{MultiSpan, Span};
 use std::fmt;
@@ -32,6 +32,29 @@ pub fn report_extra_impl_obligation(
 
         err.span_label(sp, format!("impl has extra requirement {}", requirement));
 
+        // Tell the user what they may rely on instead, leaving out the implicit `Sized` bounds.
+        let sized_trait = self.tcx.lang_items().sized_trait();
+        let trait_requirements = self
+            .tcx
+            .predicates_of(trait_item_def_id)
+            .predicates
+            .iter()
+            .filter(|(predicate, _)| match predicate.kind().skip_binder() {
+                ty::PredicateKind::Trait(trait_predicate, _) => {
+                    Some(trait_predicate.def_id()) != sized_trait
+                }
+                _ => true,
+            })
+            .map(|(predicate, _)| format!("`{}`", predicate))
+            .collect::<Vec<_>>();
+        if !trait_requirements.is_empty() {
+            err.note(&format!(
+                "the definition of `{}` in the trait only requires {}",
+                item_name,
+                trait_requirements.join(", "),
+            ));
+        }
+
         err
     }
 }
@@ -20,6 +20,7 @@
 use rustc_span::source_map::{FileLoader, FileName};
 use std::path::PathBuf;
 use std::result;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
 pub type Result<T> = result::Result<T, ErrorReported>;
@@ -165,6 +166,11 @@ pub struct Config {
 
     /// Registry of diagnostics codes.
     pub registry: Registry,
+
+    /// Setting this flag from another thread makes long-running passes like type checking
+    /// abort with a `FatalError` at their next cancellation point, without emitting any
+    /// diagnostic. See `Session::abort_if_cancelled`.
+    pub cancellation_flag: Option<Arc<AtomicBool>>,
 }
 
 pub fn create_compiler_and_run<R>(config: Config, f: impl FnOnce(&Compiler) -> R) -> R {
@@ -188,6 +194,12 @@ pub fn create_compiler_and_run<R>(config: Config, f: impl FnOnce(&Compiler) -> R
         );
     }
 
+    if let Some(cancellation_flag) = config.cancellation_flag {
+        Lrc::get_mut(&mut sess)
+            .expect("create_session() should never share the returned session")
+            .cancellation_flag = Some(cancellation_flag);
+    }
+
     let compiler = Compiler {
         sess,
         codegen_backend,
@@ -659,6 +659,7 @@ macro_rules! untracked {
     untracked!(print_llvm_passes, true);
     untracked!(print_mono_items, Some(String::from("abc")));
     untracked!(print_type_sizes, true);
+    untracked!(probe_trace, Some(String::from("abc")));
     untracked!(proc_macro_backtrace, true);
     untracked!(query_dep_graph, true);
     untracked!(query_stats, true);
@@ -668,6 +669,7 @@ macro_rules! untracked {
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
     untracked!(strip, Strip::Debuginfo);
+    untracked!(suggestion_stats, true);
     untracked!(terminal_width, Some(80));
     untracked!(threads, 99);
     untracked!(time, true);
@@ -675,11 +677,13 @@ macro_rules! untracked {
     untracked!(time_passes, true);
     untracked!(trace_macros, true);
     untracked!(trim_diagnostic_paths, false);
+    untracked!(typeck_stats, true);
     untracked!(ui_testing, true);
     untracked!(unpretty, Some("expanded".to_string()));
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
     untracked!(verbose, true);
+    untracked!(verify_wf_check_coverage, true);
 
     macro_rules! tracked {
         ($name: ident, $non_default_value: expr) => {
@@ -752,6 +756,7 @@ macro_rules! tracked {
     tracked!(tls_model, Some(TlsModel::GeneralDynamic));
     tracked!(trap_unreachable, Some(false));
     tracked!(treat_err_as_bug, NonZeroUsize::new(1));
+    tracked!(typeck_const_checks, true);
     tracked!(unleash_the_miri_inside_of_you, true);
     tracked!(use_ctors_section, Some(true));
     tracked!(verify_llvm_ir, true);
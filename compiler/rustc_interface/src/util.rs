@@ -772,6 +772,7 @@ fn involves_impl_trait(ty: &ast::Ty) -> bool {
                                     },
                                     ast::AngleBracketedArg::Constraint(c) => match c.kind {
                                         ast::AssocTyConstraintKind::Bound { .. } => true,
+                                        ast::AssocTyConstraintKind::ConstEquality { .. } => false,
                                         ast::AssocTyConstraintKind::Equality { ref ty } => {
                                             involves_impl_trait(ty)
                                         }
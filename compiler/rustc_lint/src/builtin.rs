@@ -1107,33 +1107,59 @@ fn check_expr(&mut self, cx: &EarlyContext<'_>, expr: &ast::Expr) {
 
 declare_lint_pass!(InvalidNoMangleItems => [NO_MANGLE_CONST_ITEMS, NO_MANGLE_GENERIC_ITEMS]);
 
+impl InvalidNoMangleItems {
+    /// Lints functions with a fixed symbol name, through `#[no_mangle]` or `#[export_name]`,
+    /// that have type or const parameters, including those of the impl they're in.
+    fn check_no_mangle_on_generic_fn(
+        cx: &LateContext<'_>,
+        attrs: &[ast::Attribute],
+        generics: &[&hir::Generics<'_>],
+        span: Span,
+    ) {
+        let no_mangle_attr = match attrs
+            .iter()
+            .find(|attr| attr.has_name(sym::no_mangle) || attr.has_name(sym::export_name))
+        {
+            Some(attr) => attr,
+            None => return,
+        };
+        let is_generic = generics.iter().flat_map(|generics| generics.params).any(|param| {
+            !matches!(param.kind, GenericParamKind::Lifetime { .. })
+        });
+        if is_generic {
+            cx.struct_span_lint(NO_MANGLE_GENERIC_ITEMS, span, |lint| {
+                lint.build("functions generic over types or consts must be mangled")
+                    .span_suggestion_short(
+                        no_mangle_attr.span,
+                        "remove this attribute",
+                        String::new(),
+                        // Use of `#[no_mangle]` suggests FFI intent; correct
+                        // fix may be to monomorphize source by hand
+                        Applicability::MaybeIncorrect,
+                    )
+                    .emit();
+            });
+        }
+    }
+}
+
 impl<'tcx> LateLintPass<'tcx> for InvalidNoMangleItems {
     fn check_item(&mut self, cx: &LateContext<'_>, it: &hir::Item<'_>) {
         let attrs = cx.tcx.hir().attrs(it.hir_id());
         match it.kind {
             hir::ItemKind::Fn(.., ref generics, _) => {
-                if let Some(no_mangle_attr) = cx.sess().find_by_name(attrs, sym::no_mangle) {
-                    for param in generics.params {
-                        match param.kind {
-                            GenericParamKind::Lifetime { .. } => {}
-                            GenericParamKind::Type { .. } | GenericParamKind::Const { .. } => {
-                                cx.struct_span_lint(NO_MANGLE_GENERIC_ITEMS, it.span, |lint| {
-                                    lint.build(
-                                        "functions generic over types or consts must be mangled",
-                                    )
-                                    .span_suggestion_short(
-                                        no_mangle_attr.span,
-                                        "remove this attribute",
-                                        String::new(),
-                                        // Use of `#[no_mangle]` suggests FFI intent; correct
-                                        // fix may be to monomorphize source by hand
-                                        Applicability::MaybeIncorrect,
-                                    )
-                                    .emit();
-                                });
-                                break;
-                            }
-                        }
+                Self::check_no_mangle_on_generic_fn(cx, attrs, &[generics], it.span);
+            }
+            hir::ItemKind::Impl(ref impl_) => {
+                for item in impl_.items {
+                    if let hir::AssocItemKind::Fn { .. } = item.kind {
+                        let impl_item = cx.tcx.hir().impl_item(item.id);
+                        Self::check_no_mangle_on_generic_fn(
+                            cx,
+                            cx.tcx.hir().attrs(impl_item.hir_id()),
+                            &[&impl_.generics, &impl_item.generics],
+                            impl_item.span,
+                        );
                     }
                 }
             }
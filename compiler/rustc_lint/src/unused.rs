@@ -102,7 +102,21 @@ fn check_stmt(&mut self, cx: &LateContext<'_>, s: &hir::Stmt<'_>) {
         }
 
         let ty = cx.typeck_results().expr_ty(&expr);
-        let type_permits_lack_of_use = check_must_use_ty(cx, ty, &expr, s.span, "", "", 1);
+        let mut type_permits_lack_of_use = check_must_use_ty(cx, ty, &expr, s.span, "", "", 1);
+
+        // A coercion can hide a `#[must_use]` type, like a `Box` of one unsized by a branch of
+        // an `if` to the `Box<dyn Any>` of the other branch, so also look at the values the
+        // statement discards as they were before being adjusted.
+        if !type_permits_lack_of_use {
+            for (hir_id, tail_ty) in cx.typeck_results().stmt_expr_tail_tys(expr) {
+                if tail_ty != ty {
+                    let tail = cx.tcx.hir().expect_expr(hir_id);
+                    if check_must_use_ty(cx, tail_ty, tail, tail.span, "", "", 1) {
+                        type_permits_lack_of_use = true;
+                    }
+                }
+            }
+        }
 
         let mut fn_warned = false;
         let mut op_warned = false;
@@ -513,7 +527,10 @@ fn is_expr_delims_necessary(inner: &ast::Expr, followed_by_block: bool) -> bool
         lhs_needs_parens
             || (followed_by_block
                 && match inner.kind {
-                    ExprKind::Ret(_) | ExprKind::Break(..) | ExprKind::Yield(..) => true,
+                    ExprKind::Ret(_)
+                    | ExprKind::Break(..)
+                    | ExprKind::Yield(..)
+                    | ExprKind::Yeet(..) => true,
                     _ => parser::contains_exterior_struct_lit(&inner),
                 })
     }
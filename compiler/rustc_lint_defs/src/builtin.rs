@@ -2974,6 +2974,12 @@
         LARGE_ASSIGNMENTS,
         FUTURE_PRELUDE_COLLISION,
         RESERVED_PREFIX,
+        HASH_EQ_MISMATCH,
+        UNINHABITED_READS,
+        UNNECESSARY_MUT_AUTOREF,
+        SHADOWED_TRAIT_METHODS,
+        FUTURE_IMPL_COLLISION,
+        RECURSIVE_DEFAULT_METHODS,
     ]
 }
 
@@ -3303,3 +3309,222 @@
     };
     crate_level_only
 }
+
+declare_lint! {
+    /// The `hash_eq_mismatch` lint detects types that derive one of `Hash` and
+    /// `PartialEq` and implement the other one by hand.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(hash_eq_mismatch)]
+    ///
+    /// #[derive(Hash)]
+    /// struct Name(String);
+    ///
+    /// impl PartialEq for Name {
+    ///     fn eq(&self, other: &Name) -> bool {
+    ///         self.0.eq_ignore_ascii_case(&other.0)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Collections like `HashMap` and constants used as patterns rely on
+    /// `a == b` implying that `a` and `b` have the same hash. A derived impl
+    /// compares or hashes every field, so it is only consistent with a
+    /// hand-written impl of the other trait if that impl happens to look at
+    /// exactly the same fields in the same way. Here, two `Name`s that only
+    /// differ in case are equal but have different hashes.
+    pub HASH_EQ_MISMATCH,
+    Allow,
+    "detects types deriving one of `Hash` and `PartialEq` and implementing the other by hand"
+}
+
+declare_lint! {
+    /// The `uninhabited_reads` lint detects values of uninhabited types that
+    /// are coerced to another type with the `uninhabited_coercions` feature.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![feature(uninhabited_coercions)]
+    ///
+    /// enum Void {}
+    ///
+    /// fn absurd(v: Void) -> u32 {
+    ///     v
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// A value of an uninhabited type can never exist, so an expression
+    /// reading one is never evaluated. Coercing it like a `!` is sound, but
+    /// unless it is meant as a proof that the code is unreachable, it is
+    /// likely a mistake, such as matching on the wrong value.
+    pub UNINHABITED_READS,
+    Warn,
+    "detects values of uninhabited types that are coerced to another type"
+}
+
+declare_lint! {
+    /// The `unnecessary_mut_autoref` lint detects method calls that mutably
+    /// borrow their receiver, when a method with the same name that only
+    /// needs a shared borrow is reachable through autoderef.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(unnecessary_mut_autoref)]
+    /// use std::ops::Deref;
+    ///
+    /// struct Inner;
+    ///
+    /// impl Inner {
+    ///     fn value(&self) -> u32 { 0 }
+    /// }
+    ///
+    /// struct Outer(Inner);
+    ///
+    /// impl Outer {
+    ///     fn value(&mut self) -> u32 { 1 }
+    /// }
+    ///
+    /// impl Deref for Outer {
+    ///     type Target = Inner;
+    ///     fn deref(&self) -> &Inner { &self.0 }
+    /// }
+    ///
+    /// fn main() {
+    ///     let mut outer = Outer(Inner);
+    ///     outer.value();
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Method resolution tries the autoref'd receiver `&mut Outer` before
+    /// dereferencing it to `Inner`, so `Outer::value` is called. This
+    /// requires the receiver to be mutable and keeps it mutably borrowed for
+    /// as long as the result is used, which may not be what was intended if
+    /// `Inner::value` would have done.
+    pub UNNECESSARY_MUT_AUTOREF,
+    Allow,
+    "detects method calls that mutably autoref their receiver when a method only needing a \
+     shared borrow is available through autoderef"
+}
+
+declare_lint! {
+    /// The `shadowed_trait_methods` lint detects method calls that resolve
+    /// to an inherent method when a method of a trait in scope would also
+    /// apply to the receiver.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(shadowed_trait_methods)]
+    /// struct Meters(f64);
+    ///
+    /// impl Meters {
+    ///     fn describe(self) -> String { format!("{}", self.0) }
+    /// }
+    ///
+    /// trait Describe {
+    ///     fn describe(&self) -> String;
+    /// }
+    ///
+    /// impl Describe for Meters {
+    ///     fn describe(&self) -> String { format!("{} m", self.0) }
+    /// }
+    ///
+    /// fn main() {
+    ///     Meters(1.0).describe();
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// For every autoderef step of the receiver, method resolution tries the
+    /// receiver by value, then autoref'd as `&` and then as `&mut`, and at
+    /// each of these it looks at inherent methods before trait methods. An
+    /// inherent method therefore wins over a trait method that applies to the
+    /// same receiver, even if the trait method would have been picked before
+    /// the inherent method was added. Calling the trait method through its
+    /// path, like `Describe::describe(&meters)`, makes the choice explicit.
+    pub SHADOWED_TRAIT_METHODS,
+    Allow,
+    "detects method calls where an inherent method is picked over an applicable trait method"
+}
+
+declare_lint! {
+    /// The `future_impl_collision` lint detects method calls that will
+    /// resolve to a different method once the standard library adds an impl
+    /// that it is expected to add in a future release.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(future_impl_collision)]
+    /// let boxed: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+    /// for x in boxed.into_iter() {
+    ///     let _: &i32 = x;
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// `Box<[T]>` doesn't implement `IntoIterator` yet, so `boxed.into_iter()`
+    /// dereferences the box and calls `into_iter` on `&[T]`, which iterates
+    /// over references. Once the impl for `Box<[T]>` is added, the same call
+    /// picks it instead because method resolution tries the receiver by value
+    /// before dereferencing it, and the loop iterates over values. Calling
+    /// `iter()` keeps iterating over references either way.
+    pub FUTURE_IMPL_COLLISION,
+    Allow,
+    "detects method calls that will resolve to another method once impls pending in the \
+     standard library are added"
+}
+
+declare_lint! {
+    /// The `recursive_default_methods` lint detects trait impls that override
+    /// none of the methods whose default bodies call each other.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(recursive_default_methods)]
+    /// trait Equal {
+    ///     fn eq(&self, other: &Self) -> bool { !self.ne(other) }
+    ///     fn ne(&self, other: &Self) -> bool { !self.eq(other) }
+    /// }
+    ///
+    /// struct Unit;
+    ///
+    /// impl Equal for Unit {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Traits sometimes define methods in terms of each other, so that an
+    /// impl can pick whichever one is the easiest to write. An impl that
+    /// overrides none of them compiles fine, but calling any of the methods
+    /// recurses until the stack overflows. Only calls that the default bodies
+    /// make every time they run are considered.
+    pub RECURSIVE_DEFAULT_METHODS,
+    Allow,
+    "detects trait impls that override none of the methods whose default bodies call each other"
+}
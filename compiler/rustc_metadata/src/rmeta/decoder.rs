@@ -779,6 +779,7 @@ fn get_trait_def(&self, item_id: DefIndex, sess: &Session) -> ty::TraitDef {
                     data.is_marker,
                     data.skip_array_during_method_dispatch,
                     data.specialization_kind,
+                    data.must_implement_one_of,
                     self.def_path_hash(item_id),
                 )
             }
@@ -790,6 +791,7 @@ fn get_trait_def(&self, item_id: DefIndex, sess: &Session) -> ty::TraitDef {
                 false,
                 false,
                 ty::trait_def::TraitSpecializationKind::None,
+                Vec::new(),
                 self.def_path_hash(item_id),
             ),
             _ => bug!("def-index does not refer to trait or trait alias"),
@@ -1388,6 +1390,25 @@ fn get_implementations_for_trait(
         }
     }
 
+    fn get_incoherent_impls(&self, tcx: TyCtxt<'tcx>, self_ty: DefId) -> &'tcx [DefId] {
+        if self.root.is_proc_macro_crate() {
+            // proc-macro crates export no impls.
+            return &[];
+        }
+
+        let self_ty = match self.reverse_translate_def_id(self_ty) {
+            Some(def_id) => (def_id.krate.as_u32(), def_id.index),
+            None => return &[],
+        };
+
+        match self.root.incoherent_impls.decode(self).find(|impls| impls.self_ty == self_ty) {
+            Some(impls) => tcx.arena.alloc_from_iter(
+                impls.impls.decode(self).map(|index| self.local_def_id(index)),
+            ),
+            None => &[],
+        }
+    }
+
     fn get_trait_of_item(&self, id: DefIndex) -> Option<DefId> {
         let def_key = self.def_key(id);
         match def_key.disambiguated_data.data {
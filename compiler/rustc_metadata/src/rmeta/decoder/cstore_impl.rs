@@ -200,6 +200,10 @@ fn into_args(self) -> (DefId, DefId) {
         cdata.get_implementations_for_trait(tcx, None)
     }
 
+    crate_incoherent_impls => {
+        cdata.get_incoherent_impls(tcx, other)
+    }
+
     visibility => { cdata.get_visibility(def_id.index) }
     dep_kind => {
         let r = *cdata.dep_kind.lock();
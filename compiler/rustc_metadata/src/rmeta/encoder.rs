@@ -589,6 +589,7 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
         // Encode the def IDs of impls, for coherence checking.
         i = self.position();
         let impls = self.encode_impls();
+        let incoherent_impls = self.encode_incoherent_impls();
         let impl_bytes = self.position() - i;
 
         let tcx = self.tcx;
@@ -697,6 +698,7 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
             foreign_modules,
             source_map,
             impls,
+            incoherent_impls,
             exported_symbols,
             interpret_alloc_index,
             tables,
@@ -862,10 +864,11 @@ fn should_encode_variances(def_kind: DefKind) -> bool {
         | DefKind::Variant
         | DefKind::Fn
         | DefKind::Ctor(..)
-        | DefKind::AssocFn => true,
+        | DefKind::AssocFn
+        | DefKind::AssocTy
+        | DefKind::OpaqueTy => true,
         DefKind::Mod
         | DefKind::Field
-        | DefKind::AssocTy
         | DefKind::AssocConst
         | DefKind::TyParam
         | DefKind::ConstParam
@@ -873,7 +876,6 @@ fn should_encode_variances(def_kind: DefKind) -> bool {
         | DefKind::Const
         | DefKind::ForeignMod
         | DefKind::TyAlias
-        | DefKind::OpaqueTy
         | DefKind::Impl
         | DefKind::Trait
         | DefKind::TraitAlias
@@ -1452,6 +1454,7 @@ fn encode_info_for_item(&mut self, def_id: DefId, item: &'tcx hir::Item<'tcx>) {
                     is_marker: trait_def.is_marker,
                     skip_array_during_method_dispatch: trait_def.skip_array_during_method_dispatch,
                     specialization_kind: trait_def.specialization_kind,
+                    must_implement_one_of: trait_def.must_implement_one_of.clone(),
                 };
 
                 EntryKind::Trait(self.lazy(data))
@@ -1773,6 +1776,37 @@ fn encode_impls(&mut self) -> Lazy<[TraitImpls]> {
         self.lazy(&all_impls)
     }
 
+    /// Encodes an index, mapping each type of another crate that allows incoherent inherent
+    /// impls to its (local) inherent impls.
+    fn encode_incoherent_impls(&mut self) -> Lazy<[IncoherentImpls]> {
+        empty_proc_macro!(self);
+        debug!("EncodeContext::encode_incoherent_impls()");
+        let tcx = self.tcx;
+        let mut all_impls: Vec<_> = tcx.crate_inherent_impls(()).incoherent_impls.iter().collect();
+
+        // Bring everything into deterministic order for hashing
+        all_impls.sort_by_cached_key(|&(&self_ty, _)| tcx.def_path_hash(self_ty));
+
+        let all_impls: Vec<_> = all_impls
+            .into_iter()
+            .map(|(self_ty, impls)| {
+                let mut impls: Vec<_> =
+                    impls.iter().map(|impl_def_id| impl_def_id.local_def_index).collect();
+                // Bring everything into deterministic order for hashing
+                impls.sort_by_cached_key(|&index| {
+                    tcx.hir().definitions().def_path_hash(LocalDefId { local_def_index: index })
+                });
+
+                IncoherentImpls {
+                    self_ty: (self_ty.krate.as_u32(), self_ty.index),
+                    impls: self.lazy(&impls),
+                }
+            })
+            .collect();
+
+        self.lazy(&all_impls)
+    }
+
     // Encodes all symbols exported from this crate into the metadata.
     //
     // This pass is seeded off the reachability list calculated in the
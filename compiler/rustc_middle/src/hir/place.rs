@@ -1,7 +1,8 @@
 use crate::ty;
 use crate::ty::Ty;
 
-use rustc_hir::HirId;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{HirId, ItemLocalMap, Mutability};
 use rustc_target::abi::VariantIdx;
 
 #[derive(
@@ -143,3 +144,67 @@ pub fn ty_before_projection(&self, projection_index: usize) -> Ty<'tcx> {
         if projection_index == 0 { self.base_ty } else { self.projections[projection_index - 1].ty }
     }
 }
+
+/// The outermost operation of a place expression, see the `place_categorizations` query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, HashStable)]
+pub enum PlaceCategory {
+    /// A local variable or argument of the body.
+    Local(HirId),
+    /// A variable of an enclosing body, captured by the closure whose body this is.
+    Upvar(HirId),
+    /// A named `static` item.
+    StaticItem(DefId),
+    /// A dereference, `*E`, either explicit or inserted by autoderef.
+    Deref(DerefKind),
+    /// A field of a struct, enum variant or tuple.
+    Field,
+    /// A field of a union.
+    UnionField,
+    /// An index `B[x]` into an array or a slice, or through the `Index` trait.
+    Index { overloaded: bool },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, HashStable)]
+pub enum DerefKind {
+    Ref(Mutability),
+    RawPtr(Mutability),
+    Box,
+    /// A dereference through the `Deref` or `DerefMut` trait.
+    Overloaded,
+}
+
+/// Why a place can't be assigned to or mutably borrowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, HashStable)]
+pub enum ImmutablePlaceReason<'tcx> {
+    /// The place is (part of) a binding that isn't declared `mut`.
+    BindingNotMut(HirId),
+    /// The place is (part of) a `static` that isn't declared `mut`.
+    ImmutableStatic(DefId),
+    /// The place is behind a shared reference of the given type, which is the value of the
+    /// given expression.
+    BehindRef(Ty<'tcx>, HirId),
+    /// The place is behind a `*const` pointer of the given type, which is the value of the given
+    /// expression.
+    BehindConstPtr(Ty<'tcx>, HirId),
+    /// The place is behind a dereference of a type that implements `Deref` but not `DerefMut`,
+    /// like `Rc<T>`, which is the type of the given expression.
+    BehindDeref(Ty<'tcx>, HirId),
+    /// The place is an index into a type that implements `Index` but not `IndexMut`, which is
+    /// the type of the given expression.
+    BehindIndex(Ty<'tcx>, HirId),
+}
+
+#[derive(Clone, Copy, Debug, HashStable)]
+pub struct PlaceCategorization<'tcx> {
+    pub category: PlaceCategory,
+    /// Whether the place can be assigned to or mutably borrowed, and if not, the reason found
+    /// closest to the place when walking from it to its base.
+    pub mutability: Result<(), ImmutablePlaceReason<'tcx>>,
+}
+
+/// Result of the `place_categorizations` query.
+#[derive(Debug, Default, HashStable)]
+pub struct PlaceCategorizations<'tcx> {
+    /// The place expressions of the body, before adjustments, by the local id of their `HirId`.
+    pub places: ItemLocalMap<PlaceCategorization<'tcx>>,
+}
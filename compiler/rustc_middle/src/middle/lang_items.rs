@@ -9,24 +9,54 @@
 
 use crate::ty::{self, TyCtxt};
 
+use rustc_errors::{DiagnosticBuilder, FatalError};
 use rustc_hir::def_id::DefId;
 use rustc_hir::LangItem;
+use rustc_span::symbol::sym;
 use rustc_span::Span;
 use rustc_target::spec::PanicStrategy;
 
 impl<'tcx> TyCtxt<'tcx> {
     /// Returns the `DefId` for a given `LangItem`.
-    /// If not found, fatally aborts compilation.
+    /// If not found, fatally aborts compilation, pointing at `span` as the code requiring it.
     pub fn require_lang_item(self, lang_item: LangItem, span: Option<Span>) -> DefId {
         self.lang_items().require(lang_item).unwrap_or_else(|msg| {
-            if let Some(span) = span {
-                self.sess.span_fatal(span, &msg)
-            } else {
-                self.sess.fatal(&msg)
+            let name = lang_item.name();
+            let mut err = match span {
+                Some(span) => {
+                    let mut err = self.sess.struct_span_fatal(span, &msg);
+                    let label = match span.desugaring_kind() {
+                        Some(kind) => {
+                            format!("this {} requires the `{}` lang item", kind.descr(), name)
+                        }
+                        None => format!("the `{}` lang item is required here", name),
+                    };
+                    err.span_label(span, label);
+                    err
+                }
+                None => self.sess.struct_fatal(&msg),
+            };
+            self.suggest_defining_lang_item(&mut err, lang_item);
+            if self.sess.contains_name(self.hir().krate_attrs(), sym::no_core) {
+                err.note(
+                    "`#![no_core]` crates have to define the lang items `core` usually provides",
+                );
             }
+            err.emit();
+            FatalError.raise()
         })
     }
 
+    /// Explains how to define the missing `lang_item`.
+    pub fn suggest_defining_lang_item(self, err: &mut DiagnosticBuilder<'_>, lang_item: LangItem) {
+        err.help(&format!(
+            "add `#[lang = \"{}\"]` to the {} that should be used as the `{}` lang item",
+            lang_item.name(),
+            lang_item.target(),
+            lang_item.name(),
+        ));
+    }
+
     pub fn fn_trait_kind_from_lang_item(self, id: DefId) -> Option<ty::ClosureKind> {
         let items = self.lang_items();
         match Some(id) {
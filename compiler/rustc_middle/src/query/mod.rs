@@ -438,6 +438,16 @@
         desc { |tcx| "computing inferred outlives predicates of `{}`", tcx.def_path_str(key) }
     }
 
+    /// Returns the field each inferred outlives predicate of a local item was inferred from
+    /// (e.g., for `struct Foo<'a, T> { x: &'a T }`, this would return `T: 'a` with the
+    /// field `x`).
+    query inferred_outlives_fields_of(key: LocalDefId) -> &'tcx [(ty::Predicate<'tcx>, DefId)] {
+        desc {
+            |tcx| "computing the fields inferred outlives predicates of `{}` come from",
+            tcx.def_path_str(key.to_def_id())
+        }
+    }
+
     /// Maps from the `DefId` of a trait to the list of
     /// super-predicates. This is a subset of the full list of
     /// predicates. We store these in a separate map because we must
@@ -472,6 +482,16 @@
         desc { |tcx| "computing trait definition for `{}`", tcx.def_path_str(key) }
         storage(ArenaCacheSelector<'tcx>)
     }
+
+    /// Maps each method with a default body of the local trait `key` to the methods of the
+    /// same trait that its default body unconditionally calls on `Self`.
+    query default_body_calls(key: LocalDefId) -> FxHashMap<DefId, Vec<DefId>> {
+        desc { |tcx|
+            "computing the calls between the default method bodies of `{}`",
+            tcx.def_path_str(key.to_def_id())
+        }
+        storage(ArenaCacheSelector<'tcx>)
+    }
     query adt_def(key: DefId) -> &'tcx ty::AdtDef {
         desc { |tcx| "computing ADT definition for `{}`", tcx.def_path_str(key) }
     }
@@ -600,6 +620,13 @@
         eval_always
     }
 
+    /// Maps a `DefId` of a type marked with `#[rustc_has_incoherent_inherent_impls]` to the
+    /// inherent impls of it in all crates other than the one defining it.
+    query incoherent_impls(key: DefId) -> &'tcx [DefId] {
+        desc { |tcx| "collecting all incoherent inherent impls for `{}`", tcx.def_path_str(key) }
+        eval_always
+    }
+
     /// The result of unsafety-checking this `LocalDefId`.
     query unsafety_check_result(key: LocalDefId) -> &'tcx mir::UnsafetyCheckResult {
         desc { |tcx| "unsafety-checking `{}`", tcx.def_path_str(key.to_def_id()) }
@@ -670,6 +697,11 @@
         desc { |tcx| "checking item types in {}", describe_as_module(key, tcx) }
     }
 
+    /// Checks that the items in the module, and the types they mention, are well-formed.
+    query check_mod_type_wf(key: LocalDefId) -> () {
+        desc { |tcx| "checking that types are well-formed in {}", describe_as_module(key, tcx) }
+    }
+
     query check_mod_privacy(key: LocalDefId) -> () {
         desc { |tcx| "checking privacy in {}", describe_as_module(key, tcx) }
     }
@@ -704,9 +736,19 @@
         desc { |tcx| "type-checking `{}`", tcx.def_path_str(key.to_def_id()) }
         cache_on_disk_if { true }
     }
+    /// Type-checks the body of `key` and returns both of the results of its writeback: the
+    /// `typeck` results and the `used_trait_imports`. Nothing else should use this query
+    /// directly: going through those two queries instead means that a body edit that only
+    /// changes one of them doesn't invalidate what depends on the other.
+    query typeck_and_used_trait_imports(
+        key: LocalDefId
+    ) -> (&'tcx ty::TypeckResults<'tcx>, &'tcx FxHashSet<LocalDefId>) {
+        no_hash
+        desc { |tcx| "type-checking `{}`", tcx.def_path_str(key.to_def_id()) }
+    }
     query typeck_const_arg(
         key: (LocalDefId, DefId)
-    ) -> &'tcx ty::TypeckResults<'tcx> {
+    ) -> (&'tcx ty::TypeckResults<'tcx>, &'tcx FxHashSet<LocalDefId>) {
         desc {
             |tcx| "type-checking the const argument `{}`",
             tcx.def_path_str(key.0.to_def_id()),
@@ -724,11 +766,33 @@
         }
     }
 
+    /// The trait imports used by method resolution in the body of `key`.
+    ///
+    /// These are produced by writeback along with the `typeck` results but kept apart from them,
+    /// see `typeck_and_used_trait_imports`.
     query used_trait_imports(key: LocalDefId) -> &'tcx FxHashSet<LocalDefId> {
         desc { |tcx| "used_trait_imports `{}`", tcx.def_path_str(key.to_def_id()) }
         cache_on_disk_if { true }
     }
 
+    /// Categorizes the place expressions in the body of `key`, and explains why the ones that
+    /// can't be assigned to or mutably borrowed are immutable.
+    query place_categorizations(
+        key: LocalDefId
+    ) -> crate::hir::place::PlaceCategorizations<'tcx> {
+        storage(ArenaCacheSelector<'tcx>)
+        desc { |tcx| "categorizing the places of `{}`", tcx.def_path_str(key.to_def_id()) }
+    }
+
+    /// Records what every method call in the body of `key` was resolved to: the impl or trait
+    /// default that was selected, and with which substitutions.
+    query method_resolutions(
+        key: LocalDefId
+    ) -> crate::ty::method_resolution::MethodResolutions<'tcx> {
+        storage(ArenaCacheSelector<'tcx>)
+        desc { |tcx| "resolving the method calls of `{}`", tcx.def_path_str(key.to_def_id()) }
+    }
+
     query has_typeck_results(def_id: DefId) -> bool {
         desc { |tcx| "checking whether `{}` has a body", tcx.def_path_str(def_id) }
     }
@@ -924,6 +988,12 @@
         desc { |tcx| "collecting attributes of `{}`", tcx.def_path_str(def_id) }
     }
 
+    /// Returns the names given to an item with `#[doc(alias = "...")]`, which method lookup
+    /// falls back on when a method can't be found by the name it was called with.
+    query doc_aliases(def_id: DefId) -> &'tcx [Symbol] {
+        desc { |tcx| "collecting the doc aliases of `{}`", tcx.def_path_str(def_id) }
+    }
+
     query codegen_fn_attrs(def_id: DefId) -> CodegenFnAttrs {
         desc { |tcx| "computing codegen attributes of `{}`", tcx.def_path_str(def_id) }
         storage(ArenaCacheSelector<'tcx>)
@@ -1263,6 +1333,12 @@
         desc { "looking up implementations of a trait in a crate" }
     }
 
+    /// Given a crate and a type marked with `#[rustc_has_incoherent_inherent_impls]`, look up
+    /// the inherent impls of that type in the crate.
+    query crate_incoherent_impls(_: (CrateNum, DefId)) -> &'tcx [DefId] {
+        desc { "looking up incoherent inherent impls of a type in a crate" }
+    }
+
     /// Given a crate, look up all trait impls in that crate.
     /// Return `(impl_id, self_ty)`.
     query all_trait_implementations(_: CrateNum)
@@ -1658,6 +1734,21 @@
         desc { "computing autoderef types for `{:?}`", goal }
     }
 
+    /// Lists the methods that can be called on a receiver of type `key.1` in the body of
+    /// `key.0`, grouped by the autoderef step method resolution picks them at. This is meant
+    /// for completion engines, which must offer exactly the methods a call would resolve to.
+    /// Methods from all traits are considered, not only those in scope.
+    query method_completion_candidates(
+        key: (LocalDefId, Ty<'tcx>)
+    ) -> MethodCompletions<'tcx> {
+        storage(ArenaCacheSelector<'tcx>)
+        desc { |tcx|
+            "computing the methods callable on `{}` in `{}`",
+            key.1,
+            tcx.def_path_str(key.0.to_def_id())
+        }
+    }
+
     query supported_target_features(_: CrateNum) -> FxHashMap<String, Option<Symbol>> {
         storage(ArenaCacheSelector<'tcx>)
         eval_always
@@ -195,6 +195,14 @@ pub enum ObligationCauseCode<'tcx> {
     /// Like `ItemObligation`, but with extra detail on the source of the obligation.
     BindingObligation(DefId, Span),
 
+    /// A where-clause of the method picked for a method call: the `index`th of the method's own
+    /// predicates, instantiated with the `substs` of the call.
+    MethodWhereClause {
+        method: DefId,
+        index: usize,
+        substs: SubstsRef<'tcx>,
+    },
+
     /// A type like `&'a T` is WF only if `T: 'a`.
     ReferenceOutlivesReferent(Ty<'tcx>),
 
@@ -321,6 +329,9 @@ pub enum ObligationCauseCode<'tcx> {
     /// Block implicit return
     BlockTailExpression(hir::HirId),
 
+    /// `yield` whose value must have the type yielded by an earlier `yield` of the generator
+    YieldExpression { prior_yield_span: Span },
+
     /// #[feature(trivial_bounds)] is not enabled
     TrivialBound,
 
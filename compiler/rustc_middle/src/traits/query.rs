@@ -14,6 +14,8 @@
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::struct_span_err;
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
 use rustc_span::source_map::Span;
 use std::iter::FromIterator;
 use std::mem;
@@ -200,6 +202,9 @@ pub struct MethodAutoderefStepsResult<'tcx> {
     /// If `true`, `steps` has been truncated due to reaching the
     /// recursion limit.
     pub reached_recursion_limit: bool,
+    /// If `Some(i)`, `steps` ends because the type of its last step dereferences to the type
+    /// of step `i` again.
+    pub deref_cycle_start: Option<usize>,
 }
 
 #[derive(Debug, HashStable)]
@@ -208,6 +213,34 @@ pub struct MethodAutoderefBadTy<'tcx> {
     pub ty: Canonical<'tcx, QueryResponse<'tcx, Ty<'tcx>>>,
 }
 
+/// Result from the `method_completion_candidates` query.
+#[derive(Debug, HashStable)]
+pub struct MethodCompletions<'tcx> {
+    /// The autoderef steps of the receiver, in the order method resolution visits them.
+    pub steps: Vec<MethodCompletionStep<'tcx>>,
+}
+
+#[derive(Debug, HashStable)]
+pub struct MethodCompletionStep<'tcx> {
+    pub self_ty: Ty<'tcx>,
+    pub autoderefs: usize,
+    /// `true` if the type results from a dereference of a raw pointer.
+    pub from_unsafe_deref: bool,
+    /// `true` for the `[T]` step that follows an array `[T; N]`.
+    pub unsize: bool,
+    /// The methods that method resolution picks at this step, sorted by name.
+    pub methods: Vec<MethodCompletion>,
+}
+
+#[derive(Debug, HashStable)]
+pub struct MethodCompletion {
+    pub def_id: DefId,
+    /// The trait the method is resolved through, `None` for inherent methods.
+    pub trait_def_id: Option<DefId>,
+    /// The autoref applied to the receiver at this step before calling the method, if any.
+    pub autoref: Option<hir::Mutability>,
+}
+
 /// Result from the `normalize_projection_ty` query.
 #[derive(Clone, Debug, HashStable, TypeFoldable, Lift)]
 pub struct NormalizationResult<'tcx> {
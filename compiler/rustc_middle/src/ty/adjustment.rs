@@ -77,7 +77,7 @@ pub enum PointerCast {
 ///    At some point, of course, `Box` should move out of the compiler, in which
 ///    case this is analogous to transforming a struct. E.g., `Box<[i32; 4]>` ->
 ///    `Box<[i32]>` is an `Adjust::Unsize` with the target `Box<[i32]>`.
-#[derive(Clone, TyEncodable, TyDecodable, HashStable, TypeFoldable)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, TyEncodable, TyDecodable, HashStable, TypeFoldable)]
 pub struct Adjustment<'tcx> {
     pub kind: Adjust<'tcx>,
     pub target: Ty<'tcx>,
@@ -89,7 +89,32 @@ pub fn is_region_borrow(&self) -> bool {
     }
 }
 
+/// A coercion that type checking applied to an expression. These are only recorded for
+/// `-Zunpretty=typeck-coercions`, to debug the choices made by `coercion.rs`.
 #[derive(Clone, Debug, TyEncodable, TyDecodable, HashStable, TypeFoldable)]
+pub struct CoercionSite<'tcx> {
+    /// The type of the expression before it was coerced.
+    pub source: Ty<'tcx>,
+    pub target: Ty<'tcx>,
+    /// The adjustments the coercion chose, from `source` to `target`.
+    pub adjustments: &'tcx ty::List<Adjustment<'tcx>>,
+    /// The obligations registered by the coercion, like the `CoerceUnsized` obligation of an
+    /// unsizing coercion.
+    pub obligations: Vec<ty::Predicate<'tcx>>,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    TyEncodable,
+    TyDecodable,
+    HashStable,
+    TypeFoldable,
+)]
 pub enum Adjust<'tcx> {
     /// Go from ! to any type.
     NeverToAny,
@@ -107,7 +132,18 @@ pub enum Adjust<'tcx> {
 /// call, with the signature `&'a T -> &'a U` or `&'a mut T -> &'a mut U`.
 /// The target type is `U` in both cases, with the region and mutability
 /// being those shared by both the receiver and the returned reference.
-#[derive(Copy, Clone, PartialEq, Debug, TyEncodable, TyDecodable, HashStable, TypeFoldable)]
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    TyEncodable,
+    TyDecodable,
+    HashStable,
+    TypeFoldable,
+)]
 pub struct OverloadedDeref<'tcx> {
     pub region: ty::Region<'tcx>,
     pub mutbl: hir::Mutability,
@@ -137,20 +173,21 @@ pub fn method_call(&self, tcx: TyCtxt<'tcx>, source: Ty<'tcx>) -> (DefId, Substs
 /// into method calls:
 /// - using `x.some_method()` syntax, where some_method takes `&mut self`,
 /// - using `Foo::some_method(&mut x, ...)` syntax,
-/// - binary assignment operators (`+=`, `-=`, `*=`, etc.).
+/// - binary assignment operators (`+=`, `-=`, `*=`, etc.),
+/// - mutable overloaded indexing (`x[i]` through `IndexMut`).
 /// Anything else should be rejected until generalized two-phase borrow support
 /// is implemented. Right now, dataflow can't handle the general case where there
 /// is more than one use of a mutable borrow, and we don't want to accept too much
 /// new code via two-phase borrows, so we try to limit where we create two-phase
 /// capable mutable borrows.
 /// See #49434 for tracking.
-#[derive(Copy, Clone, PartialEq, Debug, TyEncodable, TyDecodable, HashStable)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, TyEncodable, TyDecodable, HashStable)]
 pub enum AllowTwoPhase {
     Yes,
     No,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug, TyEncodable, TyDecodable, HashStable)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, TyEncodable, TyDecodable, HashStable)]
 pub enum AutoBorrowMutability {
     Mut { allow_two_phase_borrow: AllowTwoPhase },
     Not,
@@ -165,7 +202,18 @@ fn from(m: AutoBorrowMutability) -> Self {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Debug, TyEncodable, TyDecodable, HashStable, TypeFoldable)]
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    TyEncodable,
+    TyDecodable,
+    HashStable,
+    TypeFoldable,
+)]
 pub enum AutoBorrow<'tcx> {
     /// Converts from T to &T.
     Ref(ty::Region<'tcx>, AutoBorrowMutability),
@@ -12,7 +12,9 @@
 use crate::middle::resolve_lifetime::{self, LifetimeScopeForPath, ObjectLifetimeDefault};
 use crate::middle::stability;
 use crate::mir::interpret::{self, AllocId, Allocation, ConstValue, Scalar};
-use crate::mir::{Body, Field, Local, Place, PlaceElem, ProjectionKind, Promoted};
+use crate::mir::{
+    Body, Field, Local, Place, PlaceElem, ProjectionKind, Promoted, UnsafetyViolationDetails,
+};
 use crate::thir::Thir;
 use crate::traits;
 use crate::ty::query::{self, OnDiskCache, TyCtxtAt};
@@ -55,6 +57,7 @@
 use rustc_session::lint::{Level, Lint};
 use rustc_session::Session;
 use rustc_span::def_id::StableCrateId;
+use rustc_span::edition::Edition;
 use rustc_span::source_map::MultiSpan;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{Span, DUMMY_SP};
@@ -102,6 +105,7 @@ pub struct CtxtInterners<'tcx> {
     /// Const allocations.
     allocation: InternedSet<'tcx, Allocation>,
     bound_variable_kinds: InternedSet<'tcx, List<ty::BoundVariableKind>>,
+    adjustments: InternedSet<'tcx, List<ty::adjustment::Adjustment<'tcx>>>,
 }
 
 impl<'tcx> CtxtInterners<'tcx> {
@@ -121,6 +125,7 @@ fn new(arena: &'tcx WorkerLocal<Arena<'tcx>>) -> CtxtInterners<'tcx> {
             const_: Default::default(),
             allocation: Default::default(),
             bound_variable_kinds: Default::default(),
+            adjustments: Default::default(),
         }
     }
 
@@ -323,6 +328,118 @@ pub struct GeneratorInteriorTypeCause<'tcx> {
     pub expr: Option<hir::HirId>,
 }
 
+/// An operation that requires `unsafe`, as found by type checking.
+#[derive(Copy, Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct UnsafeOp {
+    pub details: UnsafetyViolationDetails,
+    /// The unsafe block that allows the operation, or the unsafe function it's in. `None` when
+    /// the operation isn't in an unsafe context, which the unsafety checker reports.
+    pub context: Option<hir::HirId>,
+}
+
+/// The inherent methods that can modify the value of an overloaded index expression that is
+/// used mutably, for types like `HashMap` that implement `Index` but not `IndexMut`. Borrowck
+/// suggests them in place of the indexing it reports.
+#[derive(Copy, Clone, Debug, Default, TyEncodable, TyDecodable, HashStable)]
+pub struct IndexMutAlternatives {
+    /// `get_mut(index) -> Option<&mut Value>`.
+    pub get_mut: bool,
+    /// `insert(index, value)`.
+    pub insert: bool,
+    /// `entry(index)`, whose result can `or_insert(value)`.
+    pub entry: bool,
+}
+
+/// A fact about the value of a `match` scrutinee that follows from how it is computed. Matches
+/// are checked for exhaustiveness based on the type of the scrutinee alone, so these are only
+/// used to explain why a match that looks exhaustive isn't.
+#[derive(Copy, Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub enum ScrutineeFact {
+    /// The scrutinee is an unsigned integer that is at most `max`, like `x % 4` or `x & 0xf`.
+    IntAtMost { max: u128 },
+    /// The scrutinee is a slice of an array of length `len`, like `&array[..]`.
+    SliceLen { len: u64 },
+}
+
+/// The versions of the rules for when the temporaries created while evaluating the scrutinee of
+/// an `if let`, or of an `if let` guard, are dropped. Moving that point changes when destructors
+/// run, so the version in effect is chosen by the edition of the code.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, TyEncodable, TyDecodable, HashStable)]
+pub enum ScrutineeTempScopeVersion {
+    /// The temporaries outlive the whole `if let` expression, so they are still alive while its
+    /// `else` block runs, and those of an `if let` guard outlive the whole arm.
+    V1,
+    /// The temporaries are dropped at the end of the `then` block of an `if let`, before its
+    /// `else` block runs, and at the end of an `if let` guard.
+    V2,
+}
+
+impl ScrutineeTempScopeVersion {
+    pub fn for_edition(edition: Edition) -> Self {
+        match edition {
+            Edition::Edition2015 | Edition::Edition2018 | Edition::Edition2021 => Self::V1,
+        }
+    }
+}
+
+/// Where the temporaries of the scrutinee of an `if let` or of an `if let` guard are dropped
+/// under each version of the rules, which a migration lint can compare to find the scrutinees
+/// whose temporaries are dropped elsewhere in a newer edition.
+#[derive(Copy, Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct ScrutineeTempScope {
+    /// The version in effect for the scrutinee.
+    pub version: ScrutineeTempScopeVersion,
+    /// The node the temporaries live until the end of, at least, under `V1`.
+    pub v1_scope: hir::ItemLocalId,
+    /// The node the temporaries live until the end of under `V2`.
+    pub v2_scope: hir::ItemLocalId,
+}
+
+impl ScrutineeTempScope {
+    /// The node the temporaries live until the end of under `version`.
+    pub fn scope(&self, version: ScrutineeTempScopeVersion) -> hir::ItemLocalId {
+        match version {
+            ScrutineeTempScopeVersion::V1 => self.v1_scope,
+            ScrutineeTempScopeVersion::V2 => self.v2_scope,
+        }
+    }
+
+    /// The node the temporaries live until the end of under the version in effect.
+    pub fn current_scope(&self) -> hir::ItemLocalId {
+        self.scope(self.version)
+    }
+}
+
+/// Why type checking already knows an rvalue to be eligible for promotion to a `'static`
+/// constant, as in `&3` or `&[None, Some(1)]`. MIR promotion still has the final say, since it
+/// also rejects values with interior mutability or a `Drop` impl.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, TyEncodable, TyDecodable, HashStable)]
+pub enum PromotionCandidacy {
+    /// A literal, like `1` or `"str"`.
+    Literal,
+    /// A path to a constant, a const parameter, a function or a constructor.
+    ConstPath,
+    /// A call to a promotable `const fn`, or to any `const fn` in the initializer of a constant
+    /// or static, with eligible arguments.
+    ConstFnCall,
+    /// A tuple, array, struct, enum variant or repeat expression with eligible operands.
+    Aggregate,
+    /// A shared borrow of an eligible place.
+    Borrow,
+    /// A built-in unary or binary operation that can't panic, on eligible operands.
+    Operation,
+}
+
+/// An expression producing the value that an expression statement discards, like the tail of
+/// a block or an arm of a `match` in statement position, with its type before and after the
+/// adjustments applied to it. The adjusted type is the one the value has in the statement.
+#[derive(Copy, Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct StmtExprTail<'tcx> {
+    pub hir_id: hir::HirId,
+    pub unadjusted_ty: Ty<'tcx>,
+    pub adjusted_ty: Ty<'tcx>,
+}
+
 #[derive(TyEncodable, TyDecodable, Debug)]
 pub struct TypeckResults<'tcx> {
     /// The `HirId::owner` all `ItemLocalId`s in this table are relative to.
@@ -346,7 +463,7 @@ pub struct TypeckResults<'tcx> {
     /// Stores the type parameters which were substituted to obtain the type
     /// of this node. This only applies to nodes that refer to entities
     /// parameterized by type parameters, such as generic fns, types, or
-    /// other items.
+    /// other items. Empty substitutions aren't stored, see `node_substs`.
     node_substs: ItemLocalMap<SubstsRef<'tcx>>,
 
     /// This will either store the canonicalized types provided by the user
@@ -364,7 +481,9 @@ pub struct TypeckResults<'tcx> {
     /// `AscribeUserType` statement in MIR.
     pub user_provided_sigs: DefIdMap<CanonicalPolyFnSig<'tcx>>,
 
-    adjustments: ItemLocalMap<Vec<ty::adjustment::Adjustment<'tcx>>>,
+    /// The adjustments applied to each expression. These are interned, as large bodies tend to
+    /// repeat the same few sequences (like `Deref` followed by `Borrow`) over and over.
+    adjustments: ItemLocalMap<&'tcx List<ty::adjustment::Adjustment<'tcx>>>,
 
     /// Stores the actual binding mode for all instances of hir::BindingAnnotation.
     pat_binding_modes: ItemLocalMap<BindingMode>,
@@ -433,12 +552,6 @@ pub struct TypeckResults<'tcx> {
     /// expression to this set.
     coercion_casts: ItemLocalSet,
 
-    /// Set of trait imports actually used in the method resolution.
-    /// This is used for warning unused imports. During type
-    /// checking, this `Lrc` should not be cloned: it must have a ref-count
-    /// of 1 so that we can insert things into the set mutably.
-    pub used_trait_imports: Lrc<FxHashSet<LocalDefId>>,
-
     /// If any errors occurred while type-checking this body,
     /// this field will be set to `Some(ErrorReported)`.
     pub tainted_by_errors: Option<ErrorReported>,
@@ -485,6 +598,39 @@ pub struct TypeckResults<'tcx> {
     /// like this to allow `const_to_pat` to reliably handle this situation.
     pub treat_byte_string_as_slice: ItemLocalSet,
 
+    /// The operations type checking knows to require `unsafe`, like calls to unsafe functions
+    /// or dereferences of raw pointers, along with the unsafe context that allows each of them.
+    unsafe_ops: ItemLocalMap<UnsafeOp>,
+
+    /// The unsafe blocks nested in another unsafe context, mapped to the innermost enclosing
+    /// unsafe block or unsafe function, which already allows everything the nested block does.
+    nested_unsafe_blocks: ItemLocalMap<hir::HirId>,
+
+    /// The coercions applied to each expression, in the order they were made. Only recorded
+    /// for `-Zunpretty=typeck-coercions`, so this is empty otherwise.
+    coercion_sites: ItemLocalMap<Vec<ty::adjustment::CoercionSite<'tcx>>>,
+
+    /// The methods that could be used instead of the index expressions that need `IndexMut`
+    /// when the indexed type only implements `Index`.
+    index_mut_alternatives: ItemLocalMap<IndexMutAlternatives>,
+
+    /// Facts about the scrutinees of `match` expressions that exhaustiveness checking doesn't
+    /// take into account, keyed by the scrutinee.
+    scrutinee_facts: ItemLocalMap<ScrutineeFact>,
+
+    /// Where the temporaries of the scrutinees of `if let` expressions and `if let` guards are
+    /// dropped under each version of the rules, keyed by the scrutinee.
+    scrutinee_temp_scopes: ItemLocalMap<ScrutineeTempScope>,
+
+    /// The expressions type checking knows to be eligible for promotion, so that MIR promotion
+    /// and lints suggesting inline `const` blocks instead agree on them.
+    promotion_candidates: ItemLocalMap<PromotionCandidacy>,
+
+    /// The expressions producing the value of each expression statement, keyed by the
+    /// expression of the statement. Statements whose value is `()` or never produced have no
+    /// entry.
+    stmt_expr_tails: ItemLocalMap<Vec<StmtExprTail<'tcx>>>,
+
     /// Contains the data for evaluating the effect of feature `capture_disjoint_fields`
     /// on closure size.
     pub closure_size_eval: FxHashMap<DefId, ClosureSizeProfileData<'tcx>>,
@@ -507,13 +653,20 @@ pub fn new(hir_owner: LocalDefId) -> TypeckResults<'tcx> {
             liberated_fn_sigs: Default::default(),
             fru_field_types: Default::default(),
             coercion_casts: Default::default(),
-            used_trait_imports: Lrc::new(Default::default()),
             tainted_by_errors: None,
             concrete_opaque_types: Default::default(),
             closure_min_captures: Default::default(),
             closure_fake_reads: Default::default(),
             generator_interior_types: ty::Binder::dummy(Default::default()),
             treat_byte_string_as_slice: Default::default(),
+            unsafe_ops: Default::default(),
+            nested_unsafe_blocks: Default::default(),
+            coercion_sites: Default::default(),
+            index_mut_alternatives: Default::default(),
+            scrutinee_facts: Default::default(),
+            scrutinee_temp_scopes: Default::default(),
+            promotion_candidates: Default::default(),
+            stmt_expr_tails: Default::default(),
             closure_size_eval: Default::default(),
         }
     }
@@ -590,11 +743,15 @@ pub fn node_substs_mut(&mut self) -> LocalTableInContextMut<'_, SubstsRef<'tcx>>
         LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.node_substs }
     }
 
+    /// Returns the substitutions of the node, which are empty when it doesn't refer to anything
+    /// generic.
     pub fn node_substs(&self, id: hir::HirId) -> SubstsRef<'tcx> {
         validate_hir_id_for_typeck_results(self.hir_owner, id);
         self.node_substs.get(&id.local_id).cloned().unwrap_or_else(|| InternalSubsts::empty())
     }
 
+    /// Like `node_substs`, but returns `None` rather than empty substitutions. Note that once
+    /// writeback is done, empty substitutions are indistinguishable from missing ones.
     pub fn node_substs_opt(&self, id: hir::HirId) -> Option<SubstsRef<'tcx>> {
         validate_hir_id_for_typeck_results(self.hir_owner, id);
         self.node_substs.get(&id.local_id).cloned()
@@ -624,13 +781,15 @@ pub fn expr_ty_opt(&self, expr: &hir::Expr<'_>) -> Option<Ty<'tcx>> {
         self.node_type_opt(expr.hir_id)
     }
 
-    pub fn adjustments(&self) -> LocalTableInContext<'_, Vec<ty::adjustment::Adjustment<'tcx>>> {
+    pub fn adjustments(
+        &self,
+    ) -> LocalTableInContext<'_, &'tcx List<ty::adjustment::Adjustment<'tcx>>> {
         LocalTableInContext { hir_owner: self.hir_owner, data: &self.adjustments }
     }
 
     pub fn adjustments_mut(
         &mut self,
-    ) -> LocalTableInContextMut<'_, Vec<ty::adjustment::Adjustment<'tcx>>> {
+    ) -> LocalTableInContextMut<'_, &'tcx List<ty::adjustment::Adjustment<'tcx>>> {
         LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.adjustments }
     }
 
@@ -721,6 +880,108 @@ pub fn fru_field_types_mut(&mut self) -> LocalTableInContextMut<'_, Vec<Ty<'tcx>
         LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.fru_field_types }
     }
 
+    pub fn unsafe_ops(&self) -> LocalTableInContext<'_, UnsafeOp> {
+        LocalTableInContext { hir_owner: self.hir_owner, data: &self.unsafe_ops }
+    }
+
+    pub fn unsafe_ops_mut(&mut self) -> LocalTableInContextMut<'_, UnsafeOp> {
+        LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.unsafe_ops }
+    }
+
+    pub fn nested_unsafe_blocks(&self) -> LocalTableInContext<'_, hir::HirId> {
+        LocalTableInContext { hir_owner: self.hir_owner, data: &self.nested_unsafe_blocks }
+    }
+
+    pub fn nested_unsafe_blocks_mut(&mut self) -> LocalTableInContextMut<'_, hir::HirId> {
+        LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.nested_unsafe_blocks }
+    }
+
+    /// Whether the unsafe block `block` allows any operation that requires `unsafe`, as far as
+    /// type checking knows. Operations found by the unsafety checker alone, like accesses to
+    /// the fields of packed structs, aren't taken into account.
+    pub fn unsafe_block_is_used(&self, block: hir::HirId) -> bool {
+        validate_hir_id_for_typeck_results(self.hir_owner, block);
+        self.unsafe_ops.values().any(|op| op.context == Some(block))
+    }
+
+    /// The unsafe block or unsafe function that already covers the unsafe block `block`, if
+    /// it's nested in another unsafe context.
+    pub fn enclosing_unsafe_context(&self, block: hir::HirId) -> Option<hir::HirId> {
+        self.nested_unsafe_blocks().get(block).copied()
+    }
+
+    pub fn coercion_sites(
+        &self,
+    ) -> LocalTableInContext<'_, Vec<ty::adjustment::CoercionSite<'tcx>>> {
+        LocalTableInContext { hir_owner: self.hir_owner, data: &self.coercion_sites }
+    }
+
+    pub fn coercion_sites_mut(
+        &mut self,
+    ) -> LocalTableInContextMut<'_, Vec<ty::adjustment::CoercionSite<'tcx>>> {
+        LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.coercion_sites }
+    }
+
+    pub fn index_mut_alternatives(&self) -> LocalTableInContext<'_, IndexMutAlternatives> {
+        LocalTableInContext { hir_owner: self.hir_owner, data: &self.index_mut_alternatives }
+    }
+
+    pub fn index_mut_alternatives_mut(
+        &mut self,
+    ) -> LocalTableInContextMut<'_, IndexMutAlternatives> {
+        LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.index_mut_alternatives }
+    }
+
+    pub fn scrutinee_facts(&self) -> LocalTableInContext<'_, ScrutineeFact> {
+        LocalTableInContext { hir_owner: self.hir_owner, data: &self.scrutinee_facts }
+    }
+
+    pub fn scrutinee_facts_mut(&mut self) -> LocalTableInContextMut<'_, ScrutineeFact> {
+        LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.scrutinee_facts }
+    }
+
+    pub fn scrutinee_temp_scopes(&self) -> LocalTableInContext<'_, ScrutineeTempScope> {
+        LocalTableInContext { hir_owner: self.hir_owner, data: &self.scrutinee_temp_scopes }
+    }
+
+    pub fn scrutinee_temp_scopes_mut(
+        &mut self,
+    ) -> LocalTableInContextMut<'_, ScrutineeTempScope> {
+        LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.scrutinee_temp_scopes }
+    }
+
+    pub fn promotion_candidates(&self) -> LocalTableInContext<'_, PromotionCandidacy> {
+        LocalTableInContext { hir_owner: self.hir_owner, data: &self.promotion_candidates }
+    }
+
+    pub fn promotion_candidates_mut(&mut self) -> LocalTableInContextMut<'_, PromotionCandidacy> {
+        LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.promotion_candidates }
+    }
+
+    pub fn stmt_expr_tails(&self) -> LocalTableInContext<'_, Vec<StmtExprTail<'tcx>>> {
+        LocalTableInContext { hir_owner: self.hir_owner, data: &self.stmt_expr_tails }
+    }
+
+    pub fn stmt_expr_tails_mut(&mut self) -> LocalTableInContextMut<'_, Vec<StmtExprTail<'tcx>>> {
+        LocalTableInContextMut { hir_owner: self.hir_owner, data: &mut self.stmt_expr_tails }
+    }
+
+    /// The types that `must_use` checks of the expression statement `expr` have to consider:
+    /// those of the expressions producing its value, both before and after they were adjusted
+    /// to the type of the statement, along with each of these expressions. A coercion can turn
+    /// a `#[must_use]` type into one that isn't, so looking at the type of `expr` alone misses
+    /// it.
+    pub fn stmt_expr_tail_tys(
+        &self,
+        expr: &hir::Expr<'_>,
+    ) -> impl Iterator<Item = (hir::HirId, Ty<'tcx>)> + '_ {
+        validate_hir_id_for_typeck_results(self.hir_owner, expr.hir_id);
+        self.stmt_expr_tails.get(&expr.hir_id.local_id).into_iter().flatten().flat_map(|tail| {
+            let adjusted_ty = (tail.adjusted_ty != tail.unadjusted_ty).then(|| tail.adjusted_ty);
+            iter::once(tail.unadjusted_ty).chain(adjusted_ty).map(move |ty| (tail.hir_id, ty))
+        })
+    }
+
     pub fn is_coercion_cast(&self, hir_id: hir::HirId) -> bool {
         validate_hir_id_for_typeck_results(self.hir_owner, hir_id);
         self.coercion_casts.contains(&hir_id.local_id)
@@ -752,13 +1013,20 @@ fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHas
             ref liberated_fn_sigs,
             ref fru_field_types,
             ref coercion_casts,
-            ref used_trait_imports,
             tainted_by_errors,
             ref concrete_opaque_types,
             ref closure_min_captures,
             ref closure_fake_reads,
             ref generator_interior_types,
             ref treat_byte_string_as_slice,
+            ref unsafe_ops,
+            ref nested_unsafe_blocks,
+            ref coercion_sites,
+            ref index_mut_alternatives,
+            ref scrutinee_facts,
+            ref scrutinee_temp_scopes,
+            ref promotion_candidates,
+            ref stmt_expr_tails,
             ref closure_size_eval,
         } = *self;
 
@@ -779,13 +1047,20 @@ fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHas
             liberated_fn_sigs.hash_stable(hcx, hasher);
             fru_field_types.hash_stable(hcx, hasher);
             coercion_casts.hash_stable(hcx, hasher);
-            used_trait_imports.hash_stable(hcx, hasher);
             tainted_by_errors.hash_stable(hcx, hasher);
             concrete_opaque_types.hash_stable(hcx, hasher);
             closure_min_captures.hash_stable(hcx, hasher);
             closure_fake_reads.hash_stable(hcx, hasher);
             generator_interior_types.hash_stable(hcx, hasher);
             treat_byte_string_as_slice.hash_stable(hcx, hasher);
+            unsafe_ops.hash_stable(hcx, hasher);
+            nested_unsafe_blocks.hash_stable(hcx, hasher);
+            coercion_sites.hash_stable(hcx, hasher);
+            index_mut_alternatives.hash_stable(hcx, hasher);
+            scrutinee_facts.hash_stable(hcx, hasher);
+            scrutinee_temp_scopes.hash_stable(hcx, hasher);
+            promotion_candidates.hash_stable(hcx, hasher);
+            stmt_expr_tails.hash_stable(hcx, hasher);
             closure_size_eval.hash_stable(hcx, hasher);
         })
     }
@@ -1063,7 +1338,7 @@ pub fn typeck_opt_const_arg(
         def: ty::WithOptConstParam<LocalDefId>,
     ) -> &'tcx TypeckResults<'tcx> {
         if let Some(param_did) = def.const_param_did {
-            self.typeck_const_arg((def.did, param_did))
+            self.typeck_const_arg((def.did, param_did)).0
         } else {
             self.typeck(def.did)
         }
@@ -2095,6 +2370,7 @@ impl<'tcx> TyCtxt<'tcx> {
     projs: _intern_projs(ProjectionKind),
     place_elems: _intern_place_elems(PlaceElem<'tcx>),
     bound_variable_kinds: _intern_bound_variable_kinds(ty::BoundVariableKind),
+    adjustments: _intern_adjustments(ty::adjustment::Adjustment<'tcx>),
 );
 
 impl<'tcx> TyCtxt<'tcx> {
@@ -2538,6 +2814,13 @@ pub fn intern_bound_variable_kinds(
         if ts.is_empty() { List::empty() } else { self._intern_bound_variable_kinds(ts) }
     }
 
+    pub fn intern_adjustments(
+        self,
+        adjustments: &[ty::adjustment::Adjustment<'tcx>],
+    ) -> &'tcx List<ty::adjustment::Adjustment<'tcx>> {
+        if adjustments.is_empty() { List::empty() } else { self._intern_adjustments(adjustments) }
+    }
+
     pub fn mk_fn_sig<I>(
         self,
         inputs: I,
@@ -2607,6 +2890,18 @@ pub fn mk_bound_variable_kinds<
         iter.intern_with(|xs| self.intern_bound_variable_kinds(xs))
     }
 
+    pub fn mk_adjustments<
+        I: InternAs<
+            [ty::adjustment::Adjustment<'tcx>],
+            &'tcx List<ty::adjustment::Adjustment<'tcx>>,
+        >,
+    >(
+        self,
+        iter: I,
+    ) -> I::Output {
+        iter.intern_with(|xs| self.intern_adjustments(xs))
+    }
+
     /// Walks upwards from `id` to find a node which might change lint levels with attributes.
     /// It stops at `bound` and just returns it if reached.
     pub fn maybe_lint_level_root_bounded(self, mut id: HirId, bound: HirId) -> HirId {
@@ -1,11 +1,14 @@
 //! Diagnostics related methods for `TyS`.
 
 use crate::ty::TyKind::*;
-use crate::ty::{InferTy, TyCtxt, TyS};
+use crate::ty::subst::SubstsRef;
+use crate::ty::{self, InferTy, TyCtxt, TyS};
+use rustc_data_structures::fx::FxIndexMap;
 use rustc_errors::{Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::{QPath, TyKind, WhereBoundPredicate, WherePredicate};
+use rustc_span::Span;
 
 impl<'tcx> TyS<'tcx> {
     /// Similar to `TyS::is_primitive`, but also considers inferred numeric values to be primitive.
@@ -105,6 +108,259 @@ pub fn suggest_arbitrary_trait_bound(
     true
 }
 
+/// Where a `BoundInsertionPlanner` puts the new bounds of a type parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundPlacement {
+    /// After the bounds of an `impl Trait` in argument position, as in `impl Foo + Bar`.
+    ImplTrait,
+    /// After the bounds already written for the parameter, either in the generics or in its only
+    /// `where` predicate, as in `T: Foo + Bar`.
+    ExistingBound,
+    /// As the first bounds of a parameter that has none, as in `<T: Bar>`.
+    NewParamBound,
+    /// In new predicates at the end of the `where` clause, which is introduced if there is none.
+    WhereClause,
+}
+
+/// An edit planned by a `BoundInsertionPlanner`.
+#[derive(Clone, Debug)]
+pub struct BoundInsertion {
+    pub span: Span,
+    pub code: String,
+    pub placement: BoundPlacement,
+}
+
+/// Plans how to add missing bounds to the generic parameters of an item, so that suggestions
+/// merge them into the bounds and `where` clause the item already has instead of repeating a
+/// parameter or a bound. All the bounds of a parameter end up in the same place, and all the new
+/// `where` predicates in a single edit.
+pub struct BoundInsertionPlanner<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    generics: &'a hir::Generics<'a>,
+    /// The requested bounds, keyed by the name of the bounded parameter, in request order.
+    bounds: FxIndexMap<String, Vec<RequestedBound<'tcx>>>,
+}
+
+/// A bound requested from a `BoundInsertionPlanner`.
+struct RequestedBound<'tcx> {
+    constraint: String,
+    /// The trait of the bound, with its generic arguments when they are known, which is how the
+    /// bound is recognized among those the item already has.
+    trait_ref: Option<(DefId, Option<SubstsRef<'tcx>>)>,
+}
+
+impl<'a, 'tcx> BoundInsertionPlanner<'a, 'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, generics: &'a hir::Generics<'a>) -> Self {
+        BoundInsertionPlanner { tcx, generics, bounds: Default::default() }
+    }
+
+    /// Requests the bound `constraint` on `param_name`. Types that aren't parameters of the
+    /// item, like `T::Item`, are bounded in the `where` clause. Without the `DefId` of the
+    /// bound's trait, it can't be told apart from the bounds the item already has.
+    pub fn add_bound(&mut self, param_name: &str, constraint: &str, trait_def_id: Option<DefId>) {
+        self.request(
+            param_name,
+            RequestedBound {
+                constraint: constraint.to_owned(),
+                trait_ref: trait_def_id.map(|def_id| (def_id, None)),
+            },
+        );
+    }
+
+    /// Requests the bound `trait_ref` on its self type, the parameter `param_name`.
+    pub fn add_trait_bound(&mut self, param_name: &str, trait_ref: ty::TraitRef<'tcx>) {
+        self.request(
+            param_name,
+            RequestedBound {
+                constraint: trait_ref.print_only_trait_path().to_string(),
+                trait_ref: Some((trait_ref.def_id, Some(trait_ref.substs))),
+            },
+        );
+    }
+
+    fn request(&mut self, param_name: &str, bound: RequestedBound<'tcx>) {
+        let bounds = self.bounds.entry(param_name.to_owned()).or_default();
+        if !bounds.iter().any(|b| b.constraint == bound.constraint) {
+            bounds.push(bound);
+        }
+    }
+
+    /// The `where` predicates that bound exactly `param_name`, ignoring those bounding one of
+    /// its associated types.
+    fn where_predicates_of(&self, param_name: &str) -> Vec<&'a WhereBoundPredicate<'a>> {
+        self.generics
+            .where_clause
+            .predicates
+            .iter()
+            .filter_map(|predicate| match predicate {
+                WherePredicate::BoundPredicate(predicate) => match &predicate.bounded_ty.kind {
+                    TyKind::Path(QPath::Resolved(None, hir::Path { segments: [segment], .. }))
+                        if segment.ident.as_str() == param_name =>
+                    {
+                        Some(predicate)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The trait bounds the item already puts on `param`, either in its generics or in its
+    /// `where` clause.
+    fn existing_trait_refs(&self, param: &hir::GenericParam<'_>) -> Vec<ty::TraitRef<'tcx>> {
+        let hir = self.tcx.hir();
+        let owner = hir.local_def_id(hir.get_parent_item(param.hir_id));
+        self.tcx
+            .explicit_predicates_of(owner.to_def_id())
+            .predicates
+            .iter()
+            .filter_map(|(predicate, _)| match predicate.kind().skip_binder() {
+                ty::PredicateKind::Trait(predicate, _) => match predicate.self_ty().kind() {
+                    ty::Param(p) if p.name == param.name.ident().name => Some(predicate.trait_ref),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether `bound` is one of the `existing` bounds of its parameter. Bounds are compared by
+    /// trait and arguments rather than by how they are written, so `a::Foo` and `b::Foo` are
+    /// different bounds, while `Foo` and `crate::Foo` are the same.
+    fn has_bound(&self, existing: &[ty::TraitRef<'tcx>], bound: &RequestedBound<'tcx>) -> bool {
+        let (def_id, substs) = match bound.trait_ref {
+            Some(trait_ref) => trait_ref,
+            None => return false,
+        };
+        existing.iter().any(|existing| {
+            existing.def_id == def_id
+                && match substs {
+                    // The arguments besides `Self`, ignoring regions.
+                    Some(substs) => {
+                        self.tcx.erase_regions(existing.substs)[1..]
+                            == self.tcx.erase_regions(substs)[1..]
+                    }
+                    // Without the arguments, the bound is only known to be the same if the trait
+                    // takes none and `constraint` doesn't constrain any associated type.
+                    None => {
+                        self.tcx.generics_of(def_id).count() == 1
+                            && !bound.constraint.contains('<')
+                    }
+                }
+        })
+    }
+
+    /// Plans the edits adding the requested bounds that the item doesn't have yet. This is empty
+    /// if it already has all of them.
+    pub fn plan(&self) -> Vec<BoundInsertion> {
+        let mut insertions = vec![];
+        let mut where_predicates = vec![];
+        for (param_name, bounds) in &self.bounds {
+            let param_name = param_name.as_str();
+            let param =
+                self.generics.params.iter().find(|p| p.name.ident().as_str() == param_name);
+            let param_predicates = self.where_predicates_of(param_name);
+            let existing = param.map_or(vec![], |param| self.existing_trait_refs(param));
+            let missing = bounds
+                .iter()
+                .filter(|bound| !self.has_bound(&existing, bound))
+                .map(|bound| bound.constraint.as_str())
+                .collect::<Vec<_>>();
+            if missing.is_empty() {
+                continue;
+            }
+            let missing = missing.join(" + ");
+
+            let param = match param {
+                Some(param) => param,
+                None => {
+                    where_predicates.push(format!("{}: {}", param_name, missing));
+                    continue;
+                }
+            };
+            let has_default =
+                matches!(param.kind, hir::GenericParamKind::Type { default: Some(_), .. });
+            let (span, code, placement) = if param_name.starts_with("impl ") {
+                // `fn foo(t: impl Foo)` becomes `fn foo(t: impl Foo + Bar)`.
+                (param.span.shrink_to_hi(), format!(" + {}", missing), BoundPlacement::ImplTrait)
+            } else if let [predicate] = param_predicates[..] {
+                // `where T: Foo` becomes `where T: Foo + Bar`. With several predicates for the
+                // parameter it isn't clear which one to extend, so a new one is added instead.
+                (
+                    predicate.span.shrink_to_hi(),
+                    format!(" + {}", missing),
+                    BoundPlacement::ExistingBound,
+                )
+            } else if self.generics.where_clause.predicates.is_empty() && !has_default {
+                // Bounds are only added to the generics when there's no `where` clause to keep
+                // them together with. Given `trait Base<T = String>: Super<T>`, `T: Copy` goes
+                // in a `where` clause instead of `trait Base<T: Copy = String>: Super<T>`.
+                match param.bounds_span() {
+                    // `<T: Foo>` becomes `<T: Foo + Bar>`.
+                    Some(span) => (
+                        span.shrink_to_hi(),
+                        format!(" + {}", missing),
+                        BoundPlacement::ExistingBound,
+                    ),
+                    // `<T>` becomes `<T: Bar>`.
+                    None => (
+                        param.span.shrink_to_hi(),
+                        format!(": {}", missing),
+                        BoundPlacement::NewParamBound,
+                    ),
+                }
+            } else {
+                where_predicates.push(format!("{}: {}", param_name, missing));
+                continue;
+            };
+            insertions.push(BoundInsertion { span, code, placement });
+        }
+
+        if !where_predicates.is_empty() {
+            // `where X: Foo` becomes `where X: Foo, T: Bar, U: Baz`, and an item without a
+            // `where` clause gets `where T: Bar, U: Baz`.
+            let where_clause = &self.generics.where_clause;
+            let prefix = if where_clause.predicates.is_empty() { " where " } else { ", " };
+            insertions.push(BoundInsertion {
+                span: where_clause.tail_span_for_suggestion(),
+                code: format!("{}{}", prefix, where_predicates.join(", ")),
+                placement: BoundPlacement::WhereClause,
+            });
+        }
+        insertions
+    }
+
+    /// Suggests the planned edits with `msg`, returning `false` if there's nothing to add.
+    pub fn suggest(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        msg: &str,
+        applicability: Applicability,
+    ) -> bool {
+        match &self.plan()[..] {
+            [] => false,
+            [insertion] => {
+                err.span_suggestion_verbose(
+                    insertion.span,
+                    msg,
+                    insertion.code.clone(),
+                    applicability,
+                );
+                true
+            }
+            insertions => {
+                err.multipart_suggestion(
+                    msg,
+                    insertions.iter().map(|i| (i.span, i.code.clone())).collect(),
+                    applicability,
+                );
+                true
+            }
+        }
+    }
+}
+
 /// Suggest restricting a type param with a new bound.
 pub fn suggest_constraining_type_param(
     tcx: TyCtxt<'_>,
@@ -122,177 +378,37 @@ pub fn suggest_constraining_type_param(
         return false;
     };
 
-    const MSG_RESTRICT_BOUND_FURTHER: &str = "consider further restricting this bound";
-    let msg_restrict_type = format!("consider restricting type parameter `{}`", param_name);
-    let msg_restrict_type_further =
-        format!("consider further restricting type parameter `{}`", param_name);
-
     if def_id == tcx.lang_items().sized_trait() {
         // Type parameters are already `Sized` by default.
         err.span_label(param.span, &format!("this type parameter needs to be `{}`", constraint));
         return true;
     }
-    let mut suggest_restrict = |span| {
-        err.span_suggestion_verbose(
-            span,
-            MSG_RESTRICT_BOUND_FURTHER,
-            format!(" + {}", constraint),
-            Applicability::MachineApplicable,
-        );
-    };
-
-    if param_name.starts_with("impl ") {
-        // If there's an `impl Trait` used in argument position, suggest
-        // restricting it:
-        //
-        //   fn foo(t: impl Foo) { ... }
-        //             --------
-        //             |
-        //             help: consider further restricting this bound with `+ Bar`
-        //
-        // Suggestion for tools in this case is:
-        //
-        //   fn foo(t: impl Foo) { ... }
-        //             --------
-        //             |
-        //             replace with: `impl Foo + Bar`
 
-        suggest_restrict(param.span.shrink_to_hi());
-        return true;
-    }
-
-    if generics.where_clause.predicates.is_empty()
-        // Given `trait Base<T = String>: Super<T>` where `T: Copy`, suggest restricting in the
-        // `where` clause instead of `trait Base<T: Copy = String>: Super<T>`.
-        && !matches!(param.kind, hir::GenericParamKind::Type { default: Some(_), .. })
-    {
-        if let Some(bounds_span) = param.bounds_span() {
-            // If user has provided some bounds, suggest restricting them:
-            //
-            //   fn foo<T: Foo>(t: T) { ... }
-            //             ---
-            //             |
-            //             help: consider further restricting this bound with `+ Bar`
-            //
-            // Suggestion for tools in this case is:
-            //
-            //   fn foo<T: Foo>(t: T) { ... }
-            //          --
-            //          |
-            //          replace with: `T: Bar +`
-            suggest_restrict(bounds_span.shrink_to_hi());
-        } else {
-            // If user hasn't provided any bounds, suggest adding a new one:
-            //
-            //   fn foo<T>(t: T) { ... }
-            //          - help: consider restricting this type parameter with `T: Foo`
-            err.span_suggestion_verbose(
-                param.span.shrink_to_hi(),
-                &msg_restrict_type,
-                format!(": {}", constraint),
-                Applicability::MachineApplicable,
-            );
+    let mut planner = BoundInsertionPlanner::new(tcx, generics);
+    planner.add_bound(param_name, constraint, def_id);
+    let insertion = match planner.plan().pop() {
+        Some(insertion) => insertion,
+        // The bound is already there, so the suggestion wouldn't help.
+        None => return false,
+    };
+    let msg = match insertion.placement {
+        BoundPlacement::ImplTrait | BoundPlacement::ExistingBound => {
+            "consider further restricting this bound".to_string()
         }
-
-        true
-    } else {
-        // This part is a bit tricky, because using the `where` clause user can
-        // provide zero, one or many bounds for the same type parameter, so we
-        // have following cases to consider:
-        //
-        // 1) When the type parameter has been provided zero bounds
-        //
-        //    Message:
-        //      fn foo<X, Y>(x: X, y: Y) where Y: Foo { ... }
-        //             - help: consider restricting this type parameter with `where X: Bar`
-        //
-        //    Suggestion:
-        //      fn foo<X, Y>(x: X, y: Y) where Y: Foo { ... }
-        //                                           - insert: `, X: Bar`
-        //
-        //
-        // 2) When the type parameter has been provided one bound
-        //
-        //    Message:
-        //      fn foo<T>(t: T) where T: Foo { ... }
-        //                            ^^^^^^
-        //                            |
-        //                            help: consider further restricting this bound with `+ Bar`
-        //
-        //    Suggestion:
-        //      fn foo<T>(t: T) where T: Foo { ... }
-        //                            ^^
-        //                            |
-        //                            replace with: `T: Bar +`
-        //
-        //
-        // 3) When the type parameter has been provided many bounds
-        //
-        //    Message:
-        //      fn foo<T>(t: T) where T: Foo, T: Bar {... }
-        //             - help: consider further restricting this type parameter with `where T: Zar`
-        //
-        //    Suggestion:
-        //      fn foo<T>(t: T) where T: Foo, T: Bar {... }
-        //                                          - insert: `, T: Zar`
-        //
-        // Additionally, there may be no `where` clause whatsoever in the case that this was
-        // reached because the generic parameter has a default:
-        //
-        //    Message:
-        //      trait Foo<T=()> {... }
-        //             - help: consider further restricting this type parameter with `where T: Zar`
-        //
-        //    Suggestion:
-        //      trait Foo<T=()> where T: Zar {... }
-        //                     - insert: `where T: Zar`
-
-        if matches!(param.kind, hir::GenericParamKind::Type { default: Some(_), .. })
-            && generics.where_clause.predicates.len() == 0
-        {
-            // Suggest a bound, but there is no existing `where` clause *and* the type param has a
-            // default (`<T=Foo>`), so we suggest adding `where T: Bar`.
-            err.span_suggestion_verbose(
-                generics.where_clause.tail_span_for_suggestion(),
-                &msg_restrict_type_further,
-                format!(" where {}: {}", param_name, constraint),
-                Applicability::MachineApplicable,
-            );
-        } else {
-            let mut param_spans = Vec::new();
-
-            for predicate in generics.where_clause.predicates {
-                if let WherePredicate::BoundPredicate(WhereBoundPredicate {
-                    span,
-                    bounded_ty,
-                    ..
-                }) = predicate
-                {
-                    if let TyKind::Path(QPath::Resolved(_, path)) = &bounded_ty.kind {
-                        if let Some(segment) = path.segments.first() {
-                            if segment.ident.to_string() == param_name {
-                                param_spans.push(span);
-                            }
-                        }
-                    }
-                }
-            }
-
-            match param_spans[..] {
-                [&param_span] => suggest_restrict(param_span.shrink_to_hi()),
-                _ => {
-                    err.span_suggestion_verbose(
-                        generics.where_clause.tail_span_for_suggestion(),
-                        &msg_restrict_type_further,
-                        format!(", {}: {}", param_name, constraint),
-                        Applicability::MachineApplicable,
-                    );
-                }
-            }
+        BoundPlacement::NewParamBound => {
+            format!("consider restricting type parameter `{}`", param_name)
         }
-
-        true
-    }
+        BoundPlacement::WhereClause => {
+            format!("consider further restricting type parameter `{}`", param_name)
+        }
+    };
+    err.span_suggestion_verbose(
+        insertion.span,
+        &msg,
+        insertion.code,
+        Applicability::MachineApplicable,
+    );
+    true
 }
 
 /// Collect al types that have an implicit `'static` obligation that we could suggest `'_` for.
@@ -0,0 +1,44 @@
+//! The results of the `method_resolutions` query, which records what every method call of a
+//! body was resolved to once type checking is done, so that tools like rustdoc and Clippy don't
+//! have to repeat method probing and trait selection themselves.
+
+use crate::ty::SubstsRef;
+
+use rustc_hir::def_id::DefId;
+use rustc_hir::ItemLocalMap;
+
+/// The method that runs for a method call, as far as it can be told from the body.
+#[derive(Clone, Copy, Debug, PartialEq, HashStable)]
+pub enum MethodSource<'tcx> {
+    /// An inherent method, or a method of the selected trait impl.
+    Impl { impl_def_id: DefId, item: DefId, substs: SubstsRef<'tcx> },
+    /// The default body of a trait method, which the selected impl doesn't override.
+    TraitDefault { item: DefId, substs: SubstsRef<'tcx> },
+    /// A trait method called on a trait object, which is dispatched through its vtable.
+    Virtual,
+    /// A method implemented by the compiler, like `call` for closures and function pointers,
+    /// or `clone` for tuples.
+    Builtin,
+    /// A trait method whose implementation depends on the generic parameters of the body, like
+    /// one called through a where clause.
+    Generic,
+}
+
+/// How a method call, including an overloaded operator or a type-relative path like
+/// `Vec::new`, was resolved.
+#[derive(Clone, Copy, Debug, HashStable)]
+pub struct MethodResolution<'tcx> {
+    /// The method named by the call: an inherent method, or the method of the trait.
+    pub method: DefId,
+    /// The substitutions of `method`, including those of its trait or impl.
+    pub substs: SubstsRef<'tcx>,
+    pub source: MethodSource<'tcx>,
+}
+
+/// Result of the `method_resolutions` query.
+#[derive(Debug, Default, HashStable)]
+pub struct MethodResolutions<'tcx> {
+    /// The method calls of the body, by the local id of the `HirId` of the call expression or
+    /// path.
+    pub calls: ItemLocalMap<MethodResolution<'tcx>>,
+}
@@ -59,7 +59,9 @@
 pub use self::context::{
     tls, CanonicalUserType, CanonicalUserTypeAnnotation, CanonicalUserTypeAnnotations,
     CtxtInterners, DelaySpanBugEmitted, FreeRegionInfo, GeneratorInteriorTypeCause, GlobalCtxt,
-    Lift, TyCtxt, TypeckResults, UserType, UserTypeAnnotationIndex,
+    IndexMutAlternatives, Lift, PromotionCandidacy, ScrutineeFact, ScrutineeTempScope,
+    ScrutineeTempScopeVersion, StmtExprTail, TyCtxt, TypeckResults, UnsafeOp, UserType,
+    UserTypeAnnotationIndex,
 };
 pub use self::instance::{Instance, InstanceDef};
 pub use self::list::List;
@@ -87,6 +89,7 @@
 pub mod fold;
 pub mod inhabitedness;
 pub mod layout;
+pub mod method_resolution;
 pub mod normalize_erasing_regions;
 pub mod outlives;
 pub mod print;
@@ -505,6 +508,10 @@ pub struct CratePredicatesMap<'tcx> {
     /// predicate of its outlive bounds. If an item has no outlives
     /// bounds, it will have no entry.
     pub predicates: FxHashMap<DefId, &'tcx [(Predicate<'tcx>, Span)]>,
+
+    /// For each struct with outlive bounds, maps each of its predicates
+    /// to the field whose type it was inferred from.
+    pub predicate_fields: FxHashMap<DefId, &'tcx [(Predicate<'tcx>, DefId)]>,
 }
 
 impl<'tcx> Predicate<'tcx> {
@@ -1741,9 +1748,14 @@ pub fn impls_are_allowed_to_overlap(
         };
 
         let is_marker_overlap = {
+            // Marker traits with associated items are an error of their own, but their impls
+            // still can't overlap, as it would be ambiguous which impl's items to use.
             let is_marker_impl = |def_id: DefId| -> bool {
                 let trait_ref = self.impl_trait_ref(def_id);
-                trait_ref.map_or(false, |tr| self.trait_def(tr.def_id).is_marker)
+                trait_ref.map_or(false, |tr| {
+                    self.trait_def(tr.def_id).is_marker
+                        && self.associated_item_def_ids(tr.def_id).is_empty()
+                })
             };
             is_marker_impl(def_id1) && is_marker_impl(def_id2)
         };
@@ -2003,6 +2015,9 @@ pub fn provide(providers: &mut ty::query::Providers) {
 #[derive(Clone, Debug, Default, HashStable)]
 pub struct CrateInherentImpls {
     pub inherent_impls: LocalDefIdMap<Vec<DefId>>,
+    /// The inherent impls of this crate for types of other crates that are marked with
+    /// `#[rustc_has_incoherent_inherent_impls]`.
+    pub incoherent_impls: FxHashMap<DefId, Vec<LocalDefId>>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, TyEncodable, HashStable)]
@@ -25,8 +25,8 @@
     CanonicalTypeOpProvePredicateGoal, CanonicalTypeOpSubtypeGoal, NoSolution,
 };
 use crate::traits::query::{
-    DropckOutlivesResult, DtorckConstraint, MethodAutoderefStepsResult, NormalizationResult,
-    OutlivesBound,
+    DropckOutlivesResult, DtorckConstraint, MethodAutoderefStepsResult, MethodCompletions,
+    NormalizationResult, OutlivesBound,
 };
 use crate::traits::specialization_graph;
 use crate::traits::{self, ImplSource};
@@ -6,6 +6,7 @@
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::definitions::DefPathHash;
+use rustc_span::symbol::Ident;
 
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
@@ -43,6 +44,11 @@ pub struct TraitDef {
     /// on this trait.
     pub specialization_kind: TraitSpecializationKind,
 
+    /// The groups of methods given by `#[rustc_must_implement_one_of]` attributes on this
+    /// trait. The methods in each group have default bodies defined in terms of each other,
+    /// so every impl has to override at least one of them.
+    pub must_implement_one_of: Vec<Box<[Ident]>>,
+
     /// The ICH of this trait's DefPath, cached here so it doesn't have to be
     /// recomputed all the time.
     pub def_path_hash: DefPathHash,
@@ -88,6 +94,7 @@ pub fn new(
         is_marker: bool,
         skip_array_during_method_dispatch: bool,
         specialization_kind: TraitSpecializationKind,
+        must_implement_one_of: Vec<Box<[Ident]>>,
         def_path_hash: DefPathHash,
     ) -> TraitDef {
         TraitDef {
@@ -98,6 +105,7 @@ pub fn new(
             is_marker,
             skip_array_during_method_dispatch,
             specialization_kind,
+            must_implement_one_of,
             def_path_hash,
         }
     }
@@ -4,11 +4,11 @@
 use rustc_middle::mir::{Mutability, Place, PlaceRef, ProjectionElem};
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_middle::{
-    hir::place::PlaceBase,
+    hir::place::{ImmutablePlaceReason, PlaceBase},
     mir::{self, ClearCrossCrate, Local, LocalDecl, LocalInfo, LocalKind, Location},
 };
 use rustc_span::source_map::DesugaringKind;
-use rustc_span::symbol::{kw, Symbol};
+use rustc_span::symbol::{kw, sym, Symbol};
 use rustc_span::Span;
 
 use crate::borrow_check::diagnostics::BorrowedContentSource;
@@ -502,6 +502,10 @@ pub(crate) fn report_mutability_error(
                                 but it is not implemented for `{}`",
                             ty,
                         ));
+                        self.suggest_index_mut_alternatives(&mut err, span, ty);
+                    }
+                    Some(BorrowedContentSource::DerefSharedRef) => {
+                        self.explain_shared_ref_source(&mut err, span, acted_on);
                     }
                     _ => (),
                 }
@@ -515,6 +519,180 @@ pub(crate) fn report_mutability_error(
         err.buffer(&mut self.errors_buffer);
     }
 
+    /// Points out the call that returned the shared reference the place at `span` is behind,
+    /// suggesting the `_mut` variant of the method, like `get_mut` for `get`, if there is one.
+    fn explain_shared_ref_source(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        span: Span,
+        acted_on: &str,
+    ) {
+        let tcx = self.infcx.tcx;
+        let hir = tcx.hir();
+        let def_id = self.mir_def_id();
+
+        let pointer = match self.immutable_place_reason(span) {
+            Some(ImmutablePlaceReason::BehindRef(_, pointer)) => hir.expect_expr(pointer),
+            _ => return,
+        };
+        let mut expr = pointer;
+
+        // Look through `.unwrap()` and `.expect(..)` on an `Option<&T>` or a `Result<&T, E>`.
+        while let hir::ExprKind::MethodCall(segment, _, [receiver, ..], _) = expr.kind {
+            if segment.ident.name != sym::unwrap && segment.ident.name != sym::expect {
+                break;
+            }
+            expr = receiver;
+        }
+
+        if let hir::ExprKind::MethodCall(segment, ..) = expr.kind {
+            let mut_name = Symbol::intern(&format!("{}_mut", segment.ident));
+            let has_mut_variant = tcx
+                .typeck(def_id)
+                .type_dependent_def_id(expr.hir_id)
+                .and_then(|method| tcx.opt_associated_item(method))
+                .map_or(false, |method| {
+                    tcx.associated_items(method.container.id())
+                        .filter_by_name_unhygienic(mut_name)
+                        .any(|item| item.kind == ty::AssocKind::Fn)
+                });
+            if has_mut_variant {
+                err.span_suggestion(
+                    segment.ident.span,
+                    &format!("consider using `{}` to get a mutable reference", mut_name),
+                    mut_name.to_string(),
+                    Applicability::MaybeIncorrect,
+                );
+                return;
+            }
+        }
+        if let hir::ExprKind::MethodCall(..) | hir::ExprKind::Call(..) = pointer.kind {
+            err.span_label(
+                pointer.span,
+                format!(
+                    "this call returns a `&` reference, so the data it refers to cannot be {}",
+                    acted_on
+                ),
+            );
+        }
+    }
+
+    /// Suggests the methods that type checking found to modify the value of the indexing at
+    /// `span` in its stead, because the indexed type `ty` doesn't implement `IndexMut`. These
+    /// are `insert`, `get_mut` and the entry API of maps like `HashMap`.
+    fn suggest_index_mut_alternatives(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        span: Span,
+        ty: Ty<'tcx>,
+    ) {
+        let tcx = self.infcx.tcx;
+        let hir = tcx.hir();
+
+        let expr = match self.immutable_place_reason(span) {
+            Some(ImmutablePlaceReason::BehindIndex(_, base)) => {
+                hir.expect_expr(hir.get_parent_node(base))
+            }
+            _ => return,
+        };
+        let (base, index) = match expr.kind {
+            hir::ExprKind::Index(base, index) if !expr.span.from_expansion() => (base, index),
+            _ => return,
+        };
+        let alternatives =
+            match tcx.typeck(self.mir_def_id()).index_mut_alternatives().get(expr.hir_id) {
+                Some(&alternatives) => alternatives,
+                None => return,
+            };
+
+        let open_bracket = base.span.between(index.span);
+        let close_bracket = index.span.shrink_to_hi().with_hi(expr.span.hi());
+        let (assigned, deref) = match hir.get(hir.get_parent_node(expr.hir_id)) {
+            Node::Expr(hir::Expr { kind: hir::ExprKind::Assign(lhs, rhs, _), .. })
+                if lhs.hir_id == expr.hir_id =>
+            {
+                (Some(rhs), false)
+            }
+            Node::Expr(hir::Expr { kind: hir::ExprKind::AssignOp(_, lhs, _), .. }) => {
+                (None, lhs.hir_id == expr.hir_id)
+            }
+            _ => (None, false),
+        };
+        match assigned {
+            Some(rhs) => {
+                if alternatives.insert {
+                    err.multipart_suggestion(
+                        "use `.insert()` to set the value of the key, adding the key if it's \
+                         missing",
+                        vec![
+                            (open_bracket, ".insert(".to_string()),
+                            (index.span.between(rhs.span), ", ".to_string()),
+                            (rhs.span.shrink_to_hi(), ")".to_string()),
+                        ],
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+                if alternatives.get_mut {
+                    err.multipart_suggestion(
+                        "use `.get_mut()` to only modify the value of a key that is present",
+                        vec![
+                            (base.span.shrink_to_lo(), "if let Some(value) = ".to_string()),
+                            (open_bracket, ".get_mut(".to_string()),
+                            (close_bracket, ") { *value".to_string()),
+                            (rhs.span.shrink_to_hi(), "; }".to_string()),
+                        ],
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+                if alternatives.entry {
+                    err.multipart_suggestion(
+                        "use the entry API to only add the key with this value if it's missing",
+                        vec![
+                            (open_bracket, ".entry(".to_string()),
+                            (index.span.between(rhs.span), ").or_insert(".to_string()),
+                            (rhs.span.shrink_to_hi(), ")".to_string()),
+                        ],
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+            }
+            None if alternatives.get_mut => {
+                let mut suggestion = vec![
+                    (open_bracket, ".get_mut(".to_string()),
+                    (close_bracket, ").unwrap()".to_string()),
+                ];
+                // The value of a compound assignment has to be dereferenced explicitly.
+                if deref {
+                    suggestion.insert(0, (base.span.shrink_to_lo(), "*".to_string()));
+                }
+                err.multipart_suggestion(
+                    &format!(
+                        "use `.get_mut()` to get a mutable reference to the value in the `{}`",
+                        ty,
+                    ),
+                    suggestion,
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            None => {}
+        }
+    }
+
+    /// Finds the outermost place expression at `span`, which is the whole place for
+    /// assignments and the borrowed place for `&mut` borrows, and returns why it is immutable.
+    fn immutable_place_reason(&self, span: Span) -> Option<ImmutablePlaceReason<'tcx>> {
+        let tcx = self.infcx.tcx;
+        let hir = tcx.hir();
+        let owner = self.mir_hir_id().owner;
+        tcx.place_categorizations(self.mir_def_id())
+            .places
+            .iter()
+            .map(|(&local_id, place)| (hir.span(hir::HirId { owner, local_id }), local_id, place))
+            .filter(|(place_span, ..)| span.contains(*place_span))
+            .max_by_key(|(place_span, local_id, _)| (place_span.hi() - place_span.lo(), *local_id))
+            .and_then(|(.., place)| place.mutability.err())
+    }
+
     /// User cannot make signature of a trait mutable without changing the
     /// trait. So we find if this error belongs to a trait and if so we move
     /// suggestion to the trait or disable it if it is out of scope of this crate
@@ -13,11 +13,12 @@
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::{HirId, Pat};
 use rustc_middle::thir::PatKind;
-use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_middle::ty::{self, ScrutineeFact, Ty, TyCtxt};
 use rustc_session::lint::builtin::BINDINGS_WITH_VARIANT_NAME;
 use rustc_session::lint::builtin::{IRREFUTABLE_LET_PATTERNS, UNREACHABLE_PATTERNS};
 use rustc_session::parse::feature_err;
 use rustc_session::Session;
+use rustc_span::hygiene::DesugaringKind;
 use rustc_span::{sym, Span};
 use std::slice;
 
@@ -207,7 +208,8 @@ fn check_match(
         let is_empty_match = arms.is_empty();
         let witnesses = report.non_exhaustiveness_witnesses;
         if !witnesses.is_empty() {
-            non_exhaustive_match(&cx, scrut_ty, scrut.span, witnesses, is_empty_match);
+            let fact = self.typeck_results.scrutinee_facts().get(scrut.hir_id).copied();
+            non_exhaustive_match(&cx, scrut_ty, scrut.span, witnesses, is_empty_match, fact);
         }
     }
 
@@ -429,6 +431,9 @@ fn report_arm_reachability<'p, 'tcx>(
                         match arm_index {
                             // The arm with the user-specified pattern.
                             0 => unreachable_pattern(cx.tcx, arm.pat.span, arm.hir_id, None),
+                            // The wildcard arm of a `let` in a `let` chain, which is still
+                            // useful for its bindings even if it always matches.
+                            1 if arm.pat.span.is_desugaring(DesugaringKind::LetChain) => {}
                             // The arm with the wildcard pattern.
                             1 => irrefutable_let_pattern(cx.tcx, arm.pat.span, arm.hir_id, source),
                             _ => bug!(),
@@ -473,6 +478,7 @@ fn non_exhaustive_match<'p, 'tcx>(
     sp: Span,
     witnesses: Vec<super::Pat<'tcx>>,
     is_empty_match: bool,
+    fact: Option<ScrutineeFact>,
 ) {
     let non_empty_enum = match scrut_ty.kind() {
         ty::Adt(def, _) => def.is_enum() && !def.variants.is_empty(),
@@ -535,6 +541,24 @@ fn non_exhaustive_match<'p, 'tcx>(
             err.note("references are always considered inhabited");
         }
     }
+    match fact {
+        Some(ScrutineeFact::IntAtMost { max }) => {
+            err.note(&format!(
+                "the matched value is known to be at most `{}`, but exhaustiveness checking \
+                 only considers its type",
+                max,
+            ));
+        }
+        Some(ScrutineeFact::SliceLen { len }) => {
+            err.note(&format!(
+                "the matched slice always has length {}, but exhaustiveness checking considers \
+                 slices of any length",
+                len,
+            ));
+            err.help("consider matching on the array itself");
+        }
+        None => {}
+    }
     err.emit();
 }
 
@@ -653,10 +677,14 @@ fn check_borrow_conflicts_in_at_patterns(cx: &MatchVisitor<'_, '_>, pat: &Pat<'_
         Some(ty::BindByValue(_)) if is_binding_by_move(cx, pat.hir_id, pat.span) => {
             // We have `x @ pat` where `x` is by-move. Reject all borrows in `pat`.
             let mut conflicts_ref = Vec::new();
+            let mut borrowed_mutably = false;
             sub.each_binding(|_, hir_id, span, _| {
                 match typeck_results.extract_binding_mode(sess, hir_id, span) {
                     Some(ty::BindByValue(_)) | None => {}
-                    Some(ty::BindByReference(_)) => conflicts_ref.push(span),
+                    Some(ty::BindByReference(mutbl)) => {
+                        borrowed_mutably |= mutbl == Mutability::Mut;
+                        conflicts_ref.push(span);
+                    }
                 }
             });
             if !conflicts_ref.is_empty() {
@@ -665,11 +693,24 @@ fn check_borrow_conflicts_in_at_patterns(cx: &MatchVisitor<'_, '_>, pat: &Pat<'_
                     name,
                     typeck_results.node_type(pat.hir_id),
                 );
-                sess.struct_span_err(pat.span, "borrow of moved value")
-                    .span_label(binding_span, format!("value moved into `{}` here", name))
+                let mut err = sess.struct_span_err(pat.span, "borrow of moved value");
+                err.span_label(binding_span, format!("value moved into `{}` here", name))
                     .span_label(binding_span, occurs_because)
-                    .span_labels(conflicts_ref, "value borrowed here after move")
-                    .emit();
+                    .span_labels(conflicts_ref, "value borrowed here after move");
+                // `ref x @ pat` is fine as long as the bindings in `pat` don't borrow mutably.
+                let unannotated = matches!(
+                    pat.kind,
+                    hir::PatKind::Binding(hir::BindingAnnotation::Unannotated, ..)
+                );
+                if unannotated && !borrowed_mutably {
+                    err.span_suggestion_verbose(
+                        binding_span.shrink_to_lo(),
+                        "borrow this binding in the pattern to avoid moving the value",
+                        "ref ".to_string(),
+                        Applicability::MachineApplicable,
+                    );
+                }
+                err.emit();
             }
             return;
         }
@@ -447,22 +447,34 @@ fn parse_angle_arg(&mut self) -> PResult<'a, Option<AngleBracketedArg>> {
                         let bounds = self.parse_generic_bounds(Some(self.prev_token.span))?;
                         AssocTyConstraintKind::Bound { bounds }
                     } else if self.eat(&token::Eq) {
-                        // Parse associated type equality constraint
+                        // Parse associated type or const equality constraint
 
-                        let ty = self.parse_assoc_equality_term(ident, self.prev_token.span)?;
-                        AssocTyConstraintKind::Equality { ty }
+                        self.parse_assoc_equality_term(ident, self.prev_token.span)?
                     } else {
                         unreachable!();
                     };
 
                     let span = lo.to(self.prev_token.span);
-
-                    // Gate associated type bounds, e.g., `Iterator<Item: Ord>`.
-                    if let AssocTyConstraintKind::Bound { .. } = kind {
-                        self.sess.gated_spans.gate(sym::associated_type_bounds, span);
-                    }
                     let constraint =
                         AssocTyConstraint { id: ast::DUMMY_NODE_ID, ident, gen_args, kind, span };
+
+                    match constraint.kind {
+                        // Gate return type notation, e.g., `Trait<method(): Send>`.
+                        AssocTyConstraintKind::Bound { .. }
+                            if constraint.is_return_type_notation() =>
+                        {
+                            self.sess.gated_spans.gate(sym::return_type_notation, span);
+                        }
+                        // Gate associated type bounds, e.g., `Iterator<Item: Ord>`.
+                        AssocTyConstraintKind::Bound { .. } => {
+                            self.sess.gated_spans.gate(sym::associated_type_bounds, span);
+                        }
+                        // Gate associated const equality, e.g., `Trait<N = 3>`.
+                        AssocTyConstraintKind::ConstEquality { .. } => {
+                            self.sess.gated_spans.gate(sym::associated_const_equality, span);
+                        }
+                        AssocTyConstraintKind::Equality { .. } => {}
+                    }
                     Ok(Some(AngleBracketedArg::Constraint(constraint)))
                 } else {
                     Ok(Some(AngleBracketedArg::Arg(arg)))
@@ -474,17 +486,18 @@ fn parse_angle_arg(&mut self) -> PResult<'a, Option<AngleBracketedArg>> {
 
     /// Parse the term to the right of an associated item equality constraint.
     /// That is, parse `<term>` in `Item = <term>`.
-    /// Right now, this only admits types in `<term>`.
-    fn parse_assoc_equality_term(&mut self, ident: Ident, eq: Span) -> PResult<'a, P<ast::Ty>> {
+    /// The term is either a type or, for associated constants, a const argument.
+    fn parse_assoc_equality_term(
+        &mut self,
+        ident: Ident,
+        eq: Span,
+    ) -> PResult<'a, AssocTyConstraintKind> {
         let arg = self.parse_generic_arg()?;
         let span = ident.span.to(self.prev_token.span);
         match arg {
-            Some(GenericArg::Type(ty)) => return Ok(ty),
-            Some(GenericArg::Const(expr)) => {
-                self.struct_span_err(span, "cannot constrain an associated constant to a value")
-                    .span_label(ident.span, "this associated constant...")
-                    .span_label(expr.value.span, "...cannot be constrained to this value")
-                    .emit();
+            Some(GenericArg::Type(ty)) => return Ok(AssocTyConstraintKind::Equality { ty }),
+            Some(GenericArg::Const(value)) => {
+                return Ok(AssocTyConstraintKind::ConstEquality { value });
             }
             Some(GenericArg::Lifetime(lt)) => {
                 self.struct_span_err(span, "associated lifetimes are not supported")
@@ -511,7 +524,7 @@ fn parse_assoc_equality_term(&mut self, ident: Ident, eq: Span) -> PResult<'a, P
                     .emit();
             }
         }
-        Ok(self.mk_ty(span, ast::TyKind::Err))
+        Ok(AssocTyConstraintKind::Equality { ty: self.mk_ty(span, ast::TyKind::Err) })
     }
 
     /// We do not permit arbitrary expressions as const arguments. They must be one of:
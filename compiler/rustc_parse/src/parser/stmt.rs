@@ -56,7 +56,7 @@ pub fn parse_stmt(&mut self, force_collect: ForceCollect) -> PResult<'a, Option<
             }
         }
 
-        Ok(Some(if self.token.is_keyword(kw::Let) {
+        Ok(Some(if self.token.is_keyword(kw::Let) || self.is_super_let() {
             self.parse_local_mk(lo, attrs, capture_semi, force_collect)?
         } else if self.is_kw_followed_by_ident(kw::Mut) {
             self.recover_stmt_local(lo, attrs, "missing keyword", "let mut")?
@@ -202,8 +202,15 @@ fn parse_local_mk(
         force_collect: ForceCollect,
     ) -> PResult<'a, Stmt> {
         self.collect_tokens_trailing_token(attrs, force_collect, |this, attrs| {
+            let super_ = if this.eat_keyword(kw::Super) {
+                let span = this.prev_token.span;
+                this.sess.gated_spans.gate(sym::super_let, span);
+                Some(span)
+            } else {
+                None
+            };
             this.expect_keyword(kw::Let)?;
-            let local = this.parse_local(attrs.into())?;
+            let local = this.parse_local(super_, attrs.into())?;
             let trailing = if capture_semi && this.token.kind == token::Semi {
                 TrailingToken::Semi
             } else {
@@ -215,7 +222,7 @@ fn parse_local_mk(
 
     fn recover_local_after_let(&mut self, lo: Span, attrs: AttrWrapper) -> PResult<'a, Stmt> {
         self.collect_tokens_trailing_token(attrs, ForceCollect::No, |this, attrs| {
-            let local = this.parse_local(attrs.into())?;
+            let local = this.parse_local(None, attrs.into())?;
             // FIXME - maybe capture semicolon in recovery?
             Ok((
                 this.mk_stmt(lo.to(this.prev_token.span), StmtKind::Local(local)),
@@ -224,9 +231,14 @@ fn recover_local_after_let(&mut self, lo: Span, attrs: AttrWrapper) -> PResult<'
         })
     }
 
+    /// Is this the start of a `super let` statement?
+    fn is_super_let(&self) -> bool {
+        self.token.is_keyword(kw::Super) && self.is_keyword_ahead(1, &[kw::Let])
+    }
+
     /// Parses a local variable declaration.
-    fn parse_local(&mut self, attrs: AttrVec) -> PResult<'a, P<Local>> {
-        let lo = self.prev_token.span;
+    fn parse_local(&mut self, super_: Option<Span>, attrs: AttrVec) -> PResult<'a, P<Local>> {
+        let lo = super_.unwrap_or(self.prev_token.span);
         let (pat, colon) = self.parse_pat_before_ty(None, RecoverComma::Yes, "`let` bindings")?;
 
         let (err, ty) = if colon {
@@ -293,7 +305,16 @@ fn parse_local(&mut self, attrs: AttrVec) -> PResult<'a, P<Local>> {
             }
         };
         let hi = if self.token == token::Semi { self.token.span } else { self.prev_token.span };
-        Ok(P(ast::Local { ty, pat, init, id: DUMMY_NODE_ID, span: lo.to(hi), attrs, tokens: None }))
+        Ok(P(ast::Local {
+            super_,
+            ty,
+            pat,
+            init,
+            id: DUMMY_NODE_ID,
+            span: lo.to(hi),
+            attrs,
+            tokens: None,
+        }))
     }
 
     /// Parses the RHS of a local variable declaration (e.g., `= 14;`).
@@ -108,6 +108,8 @@ fn visit_expr(&mut self, e: &'hir hir::Expr<'hir>) {
                     };
                     match loop_kind {
                         None | Some(hir::LoopSource::Loop) => (),
+                        Some(hir::LoopSource::ForLoop)
+                            if self.sess.features_untracked().for_loop_break_value => {}
                         Some(kind) => {
                             let mut err = struct_span_err!(
                                 self.sess,
@@ -129,6 +131,29 @@ fn visit_expr(&mut self, e: &'hir hir::Expr<'hir>) {
                                     ),
                                 );
                             }
+                            err.help(&format!(
+                                "to produce a value, rewrite this `{}` loop as a `loop` {}",
+                                kind.name(),
+                                match kind {
+                                    hir::LoopSource::While => {
+                                        "that starts with `if !condition { break default_value; }`"
+                                    }
+                                    hir::LoopSource::WhileLet => {
+                                        "that matches on the scrutinee and breaks out with a \
+                                         default value when the pattern doesn't match"
+                                    }
+                                    _ => {
+                                        "that calls `next()` on the iterator and breaks out with \
+                                         a default value on `None`"
+                                    }
+                                },
+                            ));
+                            if kind == hir::LoopSource::ForLoop && self.sess.is_nightly_build() {
+                                err.help(
+                                    "add `#![feature(for_loop_break_value)]` to the crate \
+                                     attributes to enable `break` with a value in `for` loops",
+                                );
+                            }
                             err.span_suggestion(
                                 e.span,
                                 &format!(
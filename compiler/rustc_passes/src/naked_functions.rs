@@ -1,6 +1,6 @@
 //! Checks validity of naked functions.
 
-use rustc_ast::InlineAsmOptions;
+use rustc_ast::{Attribute, InlineAsmOptions};
 use rustc_hir as hir;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::intravisit::{ErasedMap, FnKind, NestedVisitorMap, Visitor};
@@ -65,8 +65,16 @@ fn visit_fn(
         let attrs = self.tcx.hir().attrs(hir_id);
         let naked = attrs.iter().any(|attr| attr.has_name(sym::naked));
         if naked {
+            if fn_header.asyncness == hir::IsAsync::Async {
+                // The body of an `async fn` is a generator, so none of the checks below would
+                // say anything helpful about it.
+                let msg = "naked functions cannot be `async`";
+                self.tcx.sess.struct_span_err(ident_span, msg).emit();
+                return;
+            }
             let body = self.tcx.hir().body(body_id);
             check_abi(self.tcx, hir_id, fn_header.abi, ident_span);
+            check_no_inline(self.tcx, hir_id, attrs);
             check_no_patterns(self.tcx, body.params);
             check_no_parameters_use(self.tcx, body);
             check_asm(self.tcx, hir_id, body, span);
@@ -74,11 +82,39 @@ fn visit_fn(
     }
 }
 
-/// Checks that function uses non-Rust ABI.
+/// Checks that function uses non-Rust ABI. The ABIs internal to Rust, which pass arguments in
+/// ways that asm can't rely on, are rejected outright.
 fn check_abi(tcx: TyCtxt<'_>, hir_id: HirId, abi: Abi, fn_ident_span: Span) {
-    if abi == Abi::Rust {
-        tcx.struct_span_lint_hir(UNSUPPORTED_NAKED_FUNCTIONS, hir_id, fn_ident_span, |lint| {
-            lint.build("Rust ABI is unsupported in naked functions").emit();
+    match abi {
+        Abi::Rust => {
+            tcx.struct_span_lint_hir(UNSUPPORTED_NAKED_FUNCTIONS, hir_id, fn_ident_span, |lint| {
+                lint.build("Rust ABI is unsupported in naked functions").emit();
+            });
+        }
+        Abi::RustCall | Abi::RustIntrinsic | Abi::PlatformIntrinsic => {
+            tcx.sess
+                .struct_span_err(
+                    fn_ident_span,
+                    &format!("the {} ABI is unsupported in naked functions", abi),
+                )
+                .emit();
+        }
+        _ => {}
+    }
+}
+
+/// Checks that the function isn't marked `#[inline]`, since inlining a naked function would
+/// splice its asm into a caller that didn't set up the calling convention it relies on.
+fn check_no_inline(tcx: TyCtxt<'_>, hir_id: HirId, attrs: &[Attribute]) {
+    let is_inline = |attr: &&Attribute| {
+        attr.has_name(sym::inline)
+            && !attr.meta_item_list().map_or(false, |list| {
+                list.iter().any(|item| item.has_name(sym::never))
+            })
+    };
+    for attr in attrs.iter().filter(is_inline) {
+        tcx.struct_span_lint_hir(UNSUPPORTED_NAKED_FUNCTIONS, hir_id, attr.span, |lint| {
+            lint.build("naked functions cannot be inlined").emit();
         });
     }
 }
@@ -29,6 +29,11 @@ pub struct Context {
 
     /// Region parent of expressions, etc., plus its depth in the scope tree.
     parent: Option<(Scope, ScopeDepth)>,
+
+    /// The scope that variables declared with `super let` in the innermost
+    /// enclosing block are placed in: the `var_parent` in effect just outside
+    /// of that block.
+    super_let_var_parent: Option<(Scope, ScopeDepth)>,
 }
 
 struct RegionResolutionVisitor<'tcx> {
@@ -91,6 +96,11 @@ fn resolve_block<'tcx>(visitor: &mut RegionResolutionVisitor<'tcx>, blk: &'tcx h
 
     let prev_cx = visitor.cx;
 
+    // `super let` bindings in this block (and the temporaries extended along
+    // with them) outlive the block itself, and instead live in the same scope
+    // as a `let` appearing where the block expression is would.
+    visitor.cx.super_let_var_parent = prev_cx.var_parent;
+
     // We treat the tail expression in the block (if any) somewhat
     // differently from the statements. The issue has to do with
     // temporary lifetimes. Consider the following:
@@ -433,10 +443,19 @@ fn resolve_local<'tcx>(
     visitor: &mut RegionResolutionVisitor<'tcx>,
     pat: Option<&'tcx hir::Pat<'tcx>>,
     init: Option<&'tcx hir::Expr<'tcx>>,
+    super_let: bool,
 ) {
-    debug!("resolve_local(pat={:?}, init={:?})", pat, init);
-
-    let blk_scope = visitor.cx.var_parent.map(|(p, _)| p);
+    debug!("resolve_local(pat={:?}, init={:?}, super_let={:?})", pat, init, super_let);
+
+    // A `super let` behaves as if it were declared just outside of the
+    // enclosing block, both for its bindings and for the temporaries in its
+    // initializer that get extended by the rules below. Outside of any block
+    // there is nothing to extend to, so it falls back to a regular `let`.
+    let var_parent = match visitor.cx.super_let_var_parent {
+        Some(super_let_var_parent) if super_let => Some(super_let_var_parent),
+        _ => visitor.cx.var_parent,
+    };
+    let blk_scope = var_parent.map(|(p, _)| p);
 
     // As an exception to the normal rules governing temporary
     // lifetimes, initializers in a let have a temporary lifetime
@@ -509,7 +528,9 @@ fn resolve_local<'tcx>(
         visitor.visit_expr(expr);
     }
     if let Some(pat) = pat {
+        let prev_var_parent = mem::replace(&mut visitor.cx.var_parent, var_parent);
         visitor.visit_pat(pat);
+        visitor.cx.var_parent = prev_var_parent;
     }
 
     /// Returns `true` if `pat` match the `P&` non-terminal.
@@ -740,6 +761,7 @@ fn visit_body(&mut self, body: &'tcx hir::Body<'tcx>) {
 
         // The arguments and `self` are parented to the fn.
         self.cx.var_parent = self.cx.parent.take();
+        self.cx.super_let_var_parent = None;
         for param in body.params {
             self.visit_pat(&param.pat);
         }
@@ -768,7 +790,7 @@ fn visit_body(&mut self, body: &'tcx hir::Body<'tcx>) {
             // (i.e., `'static`), which means that after `g` returns, it drops,
             // and all the associated destruction scope rules apply.
             self.cx.var_parent = None;
-            resolve_local(self, None, Some(&body.value));
+            resolve_local(self, None, Some(&body.value), false);
         }
 
         if body.generator_kind.is_some() {
@@ -795,7 +817,7 @@ fn visit_expr(&mut self, ex: &'tcx Expr<'tcx>) {
         resolve_expr(self, ex);
     }
     fn visit_local(&mut self, l: &'tcx Local<'tcx>) {
-        resolve_local(self, Some(&l.pat), l.init.as_deref());
+        resolve_local(self, Some(&l.pat), l.init.as_deref(), l.super_.is_some());
     }
 }
 
@@ -811,7 +833,7 @@ fn region_scope_tree(tcx: TyCtxt<'_>, def_id: DefId) -> &ScopeTree {
             tcx,
             scope_tree: ScopeTree::default(),
             expr_and_pat_count: 0,
-            cx: Context { parent: None, var_parent: None },
+            cx: Context { parent: None, var_parent: None, super_let_var_parent: None },
             terminating_scopes: Default::default(),
             pessimistic_yield: false,
             fixup_scopes: vec![],
@@ -142,6 +142,10 @@ fn visit_predicate(&mut self, predicate: ty::Predicate<'tcx>) -> ControlFlow<V::
                 }
                 ControlFlow::CONTINUE
             }
+            ty::PredicateKind::ConstEquate(assoc_const, value) => {
+                assoc_const.visit_with(self)?;
+                value.visit_with(self)
+            }
             _ => bug!("unexpected predicate: {:?}", predicate),
         }
     }
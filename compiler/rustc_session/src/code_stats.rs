@@ -45,9 +45,22 @@ pub struct TypeSizeInfo {
     pub variants: Vec<VariantInfo>,
 }
 
+/// The inference variables created while type checking a body, see `-Z typeck-stats`.
+#[derive(Clone, Debug, Default)]
+pub struct InferVarStats {
+    pub type_vars: usize,
+    pub int_vars: usize,
+    pub float_vars: usize,
+    pub region_vars: usize,
+    /// The number of type variables in the largest set of variables that were unified with
+    /// each other.
+    pub largest_unified_set: usize,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    infer_var_stats: Lock<Vec<(String, InferVarStats)>>,
 }
 
 impl CodeStats {
@@ -77,6 +90,14 @@ pub fn record_type_size<S: ToString>(
         self.type_sizes.borrow_mut().insert(info);
     }
 
+    pub fn record_infer_var_stats(&self, body: String, stats: InferVarStats) {
+        self.infer_var_stats.lock().push((body, stats));
+    }
+
+    pub fn take_infer_var_stats(&self) -> Vec<(String, InferVarStats)> {
+        std::mem::take(&mut *self.infer_var_stats.lock())
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         let mut sorted: Vec<_> = type_sizes.iter().collect();
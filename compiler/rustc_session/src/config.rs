@@ -751,6 +751,7 @@ pub fn diagnostic_handler_flags(&self, can_emit_warnings: bool) -> HandlerFlags
             report_delayed_bugs: self.report_delayed_bugs,
             macro_backtrace: self.macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics,
+            track_suggestion_stats: self.suggestion_stats,
         }
     }
 
@@ -2184,6 +2185,9 @@ fn parse_pretty_inner(efmt: ErrorOutputType, name: &str, extended: bool) -> PpMo
             ("thir-tree", true) => ThirTree,
             ("mir", true) => Mir,
             ("mir-cfg", true) => MirCFG,
+            ("typeck-coercions", true) => TypeckCoercions,
+            ("typeck-results", true) => TypeckResults,
+            ("typeck-results-json", true) => TypeckResultsJson,
             _ => {
                 if extended {
                     early_error(
@@ -2193,7 +2197,9 @@ fn parse_pretty_inner(efmt: ErrorOutputType, name: &str, extended: bool) -> PpMo
                                         `expanded`, `identified`, `expanded,identified`, \
                                         `expanded,hygiene`, `everybody_loops`, \
                                         `ast-tree`, `ast-tree,expanded`, `hir`, `hir,identified`, \
-                                        `hir,typed`, `hir-tree`, `mir` or `mir-cfg`; got {}",
+                                        `hir,typed`, `hir-tree`, `mir`, `mir-cfg`, \
+                                        `typeck-coercions`, `typeck-results` or \
+                                        `typeck-results-json`; got {}",
                             name
                         ),
                     );
@@ -2379,6 +2385,12 @@ pub enum PpMode {
     Mir,
     /// `-Zunpretty=mir-cfg`
     MirCFG,
+    /// `-Zunpretty=typeck-coercions`
+    TypeckCoercions,
+    /// `-Zunpretty=typeck-results`
+    TypeckResults,
+    /// `-Zunpretty=typeck-results-json`
+    TypeckResultsJson,
 }
 
 impl PpMode {
@@ -2394,13 +2406,19 @@ pub fn needs_ast_map(&self) -> bool {
             | HirTree
             | ThirTree
             | Mir
-            | MirCFG => true,
+            | MirCFG
+            | TypeckCoercions
+            | TypeckResults
+            | TypeckResultsJson => true,
         }
     }
 
     pub fn needs_analysis(&self) -> bool {
         use PpMode::*;
-        matches!(*self, Mir | MirCFG | ThirTree)
+        matches!(
+            *self,
+            Mir | MirCFG | ThirTree | TypeckCoercions | TypeckResults | TypeckResultsJson
+        )
     }
 }
 
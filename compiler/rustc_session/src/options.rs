@@ -1205,6 +1205,9 @@ mod parse {
         "print the result of the monomorphization collection pass"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
+    probe_trace: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print the candidates method resolution considers for calls to methods with this name, \
+        and why each of them was discarded"),
     proc_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
          "show backtraces for panics during proc-macro execution (default: no)"),
     profile: bool = (false, parse_bool, [TRACKED],
@@ -1266,6 +1269,9 @@ mod parse {
     split_dwarf_inlining: bool = (true, parse_bool, [UNTRACKED],
         "provide minimal debug info in the object/executable to facilitate online \
          symbolication/stack traces in the absence of .dwo/.dwp files when using Split DWARF"),
+    suggestion_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print how many structured suggestions of each applicability were emitted while \
+        type checking the crate (default: no)"),
     symbol_mangling_version: Option<SymbolManglingVersion> = (None,
         parse_symbol_mangling_version, [TRACKED],
         "which mangling version to use for symbol names ('legacy' (default) or 'v0')"),
@@ -1291,6 +1297,12 @@ mod parse {
         "measure time of each LLVM pass (default: no)"),
     time_passes: bool = (false, parse_bool, [UNTRACKED],
         "measure time of each rustc pass (default: no)"),
+    typeck_const_checks: bool = (false, parse_bool, [TRACKED],
+        "report operations that can't be evaluated at compile time while type checking const \
+        contexts, rather than when const checking their MIR (default: no)"),
+    typeck_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print statistics about the inference variables created while type checking the \
+        bodies of the crate (default: no)"),
     tls_model: Option<TlsModel> = (None, parse_tls_model, [TRACKED],
         "choose the TLS model to use (`rustc --print tls-models` for details)"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
@@ -1316,7 +1328,10 @@ mod parse {
         `hir` (the HIR), `hir,identified`,
         `hir,typed` (HIR with types for each node),
         `hir-tree` (dump the raw HIR),
-        `mir` (the MIR), or `mir-cfg` (graphviz formatted MIR)"),
+        `mir` (the MIR), `mir-cfg` (graphviz formatted MIR),
+        `typeck-coercions` (the coercions type checking applied to each expression),
+        `typeck-results` (the types, adjustments and resolutions of each node), or
+        `typeck-results-json` (the same as JSON, one line per body)"),
     unsound_mir_opts: bool = (false, parse_bool, [TRACKED],
         "enable unsound and buggy MIR optimizations (default: no)"),
     unstable_options: bool = (false, parse_bool, [UNTRACKED],
@@ -1329,6 +1344,9 @@ mod parse {
         "in general, enable more debug printouts (default: no)"),
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
+    verify_wf_check_coverage: bool = (false, parse_bool, [UNTRACKED],
+        "verify that well-formedness checking module by module reaches every item of the crate \
+        exactly once; this doesn't compare the diagnostics themselves (default: no)"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
         "whether to build a wasi command or reactor"),
 
@@ -1,6 +1,6 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
-pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+pub use crate::code_stats::{DataTypeKind, FieldInfo, InferVarStats, SizeKind, VariantInfo};
 use crate::config::{self, CrateType, OutputType, PrintRequest, SwitchWithOptPath};
 use crate::filesearch;
 use crate::lint::{self, LintId};
@@ -20,7 +20,7 @@
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
-use rustc_errors::{Diagnostic, DiagnosticBuilder, DiagnosticId, ErrorReported};
+use rustc_errors::{Diagnostic, DiagnosticBuilder, DiagnosticId, ErrorReported, FatalError};
 use rustc_lint_defs::FutureBreakage;
 pub use rustc_span::crate_disambiguator::CrateDisambiguator;
 use rustc_span::source_map::{FileLoader, MultiSpan, RealFileLoader, SourceMap, Span};
@@ -38,6 +38,7 @@
 use std::ops::{Div, Mul};
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -224,6 +225,10 @@ pub struct Session {
 
     /// `Span`s for `if` conditions that we have suggested turning into `if let`.
     pub if_let_suggestions: Lock<FxHashSet<Span>>,
+
+    /// Set by drivers that may want to abandon the compilation midway, like IDEs that have
+    /// since seen the source change. Long-running passes poll it through `abort_if_cancelled`.
+    pub cancellation_flag: Option<Arc<AtomicBool>>,
 }
 
 pub struct PerfStats {
@@ -465,6 +470,16 @@ pub fn has_errors_or_delayed_span_bugs(&self) -> bool {
     pub fn abort_if_errors(&self) {
         self.diagnostic().abort_if_errors();
     }
+    /// Unwinds with a `FatalError`, without emitting anything, if the driver has set the
+    /// `cancellation_flag`. This is cheap enough to be called for every expression.
+    #[inline]
+    pub fn abort_if_cancelled(&self) {
+        if let Some(flag) = &self.cancellation_flag {
+            if flag.load(Ordering::Relaxed) {
+                FatalError.raise();
+            }
+        }
+    }
     pub fn compile_status(&self) -> Result<(), ErrorReported> {
         if self.has_errors() {
             self.diagnostic().emit_stashed_diagnostics();
@@ -1421,6 +1436,7 @@ pub fn build_session(
         known_attrs: Lock::new(MarkedAttrs::new()),
         used_attrs: Lock::new(MarkedAttrs::new()),
         if_let_suggestions: Default::default(),
+        cancellation_flag: None,
     };
 
     validate_commandline_args_with_session_available(&sess);
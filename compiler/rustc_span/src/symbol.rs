@@ -167,6 +167,7 @@
         Input,
         IntoIterator,
         Is,
+        Item,
         ItemContext,
         Iterator,
         Layout,
@@ -289,6 +290,7 @@
         assert_receiver_is_total_eq,
         assert_uninit_valid,
         assert_zero_valid,
+        associated_const_equality,
         associated_consts,
         associated_type_bounds,
         associated_type_defaults,
@@ -342,6 +344,7 @@
         call_once,
         caller_location,
         capture_disjoint_fields,
+        cause,
         cdylib,
         ceilf32,
         ceilf64,
@@ -460,6 +463,7 @@
         declare_lint_pass,
         decode,
         default_alloc_error_handler,
+        default_fn,
         default_lib_allocator,
         default_type_parameter_fallback,
         default_type_params,
@@ -514,6 +518,7 @@
         enable,
         enclosing_scope,
         encode,
+        entry,
         env,
         eq,
         ermsb_target_feature,
@@ -559,8 +564,11 @@
         field_init_shorthand,
         file,
         fill,
+        filter_map,
+        find_map,
         finish,
         flags,
+        flat_map,
         float_to_int_unchecked,
         floorf32,
         floorf64,
@@ -574,6 +582,8 @@
         fn_mut,
         fn_once,
         fn_once_output,
+        for_each,
+        for_loop_break_value,
         forbid,
         forget,
         format,
@@ -594,6 +604,7 @@
         from_size_align_unchecked,
         from_trait,
         from_usize,
+        from_yeet,
         fsub_fast,
         fundamental,
         future,
@@ -607,6 +618,7 @@
         generic_associated_types,
         generic_param_attrs,
         get_context,
+        get_mut,
         global_allocator,
         global_asm,
         globs,
@@ -656,8 +668,10 @@
         inline,
         inline_const,
         inout,
+        insert,
         instruction_set,
         intel,
+        into_future,
         into_iter,
         into_trait,
         intra_doc_pointers,
@@ -671,6 +685,7 @@
         item,
         item_like_imports,
         iter,
+        iter_mut,
         keyword,
         kind,
         kreg,
@@ -975,6 +990,7 @@
         residual,
         result,
         result_type,
+        return_type_notation,
         rhs,
         rintf32,
         rintf64,
@@ -1022,6 +1038,7 @@
         rustc_error,
         rustc_evaluate_where_clauses,
         rustc_expected_cgu_reuse,
+        rustc_has_incoherent_inherent_impls,
         rustc_if_this_changed,
         rustc_inherit_overflow_checks,
         rustc_insignificant_dtor,
@@ -1032,6 +1049,7 @@
         rustc_macro_transparency,
         rustc_main,
         rustc_mir,
+        rustc_must_implement_one_of,
         rustc_nonnull_optimization_guaranteed,
         rustc_object_lifetime_default,
         rustc_on_unimplemented,
@@ -1195,6 +1213,7 @@
         sub_assign,
         sub_with_overflow,
         suggestion,
+        super_let,
         sym,
         sync,
         sync_trait,
@@ -1245,6 +1264,7 @@
         try_into,
         try_into_trait,
         try_trait_v2,
+        try_trait_v2_yeet,
         tt,
         tuple,
         tuple_from_req,
@@ -1277,6 +1297,7 @@
         underscore_imports,
         underscore_lifetimes,
         uniform_paths,
+        uninhabited_coercions,
         unit,
         universal_impl_trait,
         unix,
@@ -1343,6 +1364,9 @@
         wreg,
         write_bytes,
         xmm_reg,
+        yeet,
+        yeet_desugar_details,
+        yeet_expr,
         ymm_reg,
         zmm_reg,
     }
@@ -1,5 +1,5 @@
 use crate::traits::query::evaluate_obligation::InferCtxtExt;
-use crate::traits::{self, TraitEngine};
+use crate::traits::{self, TraitEngine, TraitEngineExt};
 use rustc_errors::struct_span_err;
 use rustc_hir as hir;
 use rustc_infer::infer::InferCtxt;
@@ -18,6 +18,9 @@ pub enum AutoderefKind {
 struct AutoderefSnapshot<'tcx> {
     at_start: bool,
     reached_recursion_limit: bool,
+    /// If `Some(i)`, autoderef stopped because the current type dereferences to the type of
+    /// step `i` again.
+    deref_cycle_start: Option<usize>,
     steps: Vec<(Ty<'tcx>, AutoderefKind)>,
     cur_ty: Ty<'tcx>,
     obligations: Vec<traits::PredicateObligation<'tcx>>,
@@ -79,6 +82,18 @@ fn next(&mut self) -> Option<Self::Item> {
             return None;
         }
 
+        // `Deref` impls that lead back to a type we've already seen would otherwise make us go
+        // around in circles until we reach the recursion limit.
+        let visited = self.state.steps.iter().map(|&(ty, _)| ty).chain(Some(self.state.cur_ty));
+        if let Some(start) = visited.clone().position(|ty| ty == new_ty) {
+            if !self.silence_errors {
+                let cycle = visited.skip(start).chain(Some(new_ty)).collect::<Vec<_>>();
+                report_autoderef_cycle_error(tcx, self.span, &cycle);
+            }
+            self.state.deref_cycle_start = Some(start);
+            return None;
+        }
+
         self.state.steps.push((self.state.cur_ty, kind));
         debug!(
             "autoderef stage #{:?} is {:?} from {:?}",
@@ -113,6 +128,7 @@ pub fn new(
                 obligations: vec![],
                 at_start: true,
                 reached_recursion_limit: false,
+                deref_cycle_start: None,
             },
             include_raw_pointers: false,
             silence_errors: false,
@@ -142,7 +158,7 @@ fn overloaded_deref_ty(&mut self, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
             return None;
         }
 
-        let mut fulfillcx = traits::FulfillmentContext::new_in_snapshot();
+        let mut fulfillcx = <dyn TraitEngine<'_>>::new_in_snapshot(tcx);
         let normalized_ty = fulfillcx.normalize_projection_type(
             &self.infcx,
             self.param_env,
@@ -200,6 +216,12 @@ pub fn reached_recursion_limit(&self) -> bool {
         self.state.reached_recursion_limit
     }
 
+    /// If autoderef stopped because it went around in a cycle of `Deref` impls, the number of
+    /// steps after which the cycle starts.
+    pub fn deref_cycle_start(&self) -> Option<usize> {
+        self.state.deref_cycle_start
+    }
+
     /// also dereference through raw pointer types
     /// e.g., assuming ptr_to_Foo is the type `*const Foo`
     /// fcx.autoderef(span, ptr_to_Foo)  => [*const Foo]
@@ -238,3 +260,38 @@ pub fn report_autoderef_recursion_limit_error<'tcx>(tcx: TyCtxt<'tcx>, span: Spa
         .emit();
     }
 }
+
+/// Reports a cycle of `Deref` impls encountered while auto-dereferencing. `cycle` lists the types
+/// in the order they were dereferenced in, starting and ending with the same type.
+pub fn report_autoderef_cycle_error<'tcx>(tcx: TyCtxt<'tcx>, span: Span, cycle: &[Ty<'tcx>]) {
+    // Probing alternative receivers can run into the same cycle from a different type, so only
+    // report the first cycle at every span.
+    let error_id = (DiagnosticMessageId::ErrorId(55), Some(span), "deref cycle".to_string());
+    let fresh = tcx.sess.one_time_diagnostics.borrow_mut().insert(error_id);
+    if !fresh {
+        return;
+    }
+    let mut err = struct_span_err!(
+        tcx.sess,
+        span,
+        E0055,
+        "cycle detected while auto-dereferencing `{}`",
+        cycle[0]
+    );
+    err.span_label(span, "deref cycle");
+    if let [ty, _] = cycle {
+        err.note(&format!("`{}` dereferences to itself", ty));
+    } else {
+        let last = cycle.len() - 2;
+        for (i, pair) in cycle.windows(2).enumerate() {
+            err.note(&if i == 0 {
+                format!("`{}` dereferences to `{}`...", pair[0], pair[1])
+            } else if i == last {
+                format!("...which dereferences to `{}` again, completing the cycle", pair[1])
+            } else {
+                format!("...which dereferences to `{}`...", pair[1])
+            });
+        }
+    }
+    err.emit();
+}
@@ -12,11 +12,19 @@
 
 pub struct FulfillmentContext<'tcx> {
     obligations: FxIndexSet<PredicateObligation<'tcx>>,
+
+    /// Whether the context can be used within a snapshot, like the `usable_in_snapshot` of the
+    /// default engine: only contexts that don't outlive the snapshot they are used in can be.
+    usable_in_snapshot: bool,
 }
 
 impl FulfillmentContext<'tcx> {
     crate fn new() -> Self {
-        FulfillmentContext { obligations: FxIndexSet::default() }
+        FulfillmentContext { obligations: FxIndexSet::default(), usable_in_snapshot: false }
+    }
+
+    crate fn new_in_snapshot() -> Self {
+        FulfillmentContext { obligations: FxIndexSet::default(), usable_in_snapshot: true }
     }
 }
 
@@ -36,7 +44,7 @@ fn register_predicate_obligation(
         infcx: &InferCtxt<'_, 'tcx>,
         obligation: PredicateObligation<'tcx>,
     ) {
-        assert!(!infcx.is_in_snapshot());
+        assert!(!infcx.is_in_snapshot() || self.usable_in_snapshot);
         let obligation = infcx.resolve_vars_if_possible(obligation);
 
         self.obligations.insert(obligation);
@@ -68,7 +76,8 @@ fn select_where_possible(
         &mut self,
         infcx: &InferCtxt<'_, 'tcx>,
     ) -> Result<(), Vec<FulfillmentError<'tcx>>> {
-        assert!(!infcx.is_in_snapshot());
+        assert!(!infcx.is_in_snapshot() || self.usable_in_snapshot);
+        let usable_in_snapshot = self.usable_in_snapshot;
 
         let mut errors = Vec::new();
         let mut next_round = FxIndexSet::default();
@@ -99,7 +108,7 @@ fn select_where_possible(
                             ) {
                                 Ok(infer_ok) => next_round.extend(
                                     infer_ok.obligations.into_iter().map(|obligation| {
-                                        assert!(!infcx.is_in_snapshot());
+                                        assert!(!infcx.is_in_snapshot() || usable_in_snapshot);
                                         infcx.resolve_vars_if_possible(obligation)
                                     }),
                                 ),
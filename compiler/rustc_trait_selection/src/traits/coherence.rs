@@ -8,6 +8,7 @@
 use crate::traits::select::IntercrateAmbiguityCause;
 use crate::traits::SkipLeakCheck;
 use crate::traits::{self, Normalized, Obligation, ObligationCause, SelectionContext};
+use rustc_errors::Applicability;
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_middle::ty::fold::TypeFoldable;
 use rustc_middle::ty::subst::Subst;
@@ -46,6 +47,40 @@ pub fn add_placeholder_note(err: &mut rustc_errors::DiagnosticBuilder<'_>) {
     );
 }
 
+/// Relates an overlap error for impls of `trait_def_id` to `#[marker]` traits, whose impls may
+/// overlap: either the trait is a marker trait whose impls can't overlap anyway because it has
+/// associated items (which is an error of its own), or it could be made a marker trait.
+pub fn add_marker_trait_note(
+    tcx: TyCtxt<'_>,
+    err: &mut rustc_errors::DiagnosticBuilder<'_>,
+    trait_def_id: DefId,
+) {
+    let trait_def = tcx.trait_def(trait_def_id);
+    let has_items = !tcx.associated_item_def_ids(trait_def_id).is_empty();
+    if trait_def.is_marker {
+        if has_items {
+            err.note(&format!(
+                "impls of the marker trait `{}` can't overlap because it has associated items",
+                tcx.def_path_str(trait_def_id),
+            ));
+        }
+    } else if !has_items
+        && !trait_def.has_auto_impl
+        && trait_def_id.is_local()
+        && tcx.features().marker_trait_attr
+    {
+        err.span_suggestion_verbose(
+            tcx.def_span(trait_def_id).shrink_to_lo(),
+            &format!(
+                "consider making `{}` a marker trait, whose impls are allowed to overlap",
+                tcx.def_path_str(trait_def_id),
+            ),
+            "#[marker]\n".to_string(),
+            Applicability::MaybeIncorrect,
+        );
+    }
+}
+
 /// If there are types that satisfy both impls, invokes `on_overlap`
 /// with a suitably-freshened `ImplHeader` with those types
 /// substituted. Otherwise, invokes `no_overlap`.
@@ -460,6 +495,54 @@ fn uncover_fundamental_ty<'tcx>(
     Err(OrphanCheckErr::NonLocalInputType(non_local_spans))
 }
 
+/// How `#[fundamental]` types decide whether a type is local, for explaining orphan check
+/// errors. References count as `#[fundamental]` types here.
+pub enum FundamentalLocality<'tcx> {
+    /// The type is local only because the types wrapping its local type `local_ty` are all
+    /// `#[fundamental]`, like `Box<LocalType>`.
+    Fundamental { local_ty: Ty<'tcx> },
+    /// The type isn't local even though it wraps the local type `local_ty`, because `wrapper`
+    /// isn't `#[fundamental]`, like `Rc<LocalType>`.
+    NotFundamental { wrapper: DefId, local_ty: Ty<'tcx> },
+}
+
+/// Explains how `#[fundamental]` types affect whether `ty` is local to the current crate, or
+/// returns `None` if they don't.
+pub fn fundamental_locality<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+) -> Option<FundamentalLocality<'tcx>> {
+    if ty_is_local_constructor(ty, InCrate::Local) {
+        return None;
+    }
+    if contained_non_local_types(tcx, ty, InCrate::Local).is_empty() {
+        let local_ty = first_local_ty(tcx, ty)?;
+        return Some(FundamentalLocality::Fundamental { local_ty });
+    }
+    match *ty.kind() {
+        // Only look at the first type parameter, as the others are usually defaulted ones like
+        // the allocator of a `Vec`.
+        ty::Adt(def, substs) if !def.is_fundamental() => {
+            let first_ty = substs.types().next()?;
+            if !contained_non_local_types(tcx, first_ty, InCrate::Local).is_empty() {
+                return None;
+            }
+            let local_ty = first_local_ty(tcx, first_ty)?;
+            Some(FundamentalLocality::NotFundamental { wrapper: def.did, local_ty })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the first local type in `ty`, looking through `#[fundamental]` types.
+fn first_local_ty(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+    if ty_is_local_constructor(ty, InCrate::Local) {
+        Some(ty)
+    } else {
+        fundamental_ty_inner_tys(tcx, ty)?.find_map(|ty| first_local_ty(tcx, ty))
+    }
+}
+
 /// Returns a list of relevant non-local types for `ty`.
 ///
 /// This is just `ty` itself unless `ty` is `#[fundamental]`,
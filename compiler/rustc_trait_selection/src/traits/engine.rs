@@ -1,18 +1,152 @@
-use rustc_middle::ty::TyCtxt;
+use crate::infer::InferCtxt;
+use crate::traits::query::evaluate_obligation::InferCtxtExt as _;
+use rustc_middle::ty::fold::TypeFoldable;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_session::Session;
 
-use super::TraitEngine;
+use super::{normalize, normalize_projection_type, SelectionContext};
 use super::{ChalkFulfillmentContext, FulfillmentContext};
+use super::{Normalized, ObligationCause, PredicateObligation, TraitEngine, TraitObligation};
+use super::{Selection, SelectionResult};
 
+/// The trait solvers type checking can run against. The one used for a session is chosen by
+/// `-Z chalk`, and code that is meant to work with any solver, like type checking, goes through
+/// the solver for the fulfillment engines it creates and for the selection, normalization and
+/// evaluation it does outside of them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TraitSolver {
+    /// The solver built on `SelectionContext`.
+    Classic,
+    /// The experimental chalk-based solver. It solves projections along with the obligations
+    /// they appear in rather than normalizing them eagerly, and doesn't tell which impl proves
+    /// an obligation.
+    Chalk,
+}
+
+impl TraitSolver {
+    pub fn for_session(sess: &Session) -> TraitSolver {
+        if sess.opts.debugging_opts.chalk { TraitSolver::Chalk } else { TraitSolver::Classic }
+    }
+
+    pub fn new_fulfillment_cx<'tcx>(self) -> Box<dyn TraitEngine<'tcx>> {
+        match self {
+            TraitSolver::Classic => Box::new(FulfillmentContext::new()),
+            TraitSolver::Chalk => Box::new(ChalkFulfillmentContext::new()),
+        }
+    }
+
+    /// Like `new_fulfillment_cx`, for an engine used within an inference snapshot, and dropped
+    /// before the snapshot ends.
+    pub fn new_fulfillment_cx_in_snapshot<'tcx>(self) -> Box<dyn TraitEngine<'tcx>> {
+        match self {
+            TraitSolver::Classic => Box::new(FulfillmentContext::new_in_snapshot()),
+            TraitSolver::Chalk => Box::new(ChalkFulfillmentContext::new_in_snapshot()),
+        }
+    }
+
+    /// Normalizes the projections in `value`, returning the obligations the result depends on
+    /// without registering them. The chalk-based solver leaves `value` as it is.
+    pub fn normalize<'tcx, T: TypeFoldable<'tcx>>(
+        self,
+        infcx: &InferCtxt<'_, 'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+        cause: ObligationCause<'tcx>,
+        value: T,
+    ) -> Normalized<'tcx, T> {
+        match self {
+            TraitSolver::Classic => {
+                normalize(&mut SelectionContext::new(infcx), param_env, cause, value)
+            }
+            TraitSolver::Chalk => Normalized { value, obligations: vec![] },
+        }
+    }
+
+    /// Like `normalize`, for a single projection, adding the obligations the result depends on
+    /// to `obligations`.
+    pub fn normalize_projection_type<'tcx>(
+        self,
+        infcx: &InferCtxt<'_, 'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+        projection_ty: ty::ProjectionTy<'tcx>,
+        cause: ObligationCause<'tcx>,
+        obligations: &mut Vec<PredicateObligation<'tcx>>,
+    ) -> Ty<'tcx> {
+        match self {
+            TraitSolver::Classic => normalize_projection_type(
+                &mut SelectionContext::new(infcx),
+                param_env,
+                projection_ty,
+                cause,
+                0,
+                obligations,
+            ),
+            TraitSolver::Chalk => infcx.tcx.mk_ty(ty::Projection(projection_ty)),
+        }
+    }
+
+    /// Selects the impl, where clause or built-in rule that proves `obligation`. The
+    /// chalk-based solver doesn't tell, so to it every selection is ambiguous.
+    pub fn select<'tcx>(
+        self,
+        infcx: &InferCtxt<'_, 'tcx>,
+        obligation: &TraitObligation<'tcx>,
+    ) -> SelectionResult<'tcx, Selection<'tcx>> {
+        match self {
+            TraitSolver::Classic => SelectionContext::new(infcx).select(obligation),
+            TraitSolver::Chalk => Ok(None),
+        }
+    }
+
+    /// Whether `obligation` may hold, without affecting the inference variables in it.
+    pub fn predicate_may_hold<'tcx>(
+        self,
+        infcx: &InferCtxt<'_, 'tcx>,
+        obligation: &PredicateObligation<'tcx>,
+    ) -> bool {
+        match self {
+            TraitSolver::Classic => infcx.predicate_may_hold(obligation),
+            TraitSolver::Chalk => infcx.probe(|_| {
+                let mut fulfillment_cx = self.new_fulfillment_cx_in_snapshot();
+                fulfillment_cx.register_predicate_obligation(infcx, obligation.clone());
+                fulfillment_cx.select_where_possible(infcx).is_ok()
+            }),
+        }
+    }
+
+    /// Whether `obligation` definitely holds, ignoring the region constraints that proving it
+    /// may take, without affecting the inference variables in it.
+    pub fn predicate_must_hold_modulo_regions<'tcx>(
+        self,
+        infcx: &InferCtxt<'_, 'tcx>,
+        obligation: &PredicateObligation<'tcx>,
+    ) -> bool {
+        match self {
+            TraitSolver::Classic => infcx.predicate_must_hold_modulo_regions(obligation),
+            TraitSolver::Chalk => infcx.probe(|_| {
+                let mut fulfillment_cx = self.new_fulfillment_cx_in_snapshot();
+                fulfillment_cx.register_predicate_obligation(infcx, obligation.clone());
+                fulfillment_cx.select_all_or_error(infcx).is_ok()
+            }),
+        }
+    }
+}
+
+/// Creates the fulfillment engines of the trait solver selected for the session, see
+/// `TraitSolver`.
 pub trait TraitEngineExt<'tcx> {
     fn new(tcx: TyCtxt<'tcx>) -> Box<Self>;
+
+    /// Like `new`, for an engine used within an inference snapshot, and dropped before the
+    /// snapshot ends.
+    fn new_in_snapshot(tcx: TyCtxt<'tcx>) -> Box<Self>;
 }
 
 impl<'tcx> TraitEngineExt<'tcx> for dyn TraitEngine<'tcx> {
     fn new(tcx: TyCtxt<'tcx>) -> Box<Self> {
-        if tcx.sess.opts.debugging_opts.chalk {
-            Box::new(ChalkFulfillmentContext::new())
-        } else {
-            Box::new(FulfillmentContext::new())
-        }
+        TraitSolver::for_session(tcx.sess).new_fulfillment_cx()
+    }
+
+    fn new_in_snapshot(tcx: TyCtxt<'tcx>) -> Box<Self> {
+        TraitSolver::for_session(tcx.sess).new_fulfillment_cx_in_snapshot()
     }
 }
@@ -14,6 +14,7 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder, ErrorReported};
 use rustc_hir as hir;
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
 use rustc_hir::intravisit::Visitor;
 use rustc_hir::Node;
@@ -220,7 +221,14 @@ fn report_overflow_error_cycle(&self, cycle: &[PredicateObligation<'tcx>]) -> !
 
         debug!("report_overflow_error_cycle: cycle={:?}", cycle);
 
-        self.report_overflow_error(&cycle[0], false);
+        // Each obligation of the cycle is required by the one after it, and the first one by the
+        // last one, so walk it backwards to follow the requirements.
+        let cycle: Vec<_> = iter::once(&cycle[0]).chain(cycle[1..].iter().rev()).collect();
+        if let Some(bounds) = self.inductive_cycle_bounds(&cycle) {
+            self.report_inductive_cycle(&cycle, &bounds);
+        }
+
+        self.report_overflow_error(cycle[0], false);
     }
 
     fn report_selection_error(
@@ -287,7 +295,8 @@ fn report_selection_error(
                         let (message, note) = if is_try_conversion {
                             (
                                 Some(format!(
-                                    "`?` couldn't convert the error to `{}`",
+                                    "`?` couldn't convert the error from `{}` to `{}`",
+                                    trait_ref.skip_binder().substs.type_at(1),
                                     trait_ref.skip_binder().self_ty(),
                                 )),
                                 Some(
@@ -361,13 +370,15 @@ fn report_selection_error(
                                 )
                             };
 
-                        if self.suggest_add_reference_to_arg(
-                            &obligation,
-                            &mut err,
-                            &trait_ref,
-                            points_at_arg,
-                            have_alt_message,
-                        ) {
+                        if self.suggest_borrowing_for_loop_head(&obligation, &mut err, trait_ref)
+                            || self.suggest_add_reference_to_arg(
+                                &obligation,
+                                &mut err,
+                                &trait_ref,
+                                points_at_arg,
+                                have_alt_message,
+                            )
+                        {
                             self.note_obligation_cause(&mut err, obligation);
                             err.emit();
                             return;
@@ -392,6 +403,9 @@ fn report_selection_error(
                             // If it has a custom `#[rustc_on_unimplemented]` note, let's display it
                             err.note(s.as_str());
                         }
+                        if obligation.cause.code == ObligationCauseCode::MainFunctionType {
+                            note_valid_main_signatures(&mut err);
+                        }
                         if let Some(ref s) = enclosing_scope {
                             let body = tcx
                                 .hir()
@@ -413,6 +427,11 @@ fn report_selection_error(
                         self.suggest_remove_reference(&obligation, &mut err, trait_ref);
                         self.suggest_semicolon_removal(&obligation, &mut err, span, trait_ref);
                         self.note_version_mismatch(&mut err, &trait_ref);
+                        self.note_immovable_generator_borrow(&mut err, trait_ref);
+                        self.suggest_residual_conversion(&mut err, &obligation, trait_ref);
+                        self.suggest_error_conversion(&mut err, &obligation, trait_ref);
+                        self.note_question_mark_in_async_block(&mut err, &obligation, trait_ref);
+                        self.note_fn_trait_impl_hierarchy(&mut err, &obligation, trait_ref);
 
                         if Some(trait_ref.def_id()) == tcx.lang_items().try_trait() {
                             self.suggest_await_before_try(&mut err, &obligation, trait_ref, span);
@@ -1047,6 +1066,12 @@ fn report_projection_error(
         error: &MismatchedProjectionTypes<'tcx>,
     );
 
+    fn report_assoc_const_mismatch(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        expected_found: &ExpectedFound<&'tcx ty::Const<'tcx>>,
+    ) -> bool;
+
     fn fuzzy_match_tys(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> bool;
 
     fn describe_generator(&self, body_id: hir::BodyId) -> Option<&'static str>;
@@ -1117,6 +1142,10 @@ fn is_recursive_obligation(
         obligated_types: &mut Vec<&ty::TyS<'tcx>>,
         cause_code: &ObligationCauseCode<'tcx>,
     ) -> bool;
+
+    fn inductive_cycle_bounds(&self, cycle: &[&PredicateObligation<'tcx>]) -> Option<Vec<Span>>;
+
+    fn report_inductive_cycle(&self, cycle: &[&PredicateObligation<'tcx>], bounds: &[Span]) -> !;
 }
 
 impl<'a, 'tcx> InferCtxtPrivExt<'tcx> for InferCtxt<'a, 'tcx> {
@@ -1190,13 +1219,15 @@ fn report_fulfillment_error(
                 .emit();
             }
             FulfillmentErrorCode::CodeConstEquateError(ref expected_found, ref err) => {
-                self.report_mismatched_consts(
-                    &error.obligation.cause,
-                    expected_found.expected,
-                    expected_found.found,
-                    err.clone(),
-                )
-                .emit();
+                if !self.report_assoc_const_mismatch(&error.obligation, expected_found) {
+                    self.report_mismatched_consts(
+                        &error.obligation.cause,
+                        expected_found.expected,
+                        expected_found.found,
+                        err.clone(),
+                    )
+                    .emit();
+                }
             }
         }
     }
@@ -1286,11 +1317,55 @@ fn report_projection_error(
                 );
                 self.note_type_err(&mut diag, &obligation.cause, None, values, err);
                 self.note_obligation_cause(&mut diag, obligation);
+                if let ty::PredicateKind::Projection(data) = predicate.kind().skip_binder() {
+                    let trait_def_id = data.projection_ty.trait_def_id(self.tcx);
+                    if Some(trait_def_id) == self.tcx.lang_items().future_trait() {
+                        let future_ty = data.projection_ty.self_ty();
+                        self.note_async_block_output_from_question_mark(&mut diag, future_ty);
+                    }
+                }
                 diag.emit();
             }
         });
     }
 
+    /// Reports an unsatisfied `T: Trait<N = 3>` bound, returning `false` if `obligation` isn't
+    /// the equality of an associated constant.
+    fn report_assoc_const_mismatch(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        expected_found: &ExpectedFound<&'tcx ty::Const<'tcx>>,
+    ) -> bool {
+        let predicate = self.resolve_vars_if_possible(obligation.predicate);
+        let (assoc_const, value) = match predicate.kind().skip_binder() {
+            ty::PredicateKind::ConstEquate(assoc_const, value) => (assoc_const, value),
+            _ => return false,
+        };
+        match assoc_const.val {
+            ty::ConstKind::Unevaluated(uv)
+                if self.tcx.def_kind(uv.def.did) == DefKind::AssocConst => {}
+            _ => return false,
+        }
+
+        let mut err = struct_span_err!(
+            self.tcx.sess,
+            obligation.cause.span,
+            E0271,
+            "type mismatch resolving `{} == {}`",
+            assoc_const,
+            value,
+        );
+        // The error holds both sides of the predicate as far as they could be evaluated, with
+        // the associated constant first.
+        err.span_label(
+            obligation.cause.span,
+            format!("expected `{}`, found `{}`", expected_found.found, expected_found.expected),
+        );
+        self.note_obligation_cause(&mut err, obligation);
+        err.emit();
+        true
+    }
+
     fn fuzzy_match_tys(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> bool {
         /// returns the fuzzy category of a given type, or None
         /// if the type can be equated to any type.
@@ -1885,6 +1960,85 @@ fn is_recursive_obligation(
         }
         false
     }
+
+    /// Returns the bounds that make each obligation of `cycle` require the next one, if the
+    /// cycle is made of obligations of traits that aren't auto traits, each of which holds
+    /// through an impl with a single bound on the trait of the next obligation.
+    fn inductive_cycle_bounds(&self, cycle: &[&PredicateObligation<'tcx>]) -> Option<Vec<Span>> {
+        let tcx = self.tcx;
+        let trait_refs = cycle
+            .iter()
+            .map(|obligation| {
+                let trait_ref = obligation.predicate.to_opt_poly_trait_ref()?.value;
+                if tcx.trait_is_auto(trait_ref.def_id()) { None } else { Some(trait_ref) }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let next_trait_refs = trait_refs.iter().cycle().skip(1);
+        cycle
+            .iter()
+            .zip(&trait_refs)
+            .zip(next_trait_refs)
+            .map(|((obligation, trait_ref), next_trait_ref)| {
+                let impl_def_id = self.probe(|_| {
+                    let obligation = obligation.with(trait_ref.to_poly_trait_predicate());
+                    match SelectionContext::new(self).select(&obligation) {
+                        Ok(Some(super::ImplSource::UserDefined(data))) => Some(data.impl_def_id),
+                        _ => None,
+                    }
+                })?;
+                let mut bounds =
+                    tcx.predicates_of(impl_def_id).predicates.iter().filter(|(predicate, _)| {
+                        predicate.to_opt_poly_trait_ref().map(|bound| bound.value.def_id())
+                            == Some(next_trait_ref.def_id())
+                    });
+                match (bounds.next(), bounds.next()) {
+                    (Some(&(_, span)), None) => Some(span),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Reports an inductive cycle, in which each obligation of `cycle` requires the next one
+    /// because of the impl bound in `bounds`, and halts compilation.
+    fn report_inductive_cycle(&self, cycle: &[&PredicateObligation<'tcx>], bounds: &[Span]) -> ! {
+        let obligation = cycle[0];
+        let mut err = struct_span_err!(
+            self.tcx.sess,
+            obligation.cause.span,
+            E0275,
+            "the requirement `{}` depends on itself",
+            obligation.predicate
+        );
+
+        self.note_obligation_cause_code(
+            &mut err,
+            &obligation.predicate,
+            &obligation.cause.code,
+            &mut vec![],
+            &mut Default::default(),
+        );
+
+        for (i, &span) in bounds.iter().enumerate() {
+            let next_obligation = cycle[(i + 1) % cycle.len()];
+            err.span_note(
+                span,
+                &format!(
+                    "`{}` requires `{}` because of this bound",
+                    cycle[i].predicate, next_obligation.predicate
+                ),
+            );
+        }
+        err.note(&format!(
+            "to break the cycle, `{}` needs a base case that holds without requiring itself, \
+             like an impl without the bounds above",
+            obligation.predicate
+        ));
+
+        err.emit();
+        self.tcx.sess.abort_if_errors();
+        bug!();
+    }
 }
 
 /// Look for type `param` in an ADT being used only through a reference to confirm that suggesting
@@ -1975,6 +2129,14 @@ pub fn recursive_type_with_infinite_size_error(
     err.emit();
 }
 
+/// Lists the signatures `main` is allowed to have, for errors about an invalid `main` function.
+pub fn note_valid_main_signatures(err: &mut DiagnosticBuilder<'_>) {
+    err.note(
+        "valid signatures for `main` are `fn main()`, `fn main() -> Result<(), E>` and \
+         `fn main() -> impl Termination`",
+    );
+}
+
 /// Summarizes information
 #[derive(Clone)]
 pub enum ArgKind {
@@ -141,6 +141,12 @@ fn on_unimplemented_note(
             }
         }
 
+        // Lets traits tell the return type of `main` apart from their other uses, like the
+        // return type of a `#[test]` function for `Termination`.
+        if let ObligationCauseCode::MainFunctionType = obligation.cause.code {
+            flags.push((sym::cause, Some("MainFunctionType".to_string())));
+        }
+
         if let ObligationCauseCode::ItemObligation(item)
         | ObligationCauseCode::BindingObligation(item, _) = obligation.cause.code
         {
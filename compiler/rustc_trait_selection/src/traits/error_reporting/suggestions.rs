@@ -11,7 +11,7 @@
 use rustc_data_structures::stack::ensure_sufficient_stack;
 use rustc_errors::{error_code, struct_span_err, Applicability, DiagnosticBuilder, Style};
 use rustc_hir as hir;
-use rustc_hir::def::DefKind;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_hir::intravisit::Visitor;
 use rustc_hir::lang_items::LangItem;
@@ -20,10 +20,13 @@
     self, suggest_arbitrary_trait_bound, suggest_constraining_type_param, AdtKind, DefIdTree,
     Infer, InferTy, ToPredicate, Ty, TyCtxt, TypeFoldable, WithConstness,
 };
+use rustc_middle::hir::map::Map;
+use rustc_middle::middle::region::ScopeTree;
+use rustc_middle::ty::subst::GenericArgKind;
 use rustc_middle::ty::{TypeAndMut, TypeckResults};
 use rustc_span::def_id::LOCAL_CRATE;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
-use rustc_span::{BytePos, MultiSpan, Span, DUMMY_SP};
+use rustc_span::{BytePos, DesugaringKind, MultiSpan, Span, DUMMY_SP};
 use rustc_target::spec::abi;
 use std::fmt;
 
@@ -71,6 +74,13 @@ fn suggest_fn_call(
         points_at_arg: bool,
     );
 
+    fn suggest_borrowing_for_loop_head(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        err: &mut DiagnosticBuilder<'_>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    ) -> bool;
+
     fn suggest_add_reference_to_arg(
         &self,
         obligation: &PredicateObligation<'tcx>,
@@ -175,6 +185,59 @@ fn suggest_await_before_try(
         trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
         span: Span,
     );
+
+    /// When `?` is used on an `Option` in a function returning `Result`, or the other way around,
+    /// suggest converting the value before applying `?`.
+    fn suggest_residual_conversion(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    );
+
+    /// When `?` can't convert an error because the `From` impl it needs is missing, suggest
+    /// converting the error with `map_err`, possibly through a type that the error can be
+    /// converted into and that `?` can convert further, or implementing `From`.
+    fn suggest_error_conversion(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    );
+
+    /// When a `static` generator has to be `Unpin`, point at a borrow of one of its locals that
+    /// is live across a `yield`, which is what makes the generator self-referential.
+    fn note_immovable_generator_borrow(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    );
+
+    /// When the residual of a `?` inside of an `async` block can't be converted into the block's
+    /// output, explain that the `?` returns from the block rather than from the function.
+    fn note_question_mark_in_async_block(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    );
+
+    /// When an impl of one of the `Fn*` traits is missing the impl of its supertrait, explain
+    /// that the traits form a hierarchy that manual impls have to follow.
+    fn note_fn_trait_impl_hierarchy(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    );
+
+    /// When the `Output` of an `async` block doesn't match what's expected of it, point at the
+    /// `?` operators that return from the block, which are often what made it a `Result`.
+    fn note_async_block_output_from_question_mark(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        future_ty: Ty<'tcx>,
+    );
 }
 
 fn predicate_constraint(generics: &hir::Generics<'_>, pred: String) -> (Span, String) {
@@ -675,6 +738,126 @@ fn suggest_fn_call(
         }
     }
 
+    /// When the head of a `for` loop doesn't implement `IntoIterator` but a reference to it does,
+    /// as is common for collections that only hand out borrowed elements, replace the generic
+    /// "not an iterator" error with one that suggests iterating over a reference instead:
+    ///
+    /// ```ignore (illustrative)
+    /// for x in collection {}  // suggest `&collection` or `collection.iter()`
+    /// ```
+    fn suggest_borrowing_for_loop_head(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        err: &mut DiagnosticBuilder<'_>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    ) -> bool {
+        let span = obligation.cause.span;
+        if !matches!(span.desugaring_kind(), Some(DesugaringKind::ForLoop(_))) {
+            return false;
+        }
+        let into_iter_trait = match self.tcx.get_diagnostic_item(sym::IntoIterator) {
+            Some(def_id) => def_id,
+            None => return false,
+        };
+        // The failing obligation is usually `T: Iterator`, required by the blanket
+        // `impl<I: Iterator> IntoIterator for I`, so look at the parent obligation as well.
+        let self_ty = if trait_ref.def_id() == into_iter_trait {
+            trait_ref.skip_binder().self_ty()
+        } else if let ObligationCauseCode::ImplDerivedObligation(derived) = &obligation.cause.code
+        {
+            if derived.parent_trait_ref.def_id() != into_iter_trait {
+                return false;
+            }
+            derived.parent_trait_ref.skip_binder().self_ty()
+        } else {
+            return false;
+        };
+        let self_ty = self.resolve_vars_if_possible(self_ty);
+        if self_ty.has_infer_types_or_consts() || self_ty.has_escaping_bound_vars() {
+            return false;
+        }
+
+        let param_env = obligation.param_env;
+        let mk_ref = |region, mutbl| self.tcx.mk_ref(region, ty::TypeAndMut { ty: self_ty, mutbl });
+        let mutbl = [hir::Mutability::Not, hir::Mutability::Mut].iter().copied().find(|&mutbl| {
+            let ref_ty = mk_ref(self.tcx.lifetimes.re_static, mutbl);
+            let trait_ref =
+                ty::TraitRef::new(into_iter_trait, self.tcx.mk_substs_trait(ref_ty, &[]));
+            let obligation = Obligation::new(
+                ObligationCause::dummy(),
+                param_env,
+                trait_ref.without_const().to_predicate(self.tcx),
+            );
+            self.predicate_must_hold_modulo_regions(&obligation)
+        });
+        let mutbl = match mutbl {
+            Some(mutbl) => mutbl,
+            None => return false,
+        };
+        let ref_ty = mk_ref(self.tcx.lifetimes.re_erased, mutbl);
+
+        // The `for` loop desugaring marks the head expression, so suggestions have to point at
+        // the original expression the user wrote.
+        let span = span.ctxt().outer_expn_data().call_site;
+        match self.tcx.sess.source_map().span_to_snippet(span) {
+            Ok(snippet) if !snippet.starts_with('&') => {}
+            _ => return false,
+        }
+
+        err.message = vec![(format!("`{}` is not an iterator", self_ty), Style::NoStyle)];
+        err.span_label(
+            span,
+            format!("`{}` is not an iterator, but `{}` implements `IntoIterator`", self_ty, ref_ty),
+        );
+        let item = self
+            .tcx
+            .associated_items(into_iter_trait)
+            .find_by_name_and_kind(
+                self.tcx,
+                Ident::with_dummy_span(sym::Item),
+                ty::AssocKind::Type,
+                into_iter_trait,
+            )
+            .map(|item| {
+                let projection = self.tcx.mk_projection(
+                    item.def_id,
+                    self.tcx.mk_substs_trait(ref_ty, &[]),
+                );
+                self.tcx.normalize_erasing_regions(param_env, projection)
+            });
+        if let Some(item) = item {
+            err.note(&format!("iterating over `{}` yields elements of type `{}`", ref_ty, item));
+        }
+        err.span_suggestion_verbose(
+            span.shrink_to_lo(),
+            &format!(
+                "consider iterating over a{} reference to the collection",
+                if mutbl == hir::Mutability::Mut { " mutable" } else { "" },
+            ),
+            format!("&{}", mutbl.prefix_str()),
+            Applicability::MaybeIncorrect,
+        );
+        let method = if mutbl == hir::Mutability::Mut { sym::iter_mut } else { sym::iter };
+        let has_method = match self_ty.kind() {
+            ty::Adt(def, _) => self.tcx.inherent_impls(def.did).iter().any(|&impl_def_id| {
+                self.tcx
+                    .associated_items(impl_def_id)
+                    .filter_by_name_unhygienic(method)
+                    .any(|item| item.kind == ty::AssocKind::Fn)
+            }),
+            _ => false,
+        };
+        if has_method && !span.from_expansion() {
+            err.span_suggestion_verbose(
+                span.shrink_to_hi(),
+                &format!("alternatively, consider calling `.{}()`", method),
+                format!(".{}()", method),
+                Applicability::MaybeIncorrect,
+            );
+        }
+        true
+    }
+
     fn suggest_add_reference_to_arg(
         &self,
         obligation: &PredicateObligation<'tcx>,
@@ -1951,6 +2134,52 @@ fn note_obligation_cause_code<T>(
                     err.note(&msg);
                 }
             }
+            ObligationCauseCode::MethodWhereClause { method, index, substs } => {
+                let (where_clause, span) = tcx.predicates_of(method).predicates[index];
+                let args: Vec<ty::GenericArg<'tcx>> = match where_clause.kind().skip_binder() {
+                    ty::PredicateKind::Trait(data, _) => data.trait_ref.substs.to_vec(),
+                    ty::PredicateKind::Projection(data) => {
+                        data.projection_ty.substs.iter().chain(Some(data.ty.into())).collect()
+                    }
+                    ty::PredicateKind::TypeOutlives(ty::OutlivesPredicate(ty, _)) => {
+                        vec![ty.into()]
+                    }
+                    _ => vec![],
+                };
+                // Name the types of the call that the parameters of the where-clause stand for,
+                // leaving out the ones that are still being inferred.
+                let substs = self.resolve_vars_if_possible(substs);
+                let mut instantiated = vec![];
+                for arg in args.into_iter().flat_map(|arg| arg.walk()) {
+                    if let GenericArgKind::Type(ty) = arg.unpack() {
+                        if let ty::Param(param) = *ty.kind() {
+                            let ty = substs.type_at(param.index as usize);
+                            let subst = format!("`{} = {}`", param.name, ty);
+                            if !ty.has_infer_types_or_consts()
+                                && !ty.references_error()
+                                && ty.to_string() != *param.name.as_str()
+                                && !instantiated.contains(&subst)
+                            {
+                                instantiated.push(subst);
+                            }
+                        }
+                    }
+                }
+                let instantiated = if instantiated.is_empty() {
+                    String::new()
+                } else {
+                    format!(", instantiated with {}", instantiated.join(", "))
+                };
+                err.span_note(
+                    span,
+                    &format!(
+                        "required by the bound `{}` of `{}`{}",
+                        where_clause,
+                        tcx.def_path_str(method),
+                        instantiated,
+                    ),
+                );
+            }
             ObligationCauseCode::ObjectCastObligation(object_ty) => {
                 err.note(&format!(
                     "required for the cast to the object type `{}`",
@@ -2138,14 +2367,50 @@ fn note_obligation_cause_code<T>(
 
                 obligated_types.push(ty);
 
-                let parent_predicate = parent_trait_ref.without_const().to_predicate(tcx);
-                if !self.is_recursive_obligation(obligated_types, &data.parent_code) {
+                let mut parent_predicate = parent_trait_ref.without_const().to_predicate(tcx);
+                let mut parent_code = &*data.parent_code;
+                // A recursive type instantiated with different arguments at each level, like
+                // `struct List<T> { next: Option<Box<List<Wrap<T>>>> }`, can appear many times
+                // in the chain: skip to its outermost level and point at the recursion instead.
+                let recursion =
+                    ty.ty_adt_def().and_then(|def| Some((def, recursive_field(tcx, def)?)));
+                if let Some((def, field)) = recursion {
+                    let mut outermost = None;
+                    let mut predicate = parent_predicate;
+                    let mut code = parent_code;
+                    let mut steps = 0;
+                    while let ObligationCauseCode::BuiltinDerivedObligation(ref parent) = *code {
+                        let trait_ref = self.resolve_vars_if_possible(parent.parent_trait_ref);
+                        if trait_ref.skip_binder().self_ty().ty_adt_def() == Some(def) {
+                            outermost = Some((steps, predicate, code));
+                        }
+                        predicate = trait_ref.without_const().to_predicate(tcx);
+                        code = &parent.parent_code;
+                        steps += 1;
+                    }
+                    if let Some((hidden, predicate, code)) = outermost {
+                        if hidden > 0 {
+                            err.note(&format!("{} redundant requirements hidden", hidden));
+                        }
+                        err.span_note(
+                            tcx.def_span(field),
+                            &format!(
+                                "required because `{}` contains itself through this field",
+                                tcx.def_path_str(def.did)
+                            ),
+                        );
+                        parent_predicate = predicate;
+                        parent_code = code;
+                    }
+                }
+
+                if !self.is_recursive_obligation(obligated_types, parent_code) {
                     // #74711: avoid a stack overflow
                     ensure_sufficient_stack(|| {
                         self.note_obligation_cause_code(
                             err,
                             &parent_predicate,
-                            &data.parent_code,
+                            parent_code,
                             obligated_types,
                             seen_requirements,
                         )
@@ -2299,7 +2564,8 @@ fn note_obligation_cause_code<T>(
             }
             ObligationCauseCode::ReturnType
             | ObligationCauseCode::ReturnValue(_)
-            | ObligationCauseCode::BlockTailExpression(_) => (),
+            | ObligationCauseCode::BlockTailExpression(_)
+            | ObligationCauseCode::YieldExpression { .. } => (),
             ObligationCauseCode::TrivialBound => {
                 err.help("see issue #48214");
                 if tcx.sess.opts.unstable_features.is_nightly_build() {
@@ -2411,6 +2677,343 @@ fn suggest_await_before_try(
             }
         }
     }
+
+    fn suggest_residual_conversion(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    ) {
+        let span = obligation.cause.span;
+        let from_residual_trait =
+            self.tcx.lang_items().from_residual_fn().and_then(|def_id| self.tcx.parent(def_id));
+        if !span.is_desugaring(DesugaringKind::QuestionMark)
+            || from_residual_trait != Some(trait_ref.def_id())
+        {
+            return;
+        }
+        let trait_ref = self.resolve_vars_if_possible(trait_ref).skip_binder();
+        // `Self` is the return type of the function, and the type parameter is the residual of
+        // the value `?` was applied to.
+        let is_adt = |ty: Ty<'tcx>, name: Symbol| {
+            matches!(ty.kind(), ty::Adt(def, _) if self.tcx.is_diagnostic_item(name, def.did))
+        };
+        let (return_ty, residual_ty) = (trait_ref.self_ty(), trait_ref.substs.type_at(1));
+        let (msg, conversion, applicability) =
+            if is_adt(residual_ty, sym::option_type) && is_adt(return_ty, sym::result_type) {
+                (
+                    "consider converting the `Option` into a `Result` with an error value",
+                    ".ok_or(/* error */)",
+                    Applicability::HasPlaceholders,
+                )
+            } else if is_adt(residual_ty, sym::result_type) && is_adt(return_ty, sym::option_type)
+            {
+                (
+                    "consider discarding the error with `.ok()`",
+                    ".ok()",
+                    Applicability::MaybeIncorrect,
+                )
+            } else {
+                return;
+            };
+        err.span_suggestion_verbose(
+            span.shrink_to_lo(),
+            msg,
+            conversion.to_string(),
+            applicability,
+        );
+    }
+
+    fn suggest_error_conversion(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    ) {
+        let span = obligation.cause.span;
+        if !self.is_try_conversion(span, trait_ref.def_id()) {
+            return;
+        }
+        // `?` needs `From<found_ty>` to be implemented for `expected_ty`.
+        let trait_ref = self.resolve_vars_if_possible(trait_ref).skip_binder();
+        let (expected_ty, found_ty) = (trait_ref.self_ty(), trait_ref.substs.type_at(1));
+        if [expected_ty, found_ty].iter().any(|ty| {
+            ty.has_infer_types_or_consts() || ty.has_escaping_bound_vars() || ty.references_error()
+        }) {
+            return;
+        }
+
+        let implements_from = |target_ty: Ty<'tcx>, source_ty: Ty<'tcx>| {
+            let trait_ref = ty::TraitRef::new(
+                trait_ref.def_id,
+                self.tcx.mk_substs_trait(target_ty, &[source_ty.into()]),
+            );
+            let obligation = Obligation::new(
+                ObligationCause::dummy(),
+                obligation.param_env,
+                trait_ref.without_const().to_predicate(self.tcx),
+            );
+            self.predicate_must_hold_modulo_regions(&obligation)
+        };
+        // The types `found_ty` can be converted into, that can themselves be converted into
+        // `expected_ty`. Generic impls, like the one converting any error into
+        // `Box<dyn Error>`, would need their bounds checked against `found_ty` and are skipped.
+        let mut intermediate_tys = Vec::new();
+        self.tcx.for_each_relevant_impl(trait_ref.def_id, expected_ty, |impl_def_id| {
+            let impl_trait_ref = match self.tcx.impl_trait_ref(impl_def_id) {
+                Some(impl_trait_ref) => impl_trait_ref,
+                None => return,
+            };
+            let intermediate_ty = self.tcx.fold_regions(
+                impl_trait_ref.substs.type_at(1),
+                &mut false,
+                |_, _| self.tcx.lifetimes.re_static,
+            );
+            if intermediate_ty.has_param_types_or_consts()
+                || intermediate_ty == found_ty
+                || intermediate_tys.contains(&intermediate_ty)
+            {
+                return;
+            }
+            if implements_from(expected_ty, intermediate_ty)
+                && implements_from(intermediate_ty, found_ty)
+            {
+                intermediate_tys.push(intermediate_ty);
+            }
+        });
+
+        match intermediate_tys[..] {
+            [intermediate_ty] => {
+                let from_path = match intermediate_ty.kind() {
+                    ty::Adt(_, substs) if substs.is_empty() => format!("{}::from", intermediate_ty),
+                    _ => format!("<{}>::from", intermediate_ty),
+                };
+                err.span_suggestion_verbose(
+                    span.shrink_to_lo(),
+                    &format!(
+                        "consider converting the error to `{}` first, which `?` can convert to \
+                         `{}`",
+                        intermediate_ty, expected_ty,
+                    ),
+                    format!(".map_err({})", from_path),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            _ => {
+                if !intermediate_tys.is_empty() {
+                    const LIMIT: usize = 4;
+                    let mut names: Vec<_> = intermediate_tys
+                        .iter()
+                        .take(LIMIT)
+                        .map(|ty| format!("`{}`", ty))
+                        .collect();
+                    if intermediate_tys.len() > LIMIT {
+                        names.push(format!("and {} others", intermediate_tys.len() - LIMIT));
+                    }
+                    err.help(&format!(
+                        "`{}` can be converted to `{}` through any of {}",
+                        found_ty,
+                        expected_ty,
+                        names.join(", "),
+                    ));
+                }
+                err.span_suggestion_verbose(
+                    span.shrink_to_lo(),
+                    &format!("consider converting the error to `{}` with `map_err`", expected_ty),
+                    format!(".map_err(|error| /* {} */)", expected_ty),
+                    Applicability::HasPlaceholders,
+                );
+            }
+        }
+
+        // The impl can only be added next to a local type, and generic types would need the
+        // impl to be generic as well.
+        if [expected_ty, found_ty].iter().any(|ty| ty.has_param_types_or_consts()) {
+            return;
+        }
+        let local_def_span = [expected_ty, found_ty].iter().find_map(|ty| match ty.kind() {
+            ty::Adt(def, _) => self.tcx.hir().span_if_local(def.did),
+            _ => None,
+        });
+        if let Some(def_span) = local_def_span {
+            err.span_suggestion_verbose(
+                def_span.shrink_to_hi(),
+                &format!("consider implementing `From<{}>` for `{}`", found_ty, expected_ty),
+                format!(
+                    "\n\nimpl From<{found}> for {expected} {{\n    \
+                     fn from(error: {found}) -> Self {{\n        \
+                     todo!()\n    \
+                     }}\n\
+                     }}",
+                    found = found_ty,
+                    expected = expected_ty,
+                ),
+                Applicability::HasPlaceholders,
+            );
+        }
+    }
+
+    fn note_immovable_generator_borrow(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    ) {
+        if Some(trait_ref.def_id()) != self.tcx.lang_items().unpin_trait() {
+            return;
+        }
+        let self_ty = self.resolve_vars_if_possible(trait_ref.self_ty()).skip_binder();
+        let generator_did = match *self_ty.kind() {
+            ty::Generator(did, _, hir::Movability::Static) => did,
+            _ => return,
+        };
+        let hir = self.tcx.hir();
+        let body = match generator_did
+            .as_local()
+            .and_then(|def_id| hir.maybe_body_owned_by(hir.local_def_id_to_hir_id(def_id)))
+        {
+            Some(body_id) => hir.body(body_id),
+            None => return,
+        };
+        // `async` blocks and functions are pinned by the executor that polls them.
+        if body.generator_kind != Some(GeneratorKind::Gen) {
+            return;
+        }
+
+        let mut visitor = SelfBorrowVisitor {
+            hir,
+            scope_tree: self.tcx.region_scope_tree(generator_did),
+            body_span: body.value.span,
+            found: None,
+        };
+        visitor.visit_body(body);
+        if let Some(SelfBorrow { borrow_span, borrowed, yield_span }) = visitor.found {
+            let mut span = MultiSpan::from_span(borrow_span);
+            span.push_span_label(
+                borrow_span,
+                format!(
+                    "this borrow of `{}` lives across this yield, making the generator immovable",
+                    borrowed
+                ),
+            );
+            span.push_span_label(yield_span, "this yield".to_string());
+            err.span_note(span, "the generator is self-referential, so it can't be unpinned");
+        }
+    }
+
+    fn note_fn_trait_impl_hierarchy(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    ) {
+        let tcx = self.tcx;
+        let is_fn_trait = |def_id| tcx.fn_trait_kind_from_lang_item(def_id).is_some();
+        if !is_fn_trait(trait_ref.def_id()) {
+            return;
+        }
+        // The supertraits of an impl are checked with the impl as the body.
+        let impl_def_id = match tcx.hir().find(obligation.cause.body_id) {
+            Some(Node::Item(hir::Item { kind: hir::ItemKind::Impl(..), def_id, .. })) => *def_id,
+            _ => return,
+        };
+        let impl_trait_ref = match tcx.impl_trait_ref(impl_def_id) {
+            Some(impl_trait_ref)
+                if is_fn_trait(impl_trait_ref.def_id)
+                    && impl_trait_ref.def_id != trait_ref.def_id() =>
+            {
+                impl_trait_ref
+            }
+            _ => return,
+        };
+        let trait_ref = self.resolve_vars_if_possible(trait_ref).skip_binder();
+        err.note(&format!(
+            "`{}` is a supertrait of `{}`, so `{}` has to implement `{}` to implement `{}`",
+            tcx.item_name(trait_ref.def_id),
+            tcx.item_name(impl_trait_ref.def_id),
+            impl_trait_ref.self_ty(),
+            trait_ref.print_only_trait_path(),
+            impl_trait_ref.print_only_trait_path(),
+        ));
+    }
+
+    fn note_question_mark_in_async_block(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::Binder<'tcx, ty::TraitRef<'tcx>>,
+    ) {
+        let from_residual_trait =
+            self.tcx.lang_items().from_residual_fn().and_then(|def_id| self.tcx.parent(def_id));
+        if !obligation.cause.span.is_desugaring(DesugaringKind::QuestionMark)
+            || from_residual_trait != Some(trait_ref.def_id())
+        {
+            return;
+        }
+        let hir = self.tcx.hir();
+        let owner = hir.enclosing_body_owner(obligation.cause.body_id);
+        let is_async_block = hir.maybe_body_owned_by(owner).map_or(false, |body_id| {
+            hir.body(body_id).generator_kind
+                == Some(GeneratorKind::Async(AsyncGeneratorKind::Block))
+        });
+        if is_async_block {
+            err.note(
+                "the `?` operator returns from the `async` block rather than from the enclosing \
+                 function",
+            );
+        }
+    }
+
+    fn note_async_block_output_from_question_mark(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        future_ty: Ty<'tcx>,
+    ) {
+        // `async` blocks are `impl Future` types returned by the `from_generator` lang item.
+        let generator_ty = match *future_ty.kind() {
+            ty::Opaque(def_id, substs)
+                if self.tcx.parent(def_id) == self.tcx.lang_items().from_generator_fn() =>
+            {
+                substs.type_at(0)
+            }
+            _ => return,
+        };
+        let hir = self.tcx.hir();
+        let body = match *generator_ty.kind() {
+            ty::Generator(did, ..) => match did
+                .as_local()
+                .and_then(|def_id| hir.maybe_body_owned_by(hir.local_def_id_to_hir_id(def_id)))
+            {
+                Some(body_id) => hir.body(body_id),
+                None => return,
+            },
+            _ => return,
+        };
+        if body.generator_kind != Some(GeneratorKind::Async(AsyncGeneratorKind::Block)) {
+            return;
+        }
+
+        let mut visitor = QuestionMarksVisitor::default();
+        visitor.visit_body(body);
+        if visitor.question_marks.is_empty() {
+            return;
+        }
+        let mut span = MultiSpan::from_spans(visitor.question_marks.clone());
+        for question_mark in visitor.question_marks {
+            span.push_span_label(
+                question_mark,
+                "this `?` returns the error from the `async` block".to_string(),
+            );
+        }
+        err.span_note(
+            span,
+            "the `?` operator applies to the `async` block, so the error becomes part of the \
+             block's `Output`",
+        );
+        err.help(
+            "to propagate the error from the enclosing function instead, apply `?` to the output \
+             of the `.await`ed block, or change the expected `Output` type",
+        );
+    }
 }
 
 /// Collect all the returned expressions within the input expression.
@@ -2498,6 +3101,94 @@ fn visit_expr(&mut self, ex: &'v hir::Expr<'v>) {
     }
 }
 
+/// Collect the spans of the `?` operators within the input expression, not counting the ones in
+/// nested closures and `async` blocks.
+#[derive(Default)]
+struct QuestionMarksVisitor {
+    question_marks: Vec<Span>,
+}
+
+impl<'v> Visitor<'v> for QuestionMarksVisitor {
+    type Map = hir::intravisit::ErasedMap<'v>;
+
+    fn nested_visit_map(&mut self) -> hir::intravisit::NestedVisitorMap<Self::Map> {
+        hir::intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, ex: &'v hir::Expr<'v>) {
+        if let hir::ExprKind::Match(_, _, hir::MatchSource::TryDesugar) = ex.kind {
+            // The `match` spans the whole `expr?`, so its last byte is the `?`.
+            self.question_marks.push(ex.span.with_lo(ex.span.hi() - BytePos(1)));
+        }
+        hir::intravisit::walk_expr(self, ex)
+    }
+}
+
+struct SelfBorrow {
+    borrow_span: Span,
+    borrowed: Symbol,
+    yield_span: Span,
+}
+
+/// Finds a `let` binding in the body of a generator that holds a borrow of one of the
+/// generator's own locals, and that is live across a `yield` according to the same scope
+/// analysis the generator interior is computed from.
+struct SelfBorrowVisitor<'tcx> {
+    hir: Map<'tcx>,
+    scope_tree: &'tcx ScopeTree,
+    body_span: Span,
+    found: Option<SelfBorrow>,
+}
+
+impl<'tcx> SelfBorrowVisitor<'tcx> {
+    /// The name of the local borrowed by `expr` if it is `&local` or `&mut local`, or a borrow
+    /// of a field or element of such a local, declared in the body of the generator.
+    fn borrowed_local(&self, expr: &hir::Expr<'_>) -> Option<Symbol> {
+        let mut place = match expr.kind {
+            hir::ExprKind::AddrOf(hir::BorrowKind::Ref, _, place) => place,
+            _ => return None,
+        };
+        while let hir::ExprKind::Field(base, _) | hir::ExprKind::Index(base, _) = place.kind {
+            place = base;
+        }
+        match place.kind {
+            hir::ExprKind::Path(hir::QPath::Resolved(
+                None,
+                hir::Path { res: Res::Local(id), .. },
+            )) if self.body_span.contains(self.hir.span(*id)) => Some(self.hir.name(*id)),
+            _ => None,
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for SelfBorrowVisitor<'tcx> {
+    type Map = hir::intravisit::ErasedMap<'tcx>;
+
+    fn nested_visit_map(&mut self) -> hir::intravisit::NestedVisitorMap<Self::Map> {
+        hir::intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local<'tcx>) {
+        if let (None, hir::PatKind::Binding(..), Some(init)) =
+            (&self.found, &local.pat.kind, local.init)
+        {
+            if let Some(borrowed) = self.borrowed_local(init) {
+                let scope = self.scope_tree.var_scope(local.pat.hir_id.local_id);
+                if let Some(yield_data) = self.scope_tree.yield_in_scope(scope) {
+                    if yield_data.span.lo() > local.span.hi() {
+                        self.found = Some(SelfBorrow {
+                            borrow_span: init.span,
+                            borrowed,
+                            yield_span: yield_data.span,
+                        });
+                    }
+                }
+            }
+        }
+        hir::intravisit::walk_local(self, local);
+    }
+}
+
 pub trait NextTypeParamName {
     fn next_type_param_name(&self, name: Option<&str>) -> String;
 }
@@ -2524,6 +3215,16 @@ fn next_type_param_name(&self, name: Option<&str>) -> String {
     }
 }
 
+/// Returns a field of `def` whose type mentions `def` itself.
+fn recursive_field(tcx: TyCtxt<'_>, def: &ty::AdtDef) -> Option<DefId> {
+    def.all_fields().map(|field| field.did).find(|&field| {
+        tcx.type_of(field).walk().any(|arg| match arg.unpack() {
+            GenericArgKind::Type(ty) => ty.ty_adt_def().map_or(false, |field_def| field_def == def),
+            _ => false,
+        })
+    })
+}
+
 fn suggest_trait_object_return_type_alternatives(
     err: &mut DiagnosticBuilder<'_>,
     ret_ty: Span,
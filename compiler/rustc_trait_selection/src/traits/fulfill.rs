@@ -517,6 +517,14 @@ fn progress_changed_obligations(
 
                 ty::PredicateKind::ConstEquate(c1, c2) => {
                     debug!(?c1, ?c2, "equating consts");
+
+                    // A `T: Trait<N = 3>` bound in the environment proves `<T as Trait>::N == 3`,
+                    // even though `<T as Trait>::N` can't be evaluated.
+                    if obligation.param_env.caller_bounds().iter().any(|bound| {
+                        bound.kind().skip_binder() == ty::PredicateKind::ConstEquate(c1, c2)
+                    }) {
+                        return ProcessResult::Changed(vec![]);
+                    }
                     if self.selcx.tcx().features().const_evaluatable_checked {
                         // FIXME: we probably should only try to unify abstract constants
                         // if the constants depend on generic parameters.
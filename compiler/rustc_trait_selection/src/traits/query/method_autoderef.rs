@@ -1,3 +1,4 @@
 pub use rustc_middle::traits::query::{
-    CandidateStep, MethodAutoderefBadTy, MethodAutoderefStepsResult,
+    CandidateStep, MethodAutoderefBadTy, MethodAutoderefStepsResult, MethodCompletion,
+    MethodCompletionStep, MethodCompletions,
 };
@@ -438,6 +438,9 @@ fn report_conflicting_impls(
         if overlap.involves_placeholder {
             coherence::add_placeholder_note(&mut err);
         }
+        if let Some(trait_ref) = tcx.impl_trait_ref(impl_def_id) {
+            coherence::add_marker_trait_note(tcx, &mut err, trait_ref.def_id);
+        }
         err.emit()
     };
 
@@ -2,6 +2,7 @@
 use crate::opaque_types::required_region_bounds;
 use crate::traits;
 use rustc_hir as hir;
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
 use rustc_hir::lang_items::LangItem;
 use rustc_middle::ty::subst::{GenericArg, GenericArgKind, SubstsRef};
@@ -136,8 +137,24 @@ pub fn predicate_obligations<'a, 'tcx>(
             }
         }
         ty::PredicateKind::ConstEquate(c1, c2) => {
-            wf.compute(c1.into());
-            wf.compute(c2.into());
+            for &c in &[c1, c2] {
+                match c.val {
+                    // Like the projection of an associated type bound, the associated constant
+                    // of a `T: Trait<N = 3>` bound only has to be well-formed, the bound
+                    // doesn't require it to be evaluatable on its own.
+                    ty::ConstKind::Unevaluated(uv)
+                        if infcx.tcx.def_kind(uv.def.did) == DefKind::AssocConst =>
+                    {
+                        let obligations = wf.nominal_obligations(uv.def.did, uv.substs);
+                        wf.out.extend(obligations);
+
+                        for arg in uv.substs.iter() {
+                            wf.compute(arg);
+                        }
+                    }
+                    _ => wf.compute(c.into()),
+                }
+            }
         }
         ty::PredicateKind::TypeWellFormedFromEnv(..) => {
             bug!("TypeWellFormedFromEnv is only used for Chalk")
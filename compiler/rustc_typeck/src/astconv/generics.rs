@@ -6,7 +6,8 @@
 use crate::errors::AssocTypeBindingNotAllowed;
 use crate::structured_errors::{GenericArgsInfo, StructuredDiagnostic, WrongNumberOfGenericArgs};
 use rustc_ast::ast::ParamKindOrd;
-use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder, ErrorReported};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder, ErrorReported};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
@@ -15,7 +16,8 @@
     self, subst, subst::SubstsRef, GenericParamDef, GenericParamDefKind, Ty, TyCtxt,
 };
 use rustc_session::lint::builtin::LATE_BOUND_LIFETIME_ARGUMENTS;
-use rustc_span::{symbol::kw, MultiSpan, Span};
+use rustc_span::symbol::{kw, Symbol};
+use rustc_span::{BytePos, MultiSpan, Span};
 use smallvec::SmallVec;
 
 impl<'o, 'tcx> dyn AstConv<'tcx> + 'o {
@@ -409,7 +411,7 @@ pub fn check_generic_arg_count_for_call(
         is_method_call: IsMethodCall,
     ) -> GenericArgCountResult {
         let empty_args = hir::GenericArgs::none();
-        let suppress_mismatch = Self::check_impl_trait(tcx, seg, &generics);
+        let suppress_mismatch = Self::check_impl_trait(tcx, def_id, seg, &generics);
 
         let gen_args = seg.args.unwrap_or(&empty_args);
         let gen_pos = if is_method_call == IsMethodCall::Yes {
@@ -600,6 +602,7 @@ pub(crate) fn check_generic_arg_count(
     /// Report error if there is an explicit type parameter when using `impl Trait`.
     pub(crate) fn check_impl_trait(
         tcx: TyCtxt<'_>,
+        def_id: DefId,
         seg: &hir::PathSegment<'_>,
         generics: &ty::Generics,
     ) -> bool {
@@ -639,12 +642,121 @@ pub(crate) fn check_impl_trait(
                 err.span_label(span, "explicit generic argument not allowed");
             }
 
+            let has_impl_trait_arg = generics.params.iter().any(|param| {
+                matches!(
+                    param.kind,
+                    ty::GenericParamDefKind::Type {
+                        synthetic: Some(hir::SyntheticTyParamKind::ImplTrait),
+                        ..
+                    }
+                )
+            });
+            if has_impl_trait_arg {
+                err.note(
+                    "`impl Trait` in argument position introduces an anonymous generic parameter, \
+                     which cannot be specified explicitly",
+                );
+                Self::suggest_named_impl_trait_params(tcx, def_id, &mut err);
+            }
+
             err.emit();
         }
 
         impl_trait
     }
 
+    /// Suggests turning the `impl Trait` arguments of the local function `def_id` into named
+    /// generic parameters, e.g. `fn foo<T>(x: impl Debug)` into `fn foo<T, U: Debug>(x: U)`,
+    /// so that they can be specified explicitly as well.
+    fn suggest_named_impl_trait_params(
+        tcx: TyCtxt<'_>,
+        def_id: DefId,
+        err: &mut DiagnosticBuilder<'_>,
+    ) {
+        let generics = match tcx.hir().get_generics(def_id) {
+            Some(generics) => generics,
+            None => return,
+        };
+        let source_map = tcx.sess.source_map();
+        let generics_snippet = match source_map.span_to_snippet(generics.span) {
+            Ok(snippet) => snippet,
+            Err(_) => return,
+        };
+
+        // Avoid the names of parameters in scope, including those of the parent impl or trait.
+        let mut used_names = FxHashSet::default();
+        let mut ty_generics = Some(tcx.generics_of(def_id));
+        while let Some(g) = ty_generics {
+            used_names.extend(g.params.iter().map(|param| param.name));
+            ty_generics = g.parent.map(|parent| tcx.generics_of(parent));
+        }
+        let mut fresh_name = || {
+            ('T'..='Z')
+                .chain('A'..'T')
+                .map(|c| Symbol::intern(&c.to_string()))
+                .find(|name| used_names.insert(*name))
+        };
+
+        let mut has_explicit_params = false;
+        let mut has_const_params = false;
+        let mut new_params = vec![];
+        let mut suggestions = vec![];
+        for param in generics.params {
+            match param.kind {
+                hir::GenericParamKind::Type {
+                    synthetic: Some(hir::SyntheticTyParamKind::ImplTrait),
+                    ..
+                } => {
+                    let name = match fresh_name() {
+                        Some(name) => name,
+                        None => return,
+                    };
+                    // Synthetic parameters are named after the `impl Bound` they replace.
+                    let bounds = param.name.ident().as_str();
+                    let bounds = bounds.strip_prefix("impl ").unwrap_or(&bounds);
+                    // Nested `impl Trait`s would need overlapping replacements.
+                    if bounds.contains("impl ") {
+                        return;
+                    }
+                    new_params.push(format!("{}: {}", name, bounds));
+                    suggestions.push((param.span, name.to_string()));
+                }
+                hir::GenericParamKind::Lifetime { kind: hir::LifetimeParamKind::Explicit }
+                | hir::GenericParamKind::Type { synthetic: None, .. } => has_explicit_params = true,
+                hir::GenericParamKind::Const { .. } => {
+                    has_explicit_params = true;
+                    has_const_params = true;
+                }
+                _ => {}
+            }
+        }
+        // The new type parameters are appended, which would put them after const parameters.
+        if has_const_params && !tcx.features().unordered_const_ty_params() {
+            return;
+        }
+
+        let count = new_params.len();
+        let new_params = new_params.join(", ");
+        if generics_snippet.ends_with('>') {
+            let before_close = generics.span.with_lo(generics.span.hi() - BytePos(1));
+            let sep = if has_explicit_params { ", " } else { "" };
+            suggestions.push((before_close.shrink_to_lo(), format!("{}{}", sep, new_params)));
+        } else {
+            suggestions.push((generics.span, format!("<{}>", new_params)));
+        }
+
+        err.multipart_suggestion(
+            &format!(
+                "consider converting the `impl Trait` argument{s} into {a}named generic \
+                 parameter{s}",
+                s = pluralize!(count),
+                a = if count > 1 { "" } else { "a " },
+            ),
+            suggestions,
+            Applicability::MaybeIncorrect,
+        );
+    }
+
     /// Emits an error regarding forbidden type binding associations
     pub fn prohibit_assoc_ty_binding(tcx: TyCtxt<'_>, span: Span) {
         tcx.sess.emit_err(AssocTypeBindingNotAllowed { span });
@@ -13,7 +13,7 @@
 };
 use crate::middle::resolve_lifetime as rl;
 use crate::require_c_abi_if_c_variadic;
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_errors::{struct_span_err, Applicability, ErrorReported, FatalError};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Namespace, Res};
@@ -130,7 +130,9 @@ struct ConvertedBinding<'a, 'tcx> {
 #[derive(Debug)]
 enum ConvertedBindingKind<'a, 'tcx> {
     Equality(Ty<'tcx>),
+    ConstEquality(&'tcx Const<'tcx>),
     Constraint(&'a [hir::GenericBound<'a>]),
+    ReturnConstraint(&'a [hir::GenericBound<'a>]),
 }
 
 /// New-typed boolean indicating whether explicit late-bound lifetimes
@@ -587,9 +589,19 @@ fn create_assoc_bindings_for_generic_args<'a>(
                     hir::TypeBindingKind::Equality { ref ty } => {
                         ConvertedBindingKind::Equality(self.ast_ty_to_ty(ty))
                     }
+                    hir::TypeBindingKind::ConstEquality { ref value } => {
+                        let value_def_id = self.tcx().hir().local_def_id(value.hir_id);
+                        ConvertedBindingKind::ConstEquality(Const::from_anon_const(
+                            self.tcx(),
+                            value_def_id,
+                        ))
+                    }
                     hir::TypeBindingKind::Constraint { ref bounds } => {
                         ConvertedBindingKind::Constraint(bounds)
                     }
+                    hir::TypeBindingKind::ReturnConstraint { ref bounds } => {
+                        ConvertedBindingKind::ReturnConstraint(bounds)
+                    }
                 };
                 ConvertedBinding {
                     hir_id: binding.hir_id,
@@ -1017,6 +1029,24 @@ fn add_predicates_for_ast_type_binding(
         //
         // We want to produce `<B as SuperTrait<i32>>::T == foo`.
 
+        if let ConvertedBindingKind::ConstEquality(value) = binding.kind {
+            return self.add_predicates_for_assoc_const_binding(
+                hir_ref_id,
+                trait_ref,
+                binding,
+                value,
+                bounds,
+                speculative,
+                dup_bindings,
+                path_span,
+            );
+        }
+        if let ConvertedBindingKind::ReturnConstraint(ast_bounds) = binding.kind {
+            return self.add_predicates_for_return_type_binding(
+                hir_ref_id, trait_ref, binding, ast_bounds, bounds,
+            );
+        }
+
         let tcx = self.tcx();
 
         let candidate =
@@ -1170,6 +1200,250 @@ fn add_predicates_for_ast_type_binding(
                 let param_ty = tcx.mk_ty(ty::Projection(projection_ty.skip_binder()));
                 self.add_bounds(param_ty, ast_bounds, bounds, candidate.bound_vars());
             }
+            // Handled by `add_predicates_for_assoc_const_binding` and
+            // `add_predicates_for_return_type_binding` above.
+            ConvertedBindingKind::ConstEquality(_) | ConvertedBindingKind::ReturnConstraint(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Lowers a bound on an associated constant like `T: Trait<N = 3>` into the predicate
+    /// `<T as Trait>::N == 3`, which is proven by evaluating and comparing both sides. As with
+    /// associated types, `N` may also be defined in a supertrait of `Trait`.
+    fn add_predicates_for_assoc_const_binding(
+        &self,
+        hir_ref_id: hir::HirId,
+        trait_ref: ty::PolyTraitRef<'tcx>,
+        binding: &ConvertedBinding<'_, 'tcx>,
+        value: &'tcx Const<'tcx>,
+        bounds: &mut Bounds<'tcx>,
+        speculative: bool,
+        dup_bindings: &mut FxHashMap<DefId, Span>,
+        path_span: Span,
+    ) -> Result<(), ErrorReported> {
+        let tcx = self.tcx();
+
+        let defines_assoc_const = |trait_def_id: DefId| {
+            tcx.associated_items(trait_def_id)
+                .find_by_name_and_kind(tcx, binding.item_name, ty::AssocKind::Const, trait_def_id)
+                .is_some()
+        };
+        let candidate = match traits::supertraits(tcx, trait_ref)
+            .find(|candidate| defines_assoc_const(candidate.def_id()))
+        {
+            Some(candidate) => candidate,
+            None => {
+                tcx.sess
+                    .struct_span_err(
+                        binding.span,
+                        &format!(
+                            "associated constant `{}` not found for `{}`",
+                            binding.item_name,
+                            trait_ref.print_only_trait_path(),
+                        ),
+                    )
+                    .span_label(
+                        binding.item_name.span,
+                        format!("associated constant `{}` not found", binding.item_name),
+                    )
+                    .emit();
+                return Err(ErrorReported);
+            }
+        };
+
+        let (assoc_ident, def_scope) =
+            tcx.adjust_ident_and_get_scope(binding.item_name, candidate.def_id(), hir_ref_id);
+        let assoc_const = tcx
+            .associated_items(candidate.def_id())
+            .filter_by_name_unhygienic(assoc_ident.name)
+            .find(|i| {
+                i.kind == ty::AssocKind::Const && i.ident.normalize_to_macros_2_0() == assoc_ident
+            })
+            .expect("missing associated const");
+
+        if !assoc_const.vis.is_accessible_from(def_scope, tcx) {
+            tcx.sess
+                .struct_span_err(
+                    binding.span,
+                    &format!("associated constant `{}` is private", binding.item_name),
+                )
+                .span_label(binding.span, "private associated constant")
+                .emit();
+        }
+        tcx.check_stability(assoc_const.def_id, Some(hir_ref_id), binding.span, None);
+
+        if !speculative {
+            dup_bindings
+                .entry(assoc_const.def_id)
+                .and_modify(|prev_span| {
+                    tcx.sess.emit_err(ValueOfAssociatedStructAlreadySpecified {
+                        span: binding.span,
+                        prev_span: *prev_span,
+                        item_name: binding.item_name,
+                        def_path: tcx.def_path_str(assoc_const.container.id()),
+                    });
+                })
+                .or_insert(binding.span);
+        }
+
+        let equality = candidate.map_bound(|trait_ref| {
+            let item_segment = hir::PathSegment {
+                ident: Ident::new(assoc_const.ident.name, binding.item_name.span),
+                hir_id: Some(binding.hir_id),
+                res: None,
+                args: Some(binding.gen_args),
+                infer_args: false,
+            };
+            let substs = self.create_substs_for_associated_item(
+                tcx,
+                path_span,
+                assoc_const.def_id,
+                &item_segment,
+                trait_ref.substs,
+            );
+            let assoc_const = tcx.mk_const(ty::Const {
+                val: ty::ConstKind::Unevaluated(ty::Unevaluated {
+                    def: ty::WithOptConstParam::unknown(assoc_const.def_id),
+                    substs,
+                    promoted: None,
+                }),
+                ty: tcx.type_of(assoc_const.def_id).subst(tcx, substs),
+            });
+            (assoc_const, value)
+        });
+        bounds.const_equality_bounds.push((equality, binding.span));
+        Ok(())
+    }
+
+    /// Lowers a bound on the return type of a trait method like `T: Trait<method(): Send>` into a
+    /// bound on the declared return type of `method`, with the substitutions of the trait-ref
+    /// applied. The late-bound lifetimes of `method` become higher-ranked, so given
+    /// `fn method(&self) -> Self::Fut<'_>` we produce `for<'a> <T as Trait>::Fut<'a>: Send`.
+    fn add_predicates_for_return_type_binding(
+        &self,
+        hir_ref_id: hir::HirId,
+        trait_ref: ty::PolyTraitRef<'tcx>,
+        binding: &ConvertedBinding<'_, 'tcx>,
+        ast_bounds: &[hir::GenericBound<'_>],
+        bounds: &mut Bounds<'tcx>,
+    ) -> Result<(), ErrorReported> {
+        let tcx = self.tcx();
+
+        let defines_assoc_fn = |trait_def_id: DefId| {
+            tcx.associated_items(trait_def_id)
+                .find_by_name_and_kind(tcx, binding.item_name, ty::AssocKind::Fn, trait_def_id)
+                .is_some()
+        };
+        let candidate = match traits::supertraits(tcx, trait_ref)
+            .find(|candidate| defines_assoc_fn(candidate.def_id()))
+        {
+            Some(candidate) => candidate,
+            None => {
+                tcx.sess
+                    .struct_span_err(
+                        binding.item_name.span,
+                        &format!(
+                            "associated function `{}` not found for `{}`",
+                            binding.item_name,
+                            trait_ref.print_only_trait_path(),
+                        ),
+                    )
+                    .span_label(
+                        binding.item_name.span,
+                        format!("associated function `{}` not found", binding.item_name),
+                    )
+                    .emit();
+                return Err(ErrorReported);
+            }
+        };
+
+        let (assoc_ident, _) =
+            tcx.adjust_ident_and_get_scope(binding.item_name, candidate.def_id(), hir_ref_id);
+        let assoc_fn = tcx
+            .associated_items(candidate.def_id())
+            .filter_by_name_unhygienic(assoc_ident.name)
+            .find(|i| {
+                i.kind == ty::AssocKind::Fn && i.ident.normalize_to_macros_2_0() == assoc_ident
+            })
+            .expect("missing associated function");
+        tcx.check_stability(assoc_fn.def_id, Some(hir_ref_id), binding.span, None);
+
+        let unsupported = |msg: &str| {
+            tcx.sess
+                .struct_span_err(binding.span, msg)
+                .span_label(assoc_fn.ident.span, "associated function defined here")
+                .emit();
+            Err(ErrorReported)
+        };
+        if !tcx.generics_of(assoc_fn.def_id).params.is_empty() {
+            return unsupported(
+                "return type notation is not supported for functions with generic parameters",
+            );
+        }
+        // Resolving lifetimes only looks through supertraits for associated types, so the
+        // bound variables of a higher-ranked supertrait are not in scope for `ast_bounds`.
+        if candidate.bound_vars().len() != trait_ref.bound_vars().len() {
+            return unsupported(
+                "return type notation is not supported for functions of higher-ranked supertraits",
+            );
+        }
+
+        // The declared return type, still bound by the late-bound lifetimes of the function.
+        let output = tcx.fn_sig(assoc_fn.def_id).output();
+        let output_vars = output.bound_vars();
+
+        for ast_bound in ast_bounds {
+            let (outer_vars, span) = match *ast_bound {
+                hir::GenericBound::Trait(ref b, _) => {
+                    (tcx.late_bound_vars(b.trait_ref.hir_ref_id), b.span)
+                }
+                hir::GenericBound::LangItemTrait(_, span, hir_id, _) => {
+                    (tcx.late_bound_vars(hir_id), span)
+                }
+                hir::GenericBound::Outlives(_) => {
+                    return unsupported("lifetime bounds are not supported in return type notation");
+                }
+            };
+
+            // Place the late-bound lifetimes of the function after the ones already bound by
+            // `ast_bound`, so they can be appended to its binder below. As with associated type
+            // bounds, the bound variables of the trait-ref are expected to be in scope already.
+            let param_ty = tcx
+                .shift_bound_var_indices(outer_vars.len(), output.skip_binder())
+                .subst(tcx, candidate.skip_binder().substs);
+
+            let mut return_bounds = Bounds::default();
+            self.add_bounds(param_ty, slice::from_ref(ast_bound), &mut return_bounds, outer_vars);
+
+            let rebind = |bound_vars: &'tcx ty::List<ty::BoundVariableKind>| {
+                if output_vars.is_empty() {
+                    Ok(bound_vars)
+                } else if bound_vars.len() == outer_vars.len() {
+                    Ok(tcx.mk_bound_variable_kinds(bound_vars.iter().chain(output_vars.iter())))
+                } else {
+                    tcx.sess.span_err(
+                        span,
+                        "higher-ranked bounds nested within return type notation are not supported",
+                    );
+                    Err(ErrorReported)
+                }
+            };
+            for (poly_trait_ref, span, constness) in return_bounds.trait_bounds {
+                let bound_vars = rebind(poly_trait_ref.bound_vars())?;
+                let poly_trait_ref =
+                    ty::Binder::bind_with_vars(poly_trait_ref.skip_binder(), bound_vars);
+                bounds.trait_bounds.push((poly_trait_ref, span, constness));
+            }
+            for (projection, span) in return_bounds.projection_bounds {
+                let bound_vars = rebind(projection.bound_vars())?;
+                let projection = ty::Binder::bind_with_vars(projection.skip_binder(), bound_vars);
+                bounds.projection_bounds.push((projection, span));
+            }
+            for (equality, span) in return_bounds.const_equality_bounds {
+                let bound_vars = rebind(equality.bound_vars())?;
+                let equality = ty::Binder::bind_with_vars(equality.skip_binder(), bound_vars);
+                bounds.const_equality_bounds.push((equality, span));
+            }
         }
         Ok(())
     }
@@ -1274,8 +1548,8 @@ trait here instead: `trait NewTrait: {} {{}}`",
             }
         }
 
-        // Use a `BTreeSet` to keep output in a more consistent order.
-        let mut associated_types: FxHashMap<Span, BTreeSet<DefId>> = FxHashMap::default();
+        // Use an `FxIndexMap` and a `BTreeSet` to keep output in a consistent order.
+        let mut associated_types: FxIndexMap<Span, BTreeSet<DefId>> = FxIndexMap::default();
 
         let regular_traits_refs_spans = bounds
             .trait_bounds
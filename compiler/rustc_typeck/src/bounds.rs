@@ -5,6 +5,9 @@
 use rustc_middle::ty::{self, ToPredicate, Ty, TyCtxt, WithConstness};
 use rustc_span::Span;
 
+/// An associated constant, and the value a bound requires it to have.
+pub type ConstEquality<'tcx> = (&'tcx ty::Const<'tcx>, &'tcx ty::Const<'tcx>);
+
 /// Collects together a list of type bounds. These lists of bounds occur in many places
 /// in Rust's syntax:
 ///
@@ -38,6 +41,11 @@ pub struct Bounds<'tcx> {
     /// here.
     pub projection_bounds: Vec<(ty::PolyProjectionPredicate<'tcx>, Span)>,
 
+    /// A list of associated const equality bounds. So if you had `T:
+    /// Trait<N = 3>` this would include the unevaluated `<T as Trait>::N`
+    /// along with the `3` it has to be equal to.
+    pub const_equality_bounds: Vec<(ty::Binder<'tcx, ConstEquality<'tcx>>, Span)>,
+
     /// `Some` if there is *no* `?Sized` predicate. The `span`
     /// is the location in the source of the `T` declaration which can
     /// be cited as the source of the `T: Sized` requirement.
@@ -84,6 +92,14 @@ pub fn predicates(
                     .iter()
                     .map(|&(projection, span)| (projection.to_predicate(tcx), span)),
             )
+            .chain(self.const_equality_bounds.iter().map(|&(equality, span)| {
+                let predicate = equality
+                    .map_bound(|(assoc_const, value)| {
+                        ty::PredicateKind::ConstEquate(assoc_const, value)
+                    })
+                    .to_predicate(tcx);
+                (predicate, span)
+            }))
             .collect()
     }
 }
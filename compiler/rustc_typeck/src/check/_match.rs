@@ -1,10 +1,15 @@
 use crate::check::coercion::{AsCoercionSite, CoerceMany};
+use crate::check::placeholder::placeholder_expr;
 use crate::check::{Diverges, Expectation, FnCtxt, Needs};
+use rustc_ast as ast;
 use rustc_errors::{Applicability, DiagnosticBuilder};
-use rustc_hir::{self as hir, ExprKind};
+use rustc_hir::{self as hir, ExprKind, LangItem, QPath};
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use rustc_infer::traits::Obligation;
-use rustc_middle::ty::{self, ToPredicate, Ty, TyS};
+use rustc_middle::ty::{
+    self, ScrutineeFact, ScrutineeTempScope, ScrutineeTempScopeVersion, ToPredicate, Ty, TyS,
+    TypeFoldable,
+};
 use rustc_span::{MultiSpan, Span};
 use rustc_trait_selection::opaque_types::InferCtxtExt as _;
 use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
@@ -80,6 +85,12 @@ pub fn check_match(
         } else {
             self.demand_scrutinee_type(scrut, arms_contain_ref_bindings(arms), arms.is_empty())
         };
+        if match_src == Normal {
+            self.record_scrutinee_fact(scrut, scrutinee_ty);
+        }
+        if let IfLetDesugar { .. } = match_src {
+            self.record_scrutinee_temp_scope(scrut, expr.hir_id, arms[0].body.hir_id);
+        }
 
         // If there are no arms, that is a diverging match; a special case.
         if arms.is_empty() {
@@ -142,6 +153,7 @@ pub fn check_match(
                             false,
                         );
                         self.check_pat_top(&pat, scrutinee_ty, None, true);
+                        self.record_scrutinee_temp_scope(e, arm.hir_id, e.hir_id);
                     }
                 };
             }
@@ -291,7 +303,28 @@ pub fn check_match(
         // We won't diverge unless the scrutinee or all arms diverge.
         self.diverges.set(scrut_diverges | all_arms_diverge);
 
-        coercion.complete(self)
+        let match_ty = coercion.complete(self);
+
+        // With `exhaustive_patterns`, none of the arms of a `match` on an uninhabited type can be
+        // reached, so like a `match` without arms, it diverges and has type `!`.
+        if match_src == Normal
+            && tcx.features().exhaustive_patterns
+            && self.is_visibly_uninhabited(scrutinee_ty)
+        {
+            self.diverges.set(
+                self.diverges.get()
+                    | Diverges::Always {
+                        span: expr.span,
+                        custom_note: Some(
+                            "any code following this `match` expression is unreachable, as its \
+                             scrutinee is uninhabited",
+                        ),
+                    },
+            );
+            return tcx.types.never;
+        }
+
+        match_ty
     }
 
     fn get_appropriate_arm_semicolon_removal_span(
@@ -316,6 +349,66 @@ fn get_appropriate_arm_semicolon_removal_span(
         (arm_span, semi_span)
     }
 
+    /// Records where the temporaries of `scrut`, the scrutinee of an `if let` or of an `if let`
+    /// guard, are dropped under each version of the rules for them: at the end of `v1_scope`,
+    /// or later, and at the end of `v2_scope`.
+    fn record_scrutinee_temp_scope(
+        &self,
+        scrut: &hir::Expr<'_>,
+        v1_scope: hir::HirId,
+        v2_scope: hir::HirId,
+    ) {
+        let scope = ScrutineeTempScope {
+            version: ScrutineeTempScopeVersion::for_edition(scrut.span.edition()),
+            v1_scope: v1_scope.local_id,
+            v2_scope: v2_scope.local_id,
+        };
+        self.typeck_results.borrow_mut().scrutinee_temp_scopes_mut().insert(scrut.hir_id, scope);
+    }
+
+    /// Records what is known about the value of `scrut` beyond its type, which exhaustiveness
+    /// checking points out when the match isn't exhaustive because of it.
+    fn record_scrutinee_fact(&self, scrut: &'tcx hir::Expr<'tcx>, scrut_ty: Ty<'tcx>) {
+        let int_lit = |expr: &hir::Expr<'_>| match expr.kind {
+            ExprKind::Lit(ref lit) => match lit.node {
+                ast::LitKind::Int(n, _) => Some(n),
+                _ => None,
+            },
+            _ => None,
+        };
+        let fact = match (self.resolve_vars_if_possible(scrut_ty).kind(), &scrut.kind) {
+            (ty::Uint(_), ExprKind::Binary(op, lhs, rhs)) => match op.node {
+                hir::BinOpKind::Rem => int_lit(*rhs).filter(|&n| n > 0).map(|n| n - 1),
+                hir::BinOpKind::BitAnd => int_lit(*rhs).or_else(|| int_lit(*lhs)),
+                _ => None,
+            }
+            .map(|max| ScrutineeFact::IntAtMost { max }),
+            (ty::Ref(_, slice_ty, _), ExprKind::AddrOf(_, _, place)) if slice_ty.is_slice() => {
+                match place.kind {
+                    ExprKind::Index(base, index)
+                        if matches!(
+                            index.kind,
+                            ExprKind::Struct(QPath::LangItem(LangItem::RangeFull, _), ..)
+                        ) =>
+                    {
+                        let base_ty = self.typeck_results.borrow().expr_ty(base);
+                        match *self.resolve_vars_if_possible(base_ty).peel_refs().kind() {
+                            ty::Array(_, len) => len
+                                .try_eval_usize(self.tcx, self.param_env)
+                                .map(|len| ScrutineeFact::SliceLen { len }),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        if let Some(fact) = fact {
+            self.typeck_results.borrow_mut().scrutinee_facts_mut().insert(scrut.hir_id, fact);
+        }
+    }
+
     /// When the previously checked expression (the scrutinee) diverges,
     /// warn the user about the match arms being unreachable.
     fn warn_arms_when_scrutinee_diverges(
@@ -352,6 +445,9 @@ pub(crate) fn if_fallback_coercion<F, T>(
         // the cause of the type coercion is the return type, point at it. (#25228)
         let ret_reason = ret_reason(then_expr.hir_id, span);
         let cause = self.cause(span, ObligationCauseCode::IfExpressionWithNoElse);
+        // The type the `else` block would need to evaluate to. We grab it before coercing, as
+        // the coercion holds on to `coercion` while reporting the error.
+        let expected_ty = self.resolve_vars_if_possible(coercion.merged_ty());
         let mut error = false;
         coercion.coerce_forced_unit(
             self,
@@ -365,7 +461,7 @@ pub(crate) fn if_fallback_coercion<F, T>(
                     }
                 }
                 err.note("`if` expressions without `else` evaluate to `()`");
-                err.help("consider adding an `else` block that evaluates to the expected type");
+                self.suggest_missing_else(err, then_expr, expected_ty);
                 error = true;
             },
             ret_reason.is_none(),
@@ -373,6 +469,41 @@ pub(crate) fn if_fallback_coercion<F, T>(
         error
     }
 
+    /// Suggest adding an `else` block evaluating to a placeholder of the expected type after
+    /// the `then` block of an `if` expression that is used as a value:
+    ///
+    /// ```
+    /// let x: usize = if cond { 3 }; // suggest `if cond { 3 } else { 42 }`
+    /// ```
+    fn suggest_missing_else(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        then_expr: &'tcx hir::Expr<'tcx>,
+        expected_ty: Ty<'tcx>,
+    ) {
+        let module = self.tcx.parent_module(self.body_id).to_def_id();
+        let val = placeholder_expr(self.tcx, self.param_env, module, expected_ty);
+        let val = match val {
+            // If the `if` comes from a macro expansion there is nowhere to put the `else`.
+            Some(val) if !then_expr.span.from_expansion() => val,
+            _ => {
+                err.help("consider adding an `else` block that evaluates to the expected type");
+                return;
+            }
+        };
+        let msg = if expected_ty.has_infer_types_or_consts() || !expected_ty.is_suggestable() {
+            "consider adding an `else` block that evaluates to the expected type".to_string()
+        } else {
+            format!("consider adding an `else` block that evaluates to `{}`", expected_ty)
+        };
+        err.span_suggestion_verbose(
+            then_expr.span.shrink_to_hi(),
+            &msg,
+            format!(" else {{ {} }}", val),
+            Applicability::HasPlaceholders,
+        );
+    }
+
     create_maybe_get_coercion_reason!(
         maybe_get_coercion_reason,
         |hir: rustc_middle::hir::map::Map<'a>, id| {
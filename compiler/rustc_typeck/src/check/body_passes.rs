@@ -0,0 +1,60 @@
+//! Analysis passes over type-checked bodies, run by drivers.
+//!
+//! Some analyses need more than the final `TypeckResults`. The inference context, and what
+//! was registered with it while checking a body, is only reachable through the `FnCtxt`, which
+//! is dropped as soon as `typeck` returns. Drivers can run such analyses, like experimental
+//! effect checkers, without forking this crate by overriding the queries that type-check bodies
+//! with the functions of this module, from the `override_queries` callback of
+//! `rustc_interface::Config`:
+//!
+//! ```ignore (illustrative)
+//! config.override_queries = Some(|_, providers, _| {
+//!     providers.typeck_and_used_trait_imports =
+//!         |tcx, def_id| body_passes::typeck_and_used_trait_imports(tcx, def_id, &[my_pass]);
+//!     providers.typeck_const_arg =
+//!         |tcx, key| body_passes::typeck_const_arg(tcx, key, &[my_pass]);
+//! });
+//! ```
+//!
+//! The passes are then part of the providers of that compilation only. They run when the
+//! results of a body are computed, so they don't see closures on their own, which are
+//! type-checked together with the body they are defined in. With incremental compilation, they
+//! don't see the bodies whose `typeck` results are reused from the previous session either:
+//! drivers that need to see every body should disable it.
+
+use super::{typeck_with_fallback, FnCtxt};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir as hir;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// An analysis pass over a body. It gets the `FnCtxt` that type-checked the body, after its
+/// results have been written back, and the resulting `TypeckResults`.
+pub type BodyPass =
+    for<'a, 'tcx> fn(&FnCtxt<'a, 'tcx>, &'tcx hir::Body<'tcx>, &'tcx ty::TypeckResults<'tcx>);
+
+/// The `typeck_and_used_trait_imports` query, running `passes` in order on the body once it is
+/// type-checked.
+pub fn typeck_and_used_trait_imports<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    passes: &[BodyPass],
+) -> (&'tcx ty::TypeckResults<'tcx>, &'tcx FxHashSet<LocalDefId>) {
+    if let Some(param_did) = tcx.opt_const_param_of(def_id) {
+        tcx.typeck_const_arg((def_id, param_did))
+    } else {
+        let fallback = move || tcx.type_of(def_id.to_def_id());
+        typeck_with_fallback(tcx, def_id, fallback, false, passes)
+    }
+}
+
+/// The `typeck_const_arg` query, running `passes` in order on the body once it is
+/// type-checked.
+pub fn typeck_const_arg<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    (did, param_did): (LocalDefId, DefId),
+    passes: &[BodyPass],
+) -> (&'tcx ty::TypeckResults<'tcx>, &'tcx FxHashSet<LocalDefId>) {
+    let fallback = move || tcx.type_of(param_did);
+    typeck_with_fallback(tcx, did, fallback, false, passes)
+}
@@ -14,6 +14,7 @@
     infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind},
     traits::ObligationCause,
 };
+use rustc_middle::mir::UnsafetyViolationDetails;
 use rustc_middle::ty::adjustment::{
     Adjust, Adjustment, AllowTwoPhase, AutoBorrow, AutoBorrowMutability,
 };
@@ -327,7 +328,8 @@ fn confirm_builtin_call(
                             .emit();
                     }
                 }
-                (callee_ty.fn_sig(self.tcx), Some(def_id))
+                self.check_const_call(def_id, call_expr.span);
+                (self.substituted_callee_sig(call_expr.span, def_id, subst), Some(def_id))
             }
             ty::FnPtr(sig) => (sig, None),
             ref t => {
@@ -456,6 +458,17 @@ fn confirm_builtin_call(
             }
         };
 
+        if fn_sig.unsafety() == hir::Unsafety::Unsafe {
+            // This includes the calls to intrinsics, which are unsafe unless they are
+            // explicitly listed as safe.
+            self.record_unsafe_op(call_expr.hir_id, UnsafetyViolationDetails::CallToUnsafeFunction);
+        } else if let Some(def_id) = def_id {
+            if !self.has_target_features_of(def_id) {
+                let details = UnsafetyViolationDetails::CallToFunctionWith;
+                self.record_unsafe_op(call_expr.hir_id, details);
+            }
+        }
+
         // Replace any late-bound regions that appear in the function
         // signature with region variables. We also have to
         // renormalize the associated types at this point, since they
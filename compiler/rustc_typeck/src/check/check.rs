@@ -4,6 +4,7 @@
 use super::*;
 
 use rustc_attr as attr;
+use rustc_data_structures::sync::par_iter;
 use rustc_errors::{Applicability, ErrorReported};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
@@ -12,12 +13,16 @@
 use rustc_hir::{def::Res, ItemKind, Node, PathSegment};
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use rustc_infer::infer::{RegionVariableOrigin, TyCtxtInferExt};
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::ty::fold::TypeFoldable;
 use rustc_middle::ty::layout::MAX_SIMD_LANES;
 use rustc_middle::ty::subst::GenericArgKind;
 use rustc_middle::ty::util::{Discr, IntTypeExt};
 use rustc_middle::ty::{self, OpaqueTypeKey, ParamEnv, RegionKind, Ty, TyCtxt};
-use rustc_session::lint::builtin::UNINHABITED_STATIC;
+use rustc_session::lint::builtin::{
+    HASH_EQ_MISMATCH, RECURSIVE_DEFAULT_METHODS, UNINHABITED_STATIC,
+};
+use rustc_session::lint::Level;
 use rustc_span::symbol::sym;
 use rustc_span::{self, MultiSpan, Span};
 use rustc_target::spec::abi::Abi;
@@ -29,9 +34,15 @@
 use std::iter;
 use std::ops::ControlFlow;
 
+/// Checks that all items of the crate are well-formed, one module at a time so that the
+/// diagnostics of each module are emitted together and the results can be cached per module.
 pub fn check_wf_new(tcx: TyCtxt<'_>) {
-    let visit = wfcheck::CheckTypeWellFormedVisitor::new(tcx);
-    tcx.hir().krate().par_visit_all_item_likes(&visit);
+    if tcx.sess.opts.debugging_opts.verify_wf_check_coverage {
+        wfcheck::verify_wf_check_coverage(tcx);
+    }
+    par_iter(&tcx.hir().krate().modules).for_each(|(&module, _)| {
+        tcx.ensure().check_mod_type_wf(module);
+    });
 }
 
 pub(super) fn check_abi(tcx: TyCtxt<'_>, span: Span, abi: Abi) {
@@ -64,10 +75,12 @@ pub(super) fn check_abi(tcx: TyCtxt<'_>, span: Span, abi: Abi) {
 ///
 /// * ...
 /// * inherited: other fields inherited from the enclosing fn (if any)
+/// * unsafety: the unsafe context of the body, which closures inherit from the enclosing fn
 pub(super) fn check_fn<'a, 'tcx>(
     inherited: &'a Inherited<'a, 'tcx>,
     param_env: ty::ParamEnv<'tcx>,
     fn_sig: ty::FnSig<'tcx>,
+    unsafety: UnsafetyState,
     decl: &'tcx hir::FnDecl<'tcx>,
     fn_id: hir::HirId,
     body: &'tcx hir::Body<'tcx>,
@@ -80,7 +93,8 @@ pub(super) fn check_fn<'a, 'tcx>(
     // Create the function context. This is either derived from scratch or,
     // in the case of closures, based on the outer context.
     let mut fcx = FnCtxt::new(inherited, param_env, body.value.hir_id);
-    fcx.ps.set(UnsafetyState::function(fn_sig.unsafety, fn_id));
+    fcx.ps.set(unsafety);
+    fcx.const_cx = ConstCx::for_body(fcx.tcx, body.id());
 
     let tcx = fcx.tcx;
     let sess = tcx.sess;
@@ -170,38 +184,7 @@ pub(super) fn check_fn<'a, 'tcx>(
     fn_maybe_err(tcx, span, fn_sig.abi);
 
     if fn_sig.abi == Abi::RustCall {
-        let expected_args = if let ImplicitSelfKind::None = decl.implicit_self { 1 } else { 2 };
-
-        let err = || {
-            let item = match tcx.hir().get(fn_id) {
-                Node::Item(hir::Item { kind: ItemKind::Fn(header, ..), .. }) => Some(header),
-                Node::ImplItem(hir::ImplItem {
-                    kind: hir::ImplItemKind::Fn(header, ..), ..
-                }) => Some(header),
-                Node::TraitItem(hir::TraitItem {
-                    kind: hir::TraitItemKind::Fn(header, ..),
-                    ..
-                }) => Some(header),
-                // Closures are RustCall, but they tuple their arguments, so shouldn't be checked
-                Node::Expr(hir::Expr { kind: hir::ExprKind::Closure(..), .. }) => None,
-                node => bug!("Item being checked wasn't a function/closure: {:?}", node),
-            };
-
-            if let Some(header) = item {
-                tcx.sess.span_err(header.span, "functions with the \"rust-call\" ABI must take a single non-self argument that is a tuple")
-            }
-        };
-
-        if fn_sig.inputs().len() != expected_args {
-            err()
-        } else {
-            // FIXME(CraftSpider) Add a check on parameter expansion, so we don't just make the ICE happen later on
-            //   This will probably require wide-scale changes to support a TupleKind obligation
-            //   We can't resolve this without knowing the type of the param
-            if !matches!(fn_sig.inputs()[expected_args - 1].kind(), ty::Tuple(_) | ty::Param(_)) {
-                err()
-            }
-        }
+        check_rust_call_abi(tcx, fn_id, decl, body, fn_sig);
     }
 
     if body.generator_kind.is_some() && can_be_generator.is_some() {
@@ -236,8 +219,15 @@ pub(super) fn check_fn<'a, 'tcx>(
     let inputs_fn = fn_sig.inputs().iter().copied();
     for (idx, (param_ty, param)) in inputs_fn.chain(maybe_va_list).zip(body.params).enumerate() {
         // Check the pattern.
-        let ty_span = try { inputs_hir?.get(idx)?.span };
+        let param_hir_ty: Option<&hir::Ty<'_>> = try { inputs_hir?.get(idx)? };
+        let ty_span = param_hir_ty.map(|ty| ty.span);
         fcx.check_pat_top(&param.pat, param_ty, ty_span, false);
+        if let Some(hir_ty) = param_hir_ty {
+            if !matches!(hir_ty.kind, hir::TyKind::Infer) {
+                let local_ty = LocalTy { decl_ty: param_ty, revealed_ty: param_ty };
+                fcx.record_local_decl(&param.pat, local_ty, hir_ty.span);
+            }
+        }
 
         // Check that argument is Sized.
         // The check for a non-trivial pattern is a hack to avoid duplicate warnings
@@ -413,6 +403,74 @@ pub(super) fn check_fn<'a, 'tcx>(
     (fcx, gen_ty)
 }
 
+/// Checks that a function with the "rust-call" ABI takes its arguments as a single tuple after
+/// its `self` receiver, if any, as calls through the `Fn*` traits untuple them.
+fn check_rust_call_abi<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_id: hir::HirId,
+    decl: &'tcx hir::FnDecl<'tcx>,
+    body: &'tcx hir::Body<'tcx>,
+    fn_sig: ty::FnSig<'tcx>,
+) {
+    let sig_span = match tcx.hir().get(fn_id) {
+        Node::Item(hir::Item { kind: ItemKind::Fn(sig, ..), .. })
+        | Node::ImplItem(hir::ImplItem { kind: hir::ImplItemKind::Fn(sig, ..), .. })
+        | Node::TraitItem(hir::TraitItem { kind: hir::TraitItemKind::Fn(sig, ..), .. }) => {
+            sig.span
+        }
+        // Closures are RustCall, but they tuple their arguments, so shouldn't be checked
+        Node::Expr(hir::Expr { kind: hir::ExprKind::Closure(..), .. }) => return,
+        node => bug!("Item being checked wasn't a function/closure: {:?}", node),
+    };
+
+    let receiver = if let hir::ImplicitSelfKind::None = decl.implicit_self { 0 } else { 1 };
+    // FIXME(CraftSpider) Add a check on parameter expansion, so we don't just make the ICE happen
+    //   later on. This will probably require wide-scale changes to support a TupleKind
+    //   obligation, as we can't resolve this without knowing the type of the param.
+    if let [arg] = &fn_sig.inputs()[receiver..] {
+        if matches!(arg.kind(), ty::Tuple(_) | ty::Param(_)) {
+            return;
+        }
+    }
+
+    let mut err = struct_span_err!(
+        tcx.sess,
+        sig_span,
+        E0784,
+        "functions with the \"rust-call\" ABI must take a single non-self argument that is a tuple"
+    );
+    let source_map = tcx.sess.source_map();
+    match &body.params[receiver..] {
+        [] => {
+            err.help("take an empty tuple, like `_: ()`, if the function has no arguments");
+        }
+        params @ [first, ..] => {
+            let pats = params
+                .iter()
+                .map(|param| source_map.span_to_snippet(param.pat.span))
+                .collect::<Result<Vec<_>, _>>();
+            let tys = decl.inputs[receiver..]
+                .iter()
+                .map(|ty| source_map.span_to_snippet(ty.span))
+                .collect::<Result<Vec<_>, _>>();
+            if let (Ok(pats), Ok(tys)) = (pats, tys) {
+                let sugg = if let ([pat], [ty]) = (&pats[..], &tys[..]) {
+                    format!("({},): ({},)", pat, ty)
+                } else {
+                    format!("({}): ({})", pats.join(", "), tys.join(", "))
+                };
+                err.span_suggestion_verbose(
+                    first.span.to(params[params.len() - 1].span),
+                    "take the arguments as a single tuple",
+                    sugg,
+                    Applicability::MaybeIncorrect,
+                );
+            }
+        }
+    }
+    err.emit();
+}
+
 fn check_struct(tcx: TyCtxt<'_>, def_id: LocalDefId, span: Span) {
     let def = tcx.adt_def(def_id);
     def.destructor(tcx); // force the destructor to be evaluated
@@ -745,6 +803,38 @@ fn check_opaque_meets_bounds<'tcx>(
     });
 }
 
+/// Checks that a function exported under a fixed symbol name doesn't return an opaque type,
+/// which code linking against the symbol has no way of knowing.
+fn check_exported_fn_return(tcx: TyCtxt<'_>, def_id: LocalDefId, decl: &hir::FnDecl<'_>) {
+    let codegen_attrs = tcx.codegen_fn_attrs(def_id);
+    let attr = if codegen_attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE) {
+        "no_mangle"
+    } else if codegen_attrs.export_name.is_some() {
+        "export_name"
+    } else {
+        return;
+    };
+    let output = tcx.fn_sig(def_id).output().skip_binder();
+    let returns_opaque = output.walk().any(|arg| match arg.unpack() {
+        GenericArgKind::Type(ty) => matches!(ty.kind(), ty::Opaque(..)),
+        _ => false,
+    });
+    if returns_opaque {
+        tcx.sess
+            .struct_span_err(
+                decl.output.span(),
+                &format!("functions with `#[{}]` cannot return `impl Trait`", attr),
+            )
+            .span_label(decl.output.span(), "the concrete type is hidden from other code")
+            .note(
+                "code linking against the exported symbol can't know the type the function \
+                 actually returns",
+            )
+            .help("return the concrete type, or export a wrapper function that does")
+            .emit();
+    }
+}
+
 pub fn check_item_type<'tcx>(tcx: TyCtxt<'tcx>, it: &'tcx hir::Item<'tcx>) {
     debug!(
         "check_item_type(it.def_id={:?}, it.name={})",
@@ -765,9 +855,15 @@ pub fn check_item_type<'tcx>(tcx: TyCtxt<'tcx>, it: &'tcx hir::Item<'tcx>) {
         hir::ItemKind::Enum(ref enum_definition, _) => {
             check_enum(tcx, it.span, &enum_definition.variants, it.def_id);
         }
-        hir::ItemKind::Fn(..) => {} // entirely within check_item_body
+        // The body is checked within `check_item_body`.
+        hir::ItemKind::Fn(ref sig, ..) => check_exported_fn_return(tcx, it.def_id, sig.decl),
         hir::ItemKind::Impl(ref impl_) => {
             debug!("ItemKind::Impl {} with id {:?}", it.ident, it.def_id);
+            for item in impl_.items {
+                if let hir::ImplItemKind::Fn(ref sig, _) = tcx.hir().impl_item(item.id).kind {
+                    check_exported_fn_return(tcx, item.id.def_id, sig.decl);
+                }
+            }
             if let Some(impl_trait_ref) = tcx.impl_trait_ref(it.def_id) {
                 check_impl_items_against_trait(
                     tcx,
@@ -778,6 +874,7 @@ pub fn check_item_type<'tcx>(tcx: TyCtxt<'tcx>, it: &'tcx hir::Item<'tcx>) {
                 );
                 let trait_def_id = impl_trait_ref.def_id;
                 check_on_unimplemented(tcx, trait_def_id, it);
+                check_hash_eq_consistency(tcx, it.def_id, impl_trait_ref);
             }
         }
         hir::ItemKind::Trait(_, _, _, _, ref items) => {
@@ -891,6 +988,69 @@ pub fn check_item_type<'tcx>(tcx: TyCtxt<'tcx>, it: &'tcx hir::Item<'tcx>) {
     }
 }
 
+/// Lints a `Hash` impl of a type whose `PartialEq` impl is derived while the `Hash` impl is
+/// written by hand, or the other way around. Values that compare equal must hash to the same
+/// value for the type to work as the key of a `HashMap`, which a derived impl and a hand-written
+/// one rarely agree on.
+fn check_hash_eq_consistency(
+    tcx: TyCtxt<'tcx>,
+    hash_impl: LocalDefId,
+    trait_ref: ty::TraitRef<'tcx>,
+) {
+    if Some(trait_ref.def_id) != tcx.get_diagnostic_item(sym::Hash) {
+        return;
+    }
+    let self_ty = trait_ref.self_ty();
+    let (adt, partial_eq) = match (self_ty.kind(), tcx.lang_items().eq_trait()) {
+        (ty::Adt(adt, _), Some(partial_eq)) => (adt, partial_eq),
+        _ => return,
+    };
+    // Only `PartialEq<Self>` impls are relevant, comparisons with other types don't need to
+    // agree with `Hash`.
+    let is_same_adt = |ty: Ty<'_>| matches!(ty.kind(), ty::Adt(def, _) if def == adt);
+    let mut eq_impl = None;
+    tcx.for_each_relevant_impl(partial_eq, self_ty, |impl_def_id| {
+        if eq_impl.is_some() || !impl_def_id.is_local() {
+            return;
+        }
+        let eq_trait_ref = tcx.impl_trait_ref(impl_def_id).unwrap();
+        if is_same_adt(eq_trait_ref.self_ty()) && is_same_adt(eq_trait_ref.substs.type_at(1)) {
+            eq_impl = Some(impl_def_id.expect_local());
+        }
+    });
+    let eq_impl = match eq_impl {
+        Some(eq_impl) => eq_impl,
+        None => return,
+    };
+
+    let is_derived =
+        |def_id: LocalDefId| tcx.has_attr(def_id.to_def_id(), sym::automatically_derived);
+    let (manual, manual_trait, derived, derived_trait) =
+        match (is_derived(hash_impl), is_derived(eq_impl)) {
+            (true, false) => (eq_impl, "PartialEq", hash_impl, "Hash"),
+            (false, true) => (hash_impl, "Hash", eq_impl, "PartialEq"),
+            _ => return,
+        };
+    let impl_span = |def_id: LocalDefId| {
+        tcx.sess.source_map().guess_head_span(tcx.def_span(def_id.to_def_id()))
+    };
+    tcx.struct_span_lint_hir(
+        HASH_EQ_MISMATCH,
+        tcx.hir().local_def_id_to_hir_id(manual),
+        impl_span(manual),
+        |lint| {
+            lint.build(&format!(
+                "`{}` is implemented by hand for `{}`, but `{}` is derived",
+                manual_trait, self_ty, derived_trait,
+            ))
+            .span_note(impl_span(derived), &format!("`{}` is derived here", derived_trait))
+            .note("values that are equal must have equal hashes, which a derived impl only \
+                   guarantees together with the other trait being derived as well")
+            .emit();
+        },
+    );
+}
+
 pub(super) fn check_on_unimplemented(tcx: TyCtxt<'_>, trait_def_id: DefId, item: &hir::Item<'_>) {
     // an error would be reported if this fails.
     let _ = traits::OnUnimplementedDirective::of_item(tcx, trait_def_id, item.def_id.to_def_id());
@@ -1097,11 +1257,120 @@ pub(super) fn check_impl_items_against_trait<'tcx>(
         }
 
         if !missing_items.is_empty() {
-            missing_items_err(tcx, impl_span, &missing_items, full_impl_span);
+            missing_items_err(tcx, impl_id, impl_span, &missing_items, full_impl_span);
+        }
+
+        if tcx.impl_defaultness(impl_id).is_final() {
+            for group in trait_def.must_implement_one_of.iter() {
+                let is_implemented = group.iter().any(|&ident| {
+                    ancestors
+                        .leaf_def(tcx, ident, ty::AssocKind::Fn)
+                        .map_or(false, |node_item| !node_item.defining_node.is_from_trait())
+                });
+                if !is_implemented {
+                    missing_items_must_implement_one_of_err(
+                        tcx,
+                        impl_trait_ref.def_id,
+                        impl_span,
+                        group,
+                    );
+                }
+            }
+
+            check_default_body_cycles(tcx, impl_id, impl_trait_ref.def_id, impl_span, |item| {
+                ancestors
+                    .leaf_def(tcx, item.ident, item.kind)
+                    .map_or(false, |node_item| !node_item.defining_node.is_from_trait())
+            });
         }
     }
 }
 
+/// Lints an impl that overrides none of the methods in a cycle of default method bodies that
+/// unconditionally call each other, which makes calling any of them recurse forever. Methods
+/// that only call themselves are already linted by `unconditional_recursion`.
+fn check_default_body_cycles(
+    tcx: TyCtxt<'_>,
+    impl_id: LocalDefId,
+    trait_def_id: DefId,
+    impl_span: Span,
+    is_overridden: impl Fn(&ty::AssocItem) -> bool,
+) {
+    let impl_hir_id = tcx.hir().local_def_id_to_hir_id(impl_id);
+    let (level, _) = tcx.lint_level_at_node(RECURSIVE_DEFAULT_METHODS, impl_hir_id);
+    // The default bodies of foreign traits aren't available.
+    let local_trait_def_id = match trait_def_id.as_local() {
+        Some(local_trait_def_id) if level != Level::Allow => local_trait_def_id,
+        _ => return,
+    };
+    let calls = tcx.default_body_calls(local_trait_def_id);
+    let defaults: Vec<_> = tcx
+        .associated_items(trait_def_id)
+        .in_definition_order()
+        .filter(|item| calls.contains_key(&item.def_id) && !is_overridden(item))
+        .collect();
+    let reachable: Vec<FxHashSet<DefId>> = defaults
+        .iter()
+        .map(|item| {
+            let mut reachable = FxHashSet::default();
+            let mut stack = vec![item.def_id];
+            while let Some(caller) = stack.pop() {
+                for &callee in &calls[&caller] {
+                    let is_default = defaults.iter().any(|default| default.def_id == callee);
+                    if is_default && reachable.insert(callee) {
+                        stack.push(callee);
+                    }
+                }
+            }
+            reachable
+        })
+        .collect();
+
+    let mut reported = FxHashSet::default();
+    for (i, item) in defaults.iter().enumerate() {
+        if reported.contains(&item.def_id) || !reachable[i].contains(&item.def_id) {
+            continue;
+        }
+        let cycle: Vec<_> = defaults
+            .iter()
+            .enumerate()
+            .filter(|&(j, other)| {
+                reachable[i].contains(&other.def_id) && reachable[j].contains(&item.def_id)
+            })
+            .map(|(_, other)| *other)
+            .collect();
+        reported.extend(cycle.iter().map(|item| item.def_id));
+        if cycle.len() < 2 {
+            continue;
+        }
+
+        let names: Vec<_> = cycle.iter().map(|item| format!("`{}`", item.ident)).collect();
+        let (last, rest) = names.split_last().unwrap();
+        let names = format!("{} and {}", rest.join(", "), last);
+        tcx.struct_span_lint_hir(RECURSIVE_DEFAULT_METHODS, impl_hir_id, impl_span, |lint| {
+            let mut err = lint.build(&format!(
+                "the default bodies of {} call each other, so calling them recurses forever",
+                names
+            ));
+            err.span_label(impl_span, "none of these methods are overridden in this impl");
+            for item in &cycle {
+                let callee = calls[&item.def_id]
+                    .iter()
+                    .find_map(|&callee| cycle.iter().find(|other| other.def_id == callee));
+                if let Some(callee) = callee {
+                    let span = tcx.sess.source_map().guess_head_span(tcx.def_span(item.def_id));
+                    err.span_label(
+                        span,
+                        format!("`{}` calls `{}` by default", item.ident, callee.ident),
+                    );
+                }
+            }
+            err.help("override at least one of these methods");
+            err.emit();
+        });
+    }
+}
+
 #[inline(never)]
 #[cold]
 fn report_mismatch_error<'tcx>(
@@ -1544,6 +1813,11 @@ pub(super) fn check_mod_item_types(tcx: TyCtxt<'_>, module_def_id: LocalDefId) {
     tcx.hir().visit_item_likes_in_module(module_def_id, &mut CheckItemTypesVisitor { tcx });
 }
 
+pub(super) fn check_mod_type_wf(tcx: TyCtxt<'_>, module_def_id: LocalDefId) {
+    let mut visitor = wfcheck::CheckTypeWellFormedVisitor::new(tcx);
+    tcx.hir().visit_item_likes_in_module(module_def_id, &mut visitor.as_deep_visitor());
+}
+
 pub(super) fn check_item_well_formed(tcx: TyCtxt<'_>, def_id: LocalDefId) {
     wfcheck::check_item_well_formed(tcx, def_id);
 }
@@ -73,8 +73,17 @@ fn check_closure(
 
         debug!("check_closure: ty_of_closure returns {:?}", liberated_sig);
 
-        let generator_types =
-            check_fn(self, self.param_env, liberated_sig, decl, expr.hir_id, body, gen).1;
+        let generator_types = check_fn(
+            self,
+            self.param_env,
+            liberated_sig,
+            self.ps.get(),
+            decl,
+            expr.hir_id,
+            body,
+            gen,
+        )
+        .1;
 
         let parent_substs = InternalSubsts::identity_for_item(
             self.tcx,
@@ -44,13 +44,16 @@
 use rustc_infer::infer::{Coercion, InferOk, InferResult};
 use rustc_middle::lint::in_external_macro;
 use rustc_middle::ty::adjustment::{
-    Adjust, Adjustment, AllowTwoPhase, AutoBorrow, AutoBorrowMutability, PointerCast,
+    Adjust, Adjustment, AllowTwoPhase, AutoBorrow, AutoBorrowMutability, CoercionSite,
+    PointerCast,
 };
 use rustc_middle::ty::error::TypeError;
 use rustc_middle::ty::fold::TypeFoldable;
 use rustc_middle::ty::relate::RelateResult;
 use rustc_middle::ty::subst::SubstsRef;
 use rustc_middle::ty::{self, Ty, TypeAndMut};
+use rustc_session::config::PpMode;
+use rustc_session::lint::builtin::UNINHABITED_READS;
 use rustc_session::parse::feature_err;
 use rustc_span::symbol::sym;
 use rustc_span::{self, BytePos, Span};
@@ -176,6 +179,10 @@ fn coerce(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> CoerceResult<'tcx> {
             };
         }
 
+        if self.coerces_as_never(a, b) {
+            return success(simple(Adjust::NeverToAny)(b), b, vec![]);
+        }
+
         // Consider coercing the subtype to a DST
         //
         // NOTE: this is wrapped in a `commit_if_ok` because it creates
@@ -236,6 +243,15 @@ fn coerce(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> CoerceResult<'tcx> {
         }
     }
 
+    /// With `uninhabited_coercions`, a value of an uninhabited type coerces to any type it
+    /// doesn't already unify with, the same way `!` does.
+    fn coerces_as_never(&self, a: Ty<'tcx>, b: Ty<'tcx>) -> bool {
+        self.tcx.features().uninhabited_coercions
+            && !self.shallow_resolve(b).is_ty_var()
+            && self.is_visibly_uninhabited(a)
+            && self.probe(|_| self.unify(a, b)).is_err()
+    }
+
     /// Reborrows `&mut A` to `&mut B` and `&(mut) A` to `&B`.
     /// To match `A` with `B`, autoderef will be performed,
     /// calling `deref`/`deref_mut` where necessary.
@@ -549,6 +565,9 @@ fn coerce_unsized(&self, mut source: Ty<'tcx>, mut target: Ty<'tcx>) -> CoerceRe
             }
         })?;
 
+        // This goes through `SelectionContext` whatever the trait solver is, as the chalk-based
+        // one doesn't tell which impl proves an obligation, and so can't pick the source type to
+        // unsize below.
         let mut selcx = traits::SelectionContext::new(self);
 
         // Create an obligation for `Source: CoerceUnsized<Target>`.
@@ -862,8 +881,10 @@ pub fn try_coerce(
         let cause = self.cause(expr.span, ObligationCauseCode::ExprAssignable);
         let coerce = Coerce::new(self, cause, allow_two_phase);
         let ok = self.commit_if_ok(|_| coerce.coerce(source, target))?;
+        self.record_coercion_site(expr, source, &ok);
 
         let (adjustments, _) = self.register_infer_ok_obligations(ok);
+        self.lint_uninhabited_read(expr, source, &adjustments);
         self.apply_adjustments(expr, adjustments);
         Ok(if expr_ty.references_error() { self.tcx.ty_error() } else { target })
     }
@@ -891,6 +912,62 @@ pub fn deref_steps(&self, expr_ty: Ty<'tcx>, target: Ty<'tcx>) -> Option<usize>
             .find_map(|(ty, steps)| self.probe(|_| coerce.unify(ty, target)).ok().map(|_| steps))
     }
 
+    /// Lints the coercion of `expr` from an uninhabited type other than `!` that
+    /// `uninhabited_coercions` allows.
+    fn lint_uninhabited_read(
+        &self,
+        expr: &hir::Expr<'_>,
+        source: Ty<'tcx>,
+        adjustments: &[Adjustment<'tcx>],
+    ) {
+        let target = match adjustments {
+            [Adjustment { kind: Adjust::NeverToAny, target }] => target,
+            _ => return,
+        };
+        let source = self.resolve_vars_if_possible(source);
+        if source.is_never() || !self.is_visibly_uninhabited(source) {
+            return;
+        }
+        self.tcx.struct_span_lint_hir(UNINHABITED_READS, expr.hir_id, expr.span, |lint| {
+            lint.build(&format!("reading a value of the uninhabited type `{}`", source))
+                .span_label(
+                    expr.span,
+                    format!(
+                        "coerced to `{}` as if it were `!`",
+                        self.resolve_vars_if_possible(*target)
+                    ),
+                )
+                .note("a value of an uninhabited type can never exist, so this code is unreachable")
+                .emit();
+        });
+    }
+
+    /// Records the coercion of `expr` from `source` for `-Zunpretty=typeck-coercions`, along
+    /// with the obligations it generated, which aren't kept around anywhere else.
+    fn record_coercion_site(
+        &self,
+        expr: &hir::Expr<'_>,
+        source: Ty<'tcx>,
+        ok: &InferOk<'tcx, (Vec<Adjustment<'tcx>>, Ty<'tcx>)>,
+    ) {
+        if self.tcx.sess.opts.pretty != Some(PpMode::TypeckCoercions) {
+            return;
+        }
+        let (ref adjustments, target) = ok.value;
+        let site = CoercionSite {
+            source,
+            target,
+            adjustments: self.tcx.intern_adjustments(adjustments),
+            obligations: ok.obligations.iter().map(|obligation| obligation.predicate).collect(),
+        };
+        self.typeck_results
+            .borrow_mut()
+            .coercion_sites_mut()
+            .entry(expr.hir_id)
+            .or_default()
+            .push(site);
+    }
+
     /// Given some expressions, their known unified type and another expression,
     /// tries to unify the types, potentially inserting coercions on any of the
     /// provided expressions and returns their LUB (aka "common supertype").
@@ -1027,7 +1104,9 @@ fn try_find_coercion_lub<E>(
             let result = self.commit_if_ok(|_| coerce.coerce(new_ty, prev_ty));
             match result {
                 Ok(ok) => {
+                    self.record_coercion_site(new, new_ty, &ok);
                     let (adjustments, target) = self.register_infer_ok_obligations(ok);
+                    self.lint_uninhabited_read(new, new_ty, &adjustments);
                     self.apply_adjustments(new, adjustments);
                     debug!(
                         "coercion::try_find_coercion_lub: was able to coerce from previous type {:?} to new type {:?}",
@@ -1089,6 +1168,9 @@ fn try_find_coercion_lub<E>(
                     "coercion::try_find_coercion_lub: was able to coerce previous type {:?} to new type {:?}",
                     prev_ty, new_ty,
                 );
+                for expr in exprs {
+                    self.record_coercion_site(expr.as_coercion_site(), prev_ty, &ok);
+                }
                 let (adjustments, target) = self.register_infer_ok_obligations(ok);
                 for expr in exprs {
                     let expr = expr.as_coercion_site();
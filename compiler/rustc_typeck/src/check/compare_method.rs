@@ -1,4 +1,5 @@
 use crate::errors::LifetimesOrBoundsMismatchOnTrait;
+use rustc_ast as ast;
 use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticId, ErrorReported};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
@@ -10,7 +11,7 @@
 use rustc_middle::ty::error::{ExpectedFound, TypeError};
 use rustc_middle::ty::subst::{InternalSubsts, Subst};
 use rustc_middle::ty::util::ExplicitSelf;
-use rustc_middle::ty::{GenericParamDefKind, ToPredicate, TyCtxt};
+use rustc_middle::ty::{GenericParamDefKind, ToPredicate, Ty, TyCtxt};
 use rustc_span::Span;
 use rustc_trait_selection::traits::error_reporting::InferCtxtExt;
 use rustc_trait_selection::traits::{self, ObligationCause, ObligationCauseCode, Reveal};
@@ -51,6 +52,10 @@
         return;
     }
 
+    if let Err(ErrorReported) = compare_generic_param_kinds(tcx, impl_m, trait_m) {
+        return;
+    }
+
     if let Err(ErrorReported) =
         compare_number_of_method_arguments(tcx, impl_m, impl_m_span, trait_m, trait_item_span)
     {
@@ -219,15 +224,17 @@ fn compare_predicate_entailment<'tcx>(
 
         debug!("compare_impl_method: caller_bounds={:?}", param_env.caller_bounds());
 
-        let mut selcx = traits::SelectionContext::new(&infcx);
-
         let impl_m_own_bounds = impl_m_predicates.instantiate_own(tcx, impl_to_placeholder_substs);
-        for predicate in impl_m_own_bounds.predicates {
+        for (predicate, span) in iter::zip(impl_m_own_bounds.predicates, impl_m_own_bounds.spans) {
             let traits::Normalized { value: predicate, obligations } =
-                traits::normalize(&mut selcx, param_env, normalize_cause.clone(), predicate);
+                inh.trait_solver.normalize(infcx, param_env, normalize_cause.clone(), predicate);
+
+            // Point at the predicate itself if it turns out not to be entailed by the trait.
+            let mut cause = cause.clone();
+            cause.make_mut().span = span;
 
             inh.register_predicates(obligations);
-            inh.register_predicate(traits::Obligation::new(cause.clone(), param_env, predicate));
+            inh.register_predicate(traits::Obligation::new(cause, param_env, predicate));
         }
 
         // We now need to check that the signature of the impl method is
@@ -273,6 +280,33 @@ fn compare_predicate_entailment<'tcx>(
         if let Err(terr) = sub_result {
             debug!("sub_types failed: impl ty {:?}, trait ty {:?}", impl_fty, trait_fty);
 
+            // If the signatures only differ in their receivers, say so instead of comparing the
+            // whole signatures.
+            if trait_m.fn_has_self_parameter
+                && matches!(terr, TypeError::ArgumentMutability(0) | TypeError::ArgumentSorts(_, 0))
+            {
+                let impl_sig_with_trait_receiver = tcx.mk_fn_sig(
+                    iter::once(trait_sig.inputs()[0]).chain(impl_sig.inputs()[1..].iter().copied()),
+                    impl_sig.output(),
+                    impl_sig.c_variadic,
+                    impl_sig.unsafety,
+                    impl_sig.abi,
+                );
+                let fty = tcx.mk_fn_ptr(ty::Binder::dummy(impl_sig_with_trait_receiver));
+                if infcx.probe(|_| infcx.at(&cause, param_env).sup(trait_fty, fty).is_ok())
+                    && report_receiver_mismatch(
+                        tcx,
+                        impl_m,
+                        trait_m,
+                        impl_trait_ref.self_ty(),
+                        trait_sig.inputs()[0],
+                        impl_sig.inputs()[0],
+                    )
+                {
+                    return Err(ErrorReported);
+                }
+            }
+
             let (impl_err_span, trait_err_span) =
                 extract_spans_for_error_reporting(&infcx, &terr, &cause, impl_m, trait_m);
 
@@ -290,34 +324,13 @@ fn compare_predicate_entailment<'tcx>(
                     if trait_m.fn_has_self_parameter =>
                 {
                     let ty = trait_sig.inputs()[0];
-                    let sugg = match ExplicitSelf::determine(ty, |_| ty == impl_trait_ref.self_ty())
-                    {
-                        ExplicitSelf::ByValue => "self".to_owned(),
-                        ExplicitSelf::ByReference(_, hir::Mutability::Not) => "&self".to_owned(),
-                        ExplicitSelf::ByReference(_, hir::Mutability::Mut) => {
-                            "&mut self".to_owned()
-                        }
-                        _ => format!("self: {}", ty),
-                    };
-
-                    // When the `impl` receiver is an arbitrary self type, like `self: Box<Self>`, the
-                    // span points only at the type `Box<Self`>, but we want to cover the whole
-                    // argument pattern and type.
-                    let impl_m_hir_id =
-                        tcx.hir().local_def_id_to_hir_id(impl_m.def_id.expect_local());
-                    let span = match tcx.hir().expect_impl_item(impl_m_hir_id).kind {
-                        ImplItemKind::Fn(ref sig, body) => tcx
-                            .hir()
-                            .body_param_names(body)
-                            .zip(sig.decl.inputs.iter())
-                            .map(|(param, ty)| param.span.to(ty.span))
-                            .next()
-                            .unwrap_or(impl_err_span),
-                        _ => bug!("{:?} is not a method", impl_m),
+                    let sugg = match receiver_str(ty, impl_trait_ref.self_ty()) {
+                        Some(receiver) => receiver.to_owned(),
+                        None => format!("self: {}", ty),
                     };
 
                     diag.span_suggestion(
-                        span,
+                        impl_receiver_span(tcx, impl_m).unwrap_or(impl_err_span),
                         "change the self-receiver type to match the trait",
                         sugg,
                         Applicability::MachineApplicable,
@@ -348,6 +361,20 @@ fn compare_predicate_entailment<'tcx>(
                             }
                             _ => {}
                         };
+                        // `Fn::call` and `FnMut::call_mut` return the `Output` of the `FnOnce`
+                        // impl, which is usually somewhere else.
+                        if let Some(ty::ClosureKind::Fn | ty::ClosureKind::FnMut) =
+                            tcx.fn_trait_kind_from_lang_item(impl_trait_ref.def_id)
+                        {
+                            diag.note(&format!(
+                                "`{}` has to return the `Output` of the `FnOnce<{}>` \
+                                 implementation for `{}`, which is `{}`",
+                                trait_m.ident,
+                                impl_trait_ref.substs.type_at(1),
+                                impl_trait_ref.self_ty(),
+                                trait_sig.output(),
+                            ));
+                        }
                     } else if let Some(trait_ty) = trait_sig.inputs().get(*i) {
                         diag.span_suggestion(
                             impl_err_span,
@@ -390,6 +417,92 @@ fn compare_predicate_entailment<'tcx>(
     })
 }
 
+/// Returns `self`, `&self` or `&mut self` for a receiver of type `receiver_ty`, or `None` for
+/// other receiver types.
+fn receiver_str<'tcx>(receiver_ty: Ty<'tcx>, self_ty: Ty<'tcx>) -> Option<&'static str> {
+    match ExplicitSelf::determine(receiver_ty, |ty| ty == self_ty) {
+        ExplicitSelf::ByValue => Some("self"),
+        ExplicitSelf::ByReference(_, hir::Mutability::Not) => Some("&self"),
+        ExplicitSelf::ByReference(_, hir::Mutability::Mut) => Some("&mut self"),
+        _ => None,
+    }
+}
+
+/// Returns the span of the receiver of `impl_m`, including both the pattern and the type.
+///
+/// When the receiver is an arbitrary self type, like `self: Box<Self>`, the span of its type
+/// points only at `Box<Self>`, but we want to cover the whole argument pattern and type.
+fn impl_receiver_span(tcx: TyCtxt<'_>, impl_m: &ty::AssocItem) -> Option<Span> {
+    let impl_m_hir_id = tcx.hir().local_def_id_to_hir_id(impl_m.def_id.expect_local());
+    match tcx.hir().expect_impl_item(impl_m_hir_id).kind {
+        ImplItemKind::Fn(ref sig, body) => tcx
+            .hir()
+            .body_param_names(body)
+            .zip(sig.decl.inputs.iter())
+            .map(|(param, ty)| param.span.to(ty.span))
+            .next(),
+        _ => bug!("{:?} is not a method", impl_m),
+    }
+}
+
+/// Reports a method whose signature only differs from the trait's in the form of its receiver,
+/// like `&mut self` in the impl for `&self` in the trait.
+///
+/// Returns `false` without emitting anything if either receiver is not one of `self`, `&self`
+/// and `&mut self`, in which case the mismatch is reported as a general signature mismatch.
+fn report_receiver_mismatch<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    impl_m: &ty::AssocItem,
+    trait_m: &ty::AssocItem,
+    self_ty: Ty<'tcx>,
+    trait_receiver_ty: Ty<'tcx>,
+    impl_receiver_ty: Ty<'tcx>,
+) -> bool {
+    let (trait_receiver, impl_receiver, span) = match (
+        receiver_str(trait_receiver_ty, self_ty),
+        receiver_str(impl_receiver_ty, self_ty),
+        impl_receiver_span(tcx, impl_m),
+    ) {
+        (Some(trait_receiver), Some(impl_receiver), Some(span)) => {
+            (trait_receiver, impl_receiver, span)
+        }
+        _ => return false,
+    };
+
+    let mut err = struct_span_err!(
+        tcx.sess,
+        span,
+        E0053,
+        "method `{}` takes `{}` in the impl, but `{}` in the trait",
+        trait_m.ident,
+        impl_receiver,
+        trait_receiver,
+    );
+    err.span_suggestion(
+        span,
+        "change the receiver to match the trait",
+        trait_receiver.to_owned(),
+        Applicability::MachineApplicable,
+    );
+    let trait_receiver_span = trait_m.def_id.as_local().and_then(|def_id| {
+        let trait_m_hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+        match tcx.hir().expect_trait_item(trait_m_hir_id).kind {
+            TraitItemKind::Fn(ref sig, _) => sig.decl.inputs.first().map(|ty| ty.span),
+            _ => bug!("{:?} is not a TraitItemKind::Fn", trait_m),
+        }
+    });
+    match trait_receiver_span {
+        Some(span) => {
+            err.span_label(span, format!("`{}` used in trait", trait_receiver));
+        }
+        None => {
+            err.note_trait_signature(trait_m.ident.to_string(), trait_m.signature(tcx));
+        }
+    }
+    err.emit();
+    true
+}
+
 fn check_region_bounds_on_impl_item<'tcx>(
     tcx: TyCtxt<'tcx>,
     span: Span,
@@ -572,6 +685,7 @@ fn compare_number_of_generics<'tcx>(
     ];
 
     let item_kind = assoc_item_kind_str(impl_);
+    let mut suggestion = suggested_impl_generics(tcx, impl_, trait_);
 
     let mut err_occurred = false;
     for (kind, trait_count, impl_count) in matchings {
@@ -676,6 +790,15 @@ fn compare_number_of_generics<'tcx>(
                 err.span_label(*span, "`impl Trait` introduces an implicit type parameter");
             }
 
+            if let Some((span, sugg)) = suggestion.take() {
+                err.span_suggestion_verbose(
+                    span,
+                    "change the generic parameters to match the trait",
+                    sugg,
+                    Applicability::MaybeIncorrect,
+                );
+            }
+
             err.emit();
         }
     }
@@ -683,6 +806,155 @@ fn compare_number_of_generics<'tcx>(
     if err_occurred { Err(ErrorReported) } else { Ok(()) }
 }
 
+/// Checks that the type and const parameters of `impl_` are declared in the same order as those
+/// of `trait_`, and that corresponding const parameters have the same type. Their numbers have
+/// already been checked to match by `compare_number_of_generics`.
+fn compare_generic_param_kinds<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    impl_: &ty::AssocItem,
+    trait_: &ty::AssocItem,
+) -> Result<(), ErrorReported> {
+    let non_lifetime_params = |def_id| {
+        tcx.generics_of(def_id)
+            .params
+            .iter()
+            .filter(|param| !matches!(param.kind, GenericParamDefKind::Lifetime))
+    };
+    let describe = |param: &ty::GenericParamDef| match param.kind {
+        GenericParamDefKind::Type { .. } => "type parameter".to_owned(),
+        GenericParamDefKind::Const { .. } => {
+            format!("const parameter of type `{}`", tcx.type_of(param.def_id))
+        }
+        GenericParamDefKind::Lifetime => unreachable!(),
+    };
+
+    for (impl_param, trait_param) in
+        non_lifetime_params(impl_.def_id).zip(non_lifetime_params(trait_.def_id))
+    {
+        let compatible = match (&impl_param.kind, &trait_param.kind) {
+            (GenericParamDefKind::Type { .. }, GenericParamDefKind::Type { .. }) => true,
+            (GenericParamDefKind::Const { .. }, GenericParamDefKind::Const { .. }) => {
+                tcx.type_of(impl_param.def_id) == tcx.type_of(trait_param.def_id)
+            }
+            _ => false,
+        };
+        if compatible {
+            continue;
+        }
+
+        let impl_span = tcx.def_span(impl_param.def_id);
+        let mut err = struct_span_err!(
+            tcx.sess,
+            impl_span,
+            E0053,
+            "{} `{}` has an incompatible generic parameter for trait: `{}`",
+            assoc_item_kind_str(impl_),
+            trait_.ident,
+            trait_param.name,
+        );
+        err.span_label(impl_span, format!("found {}", describe(impl_param)));
+        match tcx.hir().span_if_local(trait_param.def_id) {
+            Some(span) => {
+                err.span_label(span, format!("expected {}", describe(trait_param)));
+            }
+            None => {
+                err.note(&format!(
+                    "the trait declares a {} `{}` at this position",
+                    describe(trait_param),
+                    trait_param.name,
+                ));
+            }
+        }
+        if let Some((span, sugg)) = suggested_impl_generics(tcx, impl_, trait_) {
+            err.span_suggestion_verbose(
+                span,
+                "change the generic parameters to match the trait",
+                sugg,
+                Applicability::MaybeIncorrect,
+            );
+        }
+        err.emit();
+        return Err(ErrorReported);
+    }
+
+    Ok(())
+}
+
+/// Renders the generics list that `impl_` would need to declare the same type and const
+/// parameters as `trait_`, along with the span to replace with it.
+///
+/// Parameters are matched up by kind and position: the impl keeps its names and bounds for the
+/// parameters the trait declares too, missing parameters are added with the trait's names, and
+/// extra ones are dropped. Returns `None` when this isn't expressible, e.g. because of
+/// `impl Trait` arguments, or when the lifetime parameters don't match up either.
+fn suggested_impl_generics(
+    tcx: TyCtxt<'_>,
+    impl_: &ty::AssocItem,
+    trait_: &ty::AssocItem,
+) -> Option<(Span, String)> {
+    let source_map = tcx.sess.source_map();
+    let impl_generics = tcx.hir().get_generics(impl_.def_id)?;
+    let explicit_lifetimes = |generics: &hir::Generics<'_>| {
+        generics
+            .params
+            .iter()
+            .filter(|param| {
+                matches!(
+                    param.kind,
+                    GenericParamKind::Lifetime { kind: hir::LifetimeParamKind::Explicit }
+                )
+            })
+            .count()
+    };
+    let trait_lifetimes = match tcx.hir().get_generics(trait_.def_id) {
+        Some(generics) => explicit_lifetimes(generics),
+        None => tcx.generics_of(trait_.def_id).own_counts().lifetimes,
+    };
+    if trait_lifetimes != explicit_lifetimes(impl_generics) {
+        return None;
+    }
+
+    let mut params = vec![];
+    let mut impl_types = vec![];
+    let mut impl_consts = vec![];
+    for param in impl_generics.params {
+        let with_bounds = || {
+            source_map.span_to_snippet(param.span.to(param.bounds_span().unwrap_or(param.span)))
+        };
+        match param.kind {
+            GenericParamKind::Lifetime { kind: hir::LifetimeParamKind::Explicit } => {
+                params.push(with_bounds().ok()?)
+            }
+            GenericParamKind::Lifetime { .. } => {}
+            GenericParamKind::Type { synthetic: Some(_), .. } => return None,
+            GenericParamKind::Type { .. } => impl_types.push(with_bounds().ok()?),
+            GenericParamKind::Const { .. } => impl_consts.push(param.name.ident()),
+        }
+    }
+
+    let mut impl_types = impl_types.into_iter();
+    let mut impl_consts = impl_consts.into_iter();
+    for param in &tcx.generics_of(trait_.def_id).params {
+        match param.kind {
+            GenericParamDefKind::Lifetime => {}
+            GenericParamDefKind::Type { synthetic: Some(_), .. } => return None,
+            GenericParamDefKind::Type { .. } => {
+                params.push(impl_types.next().unwrap_or_else(|| param.name.to_string()))
+            }
+            GenericParamDefKind::Const { .. } => params.push(format!(
+                "const {}: {}",
+                impl_consts.next().map_or(param.name, |ident| ident.name),
+                tcx.type_of(param.def_id),
+            )),
+        }
+    }
+
+    if params.is_empty() {
+        return None;
+    }
+    Some((impl_generics.span, format!("<{}>", params.join(", "))))
+}
+
 fn compare_number_of_method_arguments<'tcx>(
     tcx: TyCtxt<'tcx>,
     impl_m: &ty::AssocItem,
@@ -986,10 +1258,13 @@ fn nested_visit_map(
             );
 
             // Locate the Span containing just the type of the offending impl
-            match tcx.hir().expect_impl_item(impl_c_hir_id).kind {
-                ImplItemKind::Const(ref ty, _) => cause.make_mut().span = ty.span,
+            let impl_c_body = match tcx.hir().expect_impl_item(impl_c_hir_id).kind {
+                ImplItemKind::Const(ref ty, body_id) => {
+                    cause.make_mut().span = ty.span;
+                    tcx.hir().body(body_id)
+                }
                 _ => bug!("{:?} is not a impl const", impl_c),
-            }
+            };
 
             let mut diag = struct_span_err!(
                 tcx.sess,
@@ -1019,6 +1294,19 @@ fn nested_visit_map(
                 })),
                 &terr,
             );
+            // If the value would also be valid for the trait's type, the annotation in the impl
+            // is most likely what is wrong.
+            if unsuffixed_literal_fits(tcx, &impl_c_body.value, trait_ty) {
+                diag.span_suggestion(
+                    cause.span,
+                    "change the type to match the trait",
+                    trait_ty.to_string(),
+                    Applicability::MachineApplicable,
+                );
+            }
+            if trait_c_span.is_none() {
+                diag.note_trait_signature(trait_c.ident.to_string(), trait_c.signature(tcx));
+            }
             diag.emit();
         }
 
@@ -1034,6 +1322,33 @@ fn nested_visit_map(
     });
 }
 
+/// Whether `expr` is an unsuffixed numeric literal, possibly negated, whose value can also be
+/// represented by the numeric type `ty`.
+fn unsuffixed_literal_fits<'tcx>(tcx: TyCtxt<'tcx>, expr: &hir::Expr<'_>, ty: Ty<'tcx>) -> bool {
+    let (negated, lit) = match expr.kind {
+        hir::ExprKind::Lit(ref lit) => (false, lit),
+        hir::ExprKind::Unary(
+            hir::UnOp::Neg,
+            &hir::Expr { kind: hir::ExprKind::Lit(ref lit), .. },
+        ) => (true, lit),
+        _ => return false,
+    };
+    let pointer_width = tcx.sess.target.pointer_width;
+    match (&lit.node, ty.kind()) {
+        (&ast::LitKind::Int(value, ast::LitIntType::Unsuffixed), ty::Int(int_ty)) => {
+            let bits = int_ty.normalize(pointer_width).bit_width().unwrap();
+            let max = u128::MAX >> (129 - bits);
+            if negated { value <= max + 1 } else { value <= max }
+        }
+        (&ast::LitKind::Int(value, ast::LitIntType::Unsuffixed), ty::Uint(uint_ty)) => {
+            let bits = uint_ty.normalize(pointer_width).bit_width().unwrap();
+            !negated && value <= u128::MAX >> (128 - bits)
+        }
+        (&ast::LitKind::Float(_, ast::LitFloatType::Unsuffixed), ty::Float(_)) => true,
+        _ => false,
+    }
+}
+
 crate fn compare_ty_impl<'tcx>(
     tcx: TyCtxt<'tcx>,
     impl_ty: &ty::AssocItem,
@@ -1046,6 +1361,7 @@ fn nested_visit_map(
 
     let _: Result<(), ErrorReported> = (|| {
         compare_number_of_generics(tcx, impl_ty, impl_ty_span, trait_ty, trait_item_span)?;
+        compare_generic_param_kinds(tcx, impl_ty, trait_ty)?;
 
         compare_type_predicate_entailment(tcx, impl_ty, impl_ty_span, trait_ty, impl_trait_ref)?;
 
@@ -1128,14 +1444,16 @@ fn compare_type_predicate_entailment<'tcx>(
 
         debug!("compare_type_predicate_entailment: caller_bounds={:?}", param_env.caller_bounds());
 
-        let mut selcx = traits::SelectionContext::new(&infcx);
-
-        for predicate in impl_ty_own_bounds.predicates {
+        let impl_ty_own_bounds = iter::zip(impl_ty_own_bounds.predicates, impl_ty_own_bounds.spans);
+        for (predicate, span) in impl_ty_own_bounds {
             let traits::Normalized { value: predicate, obligations } =
-                traits::normalize(&mut selcx, param_env, normalize_cause.clone(), predicate);
+                inh.trait_solver.normalize(infcx, param_env, normalize_cause.clone(), predicate);
+
+            let mut cause = cause.clone();
+            cause.make_mut().span = span;
 
             inh.register_predicates(obligations);
-            inh.register_predicate(traits::Obligation::new(cause.clone(), param_env, predicate));
+            inh.register_predicate(traits::Obligation::new(cause, param_env, predicate));
         }
 
         // Check that all obligations are satisfied by the implementation's
@@ -1228,7 +1546,6 @@ pub fn check_type_bounds<'tcx>(
     tcx.infer_ctxt().enter(move |infcx| {
         let inh = Inherited::new(infcx, impl_ty.def_id.expect_local());
         let infcx = &inh.infcx;
-        let mut selcx = traits::SelectionContext::new(&infcx);
 
         let impl_ty_hir_id = tcx.hir().local_def_id_to_hir_id(impl_ty.def_id.expect_local());
         let normalize_cause = traits::ObligationCause::misc(impl_ty_span, impl_ty_hir_id);
@@ -1253,12 +1570,13 @@ pub fn check_type_bounds<'tcx>(
         debug!("check_type_bounds: item_bounds={:?}", obligations);
 
         for mut obligation in util::elaborate_obligations(tcx, obligations) {
-            let traits::Normalized { value: normalized_predicate, obligations } = traits::normalize(
-                &mut selcx,
-                normalize_param_env,
-                normalize_cause.clone(),
-                obligation.predicate,
-            );
+            let traits::Normalized { value: normalized_predicate, obligations } =
+                inh.trait_solver.normalize(
+                    infcx,
+                    normalize_param_env,
+                    normalize_cause.clone(),
+                    obligation.predicate,
+                );
             debug!("compare_projection_bounds: normalized predicate = {:?}", normalized_predicate);
             obligation.predicate = normalized_predicate;
 
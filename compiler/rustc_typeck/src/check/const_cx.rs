@@ -0,0 +1,111 @@
+//! The const context a body is type-checked in.
+//!
+//! Const functions, the initializers of constants and statics, inline `const` blocks and the
+//! defaults of const parameters are all evaluated at compile time, which rules out operations
+//! like allocating or calling trait methods. These are checked on MIR after type checking, but
+//! with `-Z typeck-const-checks` the operations type checking can already recognize are reported
+//! right away, with the same errors.
+
+use super::FnCtxt;
+use rustc_errors::struct_span_err;
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::parse::feature_err;
+use rustc_span::symbol::sym;
+use rustc_span::Span;
+
+/// The const context of the body a `FnCtxt` checks.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConstCx {
+    kind: Option<hir::ConstContext>,
+}
+
+impl ConstCx {
+    pub fn for_body(tcx: TyCtxt<'_>, body_id: hir::BodyId) -> ConstCx {
+        ConstCx { kind: tcx.hir().body_const_context(tcx.hir().body_owner_def_id(body_id)) }
+    }
+
+    /// The kind of const context, or `None` for bodies that are evaluated at runtime. This
+    /// includes closures, even when they are defined in a const context.
+    pub fn kind(self) -> Option<hir::ConstContext> {
+        self.kind
+    }
+
+    pub fn is_const(self) -> bool {
+        self.kind.is_some()
+    }
+}
+
+/// An operation that can't be evaluated at compile time, at least not without a feature.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum NonConstOp {
+    /// A `box` expression.
+    HeapAllocation,
+    /// A call of the trait method with the given `DefId`.
+    TraitMethodCall(DefId),
+    /// The dereference of a raw pointer.
+    RawPtrDeref,
+}
+
+impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
+    /// Reports `op`, performed by the expression at `span`, if the const context of this body
+    /// doesn't allow it.
+    pub(super) fn check_const_op(&self, op: NonConstOp, span: Span) {
+        let tcx = self.tcx;
+        let kind = match self.const_cx.kind() {
+            Some(kind) if tcx.sess.opts.debugging_opts.typeck_const_checks => kind,
+            _ => return,
+        };
+        let mut err = match op {
+            NonConstOp::HeapAllocation => {
+                let mut err = struct_span_err!(
+                    tcx.sess,
+                    span,
+                    E0010,
+                    "allocations are not allowed in {}s",
+                    kind
+                );
+                err.span_label(span, format!("allocation not allowed in {}s", kind));
+                err
+            }
+            NonConstOp::TraitMethodCall(def_id) => {
+                // Methods of `impl const` blocks can be called, but only const checking can
+                // tell which impl a call ends up using.
+                if tcx.features().const_trait_impl {
+                    return;
+                }
+                let mut err = struct_span_err!(
+                    tcx.sess,
+                    span,
+                    E0015,
+                    "calls in {}s are limited to constant functions, \
+                     tuple structs and tuple variants",
+                    kind
+                );
+                err.span_label(span, format!("`{}` is a trait method", tcx.def_path_str(def_id)));
+                err
+            }
+            NonConstOp::RawPtrDeref => {
+                if tcx.features().const_raw_ptr_deref {
+                    return;
+                }
+                feature_err(
+                    &tcx.sess.parse_sess,
+                    sym::const_raw_ptr_deref,
+                    span,
+                    &format!("dereferencing raw pointers in {}s is unstable", kind),
+                )
+            }
+        };
+        err.emit();
+    }
+
+    /// Reports the call of `callee` at `span` if it is a trait method and this body is in a
+    /// const context.
+    pub(super) fn check_const_call(&self, callee: DefId, span: Span) {
+        if self.tcx.trait_of_item(callee).is_some() {
+            self.check_const_op(NonConstOp::TraitMethodCall(callee), span);
+        }
+    }
+}
@@ -29,14 +29,17 @@ pub fn emit_coerce_suggestions(
         expected_ty_expr: Option<&'tcx hir::Expr<'tcx>>,
     ) {
         self.annotate_expected_due_to_let_ty(err, expr);
+        self.annotate_expected_due_to_local_decl(err, expected, expected_ty_expr);
         self.suggest_compatible_variants(err, expr, expected, expr_ty);
         self.suggest_deref_ref_or_into(err, expr, expected, expr_ty, expected_ty_expr);
+        self.suggest_iterator_element_ref_mismatch(err, expr, expected, expr_ty);
         if self.suggest_calling_boxed_future_when_appropriate(err, expr, expected, expr_ty) {
             return;
         }
         self.suggest_no_capture_closure(err, expected, expr_ty);
         self.suggest_boxing_when_appropriate(err, expr, expected, expr_ty);
         self.suggest_missing_parentheses(err, expr);
+        self.suggest_compare_with_zero(err, expr, expected, expr_ty);
         self.note_need_for_fn_pointer(err, expected, expr_ty);
         self.note_internal_mutation_in_method(err, expr, expected, expr_ty);
         self.report_closure_infered_return_type(err, expected)
@@ -171,6 +174,39 @@ fn annotate_expected_due_to_let_ty(
         }
     }
 
+    /// When assigning to a local whose type was written out by the user, point at
+    /// that annotation, as it is what forced the expected type.
+    fn annotate_expected_due_to_local_decl(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expected: Ty<'tcx>,
+        expected_ty_expr: Option<&'tcx hir::Expr<'tcx>>,
+    ) {
+        let lhs = match expected_ty_expr {
+            Some(lhs) if self.tcx.hir().is_lhs(lhs.hir_id) => lhs,
+            _ => return,
+        };
+        let (hir_id, ident) = match lhs.kind {
+            hir::ExprKind::Path(hir::QPath::Resolved(
+                None,
+                hir::Path { res: hir::def::Res::Local(hir_id), segments: [segment], .. },
+            )) => (*hir_id, segment.ident),
+            _ => return,
+        };
+        let decl = match self.local_decls.borrow().get(&hir_id) {
+            Some(decl) => *decl,
+            None => return,
+        };
+        let expected = self.resolve_vars_if_possible(expected);
+        if self.resolve_vars_if_possible(decl.ty.revealed_ty) != expected {
+            return;
+        }
+        err.span_label(
+            decl.ty_span,
+            format!("`{}` has type `{}` due to this", ident, self.ty_to_string(expected)),
+        );
+    }
+
     /// Returns whether the expected type is `bool` and the expression is `x = y`.
     pub fn is_assign_to_bool(&self, expr: &hir::Expr<'_>, expected: Ty<'tcx>) -> bool {
         if let hir::ExprKind::Assign(..) = expr.kind {
@@ -836,7 +872,7 @@ pub fn check_for_cast(
             |expr: &hir::Expr<'_>| matches!(expr.kind, hir::ExprKind::Unary(hir::UnOp::Neg, ..));
         let is_uint = |ty: Ty<'_>| matches!(ty.kind(), ty::Uint(..));
 
-        let in_const_context = self.tcx.hir().is_inside_const_context(expr.hir_id);
+        let in_const_context = self.const_cx.is_const();
 
         let suggest_fallible_into_or_lhs_from =
             |err: &mut DiagnosticBuilder<'_>, exp_to_found_is_fallible: bool| {
@@ -4,9 +4,11 @@
 
 use crate::astconv::AstConv as _;
 use crate::check::cast;
+use crate::check::const_cx::NonConstOp;
 use crate::check::coercion::CoerceMany;
 use crate::check::fatally_break_rust;
 use crate::check::method::SelfSource;
+use crate::check::placeholder::placeholder_expr;
 use crate::check::report_unexpected_variant_res;
 use crate::check::BreakableCtxt;
 use crate::check::Diverges;
@@ -33,6 +35,7 @@
 use rustc_hir::{ExprKind, QPath};
 use rustc_infer::infer;
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
+use rustc_middle::mir::UnsafetyViolationDetails;
 use rustc_middle::ty;
 use rustc_middle::ty::adjustment::{Adjust, Adjustment, AllowTwoPhase};
 use rustc_middle::ty::subst::SubstsRef;
@@ -46,6 +49,19 @@
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_trait_selection::traits::{self, ObligationCauseCode};
 
+/// One field of a field path resolved by `FnCtxt::resolve_field_path`.
+#[derive(Copy, Clone, Debug)]
+pub struct ResolvedField<'tcx> {
+    /// The struct, union or tuple type the field belongs to.
+    pub container: Ty<'tcx>,
+    /// The index of the field in `container`.
+    pub index: usize,
+    /// The definition of the field, or `None` for a field of a tuple.
+    pub def: Option<&'tcx ty::FieldDef>,
+    /// The type of the field, with the substitutions of `container` applied.
+    pub ty: Ty<'tcx>,
+}
+
 impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
     fn check_expr_eq_type(&self, expr: &'tcx hir::Expr<'tcx>, expected: Ty<'tcx>) {
         let ty = self.check_expr_with_hint(expr, expected);
@@ -91,6 +107,7 @@ fn check_expr_meets_expectation_or_error(
         if let Some(mut err) = self.demand_suptype_diag(expr.span, expected_ty, ty) {
             let expr = expr.peel_drop_temps();
             self.suggest_deref_ref_or_into(&mut err, expr, expected_ty, ty, None);
+            self.suggest_compare_with_zero(&mut err, expr, expected_ty, ty);
             extend_err(&mut err);
             // Error possibly reported in `check_assign` so avoid emitting error again.
             err.emit_unless(self.is_assign_to_bool(expr, expected_ty));
@@ -163,6 +180,8 @@ pub(super) fn check_expr_with_expectation(
     ) -> Ty<'tcx> {
         debug!(">> type-checking: expected={:?}, expr={:?} ", expected, expr);
 
+        self.tcx.sess.abort_if_cancelled();
+
         // True if `expr` is a `Try::from_ok(())` that is a result of desugaring a try block
         // without the final expr (e.g. `try { return; }`). We don't want to generate an
         // unreachable_code lint for it since warnings for autogenerated code are confusing.
@@ -228,7 +247,10 @@ fn check_expr_kind(
 
         let tcx = self.tcx;
         match expr.kind {
-            ExprKind::Box(subexpr) => self.check_expr_box(subexpr, expected),
+            ExprKind::Box(subexpr) => {
+                self.check_const_op(NonConstOp::HeapAllocation, expr.span);
+                self.check_expr_box(subexpr, expected)
+            }
             ExprKind::Lit(ref lit) => self.check_lit(&lit, expected),
             ExprKind::Binary(op, lhs, rhs) => self.check_binop(expr, op, lhs, rhs),
             ExprKind::Assign(lhs, rhs, ref span) => {
@@ -243,8 +265,12 @@ fn check_expr_kind(
                 self.check_lang_item_path(lang_item, expr)
             }
             ExprKind::Path(ref qpath) => self.check_expr_path(qpath, expr),
-            ExprKind::InlineAsm(asm) => self.check_expr_asm(asm),
+            ExprKind::InlineAsm(asm) => {
+                self.record_unsafe_op(expr.hir_id, UnsafetyViolationDetails::UseOfInlineAssembly);
+                self.check_expr_asm(asm)
+            }
             ExprKind::LlvmInlineAsm(asm) => {
+                self.record_unsafe_op(expr.hir_id, UnsafetyViolationDetails::UseOfInlineAssembly);
                 for expr in asm.outputs_exprs.iter().chain(asm.inputs_exprs.iter()) {
                     self.check_expr(expr);
                 }
@@ -329,6 +355,13 @@ fn check_expr_unary(
             oprnd_t = self.structurally_resolved_type(expr.span, oprnd_t);
             match unop {
                 hir::UnOp::Deref => {
+                    if oprnd_t.is_unsafe_ptr() {
+                        self.record_unsafe_op(
+                            expr.hir_id,
+                            UnsafetyViolationDetails::DerefOfRawPointer,
+                        );
+                        self.check_const_op(NonConstOp::RawPtrDeref, expr.span);
+                    }
                     if let Some(ty) = self.lookup_derefing(expr, oprnd, oprnd_t) {
                         oprnd_t = ty;
                     } else {
@@ -350,6 +383,17 @@ fn check_expr_unary(
                         oprnd_t = tcx.ty_error();
                     }
                 }
+                hir::UnOp::Not
+                    if oprnd_t.is_integral()
+                        && expected.only_has_type(self).map_or(false, |ty| ty.is_bool())
+                        && self.is_desugared_negation(expr, oprnd) =>
+                {
+                    // `assert!(cond)` expands to `if !cond { .. }`, with the `!` spanning the
+                    // whole macro call. Rather than negating an integer condition and blaming the
+                    // call for the result not being a `bool`, require the condition to be one.
+                    oprnd_t =
+                        self.demand_coerce(oprnd, oprnd_t, tcx.types.bool, None, AllowTwoPhase::No);
+                }
                 hir::UnOp::Not => {
                     let result = self.check_user_unop(expr, oprnd_t, unop);
                     // If it's builtin, we can reuse the type, this helps inference.
@@ -480,6 +524,14 @@ fn check_expr_path(
             _ => self.instantiate_value_path(segs, opt_ty, res, expr.span, expr.hir_id).0,
         };
 
+        if let Res::Def(DefKind::Static, def_id) = res {
+            if tcx.is_mutable_static(def_id) {
+                self.record_unsafe_op(expr.hir_id, UnsafetyViolationDetails::UseOfMutableStatic);
+            } else if tcx.is_foreign_item(def_id) {
+                self.record_unsafe_op(expr.hir_id, UnsafetyViolationDetails::UseOfExternStatic);
+            }
+        }
+
         if let ty::FnDef(..) = ty.kind() {
             let fn_sig = ty.fn_sig(tcx);
             if !tcx.features().unsized_fn_params {
@@ -606,7 +658,10 @@ fn check_expr_break(
                             self.suggest_mismatched_types_on_tail(
                                 &mut err, expr, ty, e_ty, target_id,
                             );
-                            if let Some(val) = ty_kind_suggestion(ty) {
+                            let module = self.tcx.parent_module(self.body_id).to_def_id();
+                            if let Some(val) =
+                                placeholder_expr(self.tcx, self.param_env, module, ty)
+                            {
                                 let label = destination
                                     .label
                                     .map(|l| format!(" {}", l.ident))
@@ -937,11 +992,17 @@ fn check_expr_loop(
         expr: &'tcx hir::Expr<'tcx>,
     ) -> Ty<'tcx> {
         let coerce = match source {
-            // you can only use break with a value from a normal `loop { }`
+            // you can only use break with a value from a normal `loop { }`, or from a `for` loop
+            // with `#![feature(for_loop_break_value)]`, where AST lowering makes the loop break
+            // with `Default::default()` once the iterator is exhausted.
             hir::LoopSource::Loop => {
                 let coerce_to = expected.coercion_target_type(self, body.span);
                 Some(CoerceMany::new(coerce_to))
             }
+            hir::LoopSource::ForLoop if self.tcx.features().for_loop_break_value => {
+                let coerce_to = expected.coercion_target_type(self, body.span);
+                Some(CoerceMany::new(coerce_to))
+            }
 
             hir::LoopSource::While | hir::LoopSource::WhileLet | hir::LoopSource::ForLoop => None,
         };
@@ -993,6 +1054,13 @@ fn check_method_call(
                 // trigger this codepath causing `structuraly_resolved_type` to emit an error.
 
                 self.write_method_call(expr.hir_id, method);
+                if method.sig.unsafety == hir::Unsafety::Unsafe {
+                    self.record_unsafe_op(
+                        expr.hir_id,
+                        UnsafetyViolationDetails::CallToUnsafeFunction,
+                    );
+                }
+                self.check_const_call(method.def_id, expr.span);
                 Ok(method)
             }
             Err(error) => {
@@ -1093,7 +1161,7 @@ fn check_expr_repeat(
         element: &'tcx hir::Expr<'tcx>,
         count: &'tcx hir::AnonConst,
         expected: Expectation<'tcx>,
-        _expr: &'tcx hir::Expr<'tcx>,
+        expr: &'tcx hir::Expr<'tcx>,
     ) -> Ty<'tcx> {
         let tcx = self.tcx;
         let count = self.to_const(count);
@@ -1125,6 +1193,19 @@ fn check_expr_repeat(
             return tcx.ty_error();
         }
 
+        // Constants are evaluated anew for every element, so they don't have to be `Copy`.
+        let is_constant = match element.kind {
+            ExprKind::ConstBlock(..) => true,
+            ExprKind::Path(ref qpath) => matches!(
+                self.typeck_results.borrow().qpath_res(qpath, element.hir_id),
+                Res::Def(DefKind::Const | DefKind::AssocConst, _)
+            ),
+            _ => false,
+        };
+        if !is_constant {
+            self.deferred_repeat_expr_checks.borrow_mut().push((expr, t, count));
+        }
+
         tcx.mk_ty(ty::Array(t, count))
     }
 
@@ -1669,6 +1750,76 @@ fn check_field(
         self.tcx().ty_error()
     }
 
+    /// Resolves a path of fields starting at `container`, like `a.b.0` in
+    /// `offset_of!(Container, a.b.0)`, for builtins that name fields without accessing them.
+    ///
+    /// Unlike field access expressions, this never auto-derefs: each field has to be a field of
+    /// the type of the previous one. Privacy and stability are checked as for a field access
+    /// at `hir_id`. Errors are reported at the offending field.
+    pub fn resolve_field_path(
+        &self,
+        hir_id: hir::HirId,
+        container: Ty<'tcx>,
+        fields: &[Ident],
+    ) -> Result<Vec<ResolvedField<'tcx>>, ErrorReported> {
+        let mut resolved = Vec::with_capacity(fields.len());
+        let mut current = container;
+        for &field in fields {
+            current = self.structurally_resolved_type(field.span, current);
+            let (index, def, ty) = match *current.kind() {
+                ty::Adt(adt_def, substs) if !adt_def.is_enum() => {
+                    let (ident, def_scope) =
+                        self.tcx.adjust_ident_and_get_scope(field, adt_def.did, self.body_id);
+                    let variant_fields = &adt_def.non_enum_variant().fields;
+                    let index = variant_fields
+                        .iter()
+                        .position(|f| f.ident.normalize_to_macros_2_0() == ident);
+                    let index = match index {
+                        Some(index) => index,
+                        None => {
+                            self.no_such_field_err(field, current).emit();
+                            return Err(ErrorReported);
+                        }
+                    };
+                    let field_def = &variant_fields[index];
+                    if !field_def.vis.is_accessible_from(def_scope, self.tcx) {
+                        self.private_field_err(field, adt_def.did).emit();
+                        return Err(ErrorReported);
+                    }
+                    self.tcx.check_stability(field_def.did, Some(hir_id), field.span, None);
+                    (index, Some(field_def), self.field_ty(field.span, field_def, substs))
+                }
+                ty::Tuple(tys) => {
+                    let fstr = field.as_str();
+                    let index =
+                        fstr.parse::<usize>().ok().filter(|index| fstr == index.to_string());
+                    match index.and_then(|index| Some((index, tys.get(index)?))) {
+                        Some((index, ty)) => (index, None, ty.expect_ty()),
+                        None => {
+                            self.no_such_field_err(field, current).emit();
+                            return Err(ErrorReported);
+                        }
+                    }
+                }
+                ty::Error(_) => return Err(ErrorReported),
+                _ => {
+                    let mut err = self.no_such_field_err(field, current);
+                    if current.builtin_deref(true).is_some() {
+                        err.note(&format!(
+                            "`{}` is not dereferenced to look for fields of the type it points to",
+                            current,
+                        ));
+                    }
+                    err.emit();
+                    return Err(ErrorReported);
+                }
+            };
+            resolved.push(ResolvedField { container: current, index, def, ty });
+            current = ty;
+        }
+        Ok(resolved)
+    }
+
     fn suggest_await_on_field_access(
         &self,
         err: &mut DiagnosticBuilder<'_>,
@@ -1754,18 +1905,7 @@ fn ban_private_field_access(
         field: Ident,
         base_did: DefId,
     ) {
-        let struct_path = self.tcx().def_path_str(base_did);
-        let kind_name = self.tcx().def_kind(base_did).descr(base_did);
-        let mut err = struct_span_err!(
-            self.tcx().sess,
-            field.span,
-            E0616,
-            "field `{}` of {} `{}` is private",
-            field,
-            kind_name,
-            struct_path
-        );
-        err.span_label(field.span, "private field");
+        let mut err = self.private_field_err(field, base_did);
         // Also check if an accessible method exists, which is often what is meant.
         if self.method_exists(field, expr_t, expr.hir_id, false) && !self.expr_in_place(expr.hir_id)
         {
@@ -1780,6 +1920,22 @@ fn ban_private_field_access(
         err.emit();
     }
 
+    fn private_field_err(&self, field: Ident, base_did: DefId) -> DiagnosticBuilder<'_> {
+        let struct_path = self.tcx().def_path_str(base_did);
+        let kind_name = self.tcx().def_kind(base_did).descr(base_did);
+        let mut err = struct_span_err!(
+            self.tcx().sess,
+            field.span,
+            E0616,
+            "field `{}` of {} `{}` is private",
+            field,
+            kind_name,
+            struct_path
+        );
+        err.span_label(field.span, "private field");
+        err
+    }
+
     fn ban_take_value_of_method(&self, expr: &hir::Expr<'_>, expr_t: Ty<'tcx>, field: Ident) {
         let mut err = type_error_struct!(
             self.tcx().sess,
@@ -2076,8 +2232,7 @@ fn check_expr_yield(
     ) -> Ty<'tcx> {
         match self.resume_yield_tys {
             Some((resume_ty, yield_ty)) => {
-                self.check_expr_coercable_to_type(&value, yield_ty, None);
-
+                self.check_yield_value(value, yield_ty);
                 resume_ty
             }
             // Given that this `yield` expression was generated as a result of lowering a `.await`,
@@ -2097,6 +2252,32 @@ fn check_expr_yield(
         }
     }
 
+    /// Checks the value of a `yield` against the yield type of the generator, pointing at the
+    /// first `yield`, which determined the yield type, when they disagree.
+    fn check_yield_value(&self, value: &'tcx hir::Expr<'tcx>, yield_ty: Ty<'tcx>) {
+        let prior_yield_span = match self.first_yield_span.get() {
+            Some(span) => span,
+            None => {
+                self.check_expr_coercable_to_type(value, yield_ty, None);
+                // Unless a `yield` nested in `value` was checked first.
+                if self.first_yield_span.get().is_none() {
+                    self.first_yield_span.set(Some(value.span));
+                }
+                return;
+            }
+        };
+        let value_ty = self.check_expr_with_hint(value, yield_ty);
+        if let Err(e) = self.try_coerce(value, value_ty, yield_ty, AllowTwoPhase::No) {
+            let cause =
+                self.cause(value.span, ObligationCauseCode::YieldExpression { prior_yield_span });
+            let value_ty = self.resolve_vars_with_obligations(value_ty);
+            let yield_ty = self.resolve_vars_with_obligations(yield_ty);
+            let mut err = self.report_mismatched_types(&cause, yield_ty, value_ty, e);
+            self.emit_coerce_suggestions(&mut err, value, value_ty, yield_ty, None);
+            err.emit();
+        }
+    }
+
     fn check_expr_asm_operand(&self, expr: &'tcx hir::Expr<'tcx>, is_input: bool) {
         let needs = if is_input { Needs::None } else { Needs::MutPlace };
         let ty = self.check_expr_with_needs(expr, needs);
@@ -2166,14 +2347,3 @@ fn check_expr_asm(&self, asm: &'tcx hir::InlineAsm<'tcx>) -> Ty<'tcx> {
         }
     }
 }
-
-pub(super) fn ty_kind_suggestion(ty: Ty<'_>) -> Option<&'static str> {
-    Some(match ty.kind() {
-        ty::Bool => "true",
-        ty::Char => "'a'",
-        ty::Int(_) | ty::Uint(_) => "42",
-        ty::Float(_) => "3.14159",
-        ty::Error(_) | ty::Never => return None,
-        _ => "value",
-    })
-}
@@ -8,16 +8,18 @@
 
 use rustc_ast::TraitObjectSyntax;
 use rustc_data_structures::captures::Captures;
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{Applicability, DiagnosticBuilder, ErrorReported};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Res};
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::lang_items::LangItem;
 use rustc_hir::{ExprKind, GenericArg, Node, QPath, TyKind};
 use rustc_infer::infer::canonical::{Canonical, OriginalQueryValues, QueryResponse};
 use rustc_infer::infer::error_reporting::TypeAnnotationNeeded::E0282;
 use rustc_infer::infer::{InferOk, InferResult};
+use rustc_middle::mir::UnsafetyViolationDetails;
 use rustc_middle::ty::adjustment::{Adjust, Adjustment, AutoBorrow, AutoBorrowMutability};
 use rustc_middle::ty::fold::TypeFoldable;
 use rustc_middle::ty::subst::{
@@ -25,7 +27,7 @@
 };
 use rustc_middle::ty::{
     self, AdtKind, CanonicalUserType, DefIdTree, GenericParamDefKind, ToPolyTraitRef, ToPredicate,
-    Ty, UserType,
+    Ty, TyCtxt, UnsafeOp, UserType,
 };
 use rustc_session::lint;
 use rustc_session::lint::builtin::BARE_TRAIT_OBJECTS;
@@ -47,6 +49,57 @@
 use std::slice;
 
 impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
+    /// Explains how to write a `Self` path whose self type isn't a struct of the kind the path
+    /// requires, so that `Self` constructors and struct paths in both expressions and patterns
+    /// are reported alike. `ctor` is whether `Self` was used as a constructor.
+    pub(in super::super) fn note_bad_self_path(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        span: Span,
+        self_ty: Ty<'tcx>,
+        ctor: bool,
+    ) {
+        match self_ty.ty_adt_def().map(|adt_def| adt_def.adt_kind()) {
+            Some(AdtKind::Enum) => {
+                err.help("did you mean to use one of the enum's variants?");
+            }
+            Some(AdtKind::Struct | AdtKind::Union) if ctor => {
+                err.span_suggestion(
+                    span,
+                    "use curly brackets",
+                    String::from("Self { /* fields */ }"),
+                    Applicability::HasPlaceholders,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Records that the operation `id` requires `unsafe` because of `details`, along with the
+    /// unsafe block or unsafe function that allows it, if any. Reporting operations that aren't
+    /// allowed is left to the unsafety checker.
+    pub(in super::super) fn record_unsafe_op(
+        &self,
+        id: hir::HirId,
+        details: UnsafetyViolationDetails,
+    ) {
+        let ps = self.ps.get();
+        let context = match ps.unsafety {
+            hir::Unsafety::Unsafe => Some(ps.def),
+            hir::Unsafety::Normal => None,
+        };
+        self.typeck_results.borrow_mut().unsafe_ops_mut().insert(id, UnsafeOp { details, context });
+    }
+
+    /// Whether the body being checked enables all the target features `callee` requires, which
+    /// makes calling it safe even when it has `#[target_feature]` attributes.
+    pub(in super::super) fn has_target_features_of(&self, callee: DefId) -> bool {
+        let body_owner = self.body_id.owner.to_def_id();
+        let callee_features = &self.tcx.codegen_fn_attrs(callee).target_features;
+        let body_features = &self.tcx.codegen_fn_attrs(body_owner).target_features;
+        callee_features.iter().all(|feature| body_features.contains(feature))
+    }
+
     /// Produces warning on the given node, if the current point in the
     /// function is unreachable, and there hasn't been another warning.
     pub(in super::super) fn warn_if_unreachable(&self, id: hir::HirId, span: Span, kind: &str) {
@@ -110,6 +163,16 @@ pub(in super::super) fn resolve_vars_with_obligations(&self, mut ty: Ty<'tcx>) -
         ty
     }
 
+    /// Whether `ty` is known to be uninhabited from the module of the body being checked.
+    pub(in super::super) fn is_visibly_uninhabited(&self, ty: Ty<'tcx>) -> bool {
+        let ty = self.resolve_vars_if_possible(ty);
+        if ty.has_infer_types_or_consts() || ty.references_error() {
+            return false;
+        }
+        let module = self.tcx.parent_module(self.body_id).to_def_id();
+        self.tcx.is_ty_uninhabited_from(module, ty, self.param_env)
+    }
+
     pub(in super::super) fn record_deferred_call_resolution(
         &self,
         closure_def_id: DefId,
@@ -290,7 +353,7 @@ pub fn apply_adjustments(&self, expr: &hir::Expr<'_>, adj: Vec<Adjustment<'tcx>>
 
         match self.typeck_results.borrow_mut().adjustments_mut().entry(expr.hir_id) {
             Entry::Vacant(entry) => {
-                entry.insert(adj);
+                entry.insert(self.tcx.intern_adjustments(&adj));
             }
             Entry::Occupied(mut entry) => {
                 debug!(" - composing on top of {:?}", entry.get());
@@ -314,7 +377,7 @@ pub fn apply_adjustments(&self, expr: &hir::Expr<'_>, adj: Vec<Adjustment<'tcx>>
                         bug!("while adjusting {:?}, can't compose {:?} and {:?}",
                              expr, entry.get(), adj)
                 };
-                *entry.get_mut() = adj;
+                *entry.get_mut() = self.tcx.intern_adjustments(&adj);
             }
         }
 
@@ -359,6 +422,133 @@ pub(in super::super) fn instantiate_bounds(
         (result, spans)
     }
 
+    /// Returns the signature of the function item `def_id` that is being called.
+    ///
+    /// While `diagnostic_only_typeck` infers the signature of a function with an inferred
+    /// return type (`fn foo() -> _`), asking for the signature of another such function, or of
+    /// the same one when it recurses, would cycle back into the query. In that case the
+    /// callee's signature is lowered from its declaration with an error return type instead.
+    /// The placeholder is always reported, so this only affects error recovery.
+    pub(in super::super) fn callee_fn_sig(&self, def_id: DefId) -> ty::PolyFnSig<'tcx> {
+        match def_id.as_local() {
+            Some(local_def_id)
+                if self.inh.diagnostic_only && self.callee_reaches_body_owner(local_def_id) =>
+            {
+                self.inferred_ret_fn_sig_for_recovery(local_def_id)
+                    .unwrap_or_else(|| self.tcx.fn_sig(def_id))
+            }
+            _ => self.tcx.fn_sig(def_id),
+        }
+    }
+
+    /// Returns the signature of `def_id` with `substs` applied and the associated types that don't
+    /// mention its late-bound regions normalized. Those are only normalized once the late-bound
+    /// regions have been instantiated for the call.
+    ///
+    /// Generated code tends to call the same functions over and over, so the part of the work that
+    /// doesn't depend on `substs` is memoized per callee, see `callee_sig_skeleton`. Each call
+    /// then only substitutes its own, usually fresh, generic arguments into the skeleton and
+    /// normalizes what mentions them.
+    pub(in super::super) fn substituted_callee_sig(
+        &self,
+        span: Span,
+        def_id: DefId,
+        substs: SubstsRef<'tcx>,
+    ) -> ty::PolyFnSig<'tcx> {
+        let sig = self.callee_sig_skeleton(span, def_id).subst(self.tcx, substs);
+        self.normalize_associated_types_in(span, sig)
+    }
+
+    /// Returns the generic signature of `def_id` with the projections that mention neither its
+    /// generic parameters nor its late-bound regions normalized, e.g. `<Vec<u8> as
+    /// IntoIterator>::Item`. Those normalize to the same type for every call, so the skeleton is
+    /// computed once per callee and body.
+    ///
+    /// Projections on the callee's own parameters are left alone: they can only be normalized
+    /// once `substs` are known, as the callee's parameters would otherwise be mistaken for the
+    /// parameters of the body being checked.
+    fn callee_sig_skeleton(&self, span: Span, def_id: DefId) -> ty::PolyFnSig<'tcx> {
+        if let Some(&sig) = self.callee_sigs.borrow().get(&def_id) {
+            return sig;
+        }
+        let sig = self.callee_fn_sig(def_id).fold_with(&mut ty::fold::BottomUpFolder {
+            tcx: self.tcx,
+            ty_op: |ty| match ty.kind() {
+                ty::Projection(_) if !ty.needs_subst() && !ty.has_escaping_bound_vars() => {
+                    self.normalize_associated_types_in(span, ty)
+                }
+                _ => ty,
+            },
+            lt_op: |lt| lt,
+            ct_op: |ct| ct,
+        });
+        // An ambiguous projection is normalized to an inference variable, which must not leak
+        // into the other calls.
+        if !sig.needs_infer() {
+            self.callee_sigs.borrow_mut().insert(def_id, sig);
+        }
+        sig
+    }
+
+    /// Whether computing the signature of `callee` may depend on the body currently being checked,
+    /// i.e. whether the body owner is reachable from `callee` through calls to functions whose
+    /// return type has to be inferred.
+    fn callee_reaches_body_owner(&self, callee: LocalDefId) -> bool {
+        let hir = self.tcx.hir();
+        let owner = hir.body_owner_def_id(hir::BodyId { hir_id: self.body_id });
+        let mut stack = vec![callee];
+        let mut visited = FxHashSet::default();
+        let mut inferred_ret_methods = None;
+        while let Some(def_id) = stack.pop() {
+            if def_id == owner {
+                return true;
+            }
+            if !visited.insert(def_id) {
+                continue;
+            }
+            let hir_id = hir.local_def_id_to_hir_id(def_id);
+            let infers_ret_ty = hir.fn_sig_by_hir_id(hir_id).map_or(false, |sig| {
+                crate::collect::get_infer_ret_ty(&sig.decl.output).is_some()
+            });
+            if let (true, Some(body_id)) = (infers_ret_ty, hir.maybe_body_owned_by(hir_id)) {
+                let mut collector = LocalFnCollector {
+                    tcx: self.tcx,
+                    fns: &mut stack,
+                    inferred_ret_methods: &mut inferred_ret_methods,
+                };
+                collector.visit_body(hir.body(body_id));
+            }
+        }
+        false
+    }
+
+    /// Lowers the signature of `def_id` from its declaration with an error return type, if it
+    /// is a function with an inferred return type.
+    fn inferred_ret_fn_sig_for_recovery(&self, def_id: LocalDefId) -> Option<ty::PolyFnSig<'tcx>> {
+        let hir_id = self.tcx.hir().local_def_id_to_hir_id(def_id);
+        let sig = self.tcx.hir().fn_sig_by_hir_id(hir_id)?;
+        crate::collect::get_infer_ret_ty(&sig.decl.output)?;
+        let fn_sig = <dyn AstConv<'_>>::ty_of_fn(
+            self,
+            hir_id,
+            sig.header.unsafety,
+            sig.header.abi,
+            &sig.decl,
+            &hir::Generics::empty(),
+            None,
+            None,
+        );
+        Some(fn_sig.map_bound(|fn_sig| {
+            self.tcx.mk_fn_sig(
+                fn_sig.inputs().iter().copied(),
+                self.tcx.ty_error(),
+                fn_sig.c_variadic,
+                fn_sig.unsafety,
+                fn_sig.abi,
+            )
+        }))
+    }
+
     /// Replaces the opaque types from the given value with type variables,
     /// and records the `OpaqueTypeMap` for later use during writeback. See
     /// `InferCtxt::instantiate_opaque_types` for more details.
@@ -455,7 +645,7 @@ pub fn require_type_is_sized_deferred(
         code: traits::ObligationCauseCode<'tcx>,
     ) {
         if !ty.references_error() {
-            self.deferred_sized_obligations.borrow_mut().push((ty, span, code));
+            self.deferred_sized_obligations.borrow_mut().entry((ty, code)).or_insert(span);
         }
     }
 
@@ -632,6 +822,93 @@ pub(in super::super) fn resolve_generator_interiors(&self, def_id: DefId) {
         }
     }
 
+    /// Reports `let` bindings annotated with an `impl Trait` type (under
+    /// `#![feature(impl_trait_in_bindings)]`) whose hidden type was never
+    /// constrained by the initializer or a later assignment.
+    ///
+    /// This has to run before opaque type fallback: otherwise the revealed
+    /// type would fall back to the opaque type itself, and we would later
+    /// report a much less helpful "cannot resolve opaque type" cycle error.
+    pub(in super::super) fn report_unconstrained_binding_opaque_types(&self) {
+        if self.is_tainted_by_errors() {
+            return;
+        }
+        let mut locals: Vec<(hir::HirId, LocalTy<'tcx>)> = self
+            .locals
+            .borrow()
+            .iter()
+            .filter(|(_, local_ty)| local_ty.decl_ty != local_ty.revealed_ty)
+            .map(|(&hir_id, &local_ty)| (hir_id, local_ty))
+            .collect();
+        locals.sort_by_key(|(hir_id, _)| self.tcx.hir().span(*hir_id));
+
+        for (hir_id, local_ty) in locals {
+            let local = match self.tcx.hir().find(hir_id) {
+                Some(Node::Local(local)) if local.ty.is_some() => local,
+                _ => continue,
+            };
+            // Find the opaque types in the annotation whose inference variable
+            // is still unconstrained.
+            let unconstrained: Vec<(Ty<'tcx>, Ty<'tcx>)> = self
+                .opaque_types_vars
+                .borrow()
+                .iter()
+                .filter(|(ty_var, opaque_ty)| {
+                    self.resolve_vars_if_possible(**ty_var).is_ty_var()
+                        && local_ty.decl_ty.walk().any(|arg| arg == (**opaque_ty).into())
+                })
+                .map(|(&ty_var, &opaque_ty)| (ty_var, opaque_ty))
+                .collect();
+            if unconstrained.is_empty() {
+                continue;
+            }
+            self.report_unconstrained_binding_opaque_type(local, local_ty);
+            // Don't let fallback turn these into a cycle error as well.
+            for (ty_var, _) in unconstrained {
+                self.demand_eqtype(DUMMY_SP, ty_var, self.tcx.ty_error());
+            }
+        }
+    }
+
+    fn report_unconstrained_binding_opaque_type(
+        &self,
+        local: &hir::Local<'_>,
+        local_ty: LocalTy<'tcx>,
+    ) {
+        let ty_span = local.ty.map_or(local.span, |ty| ty.span);
+        let mut err = rustc_errors::struct_span_err!(
+            self.tcx.sess,
+            ty_span,
+            E0282,
+            "type annotations needed for the hidden type of `{}`",
+            local_ty.decl_ty,
+        );
+        err.span_label(ty_span, "the concrete type behind this `impl Trait` can't be inferred");
+        match local.init {
+            Some(init) => {
+                let init_ty = self.resolve_vars_if_possible(local_ty.revealed_ty);
+                err.span_label(
+                    init.span,
+                    format!(
+                        "this initializer has type `{}`, which doesn't determine the hidden type",
+                        self.ty_to_string(init_ty),
+                    ),
+                );
+                err.help("consider giving the initializer a concrete type, e.g. with a turbofish");
+            }
+            None => {
+                err.span_label(local.pat.span, "this binding is never assigned a value");
+                err.span_suggestion_verbose(
+                    ty_span.shrink_to_hi(),
+                    "set the binding to a value for a concrete type to be resolved",
+                    " = /* value */".to_string(),
+                    Applicability::HasPlaceholders,
+                );
+            }
+        }
+        err.emit();
+    }
+
     // Tries to apply a fallback to `ty` if it is an unsolved variable.
     //
     // - Unconstrained ints are replaced with `i32`.
@@ -711,6 +988,7 @@ pub(in super::super) fn fallback_if_possible(&self, ty: Ty<'tcx>, mode: Fallback
 
     pub(in super::super) fn select_all_obligations_or_error(&self) {
         debug!("select_all_obligations_or_error");
+        self.tcx.sess.abort_if_cancelled();
         if let Err(errors) = self.fulfillment_cx.borrow_mut().select_all_or_error(&self) {
             self.report_fulfillment_errors(&errors, self.inh.body_id, false);
         }
@@ -722,6 +1000,9 @@ pub(in super::super) fn select_obligations_where_possible(
         fallback_has_occurred: bool,
         mutate_fulfillment_errors: impl Fn(&mut Vec<traits::FulfillmentError<'tcx>>),
     ) {
+        // Obligation processing can take a long time in large bodies, so this is a good point
+        // to give up on a compilation the driver isn't interested in anymore.
+        self.tcx.sess.abort_if_cancelled();
         let result = self.fulfillment_cx.borrow_mut().select_where_possible(self);
         if let Err(mut errors) = result {
             mutate_fulfillment_errors(&mut errors);
@@ -1361,21 +1642,7 @@ pub fn instantiate_value_path(
                         span,
                         "the `Self` constructor can only be used with tuple or unit structs",
                     );
-                    if let Some(adt_def) = ty.ty_adt_def() {
-                        match adt_def.adt_kind() {
-                            AdtKind::Enum => {
-                                err.help("did you mean to use one of the enum's variants?");
-                            }
-                            AdtKind::Struct | AdtKind::Union => {
-                                err.span_suggestion(
-                                    span,
-                                    "use curly brackets",
-                                    String::from("Self { /* fields */ }"),
-                                    Applicability::HasPlaceholders,
-                                );
-                            }
-                        }
-                    }
+                    self.note_bad_self_path(&mut err, span, ty, true);
                     err.emit();
 
                     return (tcx.ty_error(), res);
@@ -1649,3 +1916,68 @@ pub(in super::super) fn expr_in_place(&self, mut expr_id: hir::HirId) -> bool {
         contained_in_place
     }
 }
+
+/// Collects the local functions a body may call.
+///
+/// Functions referred to by path are resolved, but which method a method call resolves to is only
+/// known once the body is type-checked, which is what `callee_reaches_body_owner` has to avoid.
+/// Method calls are taken to call every local method of that name with an inferred return type
+/// instead, as only those can lead back to the body being checked.
+struct LocalFnCollector<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    fns: &'a mut Vec<LocalDefId>,
+    /// The local methods with an inferred return type by name, computed on the first method call.
+    inferred_ret_methods: &'a mut Option<FxHashMap<Symbol, Vec<LocalDefId>>>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for LocalFnCollector<'a, 'tcx> {
+    type Map = rustc_middle::hir::map::Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    fn visit_path(&mut self, path: &'tcx hir::Path<'tcx>, _: hir::HirId) {
+        if let Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) = path.res {
+            if let Some(def_id) = def_id.as_local() {
+                self.fns.push(def_id);
+            }
+        }
+        intravisit::walk_path(self, path);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let ExprKind::MethodCall(segment, ..) = expr.kind {
+            let tcx = self.tcx;
+            let methods =
+                self.inferred_ret_methods.get_or_insert_with(|| inferred_ret_methods(tcx));
+            if let Some(methods) = methods.get(&segment.ident.name) {
+                self.fns.extend(methods.iter().copied());
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// The local methods with an inferred return type (`fn foo(&self) -> _`), by name.
+fn inferred_ret_methods(tcx: TyCtxt<'_>) -> FxHashMap<Symbol, Vec<LocalDefId>> {
+    let krate = tcx.hir().krate();
+    let trait_methods = krate.trait_items.values().filter_map(|item| match &item.kind {
+        hir::TraitItemKind::Fn(sig, hir::TraitFn::Provided(_)) => {
+            Some((item.ident, item.def_id, sig))
+        }
+        _ => None,
+    });
+    let impl_methods = krate.impl_items.values().filter_map(|item| match &item.kind {
+        hir::ImplItemKind::Fn(sig, _) => Some((item.ident, item.def_id, sig)),
+        _ => None,
+    });
+
+    let mut methods = FxHashMap::<_, Vec<_>>::default();
+    for (ident, def_id, sig) in trait_methods.chain(impl_methods) {
+        if crate::collect::get_infer_ret_ty(&sig.decl.output).is_some() {
+            methods.entry(ident.name).or_default().push(def_id);
+        }
+    }
+    methods
+}
@@ -11,7 +11,7 @@
 use rustc_ast as ast;
 use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId};
 use rustc_hir as hir;
-use rustc_hir::def::{CtorOf, DefKind, Res};
+use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_hir::{ExprKind, Node, QPath};
 use rustc_middle::ty::adjustment::AllowTwoPhase;
@@ -20,6 +20,7 @@
 use rustc_session::Session;
 use rustc_span::symbol::Ident;
 use rustc_span::{self, MultiSpan, Span};
+use rustc_trait_selection::traits::error_reporting::suggestions::InferCtxtExt as _;
 use rustc_trait_selection::traits::{self, ObligationCauseCode, StatementAsExpression};
 
 use crate::structured_errors::StructuredDiagnostic;
@@ -34,6 +35,145 @@ pub(in super::super) fn check_casts(&self) {
         }
     }
 
+    /// Checks that the elements of repeat expressions are `Copy` when they are copied into
+    /// more than one element of the array.
+    pub(in super::super) fn check_repeat_exprs(&self) {
+        let checks = self.deferred_repeat_expr_checks.borrow_mut().drain(..).collect::<Vec<_>>();
+        for (expr, element_ty, count) in checks {
+            let element_ty = self.resolve_vars_if_possible(element_ty);
+            let count = self.resolve_vars_if_possible(count);
+            if element_ty.references_error()
+                || element_ty.has_infer_types_or_consts()
+                || count.has_infer_types_or_consts()
+            {
+                continue;
+            }
+            if self.infcx.type_is_copy_modulo_regions(self.param_env, element_ty, expr.span) {
+                continue;
+            }
+            // If the count can't be evaluated, it has to be assumed to be larger than one.
+            if count.try_eval_usize(self.tcx, self.param_env).map_or(false, |count| count <= 1) {
+                continue;
+            }
+            self.report_non_copy_repeat_element(expr, element_ty);
+        }
+    }
+
+    fn report_non_copy_repeat_element(&self, expr: &'tcx hir::Expr<'tcx>, element_ty: Ty<'tcx>) {
+        let tcx = self.tcx;
+        let (element, count) = match expr.kind {
+            ExprKind::Repeat(element, ref count) => (element, count),
+            _ => span_bug!(expr.span, "expected a repeat expression"),
+        };
+        let mut err = struct_span_err!(
+            tcx.sess,
+            expr.span,
+            E0277,
+            "the trait bound `{}: Copy` is not satisfied",
+            element_ty
+        );
+        let label = format!("the trait `Copy` is not implemented for `{}`", element_ty);
+        err.span_label(expr.span, label);
+        err.note("the `Copy` trait is required because the repeated element will be copied");
+
+        let implements_clone = || {
+            tcx.lang_items().clone_trait().map_or(false, |clone| {
+                let ty = tcx.erase_regions(element_ty);
+                tcx.type_implements_trait((clone, ty, ty::List::empty(), self.param_env))
+            })
+        };
+
+        let source_map = tcx.sess.source_map();
+        let element_snippet = source_map.span_to_snippet(element.span).ok();
+        let count_snippet = source_map.span_to_snippet(tcx.hir().body(count.body).value.span).ok();
+
+        if let (ty::Param(_) | ty::Projection(_), Some(copy)) =
+            (element_ty.kind(), tcx.lang_items().copy_trait())
+        {
+            let trait_ref = ty::TraitRef::new(copy, tcx.mk_substs_trait(element_ty, &[]));
+            self.suggest_restricting_param_bound(
+                &mut err,
+                ty::Binder::dummy(trait_ref),
+                self.body_id,
+            );
+        }
+
+        // A type whose fields are all `Copy` can become `Copy` itself.
+        if let ty::Adt(def, substs) = element_ty.kind() {
+            let can_derive_copy = def.did.is_local()
+                && !def.has_dtor(tcx)
+                && def.all_fields().all(|field| {
+                    let field_ty = field.ty(tcx, substs);
+                    self.infcx.type_is_copy_modulo_regions(self.param_env, field_ty, expr.span)
+                });
+            if can_derive_copy {
+                let derive = if implements_clone() { "Copy" } else { "Clone, Copy" };
+                err.span_suggestion_verbose(
+                    tcx.def_span(def.did).shrink_to_lo(),
+                    &format!(
+                        "consider annotating `{}` with `#[derive({})]`",
+                        tcx.def_path_str(def.did),
+                        derive
+                    ),
+                    format!("#[derive({})]\n", derive),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+        }
+
+        // Values that can be computed at compile time can be repeated through a constant.
+        let is_const_evaluable = match element.kind {
+            ExprKind::Call(callee, _) => match callee.kind {
+                ExprKind::Path(ref qpath) => {
+                    match self.typeck_results.borrow().qpath_res(qpath, callee.hir_id) {
+                        Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) => tcx.is_const_fn(def_id),
+                        Res::Def(DefKind::Ctor(..), _) => true,
+                        _ => false,
+                    }
+                }
+                _ => false,
+            },
+            ExprKind::Path(ref qpath) => matches!(
+                self.typeck_results.borrow().qpath_res(qpath, element.hir_id),
+                Res::Def(DefKind::Ctor(_, CtorKind::Const), _)
+            ),
+            _ => false,
+        };
+        if let (Some(element_snippet), Some(count_snippet)) = (element_snippet, count_snippet) {
+            if is_const_evaluable {
+                err.help(&format!(
+                    "consider creating a `const` item for the element and repeating it instead, \
+                     like `const VALUE: {} = {};` and `[VALUE; {}]`",
+                    tcx.erase_regions(element_ty),
+                    element_snippet,
+                    count_snippet,
+                ));
+                if tcx.features().inline_const {
+                    err.span_suggestion_verbose(
+                        element.span,
+                        "or create an inline `const` block",
+                        format!("const {{ {} }}", element_snippet),
+                        Applicability::MaybeIncorrect,
+                    );
+                } else if tcx.sess.is_nightly_build() {
+                    err.help(
+                        "create an inline `const` block, see RFC \
+                         #2920 <https://github.com/rust-lang/rfcs/pull/2920> \
+                         for more information",
+                    );
+                }
+            } else if !self.const_cx.is_const() && implements_clone() {
+                err.span_suggestion_verbose(
+                    expr.span,
+                    "consider using a `Vec` instead, which clones the element",
+                    format!("vec![{}; {}]", element_snippet, count_snippet),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+        }
+        err.emit();
+    }
+
     pub(in super::super) fn check_method_argument_types(
         &self,
         sp: Span,
@@ -493,15 +633,18 @@ pub fn check_struct_path(
 
             Some((variant, ty))
         } else {
-            struct_span_err!(
+            let mut err = struct_span_err!(
                 self.tcx.sess,
                 path_span,
                 E0071,
                 "expected struct, variant or union type, found {}",
                 ty.sort_string(self.tcx)
-            )
-            .span_label(path_span, "not a struct")
-            .emit();
+            );
+            err.span_label(path_span, "not a struct");
+            if let Res::SelfTy(..) = def {
+                self.note_bad_self_path(&mut err, path_span, ty, false);
+            }
+            err.emit();
             None
         }
     }
@@ -620,6 +763,9 @@ pub(in super::super) fn check_block_with_expected(
         blk: &'tcx hir::Block<'tcx>,
         expected: Expectation<'tcx>,
     ) -> Ty<'tcx> {
+        if let Some(context) = self.ps.get().covering_unsafe_context(blk) {
+            self.typeck_results.borrow_mut().nested_unsafe_blocks_mut().insert(blk.hir_id, context);
+        }
         let prev = self.ps.replace(self.ps.get().recurse(blk));
 
         // In some cases, blocks have just one exit, but other blocks
@@ -714,6 +860,11 @@ pub(in super::super) fn check_block_with_expected(
                                     "implicitly returns `()` as its body has no tail or `return` \
                                      expression",
                                 );
+                                if let Some(expected_ty) = expected.only_has_type(self) {
+                                    if self.could_remove_semicolon(blk, expected_ty).is_none() {
+                                        self.suggest_missing_return_value(err, blk, expected_ty);
+                                    }
+                                }
                             }
                         },
                         false,
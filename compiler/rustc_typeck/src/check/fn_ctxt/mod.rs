@@ -8,8 +8,9 @@
 
 use crate::astconv::AstConv;
 use crate::check::coercion::DynamicCoerceMany;
-use crate::check::{Diverges, EnclosingBreakables, Inherited, UnsafetyState};
+use crate::check::{ConstCx, Diverges, EnclosingBreakables, Inherited, UnsafetyState};
 
+use rustc_data_structures::fx::FxHashMap;
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_infer::infer;
@@ -71,8 +72,16 @@ pub struct FnCtxt<'a, 'tcx> {
 
     pub(super) resume_yield_tys: Option<(Ty<'tcx>, Ty<'tcx>)>,
 
+    /// Span of the value of the first `yield` we find, which determines the yield type of the
+    /// generator. Used in error messages.
+    pub(super) first_yield_span: Cell<Option<Span>>,
+
     pub(super) ps: Cell<UnsafetyState>,
 
+    /// The const context of the body, which rules out operations that can't be evaluated at
+    /// compile time.
+    pub(super) const_cx: ConstCx,
+
     /// Whether the last checked node generates a divergence (e.g.,
     /// `return` will set this to `Always`). In general, when entering
     /// an expression or other node in the tree, the initial value
@@ -111,6 +120,9 @@ pub struct FnCtxt<'a, 'tcx> {
 
     pub(super) enclosing_breakables: RefCell<EnclosingBreakables<'tcx>>,
 
+    /// Signature skeletons of the functions called in this body, see `callee_sig_skeleton`.
+    pub(super) callee_sigs: RefCell<FxHashMap<DefId, ty::PolyFnSig<'tcx>>>,
+
     pub(super) inh: &'a Inherited<'a, 'tcx>,
 }
 
@@ -130,13 +142,16 @@ pub fn new(
             in_tail_expr: false,
             ret_coercion_span: Cell::new(None),
             resume_yield_tys: None,
+            first_yield_span: Cell::new(None),
             ps: Cell::new(UnsafetyState::function(hir::Unsafety::Normal, hir::CRATE_HIR_ID)),
+            const_cx: ConstCx::default(),
             diverges: Cell::new(Diverges::Maybe),
             has_errors: Cell::new(false),
             enclosing_breakables: RefCell::new(EnclosingBreakables {
                 stack: Vec::new(),
                 by_id: Default::default(),
             }),
+            callee_sigs: RefCell::new(Default::default()),
             inh,
         }
     }
@@ -1,18 +1,24 @@
 use super::FnCtxt;
 use crate::astconv::AstConv;
+use crate::check::method::probe;
+use crate::check::placeholder::placeholder_expr;
 
 use rustc_ast::util::parser::ExprPrecedence;
 use rustc_span::{self, MultiSpan, Span};
 
 use rustc_errors::{Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
-use rustc_hir::def::{CtorOf, DefKind};
+use rustc_hir::def::{CtorOf, DefKind, Res};
+use rustc_hir::intravisit::Visitor;
 use rustc_hir::lang_items::LangItem;
 use rustc_hir::{Expr, ExprKind, ItemKind, Node, Stmt, StmtKind};
 use rustc_infer::infer;
 use rustc_middle::lint::in_external_macro;
-use rustc_middle::ty::{self, Binder, Ty};
-use rustc_span::symbol::kw;
+use rustc_middle::ty::{self, Binder, ToPredicate, Ty, WithConstness};
+use rustc_span::symbol::{kw, sym, Ident};
+use rustc_trait_selection::infer::InferCtxtExt as _;
+use rustc_trait_selection::traits;
+use rustc_trait_selection::traits::error_reporting::suggestions::ReturnsVisitor;
 
 use std::iter;
 
@@ -275,7 +281,7 @@ pub(in super::super) fn suggest_boxing_when_appropriate(
         expected: Ty<'tcx>,
         found: Ty<'tcx>,
     ) {
-        if self.tcx.hir().is_inside_const_context(expr.hir_id) {
+        if self.const_cx.is_const() {
             // Do not suggest `Box::new` in const context.
             return;
         }
@@ -344,7 +350,7 @@ pub(in super::super) fn suggest_calling_boxed_future_when_appropriate(
     ) -> bool {
         // Handle #68197.
 
-        if self.tcx.hir().is_inside_const_context(expr.hir_id) {
+        if self.const_cx.is_const() {
             // Do not suggest `Box::new` in const context.
             return false;
         }
@@ -539,6 +545,284 @@ pub(in super::super) fn suggest_missing_break_or_return_expr(
         }
     }
 
+    /// A function with a non-`()` return type can `return` a value on some paths and fall off
+    /// the end of its body on others:
+    ///
+    /// ```
+    /// fn foo(x: bool) -> Option<u32> {
+    ///     if x {
+    ///         return Some(42);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// This routine checks whether the body contains `return` expressions and, if so, suggests a
+    /// placeholder tail expression synthesized from the return type (`None` in the case above).
+    pub(in super::super) fn suggest_missing_return_value(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        blk: &'tcx hir::Block<'tcx>,
+        expected: Ty<'tcx>,
+    ) {
+        let mut visitor = ReturnsVisitor::default();
+        visitor.visit_block(blk);
+        if visitor.returns.is_empty() {
+            // The whole body lacks a value, so a placeholder is unlikely to be what the user
+            // wants; the "implicitly returns `()`" label is enough in that case.
+            return;
+        }
+        let last_stmt = match blk.stmts.last() {
+            Some(stmt) => stmt,
+            None => return,
+        };
+        let expected = self.resolve_vars_if_possible(expected);
+        let module = self.tcx.parent_module(self.body_id).to_def_id();
+        let val = match placeholder_expr(self.tcx, self.param_env, module, expected) {
+            Some(val) => val,
+            None => return,
+        };
+        let sm = self.tcx.sess.source_map();
+        let sugg = if sm.is_multiline(blk.span) {
+            let indent = sm.span_to_margin(last_stmt.span).unwrap_or(4);
+            format!("\n{}{}", " ".repeat(indent), val)
+        } else {
+            format!(" {}", val)
+        };
+        err.span_suggestion_verbose(
+            last_stmt.span.shrink_to_hi(),
+            &format!(
+                "add a tail expression of type `{}` for the paths that reach the end of the body",
+                expected,
+            ),
+            sugg,
+            Applicability::HasPlaceholders,
+        );
+    }
+
+    /// When a binding introduced by a `for` loop pattern or by the closure passed to an iterator
+    /// adapter differs from the expected type only by a reference, point at the call that
+    /// created the iterator and suggest switching between `iter()`, `iter_mut()` and
+    /// `into_iter()`, or destructuring the reference in the pattern:
+    ///
+    /// ```ignore (illustrative)
+    /// for x in v.iter() {
+    ///     let y: u32 = x; // suggest `&x` or `v.into_iter()`
+    /// }
+    /// ```
+    pub(in super::super) fn suggest_iterator_element_ref_mismatch(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &hir::Expr<'_>,
+        expected: Ty<'tcx>,
+        found: Ty<'tcx>,
+    ) {
+        let binding_id = match expr.peel_blocks().kind {
+            ExprKind::Path(hir::QPath::Resolved(None, hir::Path { res: Res::Local(id), .. })) => {
+                *id
+            }
+            _ => return,
+        };
+        let pat = match self.tcx.hir().find(binding_id) {
+            Some(Node::Binding(pat)) => pat,
+            _ => return,
+        };
+        let (segment, receiver) = match self.iterator_element_source(pat) {
+            Some(source) => source,
+            None => return,
+        };
+        let receiver_ty = match self.typeck_results.borrow().node_type_opt(receiver.hir_id) {
+            Some(ty) => self.resolve_vars_if_possible(ty),
+            None => return,
+        };
+        // In editions before 2021, `array.into_iter()` autorefs and iterates by reference.
+        let iterates_by_value = match receiver_ty.kind() {
+            ty::Ref(..) => false,
+            ty::Array(..) => segment.ident.span.rust_2021(),
+            _ => true,
+        };
+        let expected = self.resolve_vars_if_possible(expected);
+        let found = self.resolve_vars_if_possible(found);
+        let method = segment.ident.name;
+
+        match (found.kind(), expected.kind()) {
+            // The iterator yields references, but the elements are used by value.
+            (&ty::Ref(_, found_inner, mutbl), _)
+                if self.can_eq(self.param_env, found_inner, expected).is_ok() =>
+            {
+                err.span_label(
+                    segment.ident.span,
+                    format!("`{}` yields elements of type `{}`", method, found),
+                );
+                if let hir::PatKind::Binding(hir::BindingAnnotation::Unannotated, _, ident, None) =
+                    pat.kind
+                {
+                    if self.infcx.type_is_copy_modulo_regions(self.param_env, expected, pat.span) {
+                        err.span_suggestion_verbose(
+                            pat.span.shrink_to_lo(),
+                            &format!(
+                                "consider destructuring the reference in the pattern to bind `{}` \
+                                 by value",
+                                ident,
+                            ),
+                            format!("&{}", mutbl.prefix_str()),
+                            Applicability::MaybeIncorrect,
+                        );
+                    }
+                }
+                if (method == sym::iter || method == sym::iter_mut)
+                    && iterates_by_value
+                    && self.into_iter_item_can_eq(segment.ident.span, receiver_ty, expected)
+                {
+                    err.span_suggestion_verbose(
+                        segment.ident.span,
+                        "consider using `into_iter` to iterate over the elements by value",
+                        "into_iter".to_string(),
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+            }
+            // The iterator yields values, but the elements are used by reference.
+            (_, &ty::Ref(_, expected_inner, mutbl))
+                if self.can_eq(self.param_env, found, expected_inner).is_ok() =>
+            {
+                err.span_label(
+                    segment.ident.span,
+                    format!("`{}` yields elements of type `{}`", method, found),
+                );
+                let by_ref = if mutbl == hir::Mutability::Mut { sym::iter_mut } else { sym::iter };
+                if method == sym::into_iter
+                    && iterates_by_value
+                    && self
+                        .probe_for_name(
+                            segment.ident.span,
+                            probe::Mode::MethodCall,
+                            Ident::with_dummy_span(by_ref),
+                            probe::IsSuggestion(true),
+                            receiver_ty,
+                            receiver.hir_id,
+                            probe::ProbeScope::TraitsInScope,
+                        )
+                        .is_ok()
+                {
+                    err.span_suggestion_verbose(
+                        segment.ident.span,
+                        &format!(
+                            "consider using `{}` to iterate over the elements by {}reference",
+                            by_ref,
+                            if mutbl == hir::Mutability::Mut { "mutable " } else { "" },
+                        ),
+                        by_ref.to_string(),
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Finds the `iter()`, `iter_mut()` or `into_iter()` call that produces the values bound by
+    /// `pat`, when `pat` is the pattern of a `for` loop or the parameter of a closure passed to
+    /// an iterator adapter such as `map`. Returns the method segment and its receiver.
+    fn iterator_element_source(
+        &self,
+        pat: &hir::Pat<'_>,
+    ) -> Option<(&'tcx hir::PathSegment<'tcx>, &'tcx hir::Expr<'tcx>)> {
+        let hir = self.tcx.hir();
+        let head = match hir.find(hir.get_parent_node(pat.hir_id))? {
+            // `for <pat> in <head> {}` is lowered to a `let <pat> = __next;` statement nested in
+            // `match IntoIterator::into_iter(<head>) { .. }`.
+            Node::Local(local)
+                if local.source == hir::LocalSource::ForLoopDesugar
+                    && local.pat.hir_id == pat.hir_id =>
+            {
+                hir.parent_iter(local.hir_id).find_map(|(_, node)| match node {
+                    Node::Expr(Expr {
+                        kind:
+                            ExprKind::Match(
+                                Expr { kind: ExprKind::Call(_, [head]), .. },
+                                _,
+                                hir::MatchSource::ForLoopDesugar,
+                            ),
+                        ..
+                    }) => Some(head),
+                    _ => None,
+                })?
+            }
+            // `<head>.map(|<pat>| ..)`
+            Node::Param(param) if param.pat.hir_id == pat.hir_id => {
+                let closure_id = hir.get_parent_node(param.hir_id);
+                match hir.find(hir.get_parent_node(closure_id))? {
+                    Node::Expr(Expr {
+                        kind: ExprKind::MethodCall(segment, _, [head, args @ ..], _),
+                        ..
+                    }) if args.iter().any(|arg| arg.hir_id == closure_id)
+                        && [
+                            sym::all,
+                            sym::any,
+                            sym::filter_map,
+                            sym::find_map,
+                            sym::flat_map,
+                            sym::for_each,
+                            sym::map,
+                            sym::position,
+                        ]
+                        .contains(&segment.ident.name) =>
+                    {
+                        head
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        match head.kind {
+            ExprKind::MethodCall(segment, _, [receiver], _)
+                if [sym::iter, sym::iter_mut, sym::into_iter].contains(&segment.ident.name) =>
+            {
+                Some((segment, receiver))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether iterating over `ty` by value yields elements that can be equated with `elem_ty`.
+    fn into_iter_item_can_eq(&self, span: Span, ty: Ty<'tcx>, elem_ty: Ty<'tcx>) -> bool {
+        let into_iter_trait = match self.tcx.get_diagnostic_item(sym::IntoIterator) {
+            Some(def_id) => def_id,
+            None => return false,
+        };
+        let item = match self.tcx.associated_items(into_iter_trait).find_by_name_and_kind(
+            self.tcx,
+            Ident::with_dummy_span(sym::Item),
+            ty::AssocKind::Type,
+            into_iter_trait,
+        ) {
+            Some(item) => item,
+            None => return false,
+        };
+        let trait_ref = ty::TraitRef::new(into_iter_trait, self.tcx.mk_substs_trait(ty, &[]));
+        self.probe(|_| {
+            let obligation = traits::Obligation::new(
+                self.misc(span),
+                self.param_env,
+                trait_ref.without_const().to_predicate(self.tcx),
+            );
+            if !self.trait_solver.predicate_must_hold_modulo_regions(self, &obligation) {
+                return false;
+            }
+            let mut obligations = vec![];
+            let item_ty = self.trait_solver.normalize_projection_type(
+                self,
+                self.param_env,
+                ty::ProjectionTy { substs: trait_ref.substs, item_def_id: item.def_id },
+                self.misc(span),
+                &mut obligations,
+            );
+            obligations.iter().all(|o| self.trait_solver.predicate_may_hold(self, o))
+                && self.can_eq(self.param_env, item_ty, elem_ty).is_ok()
+        })
+    }
+
     pub(in super::super) fn suggest_missing_parentheses(
         &self,
         err: &mut DiagnosticBuilder<'_>,
@@ -551,6 +835,72 @@ pub(in super::super) fn suggest_missing_parentheses(
         }
     }
 
+    /// When an integer is used as a condition, as it would be in C, suggest comparing it with
+    /// zero instead.
+    pub(in super::super) fn suggest_compare_with_zero(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &hir::Expr<'_>,
+        expected: Ty<'tcx>,
+        found: Ty<'tcx>,
+    ) {
+        if !expected.is_bool()
+            || !self.resolve_vars_if_possible(found).is_integral()
+            || expr.span.from_expansion()
+            || !self.is_condition(expr)
+        {
+            return;
+        }
+        if let Ok(snippet) = self.tcx.sess.source_map().span_to_snippet(expr.span) {
+            err.span_suggestion(
+                expr.span,
+                "compare with zero instead",
+                format!("{} != 0", snippet),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
+    /// Whether `expr` is used as a condition: the condition of an `if` or `while`, a match
+    /// guard, an operand of `&&` or `||`, or the operand of a macro-generated negation like the
+    /// one `assert!(cond)` expands to.
+    fn is_condition(&self, expr: &hir::Expr<'_>) -> bool {
+        let hir = self.tcx.hir();
+        let mut id = expr.hir_id;
+        let mut parent = hir.find(hir.get_parent_node(id));
+        while let Some(Node::Expr(Expr { kind: ExprKind::DropTemps(_), hir_id, .. })) = parent {
+            id = *hir_id;
+            parent = hir.find(hir.get_parent_node(id));
+        }
+        match parent {
+            Some(Node::Expr(parent)) => match parent.kind {
+                ExprKind::If(cond, ..) => cond.hir_id == id,
+                ExprKind::Match(scrutinee, _, hir::MatchSource::WhileDesugar) => {
+                    scrutinee.hir_id == id
+                }
+                ExprKind::Binary(op, ..) => {
+                    matches!(op.node, hir::BinOpKind::And | hir::BinOpKind::Or)
+                }
+                ExprKind::Unary(hir::UnOp::Not, _) => self.is_desugared_negation(parent, expr),
+                _ => false,
+            },
+            Some(Node::Arm(arm)) => {
+                matches!(arm.guard, Some(hir::Guard::If(guard)) if guard.hir_id == id)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the negation `expr` of `operand` was generated by a macro around a condition the
+    /// user wrote, like `assert!(cond)` expanding to `if !cond { .. }`.
+    pub(in super::super) fn is_desugared_negation(
+        &self,
+        expr: &hir::Expr<'_>,
+        operand: &hir::Expr<'_>,
+    ) -> bool {
+        expr.span.from_expansion() && expr.span.ctxt() != operand.span.ctxt()
+    }
+
     fn is_loop(&self, id: hir::HirId) -> bool {
         let node = self.tcx.hir().get(id);
         matches!(node, Node::Expr(Expr { kind: ExprKind::Loop(..), .. }))
@@ -1,4 +1,4 @@
-use crate::check::{FnCtxt, LocalTy, UserType};
+use crate::check::{FnCtxt, LocalDecl, LocalTy, UserType};
 use rustc_hir as hir;
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::PatKind;
@@ -7,6 +7,22 @@
 use rustc_span::{sym, Span};
 use rustc_trait_selection::traits;
 
+impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
+    /// Remembers that the binding introduced by `pat` has the type written at
+    /// `ty_span`. Patterns that aren't a single by-value binding are ignored.
+    pub(super) fn record_local_decl(&self, pat: &hir::Pat<'_>, ty: LocalTy<'tcx>, ty_span: Span) {
+        if let PatKind::Binding(
+            hir::BindingAnnotation::Unannotated | hir::BindingAnnotation::Mutable,
+            hir_id,
+            _,
+            None,
+        ) = pat.kind
+        {
+            self.local_decls.borrow_mut().insert(hir_id, LocalDecl { ty, ty_span });
+        }
+    }
+}
+
 pub(super) struct GatherLocalsVisitor<'a, 'tcx> {
     fcx: &'a FnCtxt<'a, 'tcx>,
     parent_id: hir::HirId,
@@ -76,7 +92,9 @@ fn visit_local(&mut self, local: &'tcx hir::Local<'tcx>) {
                     .user_provided_types_mut()
                     .insert(ty.hir_id, c_ty);
 
-                Some(LocalTy { decl_ty: o_ty, revealed_ty })
+                let local_ty = LocalTy { decl_ty: o_ty, revealed_ty };
+                self.fcx.record_local_decl(&local.pat, local_ty, ty.span);
+                Some(local_ty)
             }
             None => None,
         };
@@ -1,19 +1,19 @@
 use super::callee::DeferredCallResolution;
 use super::MaybeInProgressTables;
 
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_data_structures::vec_map::VecMap;
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefIdMap, LocalDefId};
 use rustc_hir::HirIdMap;
 use rustc_infer::infer;
 use rustc_infer::infer::{InferCtxt, InferOk, TyCtxtInferExt};
-use rustc_middle::ty::fold::TypeFoldable;
+use rustc_middle::ty::fold::{TypeFoldable, TypeFolder};
 use rustc_middle::ty::{self, OpaqueTypeKey, Ty, TyCtxt};
 use rustc_span::{self, Span};
 use rustc_trait_selection::infer::InferCtxtExt as _;
 use rustc_trait_selection::opaque_types::OpaqueTypeDecl;
-use rustc_trait_selection::traits::{self, TraitEngine, TraitEngineExt};
+use rustc_trait_selection::traits::{self, TraitEngine};
 
 use std::cell::RefCell;
 use std::ops::Deref;
@@ -34,12 +34,25 @@ pub struct Inherited<'a, 'tcx> {
 
     pub(super) locals: RefCell<HirIdMap<super::LocalTy<'tcx>>>,
 
+    /// Binding `HirId`s whose type comes from an explicit annotation, used to
+    /// point at that annotation when a value of the wrong type is assigned.
+    pub(super) local_decls: RefCell<HirIdMap<super::LocalDecl<'tcx>>>,
+
+    /// The trait solver selected for the session with `-Z chalk`. Type checking goes through it
+    /// for the fulfillment engines it creates, and for the selection, normalization and
+    /// evaluation it does outside of them.
+    pub(super) trait_solver: traits::TraitSolver,
+
+    /// The fulfillment engine of `trait_solver`.
     pub(super) fulfillment_cx: RefCell<Box<dyn TraitEngine<'tcx>>>,
 
     // Some additional `Sized` obligations badly affect type inference.
     // These obligations are added in a later stage of typeck.
+    // Generated code tends to require the same type to be sized over and
+    // over, so these are deduplicated on the type and cause code, keeping
+    // the span of the first occurrence.
     pub(super) deferred_sized_obligations:
-        RefCell<Vec<(Ty<'tcx>, Span, traits::ObligationCauseCode<'tcx>)>>,
+        RefCell<FxIndexMap<(Ty<'tcx>, traits::ObligationCauseCode<'tcx>), Span>>,
 
     // When we process a call like `c()` where `c` is a closure type,
     // we may not have decided yet whether `c` is a `Fn`, `FnMut`, or
@@ -52,6 +65,12 @@ pub struct Inherited<'a, 'tcx> {
 
     pub(super) deferred_cast_checks: RefCell<Vec<super::cast::CastCheck<'tcx>>>,
 
+    // Repeat expressions, along with the type of their element and their count, whose
+    // element has to be `Copy` if it is repeated more than once. Both the type and the count
+    // may only be known after fallback.
+    pub(super) deferred_repeat_expr_checks:
+        RefCell<Vec<(&'tcx hir::Expr<'tcx>, Ty<'tcx>, &'tcx ty::Const<'tcx>)>>,
+
     pub(super) deferred_generator_interiors:
         RefCell<Vec<(hir::BodyId, Ty<'tcx>, hir::GeneratorKind)>>,
 
@@ -69,6 +88,30 @@ pub struct Inherited<'a, 'tcx> {
     pub(super) opaque_types_vars: RefCell<FxHashMap<Ty<'tcx>, Ty<'tcx>>>,
 
     pub(super) body_id: Option<hir::BodyId>,
+
+    /// The trait imports used by method resolution in the body so far, returned by writeback
+    /// along with the typeck results, see `typeck_and_used_trait_imports`.
+    pub(super) used_trait_imports: RefCell<FxHashSet<LocalDefId>>,
+
+    /// Normalized projection types, see `normalize_associated_types_in`.
+    pub(super) normalization_cache: RefCell<NormalizationCache<'tcx>>,
+
+    /// Whether these results are only used for error recovery, see `diagnostic_only_typeck`.
+    pub(super) diagnostic_only: bool,
+}
+
+/// Projection types that have been normalized in this body, so that normalizing the same
+/// projection again (which is common with long chains of iterator adapters) doesn't redo trait
+/// selection.
+///
+/// Only projections that don't involve inference variables, and normalize to a type that
+/// doesn't either, are cached. These entries can't be invalidated by inference variables
+/// being resolved or by snapshots being rolled back, so the cache never needs to be cleared.
+#[derive(Default)]
+pub(super) struct NormalizationCache<'tcx> {
+    map: FxHashMap<(ty::ParamEnv<'tcx>, ty::ProjectionTy<'tcx>), Ty<'tcx>>,
+    hits: usize,
+    misses: usize,
 }
 
 impl<'a, 'tcx> Deref for Inherited<'a, 'tcx> {
@@ -84,6 +127,7 @@ fn deref(&self) -> &Self::Target {
 pub struct InheritedBuilder<'tcx> {
     infcx: infer::InferCtxtBuilder<'tcx>,
     def_id: LocalDefId,
+    diagnostic_only: bool,
 }
 
 impl Inherited<'_, 'tcx> {
@@ -93,17 +137,25 @@ pub fn build(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> InheritedBuilder<'tcx> {
         InheritedBuilder {
             infcx: tcx.infer_ctxt().with_fresh_in_progress_typeck_results(hir_owner),
             def_id,
+            diagnostic_only: false,
         }
     }
 }
 
 impl<'tcx> InheritedBuilder<'tcx> {
+    /// Marks the results as only being used for error recovery, see `diagnostic_only_typeck`.
+    pub fn diagnostic_only(mut self, diagnostic_only: bool) -> Self {
+        self.diagnostic_only = diagnostic_only;
+        self
+    }
+
     pub fn enter<F, R>(&mut self, f: F) -> R
     where
         F: for<'a> FnOnce(Inherited<'a, 'tcx>) -> R,
     {
         let def_id = self.def_id;
-        self.infcx.enter(|infcx| f(Inherited::new(infcx, def_id)))
+        let diagnostic_only = self.diagnostic_only;
+        self.infcx.enter(|infcx| f(Inherited { diagnostic_only, ..Inherited::new(infcx, def_id) }))
     }
 }
 
@@ -112,21 +164,28 @@ pub(super) fn new(infcx: InferCtxt<'a, 'tcx>, def_id: LocalDefId) -> Self {
         let tcx = infcx.tcx;
         let item_id = tcx.hir().local_def_id_to_hir_id(def_id);
         let body_id = tcx.hir().maybe_body_owned_by(item_id);
+        let trait_solver = traits::TraitSolver::for_session(tcx.sess);
 
         Inherited {
             typeck_results: MaybeInProgressTables {
                 maybe_typeck_results: infcx.in_progress_typeck_results,
             },
             infcx,
-            fulfillment_cx: RefCell::new(<dyn TraitEngine<'_>>::new(tcx)),
+            trait_solver,
+            fulfillment_cx: RefCell::new(trait_solver.new_fulfillment_cx()),
             locals: RefCell::new(Default::default()),
-            deferred_sized_obligations: RefCell::new(Vec::new()),
+            local_decls: RefCell::new(Default::default()),
+            deferred_sized_obligations: RefCell::new(Default::default()),
             deferred_call_resolutions: RefCell::new(Default::default()),
             deferred_cast_checks: RefCell::new(Vec::new()),
+            deferred_repeat_expr_checks: RefCell::new(Vec::new()),
             deferred_generator_interiors: RefCell::new(Vec::new()),
             opaque_types: RefCell::new(Default::default()),
             opaque_types_vars: RefCell::new(Default::default()),
             body_id,
+            used_trait_imports: RefCell::new(Default::default()),
+            normalization_cache: RefCell::new(Default::default()),
+            diagnostic_only: false,
         }
     }
 
@@ -152,6 +211,8 @@ pub(super) fn register_infer_ok_obligations<T>(&self, infer_ok: InferOk<'tcx, T>
         infer_ok.value
     }
 
+    /// Normalizes the projections in `value`, going through the `normalization_cache` for those
+    /// that don't involve inference variables.
     pub(super) fn normalize_associated_types_in<T>(
         &self,
         span: Span,
@@ -162,7 +223,81 @@ pub(super) fn normalize_associated_types_in<T>(
     where
         T: TypeFoldable<'tcx>,
     {
+        if !value.has_projections() {
+            return value;
+        }
+        let value = value.fold_with(&mut CachedNormalizer { inh: self, span, body_id, param_env });
         let ok = self.partially_normalize_associated_types_in(span, body_id, param_env, value);
         self.register_infer_ok_obligations(ok)
     }
+
+    fn normalize_projection_cached(
+        &self,
+        span: Span,
+        body_id: hir::HirId,
+        param_env: ty::ParamEnv<'tcx>,
+        projection_ty: ty::ProjectionTy<'tcx>,
+    ) -> Ty<'tcx> {
+        let key = (param_env, projection_ty);
+        {
+            let mut cache = self.normalization_cache.borrow_mut();
+            if let Some(&ty) = cache.map.get(&key) {
+                cache.hits += 1;
+                return ty;
+            }
+            cache.misses += 1;
+        }
+
+        let ty = self.tcx.mk_projection(projection_ty.item_def_id, projection_ty.substs);
+        let ok = self.partially_normalize_associated_types_in(span, body_id, param_env, ty);
+        let normalized = self.register_infer_ok_obligations(ok);
+        if !normalized.needs_infer() {
+            self.normalization_cache.borrow_mut().map.insert(key, normalized);
+        }
+        normalized
+    }
+
+    /// Records how well the `normalization_cache` did under `-Z self-profile`.
+    pub(super) fn record_normalization_cache_stats(&self) {
+        let cache = self.normalization_cache.borrow();
+        if cache.hits + cache.misses > 0 {
+            let _timer = self.tcx.sess.prof.generic_activity_with_args(
+                "typeck_normalization_cache",
+                &[format!("hits: {}", cache.hits), format!("misses: {}", cache.misses)],
+            );
+        }
+    }
+}
+
+/// Replaces the projections in a type that don't involve inference variables with their
+/// normalized form, going through the `normalization_cache`.
+struct CachedNormalizer<'a, 'b, 'tcx> {
+    inh: &'b Inherited<'a, 'tcx>,
+    span: Span,
+    body_id: hir::HirId,
+    param_env: ty::ParamEnv<'tcx>,
+}
+
+impl<'a, 'b, 'tcx> TypeFolder<'tcx> for CachedNormalizer<'a, 'b, 'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.inh.tcx
+    }
+
+    fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
+        if !ty.has_projections() {
+            return ty;
+        }
+        let ty = ty.super_fold_with(self);
+        match *ty.kind() {
+            ty::Projection(projection_ty) if !ty.needs_infer() && !ty.has_escaping_bound_vars() => {
+                self.inh.normalize_projection_cached(
+                    self.span,
+                    self.body_id,
+                    self.param_env,
+                    projection_ty,
+                )
+            }
+            _ => ty,
+        }
+    }
 }
@@ -120,7 +120,12 @@ fn confirm(
         // We won't add these if we encountered an illegal sized bound, so that we can use
         // a custom error in that case.
         if illegal_sized_bound.is_none() {
-            self.add_obligations(self.tcx.mk_fn_ptr(method_sig), all_substs, method_predicates);
+            self.add_obligations(
+                self.tcx.mk_fn_ptr(method_sig),
+                pick.item.def_id,
+                all_substs,
+                method_predicates,
+            );
         }
 
         // Create the final `MethodCallee`.
@@ -442,7 +447,7 @@ fn instantiate_method_sig(
 
         debug!("method_predicates after subst = {:?}", method_predicates);
 
-        let sig = self.tcx.fn_sig(def_id);
+        let sig = self.callee_fn_sig(def_id);
 
         // Instantiate late-bound regions and substitute the trait
         // parameters into the method type to get the actual method type.
@@ -462,6 +467,7 @@ fn instantiate_method_sig(
     fn add_obligations(
         &mut self,
         fty: Ty<'tcx>,
+        def_id: DefId,
         all_substs: SubstsRef<'tcx>,
         method_predicates: ty::InstantiatedPredicates<'tcx>,
     ) {
@@ -470,10 +476,24 @@ fn add_obligations(
             fty, all_substs, method_predicates
         );
 
-        self.add_obligations_for_parameters(
+        // The predicates of the impl or trait the method belongs to come first, followed by the
+        // method's own where-clauses. Errors for the latter point at the where-clause that
+        // doesn't hold.
+        let parent_predicates =
+            method_predicates.predicates.len() - self.tcx.predicates_of(def_id).predicates.len();
+        for (i, mut obligation) in traits::predicates_for_generics(
             traits::ObligationCause::misc(self.span, self.body_id),
+            self.param_env,
             method_predicates,
-        );
+        )
+        .enumerate()
+        {
+            if let Some(index) = i.checked_sub(parent_predicates) {
+                obligation.cause.make_mut().code =
+                    traits::MethodWhereClause { method: def_id, index, substs: all_substs };
+            }
+            self.register_predicate(obligation);
+        }
 
         // this is a projection from a trait reference, so we have to
         // make sure that the trait reference inputs are well-formed.
@@ -3,8 +3,11 @@
 //! [rustc dev guide]: https://rustc-dev-guide.rust-lang.org/method-lookup.html
 
 mod confirm;
+mod mut_autoref;
+mod pending_impls;
 mod prelude2021;
 pub mod probe;
+mod shadowed_trait_method;
 mod suggest;
 
 pub use self::suggest::{SelfSource, TraitInfo};
@@ -12,7 +15,6 @@
 pub use self::MethodError::*;
 
 use crate::check::FnCtxt;
-use rustc_data_structures::sync::Lrc;
 use rustc_errors::{Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Namespace};
@@ -74,6 +76,8 @@ pub struct NoMatchData<'tcx> {
     pub unsatisfied_predicates: Vec<(ty::Predicate<'tcx>, Option<ty::Predicate<'tcx>>)>,
     pub out_of_scope_traits: Vec<DefId>,
     pub lev_candidate: Option<ty::AssocItem>,
+    /// A method that has the requested name as a `#[doc(alias)]`.
+    pub alias_candidate: Option<ty::AssocItem>,
     pub mode: probe::Mode,
 }
 
@@ -83,6 +87,7 @@ pub fn new(
         unsatisfied_predicates: Vec<(ty::Predicate<'tcx>, Option<ty::Predicate<'tcx>>)>,
         out_of_scope_traits: Vec<DefId>,
         lev_candidate: Option<ty::AssocItem>,
+        alias_candidate: Option<ty::AssocItem>,
         mode: probe::Mode,
     ) -> Self {
         NoMatchData {
@@ -90,6 +95,7 @@ pub fn new(
             unsatisfied_predicates,
             out_of_scope_traits,
             lev_candidate,
+            alias_candidate,
             mode,
         }
     }
@@ -205,9 +211,7 @@ pub fn lookup_method(
 
         for import_id in &pick.import_ids {
             debug!("used_trait_import: {:?}", import_id);
-            Lrc::get_mut(&mut self.typeck_results.borrow_mut().used_trait_imports)
-                .unwrap()
-                .insert(*import_id);
+            self.used_trait_imports.borrow_mut().insert(*import_id);
         }
 
         self.tcx.check_stability(pick.item.def_id, Some(call_expr.hir_id), span, None);
@@ -216,6 +220,10 @@ pub fn lookup_method(
             self.confirm_method(span, self_expr, call_expr, self_ty, pick.clone(), segment);
         debug!("result = {:?}", result);
 
+        self.lint_unnecessary_mut_autoref(call_expr, self_expr, &pick);
+        self.lint_shadowed_trait_method(span, call_expr, &pick);
+        self.lint_future_impl_collision(span, call_expr, self_expr, &pick);
+
         if let Some(span) = result.illegal_sized_bound {
             let mut needs_mut = false;
             if let ty::Ref(region, t_type, mutability) = self_ty.kind() {
@@ -497,8 +505,7 @@ pub fn resolve_fully_qualified_call(
 
         debug!("resolve_fully_qualified_call: pick={:?}", pick);
         {
-            let mut typeck_results = self.typeck_results.borrow_mut();
-            let used_trait_imports = Lrc::get_mut(&mut typeck_results.used_trait_imports).unwrap();
+            let mut used_trait_imports = self.used_trait_imports.borrow_mut();
             for import_id in pick.import_ids {
                 debug!("resolve_fully_qualified_call: used_trait_import: {:?}", import_id);
                 used_trait_imports.insert(import_id);
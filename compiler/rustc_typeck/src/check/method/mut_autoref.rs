@@ -0,0 +1,39 @@
+use rustc_hir as hir;
+use rustc_session::lint::builtin::UNNECESSARY_MUT_AUTOREF;
+
+use crate::check::{method::probe::Pick, FnCtxt};
+
+impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
+    /// Lints calls that mutably autoref their receiver although a method that only needs a shared
+    /// borrow was found at a later autoderef step. Must be called once `pick` is confirmed, so
+    /// that the receiver's adjustments are recorded.
+    pub(super) fn lint_unnecessary_mut_autoref(
+        &self,
+        call_expr: &'tcx hir::Expr<'tcx>,
+        self_expr: &'tcx hir::Expr<'tcx>,
+        pick: &Pick<'tcx>,
+    ) {
+        let alternative = match pick.shared_alternative {
+            Some(alternative) => alternative,
+            None => return,
+        };
+        let tcx = self.tcx;
+        let self_ty = self.typeck_results.borrow().expr_ty_adjusted(self_expr);
+        let self_ty = self.resolve_vars_if_possible(self_ty);
+        tcx.struct_span_lint_hir(UNNECESSARY_MUT_AUTOREF, call_expr.hir_id, call_expr.span, |lint| {
+            let mut lint = lint.build(&format!(
+                "`{}` mutably borrows its receiver",
+                tcx.def_path_str(pick.item.def_id),
+            ));
+            lint.span_label(self_expr.span, format!("implicitly borrowed as `{}`", self_ty));
+            lint.span_note(
+                tcx.def_span(alternative.def_id),
+                &format!(
+                    "`{}` would only need a shared borrow of the receiver",
+                    tcx.def_path_str(alternative.def_id),
+                ),
+            );
+            lint.emit();
+        });
+    }
+}
@@ -0,0 +1,78 @@
+//! Standard library impls that are expected to be added in a future release, and the lint for
+//! method calls whose resolution they would change.
+//!
+//! Adding an impl of a trait in scope can make method resolution pick a method at an earlier
+//! autoderef step or autoref than it does today, like `Box<[T]>::into_iter` calling the
+//! by-value `IntoIterator` impl of the box instead of the one of `&[T]`. The probe checks every
+//! call against `PENDING_IMPLS` and records the method such an impl would make it pick.
+
+use rustc_hir as hir;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::lint::builtin::FUTURE_IMPL_COLLISION;
+use rustc_span::symbol::{sym, Symbol};
+use rustc_span::Span;
+
+use crate::check::{method::probe::Pick, FnCtxt};
+
+pub(super) struct PendingImpl {
+    /// The diagnostic item of the implemented trait.
+    pub trait_name: Symbol,
+    pub self_ty: PendingImplSelfTy,
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum PendingImplSelfTy {
+    /// `Box<[T]>`
+    BoxedSlice,
+}
+
+impl PendingImplSelfTy {
+    pub(super) fn matches(self, ty: Ty<'_>) -> bool {
+        match self {
+            PendingImplSelfTy::BoxedSlice => {
+                ty.is_box() && matches!(ty.boxed_ty().kind(), ty::Slice(_))
+            }
+        }
+    }
+}
+
+pub(super) const PENDING_IMPLS: &[PendingImpl] = &[
+    // `impl<T, A: Allocator> IntoIterator for Box<[T], A>`
+    PendingImpl { trait_name: sym::IntoIterator, self_ty: PendingImplSelfTy::BoxedSlice },
+];
+
+impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
+    /// Lints calls that would resolve to another method if one of the `PENDING_IMPLS` existed.
+    /// Must be called once `pick` is confirmed, so that the receiver's adjustments are recorded.
+    pub(super) fn lint_future_impl_collision(
+        &self,
+        span: Span,
+        call_expr: &'tcx hir::Expr<'tcx>,
+        self_expr: &'tcx hir::Expr<'tcx>,
+        pick: &Pick<'tcx>,
+    ) {
+        let pending = match &pick.pending_impl_pick {
+            Some(pending) => pending,
+            None => return,
+        };
+        let tcx = self.tcx;
+        let self_ty = self.typeck_results.borrow().expr_ty_adjusted(self_expr);
+        let self_ty = self.resolve_vars_if_possible(self_ty);
+        tcx.struct_span_lint_hir(FUTURE_IMPL_COLLISION, call_expr.hir_id, span, |lint| {
+            let mut lint = lint.build(&format!(
+                "this method call will resolve differently once `{}` implements `{}`",
+                pending.self_ty,
+                tcx.def_path_str(pending.trait_def_id),
+            ));
+            let current = tcx.def_path_str(pick.item.def_id);
+            lint.span_label(span, format!("currently calls `{}` on `{}`", current, self_ty));
+            lint.note(&format!(
+                "the standard library is expected to add this impl, and `{}` would then be called \
+                 on `{}` instead",
+                tcx.def_path_str(pending.item.def_id),
+                pending.self_ty,
+            ));
+            lint.emit();
+        });
+    }
+}
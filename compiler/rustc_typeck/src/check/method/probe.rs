@@ -1,16 +1,17 @@
+use super::pending_impls::PENDING_IMPLS;
 use super::suggest;
 use super::MethodError;
 use super::NoMatchData;
 use super::{CandidateSource, ImplSource, TraitSource};
 
-use crate::check::FnCtxt;
+use crate::check::{FnCtxt, Inherited};
 use crate::errors::MethodCallOnUnknownType;
 use crate::hir::def::DefKind;
 use crate::hir::def_id::DefId;
 
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::sync::Lrc;
-use rustc_errors::Applicability;
+use rustc_errors::{pluralize, Applicability};
 use rustc_hir as hir;
 use rustc_hir::def::Namespace;
 use rustc_infer::infer::canonical::OriginalQueryValues;
@@ -27,15 +28,16 @@
 use rustc_session::lint;
 use rustc_span::def_id::LocalDefId;
 use rustc_span::lev_distance::{find_best_match_for_name, lev_distance};
-use rustc_span::{symbol::Ident, Span, Symbol, DUMMY_SP};
+use rustc_span::{sym, symbol::Ident, Span, Symbol, DUMMY_SP};
 use rustc_trait_selection::autoderef::{self, Autoderef};
-use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
 use rustc_trait_selection::traits::query::method_autoderef::MethodAutoderefBadTy;
 use rustc_trait_selection::traits::query::method_autoderef::{
-    CandidateStep, MethodAutoderefStepsResult,
+    CandidateStep, MethodAutoderefStepsResult, MethodCompletion, MethodCompletionStep,
+    MethodCompletions,
 };
 use rustc_trait_selection::traits::query::CanonicalTyGoal;
 use rustc_trait_selection::traits::{self, ObligationCause};
+use std::cell::RefCell;
 use std::cmp::max;
 use std::iter;
 use std::mem;
@@ -75,6 +77,10 @@ struct ProbeContext<'a, 'tcx> {
     /// requested name (by Levensthein distance)
     allow_similar_names: bool,
 
+    /// When probing for names, include the items that have the requested name as one of
+    /// their `#[doc(alias)]`es instead of the items with that name
+    match_doc_aliases: bool,
+
     /// Some(candidate) if there is a private candidate
     private_candidate: Option<(DefKind, DefId)>,
 
@@ -85,6 +91,10 @@ struct ProbeContext<'a, 'tcx> {
     is_suggestion: IsSuggestion,
 
     scope_expr_id: hir::HirId,
+
+    /// The candidates considered so far and their outcome, if `-Z probe-trace` asks to trace
+    /// calls to this method.
+    trace: Option<RefCell<Vec<ProbeTraceEntry<'tcx>>>>,
 }
 
 impl<'a, 'tcx> Deref for ProbeContext<'a, 'tcx> {
@@ -156,6 +166,26 @@ enum ProbeResult {
     Match,
 }
 
+/// A candidate considered by method probing, recorded for `-Z probe-trace`.
+struct ProbeTraceEntry<'tcx> {
+    item: ty::AssocItem,
+    /// The receiver type the candidate was considered for, `None` if it was discarded while
+    /// assembling candidates.
+    self_ty: Option<Ty<'tcx>>,
+    outcome: ProbeTraceOutcome<'tcx>,
+}
+
+enum ProbeTraceOutcome<'tcx> {
+    Applicable,
+    Inaccessible,
+    /// The receiver type doesn't match the candidate's `self` type, or the candidate doesn't
+    /// apply to it at all, like a trait method of an unimplemented trait.
+    DoesNotApply,
+    UnsatisfiedPredicates(Vec<ty::Predicate<'tcx>>),
+    BadReturnType,
+    Unstable(Symbol),
+}
+
 /// When adjusting a receiver we often want to do one of
 ///
 /// - Add a `&` (or `&mut`), converting the receiver from `T` to `&T` (or `&mut T`)
@@ -206,6 +236,28 @@ pub struct Pick<'tcx> {
     /// Indicates that we want to add an autoref (and maybe also unsize it), or if the receiver is
     /// `*mut T`, convert it to `*const T`.
     pub autoref_or_ptr_adjustment: Option<AutorefOrPtrAdjustment<'tcx>>,
+
+    /// If the receiver is autoref'd as `&mut`, a method with the same name that a later autoderef
+    /// step would call with only a shared borrow of the receiver. This is only computed if the
+    /// `unnecessary_mut_autoref` lint is enabled.
+    pub shared_alternative: Option<ty::AssocItem>,
+
+    /// If this picks an inherent method, a method of a trait in scope that applies to the same
+    /// autoderef step of the receiver, either at the same autoref or a later one. This is only
+    /// computed if the `shadowed_trait_methods` lint is enabled.
+    pub shadowed_trait_method: Option<ty::AssocItem>,
+
+    /// The method an earlier autoderef step or autoref would pick if one of the `PENDING_IMPLS`
+    /// existed. This is only computed if the `future_impl_collision` lint is enabled.
+    pub pending_impl_pick: Option<PendingImplPick<'tcx>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PendingImplPick<'tcx> {
+    pub item: ty::AssocItem,
+    pub trait_def_id: DefId,
+    /// The self type of the pending impl.
+    pub self_ty: Ty<'tcx>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -375,6 +427,7 @@ fn probe_op<OP, R>(
                     }]),
                     opt_bad_ty: None,
                     reached_recursion_limit: false,
+                    deref_cycle_start: None,
                 }
             })
         };
@@ -396,6 +449,24 @@ fn probe_op<OP, R>(
             });
         }
 
+        if let Some(start) = steps.deref_cycle_start {
+            self.probe(|_| {
+                let mut cycle = steps.steps[start..]
+                    .iter()
+                    .filter(|step| !step.unsize)
+                    .map(|step| {
+                        self.probe_instantiate_query_response(span, &orig_values, &step.self_ty)
+                            .unwrap_or_else(|_| {
+                                span_bug!(span, "instantiating {:?} failed?", step.self_ty)
+                            })
+                            .value
+                    })
+                    .collect::<Vec<_>>();
+                cycle.push(cycle[0]);
+                autoderef::report_autoderef_cycle_error(self.tcx, span, &cycle);
+            });
+        }
+
         // If we encountered an `_` type or an error type during autoderef, this is
         // ambiguous.
         if let Some(bad_ty) = &steps.opt_bad_ty {
@@ -431,6 +502,7 @@ fn probe_op<OP, R>(
                     Vec::new(),
                     Vec::new(),
                     None,
+                    None,
                     mode,
                 )));
             }
@@ -467,6 +539,7 @@ fn probe_op<OP, R>(
 
 pub fn provide(providers: &mut ty::query::Providers) {
     providers.method_autoderef_steps = method_autoderef_steps;
+    providers.method_completion_candidates = method_completion_candidates;
 }
 
 fn method_autoderef_steps<'tcx>(
@@ -536,10 +609,109 @@ fn method_autoderef_steps<'tcx>(
             steps: Lrc::new(steps),
             opt_bad_ty: opt_bad_ty.map(Lrc::new),
             reached_recursion_limit: autoderef.reached_recursion_limit(),
+            deref_cycle_start: autoderef.deref_cycle_start(),
         }
     })
 }
 
+fn method_completion_candidates<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    (body_owner, self_ty): (LocalDefId, Ty<'tcx>),
+) -> MethodCompletions<'tcx> {
+    debug!("method_completion_candidates({:?}, {:?})", body_owner, self_ty);
+
+    let hir_id = tcx.hir().local_def_id_to_hir_id(body_owner);
+    let body_id = tcx.hir().body_owned_by(hir_id);
+    let span = tcx.def_span(body_owner);
+    let param_env = tcx.param_env(body_owner);
+
+    Inherited::build(tcx, body_owner).enter(|inh| {
+        let fcx = FnCtxt::new(&inh, param_env, body_id.hir_id);
+
+        // Pick every method the receiver has a candidate for, exactly like a call to it would.
+        let picks: Vec<_> = fcx
+            .probe_op(
+                span,
+                Mode::MethodCall,
+                None,
+                None,
+                IsSuggestion(true),
+                self_ty,
+                body_id.hir_id,
+                ProbeScope::AllTraits,
+                |probe_cx| Ok(probe_cx.candidate_method_names()),
+            )
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|method_name| {
+                fcx.probe_for_name(
+                    span,
+                    Mode::MethodCall,
+                    method_name,
+                    IsSuggestion(true),
+                    self_ty,
+                    body_id.hir_id,
+                    ProbeScope::AllTraits,
+                )
+                .ok()
+            })
+            .collect();
+
+        let mut orig_values = OriginalQueryValues::default();
+        let goal =
+            fcx.canonicalize_query(ParamEnvAnd { param_env, value: self_ty }, &mut orig_values);
+        let steps = tcx
+            .method_autoderef_steps(goal)
+            .steps
+            .iter()
+            .filter_map(|step| {
+                let self_ty = fcx
+                    .probe_instantiate_query_response(span, &orig_values, &step.self_ty)
+                    .ok()?
+                    .value;
+                let self_ty = fcx.resolve_vars_if_possible(self_ty);
+                if self_ty.needs_infer() {
+                    return None;
+                }
+                let methods = picks
+                    .iter()
+                    .filter(|pick| {
+                        pick.autoderefs == step.autoderefs
+                            && step.unsize
+                                == pick
+                                    .autoref_or_ptr_adjustment
+                                    .as_ref()
+                                    .map_or(false, |adjustment| adjustment.get_unsize().is_some())
+                    })
+                    .map(|pick| MethodCompletion {
+                        def_id: pick.item.def_id,
+                        trait_def_id: match pick.kind {
+                            InherentImplPick => None,
+                            _ => match pick.item.container {
+                                ty::TraitContainer(trait_def_id) => Some(trait_def_id),
+                                ty::ImplContainer(impl_def_id) => tcx.trait_id_of_impl(impl_def_id),
+                            },
+                        },
+                        autoref: match pick.autoref_or_ptr_adjustment {
+                            Some(AutorefOrPtrAdjustment::Autoref { mutbl, .. }) => Some(mutbl),
+                            _ => None,
+                        },
+                    })
+                    .collect();
+                Some(MethodCompletionStep {
+                    self_ty: tcx.erase_regions(self_ty),
+                    autoderefs: step.autoderefs,
+                    from_unsafe_deref: step.from_unsafe_deref,
+                    unsize: step.unsize,
+                    methods,
+                })
+            })
+            .collect();
+
+        MethodCompletions { steps }
+    })
+}
+
 impl<'a, 'tcx> ProbeContext<'a, 'tcx> {
     fn new(
         fcx: &'a FnCtxt<'a, 'tcx>,
@@ -565,10 +737,18 @@ fn new(
             steps,
             static_candidates: Vec::new(),
             allow_similar_names: false,
+            match_doc_aliases: false,
             private_candidate: None,
             unsatisfied_predicates: Vec::new(),
             is_suggestion,
             scope_expr_id,
+            trace: method_name
+                .filter(|name| {
+                    !is_suggestion.0
+                        && fcx.tcx.sess.opts.debugging_opts.probe_trace.as_deref()
+                            == Some(&*name.as_str())
+                })
+                .map(|_| RefCell::default()),
         }
     }
 
@@ -598,9 +778,12 @@ fn push_candidate(&mut self, candidate: Candidate<'tcx>, is_inherent: bool) {
             } else {
                 self.extension_candidates.push(candidate);
             }
-        } else if self.private_candidate.is_none() {
-            self.private_candidate =
-                Some((candidate.item.kind.as_def_kind(), candidate.item.def_id));
+        } else {
+            self.trace_candidate(&candidate, None, ProbeTraceOutcome::Inaccessible);
+            if self.private_candidate.is_none() {
+                self.private_candidate =
+                    Some((candidate.item.kind.as_def_kind(), candidate.item.def_id));
+            }
         }
     }
 
@@ -738,6 +921,15 @@ fn assemble_inherent_impl_candidates_for_type(&mut self, def_id: DefId) {
         for &impl_def_id in impl_def_ids.iter() {
             self.assemble_inherent_impl_probe(impl_def_id);
         }
+        // Types that are split across crates also have inherent impls in crates other than
+        // their own, which `inherent_impls` doesn't know about.
+        if !def_id.is_local()
+            && self.tcx.has_attr(def_id, sym::rustc_has_incoherent_inherent_impls)
+        {
+            for &impl_def_id in self.tcx.at(self.span).incoherent_impls(def_id) {
+                self.assemble_inherent_impl_probe(impl_def_id);
+            }
+        }
     }
 
     fn assemble_inherent_impl_probe(&mut self, impl_def_id: DefId) {
@@ -763,9 +955,8 @@ fn assemble_inherent_impl_probe(&mut self, impl_def_id: DefId) {
             // We can't use normalize_associated_types_in as it will pollute the
             // fcx's fulfillment context after this probe is over.
             let cause = traits::ObligationCause::misc(self.span, self.body_id);
-            let selcx = &mut traits::SelectionContext::new(self.fcx);
             let traits::Normalized { value: (xform_self_ty, xform_ret_ty), obligations } =
-                traits::normalize(selcx, self.param_env, cause, xform_tys);
+                self.trait_solver.normalize(self.fcx, self.param_env, cause, xform_tys);
             debug!(
                 "assemble_inherent_impl_probe: xform_self_ty = {:?}/{:?}",
                 xform_self_ty, xform_ret_ty
@@ -1039,7 +1230,12 @@ fn candidate_method_names(&self) -> Vec<Ident> {
     fn pick(mut self) -> PickResult<'tcx> {
         assert!(self.method_name.is_some());
 
-        if let Some(r) = self.pick_core() {
+        let result = self.pick_core();
+        // Only trace the actual search, not the one for diagnostics below.
+        if let Some(trace) = self.trace.take() {
+            self.emit_probe_trace(trace.into_inner(), result.as_ref());
+        }
+        if let Some(r) = result {
             return r;
         }
 
@@ -1082,13 +1278,16 @@ fn pick(mut self) -> PickResult<'tcx> {
         if let Some((kind, def_id)) = private_candidate {
             return Err(MethodError::PrivateMatch(kind, def_id, out_of_scope_traits));
         }
-        let lev_candidate = self.probe_for_lev_candidate()?;
+        let alias_candidate = self.probe_for_doc_alias_candidate();
+        let lev_candidate =
+            if alias_candidate.is_some() { None } else { self.probe_for_lev_candidate()? };
 
         Err(MethodError::NoMatch(NoMatchData::new(
             static_candidates,
             unsatisfied_predicates,
             out_of_scope_traits,
             lev_candidate,
+            alias_candidate,
             self.mode,
         )))
     }
@@ -1099,13 +1298,14 @@ fn pick_core(&mut self) -> Option<PickResult<'tcx>> {
         // find the first step that works
         steps
             .iter()
-            .filter(|step| {
+            .enumerate()
+            .filter(|(_, step)| {
                 debug!("pick_core: step={:?}", step);
                 // skip types that are from a type error or that would require dereferencing
                 // a raw pointer
                 !step.self_ty.references_error() && !step.from_unsafe_deref
             })
-            .flat_map(|step| {
+            .flat_map(|(index, step)| {
                 let InferOk { value: self_ty, obligations: _ } = self
                     .fcx
                     .probe_instantiate_query_response(
@@ -1116,15 +1316,222 @@ fn pick_core(&mut self) -> Option<PickResult<'tcx>> {
                     .unwrap_or_else(|_| {
                         span_bug!(self.span, "{:?} was applicable but now isn't?", step.self_ty)
                     });
-                self.pick_by_value_method(step, self_ty).or_else(|| {
+                let pick = self.pick_by_value_method(step, self_ty).or_else(|| {
                     self.pick_autorefd_method(step, self_ty, hir::Mutability::Not)
-                        .or_else(|| self.pick_autorefd_method(step, self_ty, hir::Mutability::Mut))
+                        .or_else(|| {
+                            let pick =
+                                self.pick_autorefd_method(step, self_ty, hir::Mutability::Mut)?;
+                            Some(pick.map(|mut pick| {
+                                pick.shared_alternative = self.shared_alternative(&steps, index);
+                                pick
+                            }))
+                        })
                         .or_else(|| self.pick_const_ptr_method(step, self_ty))
-                })
+                });
+                Some(pick?.map(|mut pick| {
+                    if pick.kind == InherentImplPick {
+                        pick.shadowed_trait_method =
+                            self.shadowed_trait_method(step, self_ty, &pick);
+                    }
+                    pick.pending_impl_pick = self.pending_impl_pick(&steps, index, &pick);
+                    pick
+                }))
             })
             .next()
     }
 
+    /// Looks for a method of a trait in scope that applies to the receiver of `pick`, an inherent
+    /// method picked at `step`, if the receiver is autoref'd the way `pick` does it or any way
+    /// that is tried after that.
+    fn shadowed_trait_method(
+        &mut self,
+        step: &CandidateStep<'tcx>,
+        self_ty: Ty<'tcx>,
+        pick: &Pick<'tcx>,
+    ) -> Option<ty::AssocItem> {
+        let (level, _) = self
+            .tcx
+            .lint_level_at_node(lint::builtin::SHADOWED_TRAIT_METHODS, self.scope_expr_id);
+        if self.is_suggestion.0 || self.mode != Mode::MethodCall || level == lint::Level::Allow {
+            return None;
+        }
+
+        let picked = Self::receiver_index(step, pick)?;
+        let receivers = self.step_receivers(step, self_ty);
+
+        // This only informs the lint, so it must not show up in the `-Z probe-trace` output.
+        let is_suggestion = mem::replace(&mut self.is_suggestion, IsSuggestion(true));
+        let shadowed = receivers[picked..].iter().flatten().find_map(|&receiver| {
+            match self.consider_candidates(
+                receiver,
+                self.extension_candidates.iter(),
+                &mut Vec::new(),
+                None,
+            )? {
+                Ok(pick) => Some(pick.item),
+                Err(_) => None,
+            }
+        });
+        self.is_suggestion = is_suggestion;
+        shadowed
+    }
+
+    /// Looks for a method of a trait in scope that would be picked instead of `pick`, which was
+    /// picked at `steps[index]`, if one of the `PENDING_IMPLS` existed.
+    fn pending_impl_pick(
+        &self,
+        steps: &[CandidateStep<'tcx>],
+        index: usize,
+        pick: &Pick<'tcx>,
+    ) -> Option<PendingImplPick<'tcx>> {
+        let (level, _) = self
+            .tcx
+            .lint_level_at_node(lint::builtin::FUTURE_IMPL_COLLISION, self.scope_expr_id);
+        if self.is_suggestion.0 || self.mode != Mode::MethodCall || level == lint::Level::Allow {
+            return None;
+        }
+
+        let tcx = self.tcx;
+        let candidates: Vec<_> = self
+            .extension_candidates
+            .iter()
+            .filter_map(|candidate| match candidate.kind {
+                TraitCandidate(trait_ref) => PENDING_IMPLS
+                    .iter()
+                    .find(|pending| tcx.is_diagnostic_item(pending.trait_name, trait_ref.def_id))
+                    .map(|pending| (candidate, trait_ref, pending.self_ty)),
+                _ => None,
+            })
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        // Only the receivers tried before the one `pick` was found with could pick another
+        // method, the pending impls would lose to `pick` at the same autoref.
+        let picked = Self::receiver_index(&steps[index], pick).unwrap_or(3);
+        for (i, step) in steps[..=index].iter().enumerate() {
+            if step.self_ty.references_error() || step.from_unsafe_deref {
+                continue;
+            }
+            let self_ty = self
+                .fcx
+                .probe_instantiate_query_response(
+                    self.span,
+                    &self.orig_steps_var_values,
+                    &step.self_ty,
+                )
+                .ok()?
+                .value;
+            let receivers = self.step_receivers(step, self_ty);
+            let receivers = if i == index { &receivers[..picked] } else { &receivers[..] };
+            for &receiver in receivers.iter().flatten() {
+                for &(candidate, trait_ref, pending_self_ty) in &candidates {
+                    // The candidate only failed to apply because the impl is missing if its
+                    // receiver matches and `Self` is the self type of the pending impl.
+                    let impl_self_ty = self.probe(|_| {
+                        self.at(&ObligationCause::dummy(), self.param_env)
+                            .sup(candidate.xform_self_ty, receiver)
+                            .ok()?;
+                        let impl_self_ty = self.resolve_vars_if_possible(trait_ref.self_ty());
+                        pending_self_ty.matches(impl_self_ty).then_some(impl_self_ty)
+                    });
+                    if let Some(self_ty) = impl_self_ty {
+                        return Some(PendingImplPick {
+                            item: candidate.item,
+                            trait_def_id: trait_ref.def_id,
+                            self_ty,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// The receiver types a method is looked up with at `step`, in the order they are tried:
+    /// `self_ty` by value, then autoref'd as `&` and as `&mut`. This leaves out `*mut T` receivers
+    /// converted to `*const T`, which are only tried if none of these apply.
+    fn step_receivers(
+        &self,
+        step: &CandidateStep<'tcx>,
+        self_ty: Ty<'tcx>,
+    ) -> [Option<Ty<'tcx>>; 3] {
+        let tcx = self.tcx;
+        let region = tcx.lifetimes.re_erased;
+        let autorefd = |mutbl| Some(tcx.mk_ref(region, ty::TypeAndMut { ty: self_ty, mutbl }));
+        [
+            (!step.unsize).then_some(self_ty),
+            autorefd(hir::Mutability::Not),
+            autorefd(hir::Mutability::Mut),
+        ]
+    }
+
+    /// The index into `step_receivers` of the receiver `pick` was found with at `step`.
+    fn receiver_index(step: &CandidateStep<'tcx>, pick: &Pick<'tcx>) -> Option<usize> {
+        match pick.autoref_or_ptr_adjustment {
+            // Picking a method by value at a `&T` step reborrows the receiver, which is recorded
+            // as an extra autoderef.
+            Some(AutorefOrPtrAdjustment::Autoref { mutbl, .. })
+                if pick.autoderefs == step.autoderefs =>
+            {
+                Some(match mutbl {
+                    hir::Mutability::Not => 1,
+                    hir::Mutability::Mut => 2,
+                })
+            }
+            Some(AutorefOrPtrAdjustment::ToConstPtr) => None,
+            _ => Some(0),
+        }
+    }
+
+    /// Looks for a method with the same name as the one picked at `steps[index]` by autoref'ing
+    /// the receiver as `&mut`, that a later step would call with only a shared borrow of the
+    /// receiver.
+    fn shared_alternative(
+        &mut self,
+        steps: &[CandidateStep<'tcx>],
+        index: usize,
+    ) -> Option<ty::AssocItem> {
+        let (level, _) = self
+            .tcx
+            .lint_level_at_node(lint::builtin::UNNECESSARY_MUT_AUTOREF, self.scope_expr_id);
+        if self.is_suggestion.0 || self.mode != Mode::MethodCall || level == lint::Level::Allow {
+            return None;
+        }
+
+        // Probing the later steps is only done to inform the lint, it must not emit lints itself.
+        let is_suggestion = mem::replace(&mut self.is_suggestion, IsSuggestion(true));
+        let alternative = steps[index + 1..]
+            .iter()
+            .filter(|step| !step.self_ty.references_error() && !step.from_unsafe_deref)
+            .find_map(|step| {
+                let self_ty = self
+                    .fcx
+                    .probe_instantiate_query_response(
+                        self.span,
+                        &self.orig_steps_var_values,
+                        &step.self_ty,
+                    )
+                    .ok()?
+                    .value;
+                self.pick_by_value_method(step, self_ty)
+                    .or_else(|| self.pick_autorefd_method(step, self_ty, hir::Mutability::Not))
+            });
+        self.is_suggestion = is_suggestion;
+
+        match alternative? {
+            // Reborrowing a `&mut` receiver still needs a mutable borrow.
+            Ok(Pick {
+                autoref_or_ptr_adjustment:
+                    Some(AutorefOrPtrAdjustment::Autoref { mutbl: hir::Mutability::Mut, .. }),
+                ..
+            })
+            | Err(_) => None,
+            Ok(pick) => Some(pick.item),
+        }
+    }
+
     /// For each type `T` in the step list, this attempts to find a method where
     /// the (transformed) self type is exactly `T`. We do however do one
     /// transformation on the adjustment: if we are passing a region pointer in,
@@ -1270,7 +1677,23 @@ fn consider_candidates<'b, ProbesIter>(
         let mut applicable_candidates: Vec<_> = probes
             .clone()
             .map(|probe| {
-                (probe, self.consider_probe(self_ty, probe, possibly_unsatisfied_predicates))
+                let unsatisfied = possibly_unsatisfied_predicates.len();
+                let status = self.consider_probe(self_ty, probe, possibly_unsatisfied_predicates);
+                if self.is_tracing() {
+                    let new_predicates = &possibly_unsatisfied_predicates[unsatisfied..];
+                    let outcome = match status {
+                        ProbeResult::Match => ProbeTraceOutcome::Applicable,
+                        ProbeResult::BadReturnType => ProbeTraceOutcome::BadReturnType,
+                        ProbeResult::NoMatch if new_predicates.is_empty() => {
+                            ProbeTraceOutcome::DoesNotApply
+                        }
+                        ProbeResult::NoMatch => ProbeTraceOutcome::UnsatisfiedPredicates(
+                            new_predicates.iter().map(|&(predicate, _)| predicate).collect(),
+                        ),
+                    };
+                    self.trace_candidate(probe, Some(self_ty), outcome);
+                }
+                (probe, status)
             })
             .filter(|&(_, status)| status != ProbeResult::NoMatch)
             .collect();
@@ -1288,6 +1711,7 @@ fn consider_candidates<'b, ProbesIter>(
                 if let stability::EvalResult::Deny { feature, .. } =
                     self.tcx.eval_stability(p.item.def_id, None, self.span, None)
                 {
+                    self.trace_candidate(p, Some(self_ty), ProbeTraceOutcome::Unstable(feature));
                     uc.push((p, feature));
                     return false;
                 }
@@ -1309,6 +1733,94 @@ fn consider_candidates<'b, ProbesIter>(
         })
     }
 
+    /// Whether candidates are being recorded for `-Z probe-trace`. The probes made on behalf of
+    /// lints, which are marked as suggestions, aren't part of the trace.
+    fn is_tracing(&self) -> bool {
+        self.trace.is_some() && !self.is_suggestion.0
+    }
+
+    fn trace_candidate(
+        &self,
+        candidate: &Candidate<'tcx>,
+        self_ty: Option<Ty<'tcx>>,
+        outcome: ProbeTraceOutcome<'tcx>,
+    ) {
+        if let (Some(trace), true) = (&self.trace, self.is_tracing()) {
+            let self_ty = self_ty.map(|ty| self.resolve_vars_if_possible(ty));
+            trace.borrow_mut().push(ProbeTraceEntry { item: candidate.item, self_ty, outcome });
+        }
+    }
+
+    fn emit_probe_trace(
+        &self,
+        trace: Vec<ProbeTraceEntry<'tcx>>,
+        result: Option<&PickResult<'tcx>>,
+    ) {
+        let tcx = self.tcx;
+        let mut diag = tcx.sess.struct_note_without_error(&format!(
+            "method resolution trace for `{}`",
+            self.method_name.unwrap()
+        ));
+        diag.set_span(self.span);
+        for entry in trace {
+            let item = tcx.def_path_str(entry.item.def_id);
+            let candidate = match entry.self_ty {
+                Some(self_ty) => format!("`{}` for receiver type `{}`", item, self_ty),
+                None => format!("`{}`", item),
+            };
+            let outcome = match entry.outcome {
+                ProbeTraceOutcome::Applicable => "applicable".to_string(),
+                ProbeTraceOutcome::Inaccessible => "discarded: not accessible".to_string(),
+                ProbeTraceOutcome::DoesNotApply => {
+                    "discarded: does not apply to the receiver type".to_string()
+                }
+                ProbeTraceOutcome::UnsatisfiedPredicates(predicates) => format!(
+                    "discarded: unsatisfied predicate{} {}",
+                    pluralize!(predicates.len()),
+                    predicates
+                        .iter()
+                        .map(|predicate| format!("`{}`", self.resolve_vars_if_possible(*predicate)))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                ProbeTraceOutcome::BadReturnType => {
+                    "discarded: return type does not match".to_string()
+                }
+                ProbeTraceOutcome::Unstable(feature) => {
+                    format!("discarded: unstable, requires `#![feature({})]`", feature)
+                }
+            };
+            diag.note(&format!("candidate {}: {}", candidate, outcome));
+        }
+        diag.note(&match result {
+            Some(Ok(pick)) => {
+                let autoref = match pick.autoref_or_ptr_adjustment {
+                    Some(AutorefOrPtrAdjustment::Autoref { mutbl: hir::Mutability::Not, .. }) => {
+                        ", then `&`"
+                    }
+                    Some(AutorefOrPtrAdjustment::Autoref { mutbl: hir::Mutability::Mut, .. }) => {
+                        ", then `&mut`"
+                    }
+                    Some(AutorefOrPtrAdjustment::ToConstPtr) => ", then `as *const _`",
+                    None => "",
+                };
+                format!(
+                    "picked `{}` after {} autoderef{}{}",
+                    tcx.def_path_str(pick.item.def_id),
+                    pick.autoderefs,
+                    pluralize!(pick.autoderefs),
+                    autoref,
+                )
+            }
+            Some(Err(MethodError::Ambiguity(_))) => {
+                "nothing picked: multiple candidates are applicable".to_string()
+            }
+            Some(Err(_)) => "nothing picked: the applicable candidate is not usable".to_string(),
+            None => "nothing picked: no candidate is applicable".to_string(),
+        });
+        diag.emit();
+    }
+
     fn emit_unstable_name_collision_hint(
         &self,
         stable_pick: &Pick<'_>,
@@ -1375,7 +1887,7 @@ fn select_trait_candidate(
         let cause = traits::ObligationCause::misc(self.span, self.body_id);
         let predicate = trait_ref.to_poly_trait_ref().to_poly_trait_predicate();
         let obligation = traits::Obligation::new(cause, self.param_env, predicate);
-        traits::SelectionContext::new(self).select(&obligation)
+        self.trait_solver.select(self, &obligation)
     }
 
     fn candidate_source(&self, candidate: &Candidate<'tcx>, self_ty: Ty<'tcx>) -> CandidateSource {
@@ -1423,7 +1935,6 @@ fn consider_probe(
             };
 
             let mut result = ProbeResult::Match;
-            let selcx = &mut traits::SelectionContext::new(self);
             let cause = traits::ObligationCause::misc(self.span, self.body_id);
 
             // If so, impls may carry other conditions (e.g., where
@@ -1437,7 +1948,12 @@ fn consider_probe(
                     let impl_bounds = self.tcx.predicates_of(impl_def_id);
                     let impl_bounds = impl_bounds.instantiate(self.tcx, substs);
                     let traits::Normalized { value: impl_bounds, obligations: norm_obligations } =
-                        traits::normalize(selcx, self.param_env, cause.clone(), impl_bounds);
+                        self.trait_solver.normalize(
+                            self,
+                            self.param_env,
+                            cause.clone(),
+                            impl_bounds,
+                        );
 
                     // Convert the bounds into obligations.
                     let impl_obligations =
@@ -1449,7 +1965,7 @@ fn consider_probe(
                     // Evaluate those obligations to see if they might possibly hold.
                     for o in candidate_obligations {
                         let o = self.resolve_vars_if_possible(o);
-                        if !self.predicate_may_hold(&o) {
+                        if !self.trait_solver.predicate_may_hold(self, &o) {
                             result = ProbeResult::NoMatch;
                             possibly_unsatisfied_predicates.push((o.predicate, None));
                         }
@@ -1473,7 +1989,7 @@ fn consider_probe(
                     }
                     let predicate = trait_ref.without_const().to_predicate(self.tcx);
                     let obligation = traits::Obligation::new(cause, self.param_env, predicate);
-                    if !self.predicate_may_hold(&obligation) {
+                    if !self.trait_solver.predicate_may_hold(self, &obligation) {
                         result = ProbeResult::NoMatch;
                         if self.probe(|_| {
                             match self.select_trait_candidate(trait_ref) {
@@ -1484,7 +2000,10 @@ fn consider_probe(
                                     for obligation in impl_source.borrow_nested_obligations() {
                                         // Determine exactly which obligation wasn't met, so
                                         // that we can give more context in the error.
-                                        if !self.predicate_may_hold(obligation) {
+                                        if !self
+                                            .trait_solver
+                                            .predicate_may_hold(self, obligation)
+                                        {
                                             let nested_predicate =
                                                 self.resolve_vars_if_possible(obligation.predicate);
                                             let predicate =
@@ -1522,7 +2041,7 @@ fn consider_probe(
             // Evaluate those obligations to see if they might possibly hold.
             for o in sub_obligations {
                 let o = self.resolve_vars_if_possible(o);
-                if !self.predicate_may_hold(&o) {
+                if !self.trait_solver.predicate_may_hold(self, &o) {
                     result = ProbeResult::NoMatch;
                     possibly_unsatisfied_predicates.push((o.predicate, None));
                 }
@@ -1589,6 +2108,9 @@ fn collapse_candidates_to_trait_pick(
             import_ids: probes[0].0.import_ids.clone(),
             autoderefs: 0,
             autoref_or_ptr_adjustment: None,
+            shared_alternative: None,
+            shadowed_trait_method: None,
+            pending_impl_pick: None,
         })
     }
 
@@ -1644,6 +2166,42 @@ fn probe_for_lev_candidate(&mut self) -> Result<Option<ty::AssocItem>, MethodErr
         })
     }
 
+    /// Looks for a method that was called by one of its `#[doc(alias)]`es, like `push_back`
+    /// for `Vec::push`, among the inherent methods of the receiver and the methods of the
+    /// traits in scope. Aliases are a deliberate pointer to the right method, so such a
+    /// candidate is preferred over one with a similar name.
+    fn probe_for_doc_alias_candidate(&mut self) -> Option<ty::AssocItem> {
+        debug!("probing for methods with the doc alias {:?}", self.method_name);
+
+        let steps = self.steps.clone();
+        self.probe(|_| {
+            let mut pcx = ProbeContext::new(
+                self.fcx,
+                self.span,
+                self.mode,
+                self.method_name,
+                self.return_type,
+                self.orig_steps_var_values.clone(),
+                steps,
+                IsSuggestion(true),
+                self.scope_expr_id,
+            );
+            pcx.match_doc_aliases = true;
+            pcx.assemble_inherent_candidates();
+            pcx.assemble_extension_candidates_for_traits_in_scope(self.scope_expr_id);
+
+            let method_names = pcx.candidate_method_names();
+            pcx.match_doc_aliases = false;
+            method_names.into_iter().find_map(|method_name| {
+                pcx.reset();
+                pcx.method_name = Some(method_name);
+                pcx.assemble_inherent_candidates();
+                pcx.assemble_extension_candidates_for_traits_in_scope(self.scope_expr_id);
+                pcx.pick_core().and_then(|pick| pick.ok()).map(|pick| pick.item)
+            })
+        })
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     // MISCELLANY
     fn has_applicable_self(&self, item: &ty::AssocItem) -> bool {
@@ -1686,7 +2244,7 @@ fn xform_self_ty(
     }
 
     fn xform_method_sig(&self, method: DefId, substs: SubstsRef<'tcx>) -> ty::FnSig<'tcx> {
-        let fn_sig = self.tcx.fn_sig(method);
+        let fn_sig = self.callee_fn_sig(method);
         debug!("xform_self_ty(fn_sig={:?}, substs={:?})", fn_sig, substs);
 
         assert!(!substs.has_escaping_bound_vars());
@@ -1777,12 +2335,23 @@ fn erase_late_bound_regions<T>(&self, value: ty::Binder<'tcx, T>) -> T
     }
 
     /// Finds the method with the appropriate name (or return type, as the case may be). If
-    /// `allow_similar_names` is set, find methods with close-matching names.
+    /// `allow_similar_names` is set, find methods with close-matching names, and if
+    /// `match_doc_aliases` is set, find methods that have the name as a doc alias.
     // The length of the returned iterator is nearly always 0 or 1 and this
     // method is fairly hot.
     fn impl_or_trait_item(&self, def_id: DefId) -> SmallVec<[ty::AssocItem; 1]> {
         if let Some(name) = self.method_name {
-            if self.allow_similar_names {
+            if self.match_doc_aliases {
+                self.tcx
+                    .associated_items(def_id)
+                    .in_definition_order()
+                    .filter(|x| {
+                        x.kind.namespace() == Namespace::ValueNS
+                            && self.tcx.doc_aliases(x.def_id).contains(&name.name)
+                    })
+                    .copied()
+                    .collect()
+            } else if self.allow_similar_names {
                 let max_dist = max(name.as_str().len(), 3) / 3;
                 self.tcx
                     .associated_items(def_id)
@@ -1829,6 +2398,9 @@ fn to_unadjusted_pick(&self) -> Pick<'tcx> {
             import_ids: self.import_ids.clone(),
             autoderefs: 0,
             autoref_or_ptr_adjustment: None,
+            shared_alternative: None,
+            shadowed_trait_method: None,
+            pending_impl_pick: None,
         }
     }
 }
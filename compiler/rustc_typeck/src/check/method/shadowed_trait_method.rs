@@ -0,0 +1,37 @@
+use rustc_hir as hir;
+use rustc_session::lint::builtin::SHADOWED_TRAIT_METHODS;
+use rustc_span::Span;
+
+use crate::check::{method::probe::Pick, FnCtxt};
+
+impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
+    /// Lints calls that pick an inherent method although a method of a trait in scope applies to
+    /// the receiver as well, so that adding the inherent method changed which method is called.
+    pub(super) fn lint_shadowed_trait_method(
+        &self,
+        span: Span,
+        call_expr: &'tcx hir::Expr<'tcx>,
+        pick: &Pick<'tcx>,
+    ) {
+        let shadowed = match pick.shadowed_trait_method {
+            Some(shadowed) => shadowed,
+            None => return,
+        };
+        let tcx = self.tcx;
+        tcx.struct_span_lint_hir(SHADOWED_TRAIT_METHODS, call_expr.hir_id, span, |lint| {
+            let mut lint = lint.build(&format!(
+                "`{}` shadows the trait method `{}`",
+                tcx.def_path_str(pick.item.def_id),
+                tcx.def_path_str(shadowed.def_id),
+            ));
+            lint.span_note(
+                tcx.def_span(shadowed.def_id),
+                &format!(
+                    "`{}` applies to the receiver as well, but inherent methods are picked first",
+                    tcx.def_path_str(shadowed.def_id),
+                ),
+            );
+            lint.emit();
+        });
+    }
+}
@@ -2,7 +2,7 @@
 //! found or is otherwise invalid.
 
 use crate::check::FnCtxt;
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Namespace, Res};
@@ -14,7 +14,8 @@
 use rustc_middle::ty::fast_reject::simplify_type;
 use rustc_middle::ty::print::with_crate_prefix;
 use rustc_middle::ty::{
-    self, ToPolyTraitRef, ToPredicate, Ty, TyCtxt, TypeFoldable, WithConstness,
+    self, BoundInsertionPlanner, ToPolyTraitRef, ToPredicate, Ty, TyCtxt, TypeFoldable,
+    WithConstness,
 };
 use rustc_span::lev_distance;
 use rustc_span::symbol::{kw, sym, Ident};
@@ -245,6 +246,7 @@ pub fn report_method_error(
                 unsatisfied_predicates,
                 out_of_scope_traits,
                 lev_candidate,
+                alias_candidate,
                 mode,
             }) => {
                 let tcx = self.tcx;
@@ -518,29 +520,21 @@ pub fn report_method_error(
                                 inherent_impls_candidate.sort();
                                 inherent_impls_candidate.dedup();
 
-                                // number of type to shows at most.
-                                let limit = if inherent_impls_candidate.len() == 5 { 5 } else { 4 };
                                 let type_candidates = inherent_impls_candidate
                                     .iter()
-                                    .take(limit)
                                     .map(|impl_item| {
-                                        format!("- `{}`", self.tcx.at(span).type_of(*impl_item))
+                                        self.tcx.at(span).type_of(*impl_item).to_string()
                                     })
-                                    .collect::<Vec<_>>()
-                                    .join("\n");
-                                let additional_types = if inherent_impls_candidate.len() > limit {
-                                    format!(
-                                        "\nand {} more types",
-                                        inherent_impls_candidate.len() - limit
-                                    )
-                                } else {
-                                    "".to_string()
-                                };
+                                    .collect::<Vec<_>>();
                                 err.note(&format!(
-                                    "the {item_kind} was found for\n{}{}",
-                                    type_candidates, additional_types
+                                    "the {item_kind} was found for\n{}",
+                                    bulleted_type_list(&type_candidates)
                                 ));
                             }
+                        } else if let ty::Param(_) = actual.kind() {
+                            self.note_concrete_types_with_item(
+                                &mut err, span, item_name, source, mode, item_kind, &ty_str,
+                            );
                         }
                     } else {
                         err.span_label(span, format!("{item_kind} cannot be called on `{ty_str}` due to unsatisfied trait bounds"));
@@ -602,7 +596,10 @@ pub fn report_method_error(
 
                         let field_kind = if is_accessible { "field" } else { "private field" };
                         err.span_label(item_name.span, format!("{}, not a method", field_kind));
-                    } else if lev_candidate.is_none() && static_sources.is_empty() {
+                    } else if lev_candidate.is_none()
+                        && alias_candidate.is_none()
+                        && static_sources.is_empty()
+                    {
                         label_span_not_found();
                     }
                 } else {
@@ -677,15 +674,25 @@ fn report_function<T: std::fmt::Display>(
                     let def_span = |def_id| {
                         self.tcx.sess.source_map().guess_head_span(self.tcx.def_span(def_id))
                     };
-                    let mut type_params = FxHashMap::default();
+                    let mut type_params = FxIndexMap::default();
                     let mut bound_spans = vec![];
 
                     let mut collect_type_param_suggestions =
-                        |self_ty: Ty<'tcx>, parent_pred: &ty::Predicate<'tcx>, obligation: &str| {
+                        |self_ty: Ty<'tcx>,
+                         parent_pred: &ty::Predicate<'tcx>,
+                         pred: &ty::Predicate<'tcx>| {
                             // We don't care about regions here, so it's fine to skip the binder here.
-                            if let (ty::Param(_), ty::PredicateKind::Trait(p, _)) =
-                                (self_ty.kind(), parent_pred.kind().skip_binder())
-                            {
+                            // Only trait bounds can be suggested, as equality constraints on
+                            // associated types can't be written in a `where` clause.
+                            if let (
+                                ty::Param(_),
+                                ty::PredicateKind::Trait(p, _),
+                                ty::PredicateKind::Trait(bound, _),
+                            ) = (
+                                self_ty.kind(),
+                                parent_pred.kind().skip_binder(),
+                                pred.kind().skip_binder(),
+                            ) {
                                 if let ty::Adt(def, _) = p.trait_ref.self_ty().kind() {
                                     let node = def.did.as_local().map(|def_id| {
                                         self.tcx
@@ -694,18 +701,11 @@ fn report_function<T: std::fmt::Display>(
                                     });
                                     if let Some(hir::Node::Item(hir::Item { kind, .. })) = node {
                                         if let Some(g) = kind.generics() {
-                                            let key = match g.where_clause.predicates {
-                                                [.., pred] => (pred.span().shrink_to_hi(), false),
-                                                [] => (
-                                                    g.where_clause
-                                                        .span_for_predicates_or_empty_place(),
-                                                    true,
-                                                ),
-                                            };
                                             type_params
-                                                .entry(key)
-                                                .or_insert_with(FxHashSet::default)
-                                                .insert(obligation.to_owned());
+                                                .entry(def.did)
+                                                .or_insert_with(|| (g, vec![]))
+                                                .1
+                                                .push((self_ty.to_string(), bound.trait_ref));
                                         }
                                     }
                                 }
@@ -783,7 +783,11 @@ fn report_function<T: std::fmt::Display>(
                                 Some(parent_pred) => match format_pred(*parent_pred) {
                                     None => format!("`{}`", &p),
                                     Some((parent_p, _)) => {
-                                        collect_type_param_suggestions(self_ty, parent_pred, &p);
+                                        collect_type_param_suggestions(
+                                            self_ty,
+                                            parent_pred,
+                                            pred,
+                                        );
                                         format!("`{}`\nwhich is required by `{}`", p, parent_p)
                                     }
                                 },
@@ -791,22 +795,27 @@ fn report_function<T: std::fmt::Display>(
                         })
                         .enumerate()
                         .collect::<Vec<(usize, String)>>();
-                    for ((span, empty_where), obligations) in type_params.into_iter() {
-                        restrict_type_params = true;
+                    for (_, (generics, bounds)) in type_params.into_iter() {
                         // #74886: Sort here so that the output is always the same.
-                        let mut obligations = obligations.into_iter().collect::<Vec<_>>();
-                        obligations.sort();
-                        err.span_suggestion_verbose(
-                            span,
+                        let mut bounds = bounds
+                            .into_iter()
+                            .map(|(param_name, trait_ref)| {
+                                let constraint = trait_ref.print_only_trait_path().to_string();
+                                (param_name, constraint, trait_ref)
+                            })
+                            .collect::<Vec<_>>();
+                        bounds.sort_by(|(p1, c1, _), (p2, c2, _)| (p1, c1).cmp(&(p2, c2)));
+                        bounds.dedup_by(|(p1, c1, _), (p2, c2, _)| (p1, c1) == (p2, c2));
+                        let mut planner = BoundInsertionPlanner::new(self.tcx, generics);
+                        for (param_name, _, trait_ref) in &bounds {
+                            planner.add_trait_bound(param_name, *trait_ref);
+                        }
+                        restrict_type_params |= planner.suggest(
+                            &mut err,
                             &format!(
                                 "consider restricting the type parameter{s} to satisfy the \
                                  trait bound{s}",
-                                s = pluralize!(obligations.len())
-                            ),
-                            format!(
-                                "{} {}",
-                                if empty_where { " where" } else { "," },
-                                obligations.join(", ")
+                                s = pluralize!(bounds.len())
                             ),
                             Applicability::MaybeIncorrect,
                         );
@@ -888,6 +897,21 @@ trait bound{s}",
                     if fallback_span {
                         err.span_label(span, msg);
                     }
+                } else if let Some(alias_candidate) = alias_candidate {
+                    if unsatisfied_predicates.is_empty() {
+                        let def_kind = alias_candidate.kind.as_def_kind();
+                        err.span_suggestion(
+                            span,
+                            &format!(
+                                "there is {} {} with `{}` as a doc alias",
+                                def_kind.article(),
+                                def_kind.descr(alias_candidate.def_id),
+                                item_name,
+                            ),
+                            alias_candidate.ident.to_string(),
+                            Applicability::MaybeIncorrect,
+                        );
+                    }
                 } else if let Some(lev_candidate) = lev_candidate {
                     // Don't emit a suggestion if we found an actual method
                     // that had unsatisfied trait bounds
@@ -1047,6 +1071,49 @@ fn suggest_use_candidates(
         }
     }
 
+    /// Suggests pinning the receiver of a method that takes `self: Pin<&mut Self>` or
+    /// `self: Pin<&Self>`. `Pin::new` can only pin `Unpin` types, anything else has to be pinned
+    /// on the heap with `Box::pin`.
+    fn suggest_pinning_receiver(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        rcvr: &hir::Expr<'_>,
+        rcvr_ty: Ty<'tcx>,
+        borrow: &str,
+        item_name: Ident,
+    ) {
+        // `is_unpin` can't deal with inference variables, so assume the best in that case.
+        let is_unpin = rcvr_ty.needs_infer()
+            || self.tcx.erase_regions(rcvr_ty).is_unpin(self.tcx.at(rcvr.span), self.param_env);
+        if is_unpin {
+            err.multipart_suggestion(
+                &format!("consider pinning the receiver expression to call `{}`", item_name),
+                vec![
+                    (rcvr.span.shrink_to_lo(), format!("Pin::new({}", borrow)),
+                    (rcvr.span.shrink_to_hi(), ")".to_string()),
+                ],
+                Applicability::MaybeIncorrect,
+            );
+        } else {
+            let reborrow = if borrow == "&mut " { "as_mut" } else { "as_ref" };
+            err.note(&format!(
+                "`{}` is not `Unpin`, so it can't be pinned with `Pin::new`",
+                rcvr_ty
+            ));
+            err.multipart_suggestion(
+                &format!(
+                    "consider pinning the receiver expression on the heap to call `{}`",
+                    item_name
+                ),
+                vec![
+                    (rcvr.span.shrink_to_lo(), "Box::pin(".to_string()),
+                    (rcvr.span.shrink_to_hi(), format!(").{}()", reborrow)),
+                ],
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
     fn suggest_valid_traits(
         &self,
         err: &mut DiagnosticBuilder<'_>,
@@ -1091,6 +1158,7 @@ fn suggest_traits_to_import(
                 self.tcx.lang_items().deref_mut_trait(),
                 self.tcx.lang_items().drop_trait(),
             ];
+            let pinned_ty = self.resolve_vars_if_possible(rcvr_ty);
             // Try alternative arbitrary self types that could fulfill this call.
             // FIXME: probe for all types that *could* be arbitrary self-types, not
             // just this list.
@@ -1148,15 +1216,21 @@ fn suggest_traits_to_import(
                                     pick.item.ident.span,
                                     &format!("the method is available for `{}` here", new_rcvr_t),
                                 );
-                                err.multipart_suggestion(
-                                    "consider wrapping the receiver expression with the \
-                                        appropriate type",
-                                    vec![
-                                        (rcvr.span.shrink_to_lo(), format!("{}({}", pre, post)),
-                                        (rcvr.span.shrink_to_hi(), ")".to_string()),
-                                    ],
-                                    Applicability::MaybeIncorrect,
-                                );
+                                if *pre == "Pin::new" && !post.is_empty() {
+                                    self.suggest_pinning_receiver(
+                                        err, rcvr, pinned_ty, post, item_name,
+                                    );
+                                } else {
+                                    err.multipart_suggestion(
+                                        "consider wrapping the receiver expression with the \
+                                            appropriate type",
+                                        vec![
+                                            (rcvr.span.shrink_to_lo(), format!("{}({}", pre, post)),
+                                            (rcvr.span.shrink_to_hi(), ")".to_string()),
+                                        ],
+                                        Applicability::MaybeIncorrect,
+                                    );
+                                }
                                 // We don't care about the other suggestions.
                                 alt_rcvr_sugg = true;
                             }
@@ -1299,56 +1373,40 @@ fn suggest_traits_to_import(
                 let hir = &self.tcx.hir();
                 if let Some(def_id) = type_param.def_id.as_local() {
                     let id = hir.local_def_id_to_hir_id(def_id);
-                    // Get the `hir::Param` to verify whether it already has any bounds.
-                    // We do this to avoid suggesting code that ends up as `T: FooBar`,
-                    // instead we suggest `T: Foo + Bar` in that case.
+                    // Let the planner look at the bounds the parameter already has, both in the
+                    // generics and in the `where` clause, so we suggest `T: Foo + Bar` instead of
+                    // `T: FooBar` or a second predicate for `T`.
                     match hir.get(id) {
-                        Node::GenericParam(ref param) => {
-                            let mut impl_trait = false;
-                            let has_bounds =
-                                if let hir::GenericParamKind::Type { synthetic: Some(_), .. } =
-                                    &param.kind
-                                {
-                                    // We've found `fn foo(x: impl Trait)` instead of
-                                    // `fn foo<T>(x: T)`. We want to suggest the correct
-                                    // `fn foo(x: impl Trait + TraitBound)` instead of
-                                    // `fn foo<T: TraitBound>(x: T)`. (#63706)
-                                    impl_trait = true;
-                                    param.bounds.get(1)
-                                } else {
-                                    param.bounds.get(0)
-                                };
-                            let sp = hir.span(id);
-                            let sp = if let Some(first_bound) = has_bounds {
-                                // `sp` only covers `T`, change it so that it covers
-                                // `T:` when appropriate
-                                sp.until(first_bound.span())
-                            } else {
-                                sp
-                            };
-                            let trait_def_ids: FxHashSet<DefId> = param
-                                .bounds
-                                .iter()
-                                .filter_map(|bound| bound.trait_ref()?.trait_def_id())
-                                .collect();
-                            if !candidates.iter().any(|t| trait_def_ids.contains(&t.def_id)) {
-                                err.span_suggestions(
-                                    sp,
-                                    &message(format!(
-                                        "restrict type parameter `{}` with",
-                                        param.name.ident(),
-                                    )),
-                                    candidates.iter().map(|t| {
-                                        format!(
-                                            "{}{} {}{}",
-                                            param.name.ident(),
-                                            if impl_trait { " +" } else { ":" },
-                                            self.tcx.def_path_str(t.def_id),
-                                            if has_bounds.is_some() { " + " } else { "" },
-                                        )
-                                    }),
-                                    Applicability::MaybeIncorrect,
-                                );
+                        Node::GenericParam(param) => {
+                            let generics =
+                                hir.find(hir.get_parent_item(id)).and_then(|node| node.generics());
+                            if let Some(generics) = generics {
+                                let param_name = param.name.ident().to_string();
+                                let insertions = candidates
+                                    .iter()
+                                    .filter_map(|t| {
+                                        let mut planner =
+                                            BoundInsertionPlanner::new(self.tcx, generics);
+                                        planner.add_bound(
+                                            &param_name,
+                                            &self.tcx.def_path_str(t.def_id),
+                                            Some(t.def_id),
+                                        );
+                                        planner.plan().pop()
+                                    })
+                                    .collect::<Vec<_>>();
+                                // A candidate that is already a bound isn't what's missing.
+                                if !insertions.is_empty() && insertions.len() == candidates.len() {
+                                    err.span_suggestions(
+                                        insertions[0].span,
+                                        &message(format!(
+                                            "restrict type parameter `{}` with",
+                                            param_name,
+                                        )),
+                                        insertions.into_iter().map(|i| i.code),
+                                        Applicability::MaybeIncorrect,
+                                    );
+                                }
                             }
                             return;
                         }
@@ -1496,6 +1554,80 @@ fn is_local(ty: Ty<'_>) -> bool {
 
         self.autoderef(span, rcvr_ty).any(|(ty, _)| is_local(ty))
     }
+
+    /// When `item_name` can't be found on the type parameter `param_ty`, lists the self types
+    /// of the inherent impls in this crate and of the impls of traits in scope that do define
+    /// it, in case the generic code was written with one of them in mind. Restricting the
+    /// parameter with such a trait is left to `suggest_traits_to_import`.
+    fn note_concrete_types_with_item(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        span: Span,
+        item_name: Ident,
+        source: SelfSource<'tcx>,
+        mode: Mode,
+        item_kind: &str,
+        param_ty: &str,
+    ) {
+        let tcx = self.tcx;
+        let has_item = |def_id: DefId| {
+            self.associated_item(def_id, item_name, Namespace::ValueNS).map_or(false, |assoc| {
+                match mode {
+                    Mode::MethodCall => assoc.fn_has_self_parameter,
+                    Mode::Path => true,
+                }
+            })
+        };
+        let mut self_tys = tcx
+            .crate_inherent_impls(())
+            .inherent_impls
+            .values()
+            .flatten()
+            .filter(|&&impl_def_id| has_item(impl_def_id))
+            .map(|&impl_def_id| tcx.at(span).type_of(impl_def_id).to_string())
+            .collect::<Vec<_>>();
+        // Resolution records the traits in scope that have an item named `item_name` on the
+        // method call or path expression.
+        let scope_expr_id = tcx.hir().get_parent_node(match source {
+            SelfSource::MethodCall(rcvr) => rcvr.hir_id,
+            SelfSource::QPath(ty) => ty.hir_id,
+        });
+        let traits_in_scope = tcx.in_scope_traits(scope_expr_id).unwrap_or(&[]);
+        self_tys.extend(
+            traits_in_scope
+                .iter()
+                .filter(|candidate| has_item(candidate.def_id))
+                .flat_map(|candidate| tcx.all_impls(candidate.def_id))
+                .filter_map(|impl_def_id| {
+                    let self_ty = tcx.impl_trait_ref(impl_def_id)?.self_ty();
+                    // Blanket impls don't name a type to point at.
+                    (!matches!(self_ty.kind(), ty::Param(_))).then(|| self_ty.to_string())
+                }),
+        );
+        if self_tys.is_empty() {
+            return;
+        }
+        self_tys.sort();
+        self_tys.dedup();
+        err.note(&format!(
+            "the {} was found for these types, but `{}` is not known to be one of them\n{}",
+            item_kind,
+            param_ty,
+            bulleted_type_list(&self_tys),
+        ));
+    }
+}
+
+/// Formats `types` as a bulleted list for a note, showing at most a handful of them.
+fn bulleted_type_list(types: &[String]) -> String {
+    // number of type to shows at most.
+    let limit = if types.len() == 5 { 5 } else { 4 };
+    let mut list =
+        types.iter().take(limit).map(|ty| format!("- `{}`", ty)).collect::<Vec<_>>().join("\n");
+    if types.len() > limit {
+        list.push_str(&format!("\nand {} more types", types.len() - limit));
+    }
+    list
 }
 
 #[derive(Copy, Clone, Debug)]
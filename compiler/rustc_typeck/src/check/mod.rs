@@ -64,11 +64,13 @@
 
 pub mod _match;
 mod autoderef;
+pub mod body_passes;
 mod callee;
 pub mod cast;
 mod check;
 mod closure;
 pub mod coercion;
+mod const_cx;
 mod compare_method;
 pub mod demand;
 mod diverges;
@@ -84,6 +86,8 @@
 mod op;
 mod pat;
 mod place_op;
+mod placeholder;
+mod promotion;
 mod regionck;
 mod upvar;
 mod wfcheck;
@@ -91,17 +95,19 @@
 
 use check::{
     check_abi, check_fn, check_impl_item_well_formed, check_item_well_formed, check_mod_item_types,
-    check_trait_item_well_formed,
+    check_mod_type_wf, check_trait_item_well_formed,
 };
 pub use check::{check_item_type, check_wf_new};
+pub use const_cx::ConstCx;
 pub use diverges::Diverges;
 pub use expectation::Expectation;
+pub use expr::ResolvedField;
 pub use fn_ctxt::*;
 pub use inherited::{Inherited, InheritedBuilder};
 
 use crate::astconv::AstConv;
 use crate::check::gather_locals::GatherLocalsVisitor;
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_errors::{pluralize, struct_span_err, Applicability};
 use rustc_hir as hir;
 use rustc_hir::def::Res;
@@ -155,6 +161,16 @@ pub struct LocalTy<'tcx> {
     revealed_ty: Ty<'tcx>,
 }
 
+/// A local binding whose type was written out by the user, either in a `let`
+/// statement or in a function signature. Only simple by-value bindings are
+/// recorded, so `ty` is also the type of the binding itself.
+#[derive(Copy, Clone, Debug)]
+pub struct LocalDecl<'tcx> {
+    ty: LocalTy<'tcx>,
+    /// The span of the type annotation.
+    ty_span: Span,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Needs {
     MutPlace,
@@ -170,8 +186,12 @@ fn maybe_mut_place(m: hir::Mutability) -> Self {
     }
 }
 
+/// Whether the code being checked is in an unsafe context, and which unsafe block or function
+/// makes it one.
 #[derive(Copy, Clone)]
 pub struct UnsafetyState {
+    /// The innermost unsafe block, or the function if there is none or if the function is
+    /// itself unsafe.
     pub def: hir::HirId,
     pub unsafety: hir::Unsafety,
     from_fn: bool,
@@ -200,6 +220,14 @@ pub fn recurse(self, blk: &hir::Block<'_>) -> UnsafetyState {
             }
         }
     }
+
+    /// The unsafe context that already covers `blk`, if it's an unsafe block nested in one.
+    pub fn covering_unsafe_context(self, blk: &hir::Block<'_>) -> Option<hir::HirId> {
+        match (self.unsafety, blk.rules) {
+            (hir::Unsafety::Unsafe, hir::BlockCheckMode::UnsafeBlock(..)) => Some(self.def),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -242,6 +270,7 @@ pub fn provide(providers: &mut Providers) {
         typeck_item_bodies,
         typeck_const_arg,
         typeck,
+        typeck_and_used_trait_imports,
         diagnostic_only_typeck,
         has_typeck_results,
         adt_destructor,
@@ -250,6 +279,7 @@ pub fn provide(providers: &mut Providers) {
         check_trait_item_well_formed,
         check_impl_item_well_formed,
         check_mod_item_types,
+        check_mod_type_wf,
         ..*providers
     };
 }
@@ -318,7 +348,7 @@ fn has_typeck_results(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
 }
 
 fn used_trait_imports(tcx: TyCtxt<'_>, def_id: LocalDefId) -> &FxHashSet<LocalDefId> {
-    &*tcx.typeck(def_id).used_trait_imports
+    tcx.typeck_and_used_trait_imports(def_id).1
 }
 
 /// Inspects the substs of opaque types, replacing any inference variables
@@ -434,41 +464,45 @@ fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
 
 fn typeck_const_arg<'tcx>(
     tcx: TyCtxt<'tcx>,
-    (did, param_did): (LocalDefId, DefId),
-) -> &ty::TypeckResults<'tcx> {
-    let fallback = move || tcx.type_of(param_did);
-    typeck_with_fallback(tcx, did, fallback)
+    key: (LocalDefId, DefId),
+) -> (&ty::TypeckResults<'tcx>, &FxHashSet<LocalDefId>) {
+    body_passes::typeck_const_arg(tcx, key, &[])
 }
 
 fn typeck<'tcx>(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> &ty::TypeckResults<'tcx> {
-    if let Some(param_did) = tcx.opt_const_param_of(def_id) {
-        tcx.typeck_const_arg((def_id, param_did))
-    } else {
-        let fallback = move || tcx.type_of(def_id.to_def_id());
-        typeck_with_fallback(tcx, def_id, fallback)
-    }
+    tcx.typeck_and_used_trait_imports(def_id).0
+}
+
+fn typeck_and_used_trait_imports<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+) -> (&ty::TypeckResults<'tcx>, &FxHashSet<LocalDefId>) {
+    body_passes::typeck_and_used_trait_imports(tcx, def_id, &[])
 }
 
 /// Used only to get `TypeckResults` for type inference during error recovery.
-/// Currently only used for type inference of `static`s and `const`s to avoid type cycle errors.
+/// Currently only used for type inference of `static`s, `const`s and functions with an inferred
+/// return type (`fn foo() -> _`) to avoid type cycle errors.
 fn diagnostic_only_typeck<'tcx>(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> &ty::TypeckResults<'tcx> {
     let fallback = move || {
         let span = tcx.hir().span(tcx.hir().local_def_id_to_hir_id(def_id));
         tcx.ty_error_with_message(span, "diagnostic only typeck table used")
     };
-    typeck_with_fallback(tcx, def_id, fallback)
+    typeck_with_fallback(tcx, def_id, fallback, true, &[]).0
 }
 
 fn typeck_with_fallback<'tcx>(
     tcx: TyCtxt<'tcx>,
     def_id: LocalDefId,
     fallback: impl Fn() -> Ty<'tcx> + 'tcx,
-) -> &'tcx ty::TypeckResults<'tcx> {
+    diagnostic_only: bool,
+    body_passes: &[body_passes::BodyPass],
+) -> (&'tcx ty::TypeckResults<'tcx>, &'tcx FxHashSet<LocalDefId>) {
     // Closures' typeck results come from their outermost function,
     // as they are part of the same "inference environment".
     let outer_def_id = tcx.closure_base_def_id(def_id.to_def_id()).expect_local();
     if outer_def_id != def_id {
-        return tcx.typeck(outer_def_id);
+        return tcx.typeck_and_used_trait_imports(outer_def_id);
     }
 
     let id = tcx.hir().local_def_id_to_hir_id(def_id);
@@ -480,7 +514,8 @@ fn typeck_with_fallback<'tcx>(
     });
     let body = tcx.hir().body(body_id);
 
-    let typeck_results = Inherited::build(tcx, def_id).enter(|inh| {
+    let mut inherited = Inherited::build(tcx, def_id).diagnostic_only(diagnostic_only);
+    let (typeck_results, used_trait_imports) = inherited.enter(|inh| {
         let param_env = tcx.param_env(def_id);
         let fcx = if let (Some(header), Some(decl)) = (fn_header, fn_decl) {
             let fn_sig = if crate::collect::get_infer_ret_ty(&decl.output).is_some() {
@@ -512,10 +547,12 @@ fn typeck_with_fallback<'tcx>(
 
             let fn_sig = fixup_opaque_types(tcx, fn_sig);
 
-            let fcx = check_fn(&inh, param_env, fn_sig, decl, id, body, None).0;
+            let unsafety = UnsafetyState::function(fn_sig.unsafety, id);
+            let fcx = check_fn(&inh, param_env, fn_sig, unsafety, decl, id, body, None).0;
             fcx
         } else {
-            let fcx = FnCtxt::new(&inh, param_env, body.value.hir_id);
+            let mut fcx = FnCtxt::new(&inh, param_env, body.value.hir_id);
+            fcx.const_cx = ConstCx::for_body(tcx, body_id);
             let expected_type = body_ty
                 .and_then(|ty| match ty.kind {
                     hir::TyKind::Infer => Some(<dyn AstConv<'_>>::ast_ty_to_ty(&fcx, ty)),
@@ -577,12 +614,23 @@ fn typeck_with_fallback<'tcx>(
         fcx.select_obligations_where_possible(false, |_| {});
         let mut fallback_has_occurred = false;
 
+        // Selection runs to a fixed point and each pending obligation remembers the inference
+        // variables it is stalled on, so selecting again is only useful if fallback unified some
+        // variable in between. Large bodies have lots of stalled obligations, so skip the
+        // rounds that can't make progress rather than re-walking all of them.
+        let fallback_round = |mode| {
+            let mut progress = false;
+            for ty in &fcx.unsolved_variables() {
+                progress |= fcx.fallback_if_possible(ty, mode);
+            }
+            progress
+        };
+
         // We do fallback in two passes, to try to generate
         // better error messages.
         // The first time, we do *not* replace opaque types.
-        for ty in &fcx.unsolved_variables() {
-            fallback_has_occurred |= fcx.fallback_if_possible(ty, FallbackMode::NoOpaque);
-        }
+        let progress = fallback_round(FallbackMode::NoOpaque);
+        fallback_has_occurred |= progress;
         // We now see if we can make progress. This might
         // cause us to unify inference variables for opaque types,
         // since we may have unified some other type variables
@@ -606,21 +654,29 @@ fn typeck_with_fallback<'tcx>(
         // If we had tried to fallback the opaque inference variable to `MyType`,
         // we will generate a confusing type-check error that does not explicitly
         // refer to opaque types.
-        fcx.select_obligations_where_possible(fallback_has_occurred, |_| {});
+        if progress {
+            fcx.select_obligations_where_possible(fallback_has_occurred, |_| {});
+        }
+
+        // Before opaque type variables fall back to their opaque type, point out
+        // `let x: impl Trait` bindings that never got a concrete type.
+        fcx.report_unconstrained_binding_opaque_types();
 
         // We now run fallback again, but this time we allow it to replace
         // unconstrained opaque type variables, in addition to performing
         // other kinds of fallback.
-        for ty in &fcx.unsolved_variables() {
-            fallback_has_occurred |= fcx.fallback_if_possible(ty, FallbackMode::All);
-        }
+        let progress = fallback_round(FallbackMode::All);
+        fallback_has_occurred |= progress;
 
         // See if we can make any more progress.
-        fcx.select_obligations_where_possible(fallback_has_occurred, |_| {});
+        if progress {
+            fcx.select_obligations_where_possible(fallback_has_occurred, |_| {});
+        }
 
         // Even though coercion casts provide type hints, we check casts after fallback for
         // backwards compatibility. This makes fallback a stronger type hint than a cast coercion.
         fcx.check_casts();
+        fcx.check_repeat_exprs();
 
         // Closure and generator analysis may run after fallback
         // because they don't constrain other type variables.
@@ -628,12 +684,20 @@ fn typeck_with_fallback<'tcx>(
         assert!(fcx.deferred_call_resolutions.borrow().is_empty());
         fcx.resolve_generator_interiors(def_id.to_def_id());
 
-        for (ty, span, code) in fcx.deferred_sized_obligations.borrow_mut().drain(..) {
-            let ty = fcx.normalize_ty(span, ty);
+        // The same type usually comes up with several cause codes, so only normalize it once.
+        let mut normalized_tys = FxHashMap::default();
+        for ((ty, code), span) in fcx.deferred_sized_obligations.borrow_mut().drain(..) {
+            let ty = *normalized_tys.entry(ty).or_insert_with(|| fcx.normalize_ty(span, ty));
             fcx.require_type_is_sized(ty, span, code);
         }
 
         fcx.select_all_obligations_or_error();
+        fcx.record_normalization_cache_stats();
+
+        if tcx.sess.opts.debugging_opts.typeck_stats && !diagnostic_only {
+            let body = tcx.def_path_str(def_id.to_def_id());
+            tcx.sess.code_stats.record_infer_var_stats(body, fcx.infer_var_stats());
+        }
 
         if fn_decl.is_some() {
             fcx.regionck_fn(id, body);
@@ -641,14 +705,18 @@ fn typeck_with_fallback<'tcx>(
             fcx.regionck_expr(body);
         }
 
-        fcx.resolve_type_vars_in_body(body)
+        let (typeck_results, used_trait_imports) = fcx.resolve_type_vars_in_body(body);
+        for pass in body_passes {
+            pass(&fcx, body, typeck_results);
+        }
+        (typeck_results, used_trait_imports)
     });
 
     // Consistency check our TypeckResults instance can hold all ItemLocalIds
     // it will need to hold.
     assert_eq!(typeck_results.hir_owner, id.owner);
 
-    typeck_results
+    (typeck_results, used_trait_imports)
 }
 
 /// When `check_fn` is invoked on a generator (i.e., a body that
@@ -822,6 +890,7 @@ fn report_forbidden_specialization(
 
 fn missing_items_err(
     tcx: TyCtxt<'_>,
+    impl_id: LocalDefId,
     impl_span: Span,
     missing_items: &[ty::AssocItem],
     full_impl_span: Span,
@@ -852,7 +921,7 @@ fn missing_items_err(
     let padding: String = " ".repeat(indentation);
 
     for trait_item in missing_items {
-        let snippet = suggestion_signature(&trait_item, tcx);
+        let snippet = suggestion_signature(&trait_item, tcx, impl_id);
         let code = format!("{}{}\n{}", padding, snippet, padding);
         let msg = format!("implement the missing item: `{}`", snippet);
         let appl = Applicability::HasPlaceholders;
@@ -866,12 +935,59 @@ fn missing_items_err(
     err.emit();
 }
 
+/// Reports an impl that overrides none of the methods in a `#[rustc_must_implement_one_of]`
+/// group of its trait.
+fn missing_items_must_implement_one_of_err(
+    tcx: TyCtxt<'_>,
+    trait_def_id: DefId,
+    impl_span: Span,
+    group: &[Ident],
+) {
+    let missing_items_msg =
+        group.iter().map(|ident| ident.to_string()).collect::<Vec<_>>().join("`, `");
+
+    let mut err = struct_span_err!(
+        tcx.sess,
+        impl_span,
+        E0046,
+        "not all trait items implemented, missing one of: `{}`",
+        missing_items_msg
+    );
+    err.span_label(impl_span, format!("missing one of `{}` in implementation", missing_items_msg));
+
+    let associated_items = tcx.associated_items(trait_def_id);
+    for &ident in group {
+        let trait_item = associated_items.find_by_name_and_kind(
+            tcx,
+            ident,
+            ty::AssocKind::Fn,
+            trait_def_id,
+        );
+        if let Some(span) = trait_item.and_then(|item| tcx.hir().span_if_local(item.def_id)) {
+            err.span_label(span, format!("`{}` from trait", ident));
+        }
+    }
+
+    let annotation = tcx.get_attrs(trait_def_id).iter().find(|attr| {
+        attr.has_name(sym::rustc_must_implement_one_of) && attr.span.contains(group[0].span)
+    });
+    if let Some(annotation) = annotation {
+        err.span_note(
+            annotation.span,
+            "the default bodies of these methods are defined in terms of each other, \
+             so at least one of them has to be implemented",
+        );
+    }
+    err.emit();
+}
+
 /// Resugar `ty::GenericPredicates` in a way suitable to be used in structured suggestions.
 fn bounds_from_generic_predicates<'tcx>(
     tcx: TyCtxt<'tcx>,
     predicates: ty::GenericPredicates<'tcx>,
 ) -> (String, String) {
-    let mut types: FxHashMap<Ty<'tcx>, Vec<DefId>> = FxHashMap::default();
+    // Keep the bounds in the order they were declared in, so that the suggestion is stable.
+    let mut types: FxIndexMap<Ty<'tcx>, Vec<DefId>> = FxIndexMap::default();
     let mut projections = vec![];
     for (predicate, _) in predicates.predicates {
         debug!("predicate {:?}", predicate);
@@ -996,7 +1112,7 @@ fn fn_sig_suggestion<'tcx>(
 /// Return placeholder code for the given associated item.
 /// Similar to `ty::AssocItem::suggestion`, but appropriate for use as the code snippet of a
 /// structured suggestion.
-fn suggestion_signature(assoc: &ty::AssocItem, tcx: TyCtxt<'_>) -> String {
+fn suggestion_signature(assoc: &ty::AssocItem, tcx: TyCtxt<'_>, impl_id: LocalDefId) -> String {
     match assoc.kind {
         ty::AssocKind::Fn => {
             // We skip the binder here because the binder would deanonymize all
@@ -1014,7 +1130,9 @@ fn suggestion_signature(assoc: &ty::AssocItem, tcx: TyCtxt<'_>) -> String {
         ty::AssocKind::Type => format!("type {} = Type;", assoc.ident),
         ty::AssocKind::Const => {
             let ty = tcx.type_of(assoc.def_id);
-            let val = expr::ty_kind_suggestion(ty).unwrap_or("value");
+            let module = tcx.parent_module_from_def_id(impl_id).to_def_id();
+            let val = placeholder::placeholder_expr(tcx, tcx.param_env(assoc.def_id), module, ty)
+                .unwrap_or_else(|| "value".to_string());
             format!("const {}: {} = {};", assoc.ident, ty, val)
         }
     }
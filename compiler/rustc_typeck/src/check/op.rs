@@ -244,12 +244,12 @@ fn check_overloaded_binop(
                         // some cases applied on the RHS, on top of which we need
                         // to autoref, which is not allowed by apply_adjustments.
                         // self.apply_adjustments(rhs_expr, vec![autoref]);
-                        self.typeck_results
-                            .borrow_mut()
-                            .adjustments_mut()
-                            .entry(rhs_expr.hir_id)
-                            .or_default()
-                            .push(autoref);
+                        let mut typeck_results = self.typeck_results.borrow_mut();
+                        let mut adjustments = typeck_results.adjustments_mut();
+                        let previous = adjustments.get(rhs_expr.hir_id).map_or(&[][..], |a| &a[..]);
+                        let adjusted =
+                            self.tcx.mk_adjustments(previous.iter().copied().chain(Some(autoref)));
+                        adjustments.insert(rhs_expr.hir_id, adjusted);
                     }
                 }
                 self.write_method_call(expr.hir_id, method);
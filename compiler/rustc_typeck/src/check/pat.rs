@@ -1,7 +1,7 @@
 use crate::check::FnCtxt;
 use rustc_ast as ast;
 
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
 use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, DefKind, Res};
@@ -9,6 +9,7 @@
 use rustc_hir::{HirId, Pat, PatKind};
 use rustc_infer::infer;
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
+use rustc_middle::mir::UnsafetyViolationDetails;
 use rustc_middle::ty::subst::GenericArg;
 use rustc_middle::ty::{self, Adt, BindingMode, Ty, TypeFoldable};
 use rustc_span::hygiene::DesugaringKind;
@@ -187,6 +188,10 @@ fn check_pat(
                 for pat in pats {
                     self.check_pat(pat, expected, def_bm, TopInfo { parent_pat, ..ti });
                 }
+                // Nested or-patterns are checked along with the outermost one.
+                if !self.is_nested_or_pat(pat) {
+                    self.check_or_pat_bindings_eq_ty(pat, ti);
+                }
                 expected
             }
             PatKind::Tuple(elements, ddpos) => {
@@ -332,6 +337,7 @@ fn calc_adjust_mode(&self, pat: &'tcx Pat<'tcx>, opt_path_res: Option<Res>) -> A
 
     /// Peel off as many immediately nested `& mut?` from the expected type as possible
     /// and return the new expected type and binding default binding mode.
+    /// With `box_patterns`, `Box`es are peeled off as well, except for `box` patterns.
     /// The adjustments vector, if non-empty is stored in a table.
     fn peel_off_references(
         &self,
@@ -349,25 +355,38 @@ fn peel_off_references(
         // type into the adjustments vector.
         //
         // See the examples in `ui/match-defbm*.rs`.
+        let peel_boxes = self.tcx.features().box_patterns && !matches!(pat.kind, PatKind::Box(_));
         let mut pat_adjustments = vec![];
-        while let ty::Ref(_, inner_ty, inner_mutability) = *expected.kind() {
+        loop {
             debug!("inspecting {:?}", expected);
-
-            debug!("current discriminant is Ref, inserting implicit deref");
-            // Preserve the reference type. We'll need it later during THIR lowering.
-            pat_adjustments.push(expected);
-
-            expected = inner_ty;
-            def_bm = ty::BindByReference(match def_bm {
-                // If default binding mode is by value, make it `ref` or `ref mut`
-                // (depending on whether we observe `&` or `&mut`).
-                ty::BindByValue(_) |
-                // When `ref mut`, stay a `ref mut` (on `&mut`) or downgrade to `ref` (on `&`).
-                ty::BindByReference(hir::Mutability::Mut) => inner_mutability,
-                // Once a `ref`, always a `ref`.
-                // This is because a `& &mut` cannot mutate the underlying value.
-                ty::BindByReference(m @ hir::Mutability::Not) => m,
-            });
+            match *expected.kind() {
+                ty::Ref(_, inner_ty, inner_mutability) => {
+                    debug!("current discriminant is Ref, inserting implicit deref");
+                    // Preserve the reference type. We'll need it later during THIR lowering.
+                    pat_adjustments.push(expected);
+
+                    expected = inner_ty;
+                    def_bm = ty::BindByReference(match def_bm {
+                        // If default binding mode is by value, make it `ref` or `ref mut`
+                        // (depending on whether we observe `&` or `&mut`).
+                        ty::BindByValue(_) |
+                        // When `ref mut`, stay a `ref mut` (on `&mut`) or downgrade to `ref`
+                        // (on `&`).
+                        ty::BindByReference(hir::Mutability::Mut) => inner_mutability,
+                        // Once a `ref`, always a `ref`.
+                        // This is because a `& &mut` cannot mutate the underlying value.
+                        ty::BindByReference(m @ hir::Mutability::Not) => m,
+                    });
+                }
+                // The box owns its contents, so it's dereferenced like with an implicit `box`
+                // pattern, which doesn't change the default binding mode.
+                ty::Adt(def, _) if peel_boxes && def.is_box() => {
+                    debug!("current discriminant is Box, inserting implicit deref");
+                    pat_adjustments.push(expected);
+                    expected = expected.boxed_ty();
+                }
+                _ => break,
+            }
         }
 
         if !pat_adjustments.is_empty() {
@@ -578,12 +597,6 @@ fn check_pat_ident(
         };
         self.demand_eqtype_pat(pat.span, eq_ty, local_ty, ti);
 
-        // If there are multiple arms, make sure they all agree on
-        // what the type of the binding `x` ought to be.
-        if var_id != pat.hir_id {
-            self.check_binding_alt_eq_ty(pat.span, var_id, local_ty, ti);
-        }
-
         if let Some(p) = sub {
             self.check_pat(&p, expected, def_bm, TopInfo { parent_pat: Some(&pat), ..ti });
         }
@@ -591,28 +604,100 @@ fn check_pat_ident(
         local_ty
     }
 
-    fn check_binding_alt_eq_ty(&self, span: Span, var_id: HirId, ty: Ty<'tcx>, ti: TopInfo<'tcx>) {
-        let var_ty = self.local_ty(span, var_id).decl_ty;
-        if let Some(mut err) = self.demand_eqtype_pat_diag(span, var_ty, ty, ti) {
-            let hir = self.tcx.hir();
-            let var_ty = self.resolve_vars_with_obligations(var_ty);
-            let msg = format!("first introduced with type `{}` here", var_ty);
-            err.span_label(hir.span(var_id), msg);
-            let in_match = hir.parent_iter(var_id).any(|(_, n)| {
-                matches!(
-                    n,
-                    hir::Node::Expr(hir::Expr {
-                        kind: hir::ExprKind::Match(.., hir::MatchSource::Normal),
-                        ..
-                    })
-                )
-            });
-            let pre = if in_match { "in the same arm, " } else { "" };
-            err.note(&format!("{}a binding must have the same type in all alternatives", pre));
+    fn is_nested_or_pat(&self, pat: &Pat<'_>) -> bool {
+        self.tcx
+            .hir()
+            .parent_iter(pat.hir_id)
+            .take_while(|(_, node)| matches!(node, hir::Node::Pat(_)))
+            .any(|(_, node)| matches!(node, hir::Node::Pat(Pat { kind: PatKind::Or(_), .. })))
+    }
+
+    /// Makes sure that all the alternatives of the outermost or-pattern `pat` agree on the type
+    /// of each binding, reporting every variable at most once.
+    fn check_or_pat_bindings_eq_ty(&self, pat: &Pat<'_>, ti: TopInfo<'tcx>) {
+        // All bindings in source order, along with the variable they bind. The first binding of
+        // each variable is the variable itself.
+        let mut bindings = Vec::new();
+        pat.walk_always(|p| {
+            if let PatKind::Binding(_, var_id, ident, _) = p.kind {
+                bindings.push((var_id, p.hir_id, p.span, ident));
+            }
+        });
+
+        let mut errors = FxIndexMap::default();
+        for &(var_id, hir_id, span, _) in &bindings {
+            if hir_id == var_id {
+                continue;
+            }
+            let var_ty = self.local_ty(span, var_id).decl_ty;
+            let ty = self.local_ty(span, hir_id).decl_ty;
+            if let Some(mut err) = self.demand_eqtype_pat_diag(span, var_ty, ty, ti) {
+                if errors.contains_key(&var_id) {
+                    // Already reported, this binding is listed in that error.
+                    err.cancel();
+                } else {
+                    errors.insert(var_id, (err, hir_id));
+                }
+            }
+        }
+
+        for (var_id, (mut err, diverging)) in errors {
+            let var_bindings = bindings
+                .iter()
+                .filter(|&&(id, ..)| id == var_id)
+                .map(|&(_, hir_id, span, ident)| (hir_id, span, ident))
+                .collect::<Vec<_>>();
+            self.note_binding_alt_tys(&mut err, var_id, diverging, &var_bindings);
             err.emit();
         }
     }
 
+    fn note_binding_alt_tys(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        var_id: HirId,
+        diverging: HirId,
+        bindings: &[(HirId, Span, Ident)],
+    ) {
+        let hir = self.tcx.hir();
+        let var_span = hir.span(var_id);
+        let var_ty = self.resolve_vars_with_obligations(self.local_ty(var_span, var_id).decl_ty);
+        let msg = format!("first introduced with type `{}` here", var_ty);
+        err.span_label(var_span, msg);
+
+        // With more than two bindings, the labels only show two of them, so list them all.
+        if let [(_, _, ident), _, _, ..] = bindings {
+            let mut table =
+                format!("`{}` is bound with these types across the alternatives:", ident);
+            for (i, &(hir_id, span, _)) in bindings.iter().enumerate() {
+                let ty = self.resolve_vars_with_obligations(self.local_ty(span, hir_id).decl_ty);
+                let mode = match self.typeck_results.borrow().pat_binding_modes().get(hir_id) {
+                    Some(ty::BindByValue(hir::Mutability::Not)) | None => "by value",
+                    Some(ty::BindByValue(hir::Mutability::Mut)) => "by mutable value",
+                    Some(ty::BindByReference(hir::Mutability::Not)) => "by reference",
+                    Some(ty::BindByReference(hir::Mutability::Mut)) => "by mutable reference",
+                };
+                table.push_str(&format!("\n{}. `{}`, {}", i + 1, ty, mode));
+                if hir_id == diverging {
+                    table.push_str(" (first divergence)");
+                }
+            }
+            err.note(&table);
+        }
+
+        let in_match = hir.parent_iter(var_id).any(|(_, n)| {
+            matches!(
+                n,
+                hir::Node::Expr(hir::Expr {
+                    kind: hir::ExprKind::Match(.., hir::MatchSource::Normal),
+                    ..
+                })
+            )
+        });
+        let pre = if in_match { "in the same arm, " } else { "" };
+        err.note(&format!("{}a binding must have the same type in all alternatives", pre));
+    }
+
     fn borrow_pat_suggestion(
         &self,
         err: &mut DiagnosticBuilder<'_>,
@@ -652,6 +737,27 @@ fn borrow_pat_suggestion(
         }
     }
 
+    fn unbox_pat_suggestion(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        span: Span,
+        inner: &Pat<'_>,
+        expected: Ty<'tcx>,
+    ) {
+        // Values of these types can't be matched without some indirection.
+        if let ty::Dynamic(..) | ty::Str = self.resolve_vars_with_obligations(expected).kind() {
+            return;
+        }
+        if let Ok(snippet) = self.tcx.sess.source_map().span_to_snippet(inner.span) {
+            err.span_suggestion(
+                span,
+                "you can probably remove the `box`",
+                snippet,
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
     pub fn check_dereferenceable(&self, span: Span, expected: Ty<'tcx>, inner: &Pat<'_>) -> bool {
         if let PatKind::Binding(..) = inner.kind {
             if let Some(mt) = self.shallow_resolve(expected).builtin_deref(true) {
@@ -917,6 +1023,12 @@ fn check_pat_tuple_struct(
         // Type-check the path.
         let (pat_ty, res) =
             self.instantiate_value_path(segments, opt_ty, res, pat.span, pat.hir_id);
+        if let ty::Error(_) = pat_ty.kind() {
+            // `instantiate_value_path` already reported why this isn't a constructor, like for
+            // a `Self` constructor in an impl for an enum, the same way as in expressions.
+            on_error();
+            return pat_ty;
+        }
         if !pat_ty.is_fn() {
             report_unexpected_res(res);
             return tcx.ty_error();
@@ -1211,6 +1323,10 @@ fn check_struct_pat_fields(
                 }
             };
 
+            // Matching on a union field reads it, unless the field is ignored.
+            if adt.is_union() && !matches!(field.pat.kind, PatKind::Wild) {
+                self.record_unsafe_op(field.hir_id, UnsafetyViolationDetails::AccessToUnionField);
+            }
             self.check_pat(&field.pat, field_ty, def_bm, TopInfo { parent_pat: Some(&pat), ..ti });
         }
 
@@ -1669,7 +1785,10 @@ fn check_pat_box(
                 span: inner.span,
             });
             let box_ty = tcx.mk_box(inner_ty);
-            self.demand_eqtype_pat(span, expected, box_ty, ti);
+            if let Some(mut err) = self.demand_eqtype_pat_diag(span, expected, box_ty, ti) {
+                self.unbox_pat_suggestion(&mut err, span, inner, expected);
+                err.emit();
+            }
             (box_ty, inner_ty)
         } else {
             let err = tcx.ty_error();
@@ -1,12 +1,13 @@
-use crate::check::method::MethodCallee;
+use crate::check::method::{probe, MethodCallee};
 use crate::check::{has_expected_num_generic_args, FnCtxt, PlaceOp};
 use rustc_hir as hir;
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
-use rustc_infer::infer::InferOk;
+use rustc_infer::infer::{self, InferOk};
 use rustc_middle::ty::adjustment::{Adjust, Adjustment, OverloadedDeref, PointerCast};
 use rustc_middle::ty::adjustment::{AllowTwoPhase, AutoBorrow, AutoBorrowMutability};
-use rustc_middle::ty::{self, Ty};
-use rustc_span::symbol::{sym, Ident};
+use rustc_middle::ty::subst::Subst;
+use rustc_middle::ty::{self, IndexMutAlternatives, Ty};
+use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::Span;
 use rustc_trait_selection::autoderef::Autoderef;
 use std::slice;
@@ -259,7 +260,8 @@ pub fn convert_place_derefs_to_mutable(&self, expr: &hir::Expr<'_>) {
             // typeck results borrowed during (`deref_mut`) method resolution.
             let previous_adjustments =
                 self.typeck_results.borrow_mut().adjustments_mut().remove(expr.hir_id);
-            if let Some(mut adjustments) = previous_adjustments {
+            if let Some(adjustments) = previous_adjustments {
+                let mut adjustments = adjustments.to_vec();
                 for adjustment in &mut adjustments {
                     if let Adjust::Deref(Some(ref mut deref)) = adjustment.kind {
                         if let Some(ok) = self.try_mutable_overloaded_place_op(
@@ -291,6 +293,7 @@ pub fn convert_place_derefs_to_mutable(&self, expr: &hir::Expr<'_>) {
                     }
                     source = adjustment.target;
                 }
+                let adjustments = self.tcx.intern_adjustments(&adjustments);
                 self.typeck_results.borrow_mut().adjustments_mut().insert(expr.hir_id, adjustments);
             }
 
@@ -350,8 +353,13 @@ fn convert_place_op_to_mutable(
         let method = match method {
             Some(ok) => self.register_infer_ok_obligations(ok),
             // Couldn't find the mutable variant of the place op, keep the
-            // current, immutable version.
-            None => return,
+            // current, immutable version. Borrowck reports the mutable use.
+            None => {
+                if let Some(&index_ty) = arg_tys.first() {
+                    self.record_index_mut_alternatives(expr, base_expr, base_ty, index_ty);
+                }
+                return;
+            }
         };
         debug!("convert_place_op_to_mutable: method={:?}", method);
         self.write_method_call(expr.hir_id, method);
@@ -365,19 +373,24 @@ fn convert_place_op_to_mutable(
         // Convert the autoref in the base expr to mutable with the correct
         // region and mutability.
         let base_expr_ty = self.node_ty(base_expr.hir_id);
-        if let Some(adjustments) =
-            self.typeck_results.borrow_mut().adjustments_mut().get_mut(base_expr.hir_id)
-        {
+        let previous_adjustments =
+            self.typeck_results.borrow().adjustments().get(base_expr.hir_id).copied();
+        if let Some(adjustments) = previous_adjustments {
+            let mut adjustments = adjustments.to_vec();
             let mut source = base_expr_ty;
             for adjustment in &mut adjustments[..] {
                 if let Adjust::Borrow(AutoBorrow::Ref(..)) = adjustment.kind {
                     debug!("convert_place_op_to_mutable: converting autoref {:?}", adjustment);
                     let mutbl = AutoBorrowMutability::Mut {
-                        // Deref/indexing can be desugared to a method call,
-                        // so maybe we could use two-phase here.
-                        // See the documentation of AllowTwoPhase for why that's
-                        // not the case today.
-                        allow_two_phase_borrow: AllowTwoPhase::No,
+                        // Indexing desugars to a call to `index_mut` whose index argument
+                        // is evaluated after the base has been borrowed, just like the
+                        // arguments of a method call, so allow a two-phase borrow of the
+                        // base there. Dereferencing has no other operand, so it doesn't
+                        // need one.
+                        allow_two_phase_borrow: match op {
+                            PlaceOp::Index => AllowTwoPhase::Yes,
+                            PlaceOp::Deref => AllowTwoPhase::No,
+                        },
                     };
                     adjustment.kind = Adjust::Borrow(AutoBorrow::Ref(region, mutbl));
                     adjustment.target =
@@ -392,6 +405,106 @@ fn convert_place_op_to_mutable(
             {
                 *target = method.sig.inputs()[0];
             }
+
+            let adjustments = self.tcx.intern_adjustments(&adjustments);
+            self.typeck_results
+                .borrow_mut()
+                .adjustments_mut()
+                .insert(base_expr.hir_id, adjustments);
+        }
+    }
+
+    /// Records the inherent methods of `base_ty` that could modify the value of the indexing
+    /// `expr` in its stead, since `base_ty` doesn't implement `IndexMut`. These are the
+    /// `get_mut`, `insert` and `entry` methods of maps like `HashMap` and `BTreeMap`, which are
+    /// only considered when they accept `index_ty` as the key.
+    fn record_index_mut_alternatives(
+        &self,
+        expr: &hir::Expr<'_>,
+        base_expr: &hir::Expr<'_>,
+        base_ty: Ty<'tcx>,
+        index_ty: Ty<'tcx>,
+    ) {
+        if base_ty.references_error() || index_ty.references_error() {
+            return;
+        }
+        let has_method = |name, num_args, expected_output: &dyn Fn(Ty<'tcx>) -> bool| {
+            self.has_keyed_method(
+                expr.span,
+                base_expr,
+                base_ty,
+                index_ty,
+                name,
+                num_args,
+                expected_output,
+            )
+        };
+        let alternatives = IndexMutAlternatives {
+            get_mut: has_method(sym::get_mut, 1, &|output| match *output.kind() {
+                ty::Adt(def, substs) if self.tcx.is_diagnostic_item(sym::option_type, def.did) => {
+                    matches!(substs.type_at(0).kind(), ty::Ref(_, _, hir::Mutability::Mut))
+                }
+                _ => false,
+            }),
+            insert: has_method(sym::insert, 2, &|_| true),
+            entry: has_method(sym::entry, 1, &|_| true),
+        };
+        if alternatives.get_mut || alternatives.insert || alternatives.entry {
+            debug!("record_index_mut_alternatives({:?}) = {:?}", expr, alternatives);
+            self.typeck_results
+                .borrow_mut()
+                .index_mut_alternatives_mut()
+                .insert(expr.hir_id, alternatives);
         }
     }
+
+    /// Whether `base_ty` has an inherent method `name` that takes `&mut self`, a key of type
+    /// `index_ty` and `num_args - 1` other arguments, and whose output type (resolved as far as
+    /// possible) satisfies `expected_output`.
+    fn has_keyed_method(
+        &self,
+        span: Span,
+        base_expr: &hir::Expr<'_>,
+        base_ty: Ty<'tcx>,
+        index_ty: Ty<'tcx>,
+        name: Symbol,
+        num_args: usize,
+        expected_output: &dyn Fn(Ty<'tcx>) -> bool,
+    ) -> bool {
+        let pick = self.probe_for_name(
+            span,
+            probe::Mode::MethodCall,
+            Ident::with_dummy_span(name),
+            probe::IsSuggestion(true),
+            base_ty,
+            base_expr.hir_id,
+            probe::ProbeScope::TraitsInScope,
+        );
+        let pick = match pick {
+            Ok(pick) if pick.kind == probe::PickKind::InherentImplPick && pick.autoderefs == 0 => {
+                pick
+            }
+            _ => return false,
+        };
+
+        let tcx = self.tcx;
+        let method_def_id = pick.item.def_id;
+        self.probe(|_| {
+            let substs = self.fresh_substs_for_item(span, method_def_id);
+            let impl_ty = tcx.type_of(pick.item.container.id()).subst(tcx, substs);
+            let sig = tcx.fn_sig(method_def_id).subst(tcx, substs);
+            let sig = self.replace_bound_vars_with_fresh_vars(span, infer::FnCall, sig).0;
+            let cause = self.misc(span);
+            let at = self.at(&cause, self.param_env);
+            match sig.inputs() {
+                [self_ty, key_ty, ..] if sig.inputs().len() == num_args + 1 => {
+                    matches!(self_ty.kind(), ty::Ref(_, _, hir::Mutability::Mut))
+                        && at.eq(impl_ty, base_ty).is_ok()
+                        && at.eq(*key_ty, index_ty).is_ok()
+                        && expected_output(self.resolve_vars_if_possible(sig.output()))
+                }
+                _ => false,
+            }
+        })
+    }
 }
@@ -0,0 +1,156 @@
+//! Synthesis of placeholder expressions of a given type.
+//!
+//! Several structured suggestions need "some expression of type `T`" to be syntactically
+//! complete: the value of a missing associated constant, the tail expression of a function
+//! that falls off its end, the `else` branch of an `if` used as a value, and so on. This module
+//! builds a plausible, compilable (where possible) expression from the type alone.
+//!
+//! The produced expressions are always meant to be used with `Applicability::HasPlaceholders`,
+//! as the user is expected to replace them with a meaningful value. Most of them typecheck, but
+//! types without a value we can build, like type parameters or ADTs with private fields, get a
+//! bare `value` identifier that only marks the spot to fill in.
+
+use rustc_hir::def::CtorKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::subst::SubstsRef;
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeFoldable};
+use rustc_span::symbol::sym;
+
+/// How deeply nested ADT constructors are synthesized before giving up, which also keeps
+/// recursive types like `struct List { next: Option<Box<List>> }` from looping.
+const MAX_DEPTH: usize = 4;
+
+/// Returns a placeholder expression of type `ty`, suitable for use in a structured suggestion
+/// emitted from within `module`.
+///
+/// Common standard library types get their idiomatic "empty" value (`None`, `Ok(())`,
+/// `vec![]`, ...), types implementing `Default` fall back to `Default::default()`, and other
+/// ADTs are built through a constructor that is accessible from `module`. Returns `None` for
+/// types that have no meaningful value to suggest, like `!`.
+pub(super) fn placeholder_expr<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    module: DefId,
+    ty: Ty<'tcx>,
+) -> Option<String> {
+    PlaceholderExprBuilder { tcx, param_env, module }.expr_for(ty, 0)
+}
+
+struct PlaceholderExprBuilder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    module: DefId,
+}
+
+impl<'tcx> PlaceholderExprBuilder<'tcx> {
+    fn expr_for(&self, ty: Ty<'tcx>, depth: usize) -> Option<String> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let tcx = self.tcx;
+        Some(match ty.kind() {
+            ty::Bool => "true".to_string(),
+            ty::Char => "'a'".to_string(),
+            ty::Int(_) | ty::Uint(_) | ty::Infer(ty::IntVar(_)) => "42".to_string(),
+            ty::Float(_) | ty::Infer(ty::FloatVar(_)) => "3.14159".to_string(),
+            ty::Error(_) | ty::Never => return None,
+            ty::Tuple(tys) => format!(
+                "({}{})",
+                tys.iter()
+                    .map(|ty| self.expr_for(ty.expect_ty(), depth + 1))
+                    .collect::<Option<Vec<_>>>()?
+                    .join(", "),
+                if tys.len() == 1 { "," } else { "" },
+            ),
+            ty::Ref(_, inner, _) if inner.is_str() => "\"\"".to_string(),
+            ty::Ref(_, inner, mutbl) if matches!(inner.kind(), ty::Slice(_)) => {
+                format!("&{}[]", mutbl.prefix_str())
+            }
+            ty::Ref(_, inner, mutbl) => {
+                format!("&{}{}", mutbl.prefix_str(), self.expr_for(inner, depth + 1)?)
+            }
+            ty::Adt(def, _) if tcx.is_diagnostic_item(sym::option_type, def.did) => {
+                "None".to_string()
+            }
+            ty::Adt(def, substs) if tcx.is_diagnostic_item(sym::result_type, def.did) => {
+                format!("Ok({})", self.expr_for(substs.type_at(0), depth + 1)?)
+            }
+            ty::Adt(def, _) if tcx.is_diagnostic_item(sym::vec_type, def.did) => {
+                "vec![]".to_string()
+            }
+            ty::Adt(def, _) if tcx.is_diagnostic_item(sym::string_type, def.did) => {
+                "String::new()".to_string()
+            }
+            ty::Adt(def, substs) if def.is_box() => {
+                format!("Box::new({})", self.expr_for(substs.type_at(0), depth + 1)?)
+            }
+            _ if self.implements_default(ty) => "Default::default()".to_string(),
+            ty::Adt(def, substs) => match self.adt_ctor_expr(def, substs, depth) {
+                Some(expr) => expr,
+                None => "value".to_string(),
+            },
+            _ => "value".to_string(),
+        })
+    }
+
+    /// Builds a constructor expression for `def`, preferring the variant with the fewest
+    /// fields, when all of the fields of that variant are visible from `self.module`.
+    fn adt_ctor_expr(
+        &self,
+        def: &'tcx ty::AdtDef,
+        substs: SubstsRef<'tcx>,
+        depth: usize,
+    ) -> Option<String> {
+        let tcx = self.tcx;
+        if def.is_union() || (!def.did.is_local() && def.is_variant_list_non_exhaustive()) {
+            return None;
+        }
+        let mut variants = def
+            .variants
+            .iter()
+            .filter(|variant| {
+                (def.did.is_local() || !variant.is_field_list_non_exhaustive())
+                    && variant
+                        .fields
+                        .iter()
+                        .all(|field| field.vis.is_accessible_from(self.module, tcx))
+            })
+            .collect::<Vec<_>>();
+        variants.sort_by_key(|variant| variant.fields.len());
+        let variant = variants.into_iter().next()?;
+
+        let path = tcx.def_path_str(variant.def_id);
+        let fields = variant
+            .fields
+            .iter()
+            .map(|field| self.expr_for(field.ty(tcx, substs), depth + 1))
+            .collect::<Option<Vec<_>>>()?;
+        Some(match variant.ctor_kind {
+            CtorKind::Const => path,
+            CtorKind::Fn => format!("{}({})", path, fields.join(", ")),
+            CtorKind::Fictive if fields.is_empty() => format!("{} {{}}", path),
+            CtorKind::Fictive => format!(
+                "{} {{ {} }}",
+                path,
+                variant
+                    .fields
+                    .iter()
+                    .zip(fields)
+                    .map(|(field, expr)| format!("{}: {}", field.ident, expr))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        })
+    }
+
+    fn implements_default(&self, ty: Ty<'tcx>) -> bool {
+        // `type_implements_trait` can't deal with inference variables, so be conservative.
+        if ty.has_infer_types_or_consts() {
+            return false;
+        }
+        let ty = self.tcx.erase_regions(ty);
+        self.tcx.get_diagnostic_item(sym::Default).map_or(false, |default_trait| {
+            self.tcx.type_implements_trait((default_trait, ty, ty::List::empty(), self.param_env))
+        })
+    }
+}
@@ -0,0 +1,186 @@
+//! Finds the rvalues that type checking already knows to be eligible for promotion to a
+//! `'static` constant, see `ty::PromotionCandidacy`.
+//!
+//! This is a conservative approximation of what MIR promotion accepts: an expression is only
+//! recorded if all of its operands are, and anything involving overloaded operators, statics,
+//! locals or operations that may panic is left out.
+
+use rustc_hir as hir;
+use rustc_hir::def::{CtorKind, DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_hir::ItemLocalMap;
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::adjustment::Adjust;
+use rustc_middle::ty::{self, PromotionCandidacy, TyCtxt};
+
+/// Collects the promotion candidates in `body`, which was type checked into `typeck_results`.
+/// `in_const_item` is whether `body` is the initializer of a constant or static, where calls to
+/// all `const fn`s can be promoted.
+pub(super) fn promotion_candidates<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    typeck_results: &ty::TypeckResults<'tcx>,
+    body: &'tcx hir::Body<'tcx>,
+    in_const_item: bool,
+) -> ItemLocalMap<PromotionCandidacy> {
+    let mut visitor = PromotionCandidateVisitor {
+        tcx,
+        typeck_results,
+        in_const_item,
+        candidates: Default::default(),
+        closure_depth: 0,
+    };
+    visitor.visit_body(body);
+    visitor.candidates
+}
+
+struct PromotionCandidateVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck_results: &'a ty::TypeckResults<'tcx>,
+    in_const_item: bool,
+    candidates: ItemLocalMap<PromotionCandidacy>,
+    /// Closures share the typeck results of their parent, but are never evaluated in a const
+    /// context, so we track whether we are in one.
+    closure_depth: usize,
+}
+
+impl<'a, 'tcx> PromotionCandidateVisitor<'a, 'tcx> {
+    fn is_candidate(&self, expr: &hir::Expr<'_>) -> bool {
+        self.candidates.contains_key(&expr.hir_id.local_id)
+    }
+
+    fn is_promotable_call(&self, def_id: DefId) -> bool {
+        if self.in_const_item && self.closure_depth == 0 {
+            self.tcx.is_const_fn_raw(def_id)
+        } else {
+            self.tcx.is_promotable_const_fn(def_id)
+        }
+    }
+
+    /// Whether `expr` has a type built-in operators are promoted for.
+    fn is_primitive(&self, expr: &hir::Expr<'_>) -> bool {
+        self.typeck_results.expr_ty_opt(expr).map_or(false, |ty| {
+            ty.is_integral() || ty.is_bool() || ty.is_char() || ty.is_floating_point()
+        })
+    }
+
+    fn candidacy(&self, expr: &hir::Expr<'_>) -> Option<PromotionCandidacy> {
+        // Overloaded derefs run user code.
+        if self
+            .typeck_results
+            .expr_adjustments(expr)
+            .iter()
+            .any(|adjustment| matches!(adjustment.kind, Adjust::Deref(Some(_))))
+        {
+            return None;
+        }
+
+        match expr.kind {
+            hir::ExprKind::Lit(_) => Some(PromotionCandidacy::Literal),
+            hir::ExprKind::Path(ref qpath) => {
+                match self.typeck_results.qpath_res(qpath, expr.hir_id) {
+                    Res::Def(
+                        DefKind::Const
+                        | DefKind::AssocConst
+                        | DefKind::ConstParam
+                        | DefKind::Fn
+                        | DefKind::AssocFn
+                        | DefKind::Ctor(..),
+                        _,
+                    ) => Some(PromotionCandidacy::ConstPath),
+                    _ => None,
+                }
+            }
+            hir::ExprKind::Call(callee, args) => {
+                if !args.iter().all(|arg| self.is_candidate(arg)) {
+                    return None;
+                }
+                let qpath = match callee.kind {
+                    hir::ExprKind::Path(ref qpath) => qpath,
+                    _ => return None,
+                };
+                match self.typeck_results.qpath_res(qpath, callee.hir_id) {
+                    Res::Def(DefKind::Ctor(_, CtorKind::Fn), _) => {
+                        Some(PromotionCandidacy::Aggregate)
+                    }
+                    Res::Def(DefKind::Fn | DefKind::AssocFn, def_id)
+                        if self.is_promotable_call(def_id) =>
+                    {
+                        Some(PromotionCandidacy::ConstFnCall)
+                    }
+                    _ => None,
+                }
+            }
+            hir::ExprKind::MethodCall(_, _, args, _) => {
+                let def_id = self.typeck_results.type_dependent_def_id(expr.hir_id)?;
+                (args.iter().all(|arg| self.is_candidate(arg)) && self.is_promotable_call(def_id))
+                    .then_some(PromotionCandidacy::ConstFnCall)
+            }
+            hir::ExprKind::Tup(elems) | hir::ExprKind::Array(elems) => elems
+                .iter()
+                .all(|elem| self.is_candidate(elem))
+                .then_some(PromotionCandidacy::Aggregate),
+            hir::ExprKind::Struct(_, fields, None) => fields
+                .iter()
+                .all(|field| self.is_candidate(field.expr))
+                .then_some(PromotionCandidacy::Aggregate),
+            hir::ExprKind::Repeat(value, _) => {
+                self.is_candidate(value).then_some(PromotionCandidacy::Aggregate)
+            }
+            hir::ExprKind::AddrOf(hir::BorrowKind::Ref, hir::Mutability::Not, place) => {
+                self.is_candidate(place).then_some(PromotionCandidacy::Borrow)
+            }
+            hir::ExprKind::Unary(hir::UnOp::Neg | hir::UnOp::Not, operand) => {
+                (!self.typeck_results.is_method_call(expr)
+                    && self.is_candidate(operand)
+                    && self.is_primitive(operand))
+                .then_some(PromotionCandidacy::Operation)
+            }
+            hir::ExprKind::Binary(op, lhs, rhs) => {
+                // `&&` and `||` are control flow, and division can panic.
+                let can_promote_op = !matches!(
+                    op.node,
+                    hir::BinOpKind::And
+                        | hir::BinOpKind::Or
+                        | hir::BinOpKind::Div
+                        | hir::BinOpKind::Rem
+                );
+                (can_promote_op
+                    && !self.typeck_results.is_method_call(expr)
+                    && self.is_candidate(lhs)
+                    && self.is_candidate(rhs)
+                    && self.is_primitive(lhs)
+                    && self.is_primitive(rhs))
+                .then_some(PromotionCandidacy::Operation)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for PromotionCandidateVisitor<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    // Anonymous constants, like array lengths, are type checked on their own.
+    fn visit_anon_const(&mut self, _: &'tcx hir::AnonConst) {}
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        let is_closure = matches!(expr.kind, hir::ExprKind::Closure(..));
+        if is_closure {
+            self.closure_depth += 1;
+        }
+        // Operands are classified before the expressions using them.
+        intravisit::walk_expr(self, expr);
+        if is_closure {
+            self.closure_depth -= 1;
+        }
+
+        if let Some(candidacy) = self.candidacy(expr) {
+            self.candidates.insert(expr.hir_id.local_id, candidacy);
+        }
+    }
+}
@@ -29,6 +29,17 @@
 //! `ty::InferBorrow(upvar_id)` or something like that, but this would
 //! then mean that all later passes would have to check for these figments
 //! and report an error, and it just seems like more mess in the end.)
+//!
+//! ### Nested closures
+//!
+//! Closures are analyzed innermost-first (see `InferBorrowKindVisitor`). When
+//! the enclosing closure is analyzed, a nested closure expression is treated as
+//! a use of each place it captures, with the borrow kind inferred for it, so a
+//! mutable use of an outer capture makes the outer closure borrow it mutably
+//! too. A closure's captures only depend on those of the closures nested within
+//! it and on the kinds of the closures it calls, which are in scope (and hence
+//! analyzed) before it, so this single pass over the closure tree already
+//! reaches the fixed point; there is no need to iterate.
 
 use super::FnCtxt;
 
@@ -2,13 +2,13 @@
 use crate::constrained_generic_params::{identify_constrained_generic_params, Parameter};
 
 use rustc_ast as ast;
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexSet};
 use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::intravisit as hir_visit;
 use rustc_hir::intravisit::Visitor;
-use rustc_hir::itemlikevisit::ParItemLikeVisitor;
+use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_hir::lang_items::LangItem;
 use rustc_hir::ItemKind;
 use rustc_middle::hir::map as hir_map;
@@ -280,8 +280,9 @@ fn check_param_wf(tcx: TyCtxt<'_>, param: &hir::GenericParam<'_>) {
         // We currently only check wf of const params here.
         hir::GenericParamKind::Lifetime { .. } | hir::GenericParamKind::Type { .. } => (),
 
-        // Const parameters are well formed if their type is structural match.
-        // FIXME(const_generics_defaults): we also need to check that the `default` is wf.
+        // Const parameters are well formed if their type is structural match. Their defaults
+        // are checked against the bounds of the item in `check_where_clauses`, and against the
+        // type of the parameter when the anon const holding them is type checked.
         hir::GenericParamKind::Const { ty: hir_ty, default: _ } => {
             let ty = tcx.type_of(tcx.hir().local_def_id(param.hir_id));
 
@@ -714,6 +715,15 @@ fn check_where_clauses<'tcx, 'fcx>(
         GenericParamDefKind::Lifetime => unreachable!(),
     };
 
+    // The value of the default of a const parameter, unless it depends on other parameters.
+    // With `feature(const_generics)`, a default that is an arbitrary expression is an anon
+    // const with the generics of the item as its parent, so it has to be evaluated to find out
+    // whether it actually uses any of them.
+    let non_dependent_const_default = |def: &ty::GenericParamDef| {
+        let default_ct = tcx.const_param_default(def.def_id).eval(tcx, fcx.param_env);
+        if default_ct.needs_subst() { None } else { Some(default_ct) }
+    };
+
     // Check that concrete defaults are well-formed. See test `type-check-defaults.rs`.
     // For example, this forbids the declaration:
     //
@@ -738,15 +748,16 @@ fn check_where_clauses<'tcx, 'fcx>(
                 }
             }
             GenericParamDefKind::Const { .. } => {
-                // FIXME(const_generics_defaults): Figure out if this
-                // is the behavior we want, see the comment further below.
                 if is_our_default(&param) {
-                    let default_ct = tcx.const_param_default(param.def_id);
-                    fcx.register_wf_obligation(
-                        default_ct.into(),
-                        tcx.def_span(param.def_id),
-                        ObligationCauseCode::MiscObligation,
-                    );
+                    // As with type parameters, dependent defaults like `{ N + 1 }` are only
+                    // checked where they are used, once `N` is known.
+                    if let Some(default_ct) = non_dependent_const_default(&param) {
+                        fcx.register_wf_obligation(
+                            default_ct.into(),
+                            tcx.def_span(param.def_id),
+                            ObligationCauseCode::MiscObligation,
+                        );
+                    }
                 }
             }
             // Doesn't have defaults.
@@ -783,20 +794,11 @@ fn check_where_clauses<'tcx, 'fcx>(
                 tcx.mk_param_from_def(param)
             }
             GenericParamDefKind::Const { .. } => {
-                // FIXME(const_generics_defaults): I(@lcnr) feel like always
-                // using the const parameter is the right choice here, even
-                // if it needs substs.
-                //
-                // Before stabilizing this we probably want to get some tests
-                // where this makes a difference and figure out what's the exact
-                // behavior we want here.
-
-                // If the param has a default, ...
+                // If the param has a default that isn't dependent, substitute it with the
+                // evaluated default, so that e.g. `struct Foo<const N: usize = { 1 + 2 }>
+                // where Const<N>: Trait` is checked for `Const<3>`.
                 if is_our_default(param) {
-                    let default_ct = tcx.const_param_default(param.def_id);
-                    // ... and it's not a dependent default, ...
-                    if !default_ct.needs_subst() {
-                        // ... then substitute it with the default.
+                    if let Some(default_ct) = non_dependent_const_default(param) {
                         return default_ct.into();
                     }
                 }
@@ -1353,21 +1355,70 @@ pub fn new(tcx: TyCtxt<'tcx>) -> CheckTypeWellFormedVisitor<'tcx> {
     }
 }
 
-impl ParItemLikeVisitor<'tcx> for CheckTypeWellFormedVisitor<'tcx> {
-    fn visit_item(&self, i: &'tcx hir::Item<'tcx>) {
-        Visitor::visit_item(&mut self.clone(), i);
+/// Checks that the items reached by checking every module on its own are exactly the items of
+/// the whole crate, which were checked at once before `check_mod_type_wf` existed, and that each
+/// of them is reached once. Each difference is reported as a bug.
+///
+/// This only compares coverage. The diagnostics of an item are emitted by the per-item
+/// `check_*_well_formed` queries, which are the same whichever traversal reaches the item, so
+/// the two traversals can only differ in which items they reach: an item missed by the modules
+/// would have its diagnostics lost, and an item reached twice means that the modules overlap.
+pub fn verify_wf_check_coverage(tcx: TyCtxt<'_>) {
+    let mut crate_items = ItemCollector::default();
+    tcx.hir().krate().visit_all_item_likes(&mut crate_items);
+    let mut module_items = ItemCollector::default();
+    for &module in tcx.hir().krate().modules.keys() {
+        tcx.hir().visit_item_likes_in_module(module, &mut module_items);
+    }
+
+    let report = |def_id: LocalDefId, msg: &str| {
+        tcx.sess.delay_span_bug(
+            tcx.def_span(def_id),
+            &format!("`{}` {}", tcx.def_path_str(def_id.to_def_id()), msg),
+        );
+    };
+    for &def_id in crate_items.items.difference(&module_items.items) {
+        report(def_id, "is not in any module checked for well-formedness");
+    }
+    for &def_id in module_items.items.difference(&crate_items.items) {
+        report(def_id, "is checked for well-formedness but is not an item of the crate");
+    }
+    for def_id in module_items.duplicates {
+        report(def_id, "is checked for well-formedness in several modules");
+    }
+}
+
+/// Collects the items visited by an `ItemLikeVisitor`, in order.
+#[derive(Default)]
+struct ItemCollector {
+    items: FxIndexSet<LocalDefId>,
+    /// Items that were visited more than once.
+    duplicates: Vec<LocalDefId>,
+}
+
+impl ItemCollector {
+    fn insert(&mut self, def_id: LocalDefId) {
+        if !self.items.insert(def_id) {
+            self.duplicates.push(def_id);
+        }
     }
+}
 
-    fn visit_trait_item(&self, trait_item: &'tcx hir::TraitItem<'tcx>) {
-        Visitor::visit_trait_item(&mut self.clone(), trait_item);
+impl<'tcx> ItemLikeVisitor<'tcx> for ItemCollector {
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        self.insert(item.def_id);
     }
 
-    fn visit_impl_item(&self, impl_item: &'tcx hir::ImplItem<'tcx>) {
-        Visitor::visit_impl_item(&mut self.clone(), impl_item);
+    fn visit_trait_item(&mut self, trait_item: &'tcx hir::TraitItem<'tcx>) {
+        self.insert(trait_item.def_id);
+    }
+
+    fn visit_impl_item(&mut self, impl_item: &'tcx hir::ImplItem<'tcx>) {
+        self.insert(impl_item.def_id);
     }
 
-    fn visit_foreign_item(&self, foreign_item: &'tcx hir::ForeignItem<'tcx>) {
-        Visitor::visit_foreign_item(&mut self.clone(), foreign_item)
+    fn visit_foreign_item(&mut self, foreign_item: &'tcx hir::ForeignItem<'tcx>) {
+        self.insert(foreign_item.def_id);
     }
 }
 
@@ -4,10 +4,11 @@
 
 use crate::check::FnCtxt;
 
+use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::stable_map::FxHashMap;
 use rustc_errors::ErrorReported;
 use rustc_hir as hir;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_infer::infer::error_reporting::TypeAnnotationNeeded::E0282;
 use rustc_infer::infer::InferCtxt;
@@ -38,7 +39,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
     pub fn resolve_type_vars_in_body(
         &self,
         body: &'tcx hir::Body<'tcx>,
-    ) -> &'tcx ty::TypeckResults<'tcx> {
+    ) -> (&'tcx ty::TypeckResults<'tcx>, &'tcx FxHashSet<LocalDefId>) {
         let item_id = self.tcx.hir().body_owner(body.id());
         let item_def_id = self.tcx.hir().local_def_id(item_id);
 
@@ -65,16 +66,21 @@ pub fn resolve_type_vars_in_body(
         wbcx.visit_closures();
         wbcx.visit_liberated_fn_sigs();
         wbcx.visit_fru_field_types();
+        wbcx.visit_coercion_sites();
         wbcx.visit_opaque_types(body.value.span);
         wbcx.visit_coercion_casts();
+        wbcx.visit_unsafe_contexts();
+        wbcx.visit_index_mut_alternatives();
+        wbcx.visit_scrutinee_facts();
+        wbcx.visit_scrutinee_temp_scopes();
         wbcx.visit_user_provided_tys();
         wbcx.visit_user_provided_sigs();
         wbcx.visit_generator_interior_types();
+        wbcx.visit_promotion_candidates(body);
 
-        let used_trait_imports =
-            mem::take(&mut self.typeck_results.borrow_mut().used_trait_imports);
+        // These are kept apart from the results, see `typeck_and_used_trait_imports`.
+        let used_trait_imports = self.used_trait_imports.take();
         debug!("used_trait_imports({:?}) = {:?}", item_def_id, used_trait_imports);
-        wbcx.typeck_results.used_trait_imports = used_trait_imports;
 
         wbcx.typeck_results.treat_byte_string_as_slice =
             mem::take(&mut self.typeck_results.borrow_mut().treat_byte_string_as_slice);
@@ -86,7 +92,7 @@ pub fn resolve_type_vars_in_body(
 
         debug!("writeback: typeck results for {:?} are {:#?}", item_def_id, wbcx.typeck_results);
 
-        self.tcx.arena.alloc(wbcx.typeck_results)
+        (self.tcx.arena.alloc(wbcx.typeck_results), self.tcx.arena.alloc(used_trait_imports))
     }
 }
 
@@ -166,18 +172,19 @@ fn fix_scalar_builtin_expr(&mut self, e: &hir::Expr<'_>) {
                     match e.kind {
                         hir::ExprKind::Binary(..) => {
                             if !op.node.is_by_value() {
+                                let tcx = self.fcx.tcx;
                                 let mut adjustments = typeck_results.adjustments_mut();
                                 if let Some(a) = adjustments.get_mut(lhs.hir_id) {
-                                    a.pop();
+                                    pop_adjustment(tcx, a);
                                 }
                                 if let Some(a) = adjustments.get_mut(rhs.hir_id) {
-                                    a.pop();
+                                    pop_adjustment(tcx, a);
                                 }
                             }
                         }
                         hir::ExprKind::AssignOp(..) => {
                             if let Some(a) = typeck_results.adjustments_mut().get_mut(lhs.hir_id) {
-                                a.pop();
+                                pop_adjustment(self.fcx.tcx, a);
                             }
                         }
                         _ => {}
@@ -233,10 +240,10 @@ fn fix_index_builtin_expr(&mut self, e: &hir::Expr<'_>) {
                         // discarded, we do an extra `pop()`
                         if let Some(Adjustment {
                             kind: Adjust::Pointer(PointerCast::Unsize), ..
-                        }) = a.pop()
+                        }) = pop_adjustment(self.fcx.tcx, a)
                         {
                             // So the borrow discard actually happens here
-                            a.pop();
+                            pop_adjustment(self.fcx.tcx, a);
                         }
                     }
                 }
@@ -253,6 +260,16 @@ fn fix_index_builtin_expr(&mut self, e: &hir::Expr<'_>) {
 // below. In general, a function is made into a `visitor` if it must
 // traffic in node-ids or update typeck results in the type context etc.
 
+/// Removes the last adjustment of an interned adjustment list, returning it.
+fn pop_adjustment<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    adjustments: &mut &'tcx ty::List<Adjustment<'tcx>>,
+) -> Option<Adjustment<'tcx>> {
+    let (&last, rest) = adjustments.split_last()?;
+    *adjustments = tcx.intern_adjustments(rest);
+    Some(last)
+}
+
 impl<'cx, 'tcx> Visitor<'tcx> for WritebackCx<'cx, 'tcx> {
     type Map = intravisit::ErasedMap<'tcx>;
 
@@ -294,6 +311,13 @@ fn visit_block(&mut self, b: &'tcx hir::Block<'tcx>) {
         intravisit::walk_block(self, b);
     }
 
+    fn visit_stmt(&mut self, s: &'tcx hir::Stmt<'tcx>) {
+        intravisit::walk_stmt(self, s);
+        if let hir::StmtKind::Semi(expr) = s.kind {
+            self.visit_stmt_expr_tails(expr);
+        }
+    }
+
     fn visit_pat(&mut self, p: &'tcx hir::Pat<'tcx>) {
         match p.kind {
             hir::PatKind::Binding(..) => {
@@ -422,6 +446,54 @@ fn visit_coercion_casts(&mut self) {
         }
     }
 
+    fn visit_unsafe_contexts(&mut self) {
+        let fcx_typeck_results = self.fcx.typeck_results.borrow();
+        assert_eq!(fcx_typeck_results.hir_owner, self.typeck_results.hir_owner);
+        let common_hir_owner = fcx_typeck_results.hir_owner;
+
+        for (&local_id, &op) in fcx_typeck_results.unsafe_ops().iter() {
+            let hir_id = hir::HirId { owner: common_hir_owner, local_id };
+            self.typeck_results.unsafe_ops_mut().insert(hir_id, op);
+        }
+        for (&local_id, &context) in fcx_typeck_results.nested_unsafe_blocks().iter() {
+            let hir_id = hir::HirId { owner: common_hir_owner, local_id };
+            self.typeck_results.nested_unsafe_blocks_mut().insert(hir_id, context);
+        }
+    }
+
+    fn visit_index_mut_alternatives(&mut self) {
+        let fcx_typeck_results = self.fcx.typeck_results.borrow();
+        assert_eq!(fcx_typeck_results.hir_owner, self.typeck_results.hir_owner);
+        let common_hir_owner = fcx_typeck_results.hir_owner;
+
+        for (&local_id, &alternatives) in fcx_typeck_results.index_mut_alternatives().iter() {
+            let hir_id = hir::HirId { owner: common_hir_owner, local_id };
+            self.typeck_results.index_mut_alternatives_mut().insert(hir_id, alternatives);
+        }
+    }
+
+    fn visit_scrutinee_facts(&mut self) {
+        let fcx_typeck_results = self.fcx.typeck_results.borrow();
+        assert_eq!(fcx_typeck_results.hir_owner, self.typeck_results.hir_owner);
+        let common_hir_owner = fcx_typeck_results.hir_owner;
+
+        for (&local_id, &fact) in fcx_typeck_results.scrutinee_facts().iter() {
+            let hir_id = hir::HirId { owner: common_hir_owner, local_id };
+            self.typeck_results.scrutinee_facts_mut().insert(hir_id, fact);
+        }
+    }
+
+    fn visit_scrutinee_temp_scopes(&mut self) {
+        let fcx_typeck_results = self.fcx.typeck_results.borrow();
+        assert_eq!(fcx_typeck_results.hir_owner, self.typeck_results.hir_owner);
+        let common_hir_owner = fcx_typeck_results.hir_owner;
+
+        for (&local_id, &scope) in fcx_typeck_results.scrutinee_temp_scopes().iter() {
+            let hir_id = hir::HirId { owner: common_hir_owner, local_id };
+            self.typeck_results.scrutinee_temp_scopes_mut().insert(hir_id, scope);
+        }
+    }
+
     fn visit_user_provided_tys(&mut self) {
         let fcx_typeck_results = self.fcx.typeck_results.borrow();
         assert_eq!(fcx_typeck_results.hir_owner, self.typeck_results.hir_owner);
@@ -584,12 +656,78 @@ fn visit_node_id(&mut self, span: Span, hir_id: hir::HirId) {
         self.write_ty_to_typeck_results(hir_id, n_ty);
         debug!("node {:?} has type {:?}", hir_id, n_ty);
 
-        // Resolve any substitutions
+        // Resolve any substitutions. Empty ones aren't worth storing, as
+        // `node_substs` returns them for nodes without an entry anyway.
         if let Some(substs) = self.fcx.typeck_results.borrow().node_substs_opt(hir_id) {
-            let substs = self.resolve(substs, &span);
-            debug!("write_substs_to_tcx({:?}, {:?})", hir_id, substs);
-            assert!(!substs.needs_infer() && !substs.has_placeholders());
-            self.typeck_results.node_substs_mut().insert(hir_id, substs);
+            if !substs.is_empty() {
+                let substs = self.resolve(substs, &span);
+                debug!("write_substs_to_tcx({:?}, {:?})", hir_id, substs);
+                assert!(!substs.needs_infer() && !substs.has_placeholders());
+                self.typeck_results.node_substs_mut().insert(hir_id, substs);
+            }
+        }
+    }
+
+    fn visit_promotion_candidates(&mut self, body: &'tcx hir::Body<'tcx>) {
+        // This needs the resolved types and adjustments, so it runs on the final results.
+        let in_const_item = matches!(
+            self.fcx.const_cx.kind(),
+            Some(hir::ConstContext::Const | hir::ConstContext::Static(_))
+        );
+        let candidates = crate::check::promotion::promotion_candidates(
+            self.tcx(),
+            &self.typeck_results,
+            body,
+            in_const_item,
+        );
+        let hir_owner = self.typeck_results.hir_owner;
+        for (local_id, candidacy) in candidates {
+            let hir_id = hir::HirId { owner: hir_owner, local_id };
+            self.typeck_results.promotion_candidates_mut().insert(hir_id, candidacy);
+        }
+    }
+
+    fn visit_stmt_expr_tails(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        // This needs the resolved types and adjustments, so `expr` has to be visited first.
+        let mut tails = Vec::new();
+        self.collect_stmt_expr_tails(expr, &mut tails);
+        if !tails.is_empty() {
+            self.typeck_results.stmt_expr_tails_mut().insert(expr.hir_id, tails);
+        }
+    }
+
+    /// Finds the expressions producing the value of `expr`, looking through blocks and the
+    /// branches of `if` and `match` expressions. Diverging expressions produce no value.
+    fn collect_stmt_expr_tails(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        tails: &mut Vec<ty::StmtExprTail<'tcx>>,
+    ) {
+        match expr.kind {
+            hir::ExprKind::Block(block, _) => {
+                if let Some(tail) = block.expr {
+                    self.collect_stmt_expr_tails(tail, tails);
+                }
+            }
+            hir::ExprKind::If(_, then, Some(else_expr)) => {
+                self.collect_stmt_expr_tails(then, tails);
+                self.collect_stmt_expr_tails(else_expr, tails);
+            }
+            // The arms of a desugared `?` don't produce the value the user wrote.
+            hir::ExprKind::Match(_, arms, source) if source != hir::MatchSource::TryDesugar => {
+                for arm in arms {
+                    self.collect_stmt_expr_tails(arm.body, tails);
+                }
+            }
+            _ => {
+                let unadjusted_ty = match self.typeck_results.expr_ty_opt(expr) {
+                    Some(ty) if !ty.is_never() => ty,
+                    _ => return,
+                };
+                let adjusted_ty =
+                    self.typeck_results.expr_ty_adjusted_opt(expr).unwrap_or(unadjusted_ty);
+                tails.push(ty::StmtExprTail { hir_id: expr.hir_id, unadjusted_ty, adjusted_ty });
+            }
         }
     }
 
@@ -647,6 +785,18 @@ fn visit_fru_field_types(&mut self) {
         }
     }
 
+    fn visit_coercion_sites(&mut self) {
+        let fcx_typeck_results = self.fcx.typeck_results.borrow();
+        assert_eq!(fcx_typeck_results.hir_owner, self.typeck_results.hir_owner);
+        let common_hir_owner = fcx_typeck_results.hir_owner;
+
+        for (&local_id, sites) in fcx_typeck_results.coercion_sites().iter() {
+            let hir_id = hir::HirId { owner: common_hir_owner, local_id };
+            let sites = self.resolve(sites.clone(), &hir_id);
+            self.typeck_results.coercion_sites_mut().insert(hir_id, sites);
+        }
+    }
+
     fn resolve<T>(&mut self, x: T, span: &dyn Locatable) -> T
     where
         T: TypeFoldable<'tcx>,
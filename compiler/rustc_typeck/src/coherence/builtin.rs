@@ -2,7 +2,7 @@
 //! up data structures required by type-checking/codegen.
 
 use crate::errors::{CopyImplOnNonAdt, CopyImplOnTypeWithDtor, DropImplOnWrongItem};
-use rustc_errors::struct_span_err;
+use rustc_errors::{struct_span_err, Applicability};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::lang_items::LangItem;
@@ -24,7 +24,10 @@ pub fn check_trait(tcx: TyCtxt<'_>, trait_def_id: DefId) {
         .check(lang_items.drop_trait(), visit_implementation_of_drop)
         .check(lang_items.copy_trait(), visit_implementation_of_copy)
         .check(lang_items.coerce_unsized_trait(), visit_implementation_of_coerce_unsized)
-        .check(lang_items.dispatch_from_dyn_trait(), visit_implementation_of_dispatch_from_dyn);
+        .check(lang_items.dispatch_from_dyn_trait(), visit_implementation_of_dispatch_from_dyn)
+        .check(lang_items.fn_trait(), visit_implementation_of_fn_trait)
+        .check(lang_items.fn_mut_trait(), visit_implementation_of_fn_trait)
+        .check(lang_items.fn_once_trait(), visit_implementation_of_fn_trait);
 }
 
 struct Checker<'tcx> {
@@ -287,6 +290,48 @@ fn visit_implementation_of_dispatch_from_dyn(tcx: TyCtxt<'_>, impl_did: LocalDef
     })
 }
 
+fn visit_implementation_of_fn_trait(tcx: TyCtxt<'_>, impl_did: LocalDefId) {
+    debug!("visit_implementation_of_fn_trait: impl_did={:?}", impl_did);
+
+    // Calls through the `Fn*` traits pass the arguments as a tuple, so manual implementations
+    // that take anything else can never be called with call notation.
+    let trait_ref = tcx.impl_trait_ref(impl_did).unwrap();
+    let args = trait_ref.substs.type_at(1);
+    if let ty::Tuple(_) | ty::Param(_) | ty::Projection(_) | ty::Error(_) = args.kind() {
+        return;
+    }
+
+    let impl_hir_id = tcx.hir().local_def_id_to_hir_id(impl_did);
+    let args_span = match tcx.hir().expect_item(impl_hir_id).kind {
+        ItemKind::Impl(hir::Impl { of_trait: Some(ref tr), .. }) => tr
+            .path
+            .segments
+            .last()
+            .and_then(|segment| segment.args)
+            .and_then(|generic_args| generic_args.args.first())
+            .map_or(tr.path.span, |arg| arg.span()),
+        _ => bug!("expected Fn* impl item"),
+    };
+    let trait_name = tcx.item_name(trait_ref.def_id);
+    let mut err = struct_span_err!(
+        tcx.sess,
+        args_span,
+        E0059,
+        "manual implementations of `{}` must take their arguments as a tuple",
+        trait_name
+    );
+    err.span_label(args_span, format!("expected a tuple, found `{}`", args));
+    if let Ok(snippet) = tcx.sess.source_map().span_to_snippet(args_span) {
+        err.span_suggestion(
+            args_span,
+            "use a tuple with a single element",
+            format!("({},)", snippet),
+            Applicability::MaybeIncorrect,
+        );
+    }
+    err.emit();
+}
+
 pub fn coerce_unsized_info(tcx: TyCtxt<'tcx>, impl_did: DefId) -> CoerceUnsizedInfo {
     debug!("compute_coerce_unsized_info(impl_did={:?})", impl_did);
 
@@ -7,12 +7,13 @@
 //! `tcx.inherent_impls(def_id)`). That value, however,
 //! is computed by selecting an idea from this table.
 
-use rustc_errors::struct_span_err;
+use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
-use rustc_middle::ty::{self, CrateInherentImpls, TyCtxt};
+use rustc_middle::ty::{self, CrateInherentImpls, Ty, TyCtxt};
 
+use rustc_span::symbol::sym;
 use rustc_span::Span;
 
 /// On-demand query: yields a map containing all types mapped to their inherent impls.
@@ -34,6 +35,26 @@ pub fn inherent_impls(tcx: TyCtxt<'_>, ty_def_id: DefId) -> &[DefId] {
     }
 }
 
+/// On-demand query: yields the inherent impls of a type marked with
+/// `#[rustc_has_incoherent_inherent_impls]` from all crates other than the one defining it.
+/// Method probing merges these with `inherent_impls`, which only knows about the impls of the
+/// defining crate.
+pub fn incoherent_impls(tcx: TyCtxt<'_>, ty_def_id: DefId) -> &[DefId] {
+    let local_impls = tcx
+        .crate_inherent_impls(())
+        .incoherent_impls
+        .get(&ty_def_id)
+        .into_iter()
+        .flatten()
+        .map(|impl_def_id| impl_def_id.to_def_id());
+    let extern_impls = tcx
+        .crates(())
+        .iter()
+        .filter(|&&cnum| cnum != ty_def_id.krate)
+        .flat_map(|&cnum| tcx.crate_incoherent_impls((cnum, ty_def_id)).iter().copied());
+    tcx.arena.alloc_from_iter(local_impls.chain(extern_impls))
+}
+
 struct InherentCollect<'tcx> {
     tcx: TyCtxt<'tcx>,
     impls_map: CrateInherentImpls,
@@ -368,17 +389,23 @@ fn check_def_id(&mut self, item: &hir::Item<'_>, def_id: DefId) {
             // the implementation does not have any associated traits.
             let vec = self.impls_map.inherent_impls.entry(def_id).or_default();
             vec.push(item.def_id.to_def_id());
+        } else if self.tcx.has_attr(def_id, sym::rustc_has_incoherent_inherent_impls) {
+            // The defining crate opted into inherent impls from other crates, which are
+            // collected separately as they can't be found through the type's own crate.
+            let vec = self.impls_map.incoherent_impls.entry(def_id).or_default();
+            vec.push(item.def_id);
         } else {
-            struct_span_err!(
+            let mut err = struct_span_err!(
                 self.tcx.sess,
                 item.span,
                 E0116,
                 "cannot define inherent `impl` for a type outside of the crate \
                               where the type is defined"
-            )
-            .span_label(item.span, "impl for type defined outside of crate.")
-            .note("define and implement a trait or new type instead")
-            .emit();
+            );
+            err.span_label(item.span, "impl for type defined outside of crate.")
+                .note("define and implement a trait or new type instead");
+            self.suggest_extension_trait(&mut err, item);
+            err.emit();
         }
     }
 
@@ -426,7 +453,7 @@ fn check_primitive_impl(
                     )
                 };
 
-                struct_span_err!(
+                let mut err = struct_span_err!(
                     self.tcx.sess,
                     span,
                     E0390,
@@ -434,10 +461,121 @@ fn check_primitive_impl(
                                   \"{}\"]` is allowed for the `{}` primitive",
                     lang,
                     ty
-                )
-                .help(&format!("consider using a trait{}", to_implement))
-                .emit();
+                );
+                err.help(&format!("consider using a trait{}", to_implement));
+                let hir_id = self.tcx.hir().local_def_id_to_hir_id(impl_def_id);
+                self.suggest_extension_trait(&mut err, self.tcx.hir().expect_item(hir_id));
+                err.emit();
+            }
+        }
+    }
+
+    /// Suggests turning the inherent impl `item` of a type of another crate into the impl of
+    /// a new extension trait, which is the usual way of adding methods to such a type. The
+    /// suggestion adds the trait definition right before the impl, makes the impl implement
+    /// it and removes the visibilities of the items, which trait impls can't have.
+    fn suggest_extension_trait(&self, err: &mut DiagnosticBuilder<'_>, item: &hir::Item<'_>) {
+        let impl_ = match &item.kind {
+            hir::ItemKind::Impl(impl_) if !impl_.items.is_empty() => impl_,
+            _ => return,
+        };
+        let tcx = self.tcx;
+        let source_map = tcx.sess.source_map();
+        let snippet = |span| source_map.span_to_snippet(span).ok();
+
+        // The trait is generic over all the parameters of the impl, so that the signatures of
+        // the items can keep referring to them.
+        let mut params = vec![];
+        let mut args = vec![];
+        for param in impl_.generics.params {
+            let name = param.name.ident();
+            match param.kind {
+                hir::GenericParamKind::Lifetime { kind: hir::LifetimeParamKind::Explicit }
+                | hir::GenericParamKind::Type { synthetic: None, .. } => {
+                    params.push(name.to_string());
+                }
+                hir::GenericParamKind::Const { ty, .. } => match snippet(ty.span) {
+                    Some(ty) => params.push(format!("const {}: {}", name, ty)),
+                    None => return,
+                },
+                // Elided lifetimes can't be named in the trait.
+                _ => return,
+            }
+            args.push(name.to_string());
+        }
+        let generics = |list: &[String]| {
+            if list.is_empty() { String::new() } else { format!("<{}>", list.join(", ")) }
+        };
+
+        let self_ty = tcx.type_of(item.def_id);
+        let trait_name = extension_trait_name(tcx, self_ty);
+        let indent = " ".repeat(source_map.span_to_margin(item.span).unwrap_or(0));
+        let mut trait_items = vec![];
+        let mut suggestion = vec![];
+        for item_ref in impl_.items {
+            let impl_item = tcx.hir().impl_item(item_ref.id);
+            let trait_item = match impl_item.kind {
+                hir::ImplItemKind::Fn(ref sig, _) => snippet(sig.span).map(|sig| sig + ";"),
+                hir::ImplItemKind::Const(ty, _) => snippet(ty.span)
+                    .map(|ty| format!("const {}: {};", impl_item.ident, ty)),
+                hir::ImplItemKind::TyAlias(_) => Some(format!("type {};", impl_item.ident)),
+            };
+            let trait_item = match trait_item {
+                Some(trait_item) => trait_item,
+                None => return,
+            };
+            trait_items.push(format!("{}    {}\n", indent, trait_item));
+            if !matches!(impl_item.vis.node, hir::VisibilityKind::Inherited) {
+                let vis_span = impl_item.span.with_lo(impl_item.vis.span.lo());
+                suggestion.push((source_map.span_until_non_whitespace(vis_span), String::new()));
             }
         }
+        suggestion.push((
+            item.span.shrink_to_lo(),
+            format!(
+                "trait {}{} {{\n{}{}}}\n\n{}",
+                trait_name,
+                generics(&params),
+                trait_items.join(""),
+                indent,
+                indent,
+            ),
+        ));
+        suggestion.push((
+            impl_.self_ty.span.shrink_to_lo(),
+            format!("{}{} for ", trait_name, generics(&args)),
+        ));
+        err.multipart_suggestion(
+            &format!("define a trait with these items and implement it for `{}` instead", self_ty),
+            suggestion,
+            Applicability::MaybeIncorrect,
+        );
     }
 }
+
+/// Picks the name of an extension trait for `self_ty`, like `VecExt` for `Vec<u8>` or `StrExt`
+/// for `str`.
+fn extension_trait_name(tcx: TyCtxt<'_>, self_ty: Ty<'_>) -> String {
+    let base = match *self_ty.kind() {
+        ty::Adt(def, _) => tcx.item_name(def.did).to_string(),
+        ty::Foreign(did) => tcx.item_name(did).to_string(),
+        ty::Dynamic(data, _) => match data.principal_def_id() {
+            Some(did) => tcx.item_name(did).to_string(),
+            None => "Dyn".to_string(),
+        },
+        ty::Slice(_) => "Slice".to_string(),
+        ty::Array(..) => "Array".to_string(),
+        ty::RawPtr(_) => "Ptr".to_string(),
+        ty::FnPtr(_) => "Fn".to_string(),
+        ty::Tuple(_) => "Tuple".to_string(),
+        _ => {
+            let name = self_ty.to_string();
+            let mut chars = name.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => return "Ext".to_string(),
+            }
+        }
+    };
+    format!("{}Ext", base)
+}
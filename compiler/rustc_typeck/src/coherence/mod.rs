@@ -145,8 +145,10 @@ fn enforce_trait_manually_implementable(
     .emit();
 }
 
-/// We allow impls of marker traits to overlap, so they can't override impls
+/// We allow impls of marker traits to overlap, so they can't override items
 /// as that could make it ambiguous which associated item to use.
+///
+/// Marker traits that have associated items themselves are reported by wfcheck.
 fn enforce_empty_impls_for_marker_traits(
     tcx: TyCtxt<'_>,
     impl_def_id: LocalDefId,
@@ -156,23 +158,36 @@ fn enforce_empty_impls_for_marker_traits(
         return;
     }
 
-    if tcx.associated_item_def_ids(trait_def_id).is_empty() {
+    let items = tcx.associated_item_def_ids(impl_def_id);
+    if items.is_empty() {
         return;
     }
 
     let span = impl_header_span(tcx, impl_def_id);
-    struct_span_err!(tcx.sess, span, E0715, "impls for marker traits cannot contain items").emit();
+    let mut err =
+        struct_span_err!(tcx.sess, span, E0715, "impls for marker traits cannot contain items");
+    for &item in items {
+        let item_span = tcx.sess.source_map().guess_head_span(tcx.def_span(item));
+        err.span_label(item_span, "marker trait impls can't provide items");
+    }
+    err.note(&format!(
+        "impls of the marker trait `{}` are allowed to overlap, so it would be ambiguous which \
+         impl's items to use",
+        tcx.def_path_str(trait_def_id),
+    ));
+    err.emit();
 }
 
 pub fn provide(providers: &mut Providers) {
     use self::builtin::coerce_unsized_info;
-    use self::inherent_impls::{crate_inherent_impls, inherent_impls};
+    use self::inherent_impls::{crate_inherent_impls, incoherent_impls, inherent_impls};
     use self::inherent_impls_overlap::crate_inherent_impls_overlap_check;
 
     *providers = Providers {
         coherent_trait,
         crate_inherent_impls,
         inherent_impls,
+        incoherent_impls,
         crate_inherent_impls_overlap_check,
         coerce_unsized_info,
         ..*providers
@@ -48,7 +48,22 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                          arbitrary types"
                     );
                     err.span_label(sp, "impl doesn't use only types from inside the current crate");
+                    let mut wraps_local_ty = false;
                     for (ty, is_target_ty) in &tys {
+                        if let Some(traits::FundamentalLocality::NotFundamental {
+                            wrapper,
+                            local_ty,
+                        }) = traits::fundamental_locality(self.tcx, *ty)
+                        {
+                            err.note(&format!(
+                                "`{}` isn't considered local even though `{}` is, because `{}` \
+                                 isn't a `#[fundamental]` type",
+                                ty,
+                                local_ty,
+                                self.tcx.def_path_str(wrapper),
+                            ));
+                            wraps_local_ty = true;
+                        }
                         let mut ty = *ty;
                         self.tcx.infer_ctxt().enter(|infcx| {
                             // Remove the lifetimes unnecessary for this error.
@@ -79,6 +94,12 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                             err.span_label(tr.path.span, &msg);
                         }
                     }
+                    if wraps_local_ty {
+                        err.note(
+                            "a local type only stays local when it's wrapped in references or \
+                             `#[fundamental]` types like `Box<T>` and `Pin<P>`",
+                        );
+                    }
                     err.note("define and implement a trait or new type instead");
                     err.emit();
                     return;
@@ -91,9 +112,22 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                         }
                     }
 
+                    // Types like `Box<LocalType>` are local only because of the `#[fundamental]`
+                    // types wrapping them, which also don't cover the type parameter.
+                    let fundamental_note = trait_ref.substs.types().find_map(|ty| {
+                        match traits::fundamental_locality(self.tcx, ty)? {
+                            traits::FundamentalLocality::Fundamental { local_ty } => Some(format!(
+                                "`{}` counts as the local type `{}` because the types wrapping \
+                                 it are `#[fundamental]`",
+                                ty, local_ty,
+                            )),
+                            traits::FundamentalLocality::NotFundamental { .. } => None,
+                        }
+                    });
+
                     match local_type {
                         Some(local_type) => {
-                            struct_span_err!(
+                            let mut err = struct_span_err!(
                                 self.tcx.sess,
                                 sp,
                                 E0210,
@@ -101,8 +135,8 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                                 when it appears before the first local type (`{}`)",
                                 param_ty,
                                 local_type
-                            )
-                            .span_label(
+                            );
+                            err.span_label(
                                 sp,
                                 format!(
                                     "type parameter `{}` must be covered by another type \
@@ -120,11 +154,14 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                                 "in this case, 'before' refers to the following order: \
                                     `impl<..> ForeignTrait<T1, ..., Tn> for T0`, \
                                     where `T0` is the first and `Tn` is the last",
-                            )
-                            .emit();
+                            );
+                            if let Some(note) = &fundamental_note {
+                                err.note(note);
+                            }
+                            err.emit();
                         }
                         None => {
-                            struct_span_err!(
+                            let mut err = struct_span_err!(
                                 self.tcx.sess,
                                 sp,
                                 E0210,
@@ -132,7 +169,8 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                                 local type (e.g., `MyStruct<{}>`)",
                                 param_ty,
                                 param_ty
-                            ).span_label(sp, format!(
+                            );
+                            err.span_label(sp, format!(
                                 "type parameter `{}` must be used as the type parameter for some \
                                 local type",
                                 param_ty,
@@ -140,7 +178,11 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                                     least one of the types for which it is implemented is local"
                             ).note("only traits defined in the current crate can be \
                                     implemented for a type parameter"
-                            ).emit();
+                            );
+                            if let Some(note) = &fundamental_note {
+                                err.note(note);
+                            }
+                            err.emit();
                         }
                     };
                     return;
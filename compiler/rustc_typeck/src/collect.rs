@@ -23,6 +23,7 @@
 use crate::constrained_generic_params as cgp;
 use crate::errors;
 use crate::middle::resolve_lifetime as rl;
+use nameable::{nameable_ty, NameableTy, TyPosition};
 use rustc_ast as ast;
 use rustc_ast::{MetaItemKind, NestedMetaItem};
 use rustc_attr::{list_contains_name, InlineAttr, InstructionSetAttr, OptimizeAttr};
@@ -40,7 +41,7 @@
 use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
 use rustc_middle::mir::mono::Linkage;
 use rustc_middle::ty::query::Providers;
-use rustc_middle::ty::subst::InternalSubsts;
+use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts, SubstsRef};
 use rustc_middle::ty::util::Discr;
 use rustc_middle::ty::util::IntTypeExt;
 use rustc_middle::ty::{self, AdtKind, Const, DefIdTree, ToPolyTraitRef, Ty, TyCtxt};
@@ -54,6 +55,7 @@
 use std::iter;
 
 mod item_bounds;
+mod nameable;
 mod type_of;
 
 struct OnlySelfBounds(bool);
@@ -90,8 +92,10 @@ pub fn provide(providers: &mut Providers) {
         is_foreign_item,
         static_mutability,
         generator_kind,
+        doc_aliases,
         codegen_fn_attrs,
         collect_mod_item_types,
+        default_body_calls,
         ..*providers
     };
 }
@@ -1214,9 +1218,11 @@ fn trait_def(tcx: TyCtxt<'_>, def_id: DefId) -> ty::TraitDef {
     let hir_id = tcx.hir().local_def_id_to_hir_id(def_id.expect_local());
     let item = tcx.hir().expect_item(hir_id);
 
-    let (is_auto, unsafety) = match item.kind {
-        hir::ItemKind::Trait(is_auto, unsafety, ..) => (is_auto == hir::IsAuto::Yes, unsafety),
-        hir::ItemKind::TraitAlias(..) => (false, hir::Unsafety::Normal),
+    let (is_auto, unsafety, trait_item_refs) = match item.kind {
+        hir::ItemKind::Trait(is_auto, unsafety, .., trait_item_refs) => {
+            (is_auto == hir::IsAuto::Yes, unsafety, trait_item_refs)
+        }
+        hir::ItemKind::TraitAlias(..) => (false, hir::Unsafety::Normal, &[][..]),
         _ => span_bug!(item.span, "trait_def_of_item invoked on non-trait"),
     };
 
@@ -1242,6 +1248,12 @@ fn trait_def(tcx: TyCtxt<'_>, def_id: DefId) -> ty::TraitDef {
     } else {
         ty::trait_def::TraitSpecializationKind::None
     };
+    let must_implement_one_of = tcx
+        .get_attrs(def_id)
+        .iter()
+        .filter(|attr| attr.has_name(sym::rustc_must_implement_one_of))
+        .filter_map(|attr| must_implement_one_of_group(tcx, attr, trait_item_refs))
+        .collect();
     let def_path_hash = tcx.def_path_hash(def_id);
     ty::TraitDef::new(
         def_id,
@@ -1251,10 +1263,199 @@ fn trait_def(tcx: TyCtxt<'_>, def_id: DefId) -> ty::TraitDef {
         is_marker,
         skip_array_during_method_dispatch,
         spec_kind,
+        must_implement_one_of,
         def_path_hash,
     )
 }
 
+/// Parses the group of methods listed in a `#[rustc_must_implement_one_of]` attribute, which
+/// have to be methods of the trait with a default body. Returns `None` if the attribute is
+/// malformed, after reporting why.
+fn must_implement_one_of_group(
+    tcx: TyCtxt<'_>,
+    attr: &ast::Attribute,
+    trait_item_refs: &[hir::TraitItemRef],
+) -> Option<Box<[Ident]>> {
+    let list = attr.meta_item_list()?;
+    if list.len() < 2 {
+        tcx.sess
+            .struct_span_err(
+                attr.span,
+                "the `#[rustc_must_implement_one_of]` attribute must list at least 2 methods",
+            )
+            .emit();
+        return None;
+    }
+
+    let mut is_valid = true;
+    let mut group = Vec::with_capacity(list.len());
+    for item in list.iter() {
+        let ident = match item.ident() {
+            Some(ident) if item.is_word() => ident,
+            _ => {
+                tcx.sess.struct_span_err(item.span(), "expected a method name").emit();
+                is_valid = false;
+                continue;
+            }
+        };
+        match trait_item_refs.iter().find(|item_ref| item_ref.ident == ident) {
+            Some(item_ref) if !matches!(item_ref.kind, hir::AssocItemKind::Fn { .. }) => {
+                tcx.sess
+                    .struct_span_err(ident.span, "not a method of this trait")
+                    .span_note(item_ref.span, "this is not a method")
+                    .emit();
+                is_valid = false;
+            }
+            Some(item_ref) if !item_ref.defaultness.has_value() => {
+                tcx.sess
+                    .struct_span_err(ident.span, "method doesn't have a default body")
+                    .span_note(item_ref.span, "every impl already has to implement this method")
+                    .emit();
+                is_valid = false;
+            }
+            Some(_) if group.contains(&ident) => {
+                tcx.sess.struct_span_err(ident.span, "method is listed more than once").emit();
+                is_valid = false;
+            }
+            Some(_) => group.push(ident),
+            None => {
+                tcx.sess.struct_span_err(ident.span, "method not found in this trait").emit();
+                is_valid = false;
+            }
+        }
+    }
+    is_valid.then(|| group.into_boxed_slice())
+}
+
+/// Finds the methods of `trait_def_id` that the default method bodies of the trait call on
+/// `Self` every time they run. If an impl overrides none of the methods in a cycle of such
+/// calls, calling any of them recurses forever.
+fn default_body_calls(tcx: TyCtxt<'_>, trait_def_id: LocalDefId) -> FxHashMap<DefId, Vec<DefId>> {
+    let trait_def_id = trait_def_id.to_def_id();
+    let mut calls = FxHashMap::default();
+    for item in tcx.associated_items(trait_def_id).in_definition_order() {
+        if item.kind != ty::AssocKind::Fn || !item.defaultness.has_value() {
+            continue;
+        }
+        let def_id = item.def_id.expect_local();
+        let typeck_results = tcx.typeck(def_id);
+        if typeck_results.tainted_by_errors.is_some() {
+            continue;
+        }
+        let body_id = tcx.hir().body_owned_by(tcx.hir().local_def_id_to_hir_id(def_id));
+        let mut visitor = DefaultBodyCallVisitor {
+            tcx,
+            trait_def_id,
+            typeck_results,
+            conditional_depth: 0,
+            may_return_early: false,
+            callees: Vec::new(),
+        };
+        visitor.visit_body(tcx.hir().body(body_id));
+        calls.insert(item.def_id, visitor.callees);
+    }
+    calls
+}
+
+struct DefaultBodyCallVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    trait_def_id: DefId,
+    typeck_results: &'tcx ty::TypeckResults<'tcx>,
+    /// How many branches, loop bodies or lazily evaluated operands we are in.
+    conditional_depth: usize,
+    /// Whether a `return` was seen before the current expression, in which case whatever
+    /// follows doesn't always run.
+    may_return_early: bool,
+    callees: Vec<DefId>,
+}
+
+impl DefaultBodyCallVisitor<'tcx> {
+    fn visit_conditional_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        self.conditional_depth += 1;
+        self.visit_expr(expr);
+        self.conditional_depth -= 1;
+    }
+
+    /// Records a call to `def_id` if it calls a method of the trait on `Self`.
+    fn record_call(&mut self, def_id: DefId, substs: SubstsRef<'tcx>) {
+        if self.conditional_depth > 0 || self.may_return_early {
+            return;
+        }
+        if self.tcx.trait_of_item(def_id) == Some(self.trait_def_id)
+            && substs.type_at(0).is_param(0)
+            && !self.callees.contains(&def_id)
+        {
+            self.callees.push(def_id);
+        }
+    }
+}
+
+impl Visitor<'tcx> for DefaultBodyCallVisitor<'tcx> {
+    // Closures may never be called, so their bodies aren't visited.
+    type Map = intravisit::ErasedMap<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        match expr.kind {
+            hir::ExprKind::If(cond, then, else_opt) => {
+                self.visit_expr(cond);
+                self.visit_conditional_expr(then);
+                if let Some(else_expr) = else_opt {
+                    self.visit_conditional_expr(else_expr);
+                }
+                return;
+            }
+            hir::ExprKind::Match(scrutinee, arms, _) => {
+                self.visit_expr(scrutinee);
+                self.conditional_depth += 1;
+                for arm in arms {
+                    self.visit_arm(arm);
+                }
+                self.conditional_depth -= 1;
+                return;
+            }
+            hir::ExprKind::Binary(op, lhs, rhs)
+                if matches!(op.node, hir::BinOpKind::And | hir::BinOpKind::Or) =>
+            {
+                self.visit_expr(lhs);
+                self.visit_conditional_expr(rhs);
+                return;
+            }
+            hir::ExprKind::Loop(..) => {
+                self.conditional_depth += 1;
+                intravisit::walk_expr(self, expr);
+                self.conditional_depth -= 1;
+                return;
+            }
+            _ => {}
+        }
+
+        // Operands are evaluated before the call itself.
+        intravisit::walk_expr(self, expr);
+        match expr.kind {
+            hir::ExprKind::Ret(_) => self.may_return_early = true,
+            hir::ExprKind::MethodCall(..) => {
+                if let Some(def_id) = self.typeck_results.type_dependent_def_id(expr.hir_id) {
+                    self.record_call(def_id, self.typeck_results.node_substs(expr.hir_id));
+                }
+            }
+            hir::ExprKind::Call(callee, _) => {
+                if let hir::ExprKind::Path(ref qpath) = callee.kind {
+                    if let Res::Def(DefKind::AssocFn, def_id) =
+                        self.typeck_results.qpath_res(qpath, callee.hir_id)
+                    {
+                        self.record_call(def_id, self.typeck_results.node_substs(callee.hir_id));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 fn has_late_bound_regions<'tcx>(tcx: TyCtxt<'tcx>, node: Node<'tcx>) -> Option<Span> {
     struct LateBoundRegionsDetector<'tcx> {
         tcx: TyCtxt<'tcx>,
@@ -1731,7 +1932,9 @@ fn fn_sig(tcx: TyCtxt<'_>, def_id: DefId) -> ty::PolyFnSig<'_> {
         | Item(hir::Item { kind: ItemKind::Fn(sig, generics, _), ident, .. }) => {
             match get_infer_ret_ty(&sig.decl.output) {
                 Some(ty) => {
-                    let fn_sig = tcx.typeck(def_id).liberated_fn_sigs()[hir_id];
+                    // Use the diagnostic-only results, which break cycles through other functions
+                    // whose return type has to be inferred as well.
+                    let fn_sig = tcx.diagnostic_only_typeck(def_id).liberated_fn_sigs()[hir_id];
                     // Typeck doesn't expect erased regions to be returned from `type_of`.
                     let fn_sig = tcx.fold_regions(fn_sig, &mut false, |r, _| match r {
                         ty::ReErased => tcx.lifetimes.re_static,
@@ -1744,25 +1947,48 @@ fn fn_sig(tcx: TyCtxt<'_>, def_id: DefId) -> ty::PolyFnSig<'_> {
                     let mut diag = bad_placeholder_type(tcx, visitor.0, "return type");
                     let ret_ty = fn_sig.skip_binder().output();
                     if ret_ty != tcx.ty_error() {
-                        if !ret_ty.is_closure() {
-                            let ret_ty_str = match ret_ty.kind() {
-                                // Suggest a function pointer return type instead of a unique function definition
-                                // (e.g. `fn() -> i32` instead of `fn() -> i32 { f }`, the latter of which is invalid
-                                // syntax)
-                                ty::FnDef(..) => ret_ty.fn_sig(tcx).to_string(),
-                                _ => ret_ty.to_string(),
-                            };
-                            diag.span_suggestion(
-                                ty.span,
-                                "replace with the correct return type",
-                                ret_ty_str,
-                                Applicability::MaybeIncorrect,
-                            );
-                        } else {
-                            // We're dealing with a closure, so we should suggest using `impl Fn` or trait bounds
-                            // to prevent the user from getting a papercut while trying to use the unique closure
-                            // syntax (e.g. `[closure@src/lib.rs:2:5: 2:9]`).
-                            diag.help("consider using an `Fn`, `FnMut`, or `FnOnce` trait bound");
+                        let has_closure = ret_ty.walk().any(|arg| {
+                            matches!(arg.unpack(), GenericArgKind::Type(ty) if ty.is_closure())
+                        });
+                        match nameable_ty(tcx, ret_ty, TyPosition::Return) {
+                            Some(NameableTy {
+                                rendered,
+                                applicability: Applicability::MachineApplicable,
+                                ..
+                            }) => {
+                                diag.span_suggestion(
+                                    ty.span,
+                                    "replace with the correct return type",
+                                    rendered,
+                                    Applicability::MachineApplicable,
+                                );
+                            }
+                            Some(NameableTy { rendered, applicability, boxed }) => {
+                                diag.span_suggestion(
+                                    ty.span,
+                                    "replace with an appropriate return type",
+                                    rendered,
+                                    applicability,
+                                );
+                                if boxed {
+                                    diag.note(
+                                        "closures returned from within closures need to be boxed \
+                                         with `Box::new`",
+                                    );
+                                }
+                            }
+                            // The type can't be named because it contains a closure, so we
+                            // should suggest using trait bounds to prevent the user from getting
+                            // a papercut while trying to use the unique closure syntax (e.g.
+                            // `[closure@src/lib.rs:2:5: 2:9]`).
+                            None if has_closure => {
+                                diag.help(
+                                    "consider using an `Fn`, `FnMut`, or `FnOnce` trait bound",
+                                );
+                            }
+                            None => {}
+                        }
+                        if has_closure {
                             diag.note("for more information on `Fn` traits and closure types, see https://doc.rust-lang.org/book/ch13-01-closures.html");
                         }
                     }
@@ -2543,6 +2769,31 @@ fn generator_kind(tcx: TyCtxt<'_>, def_id: DefId) -> Option<hir::GeneratorKind>
     }
 }
 
+/// Collects the aliases of `#[doc(alias = "...")]` and `#[doc(alias("...", ...))]` attributes.
+/// Unlike most of the providers in this module, this works for items of other crates as well.
+fn doc_aliases(tcx: TyCtxt<'_>, def_id: DefId) -> &[Symbol] {
+    let aliases = tcx
+        .get_attrs(def_id)
+        .iter()
+        .filter(|attr| attr.has_name(sym::doc))
+        .filter_map(|attr| attr.meta_item_list())
+        .flatten()
+        .filter(|meta| meta.has_name(sym::alias))
+        .flat_map(|meta| match meta.value_str() {
+            Some(alias) => vec![alias],
+            None => meta
+                .meta_item_list()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|nested| match nested.literal()?.kind {
+                    ast::LitKind::Str(alias, _) => Some(alias),
+                    _ => None,
+                })
+                .collect(),
+        });
+    tcx.arena.alloc_from_iter(aliases)
+}
+
 fn from_target_feature(
     tcx: TyCtxt<'_>,
     id: DefId,
@@ -0,0 +1,212 @@
+//! Rendering of inferred types for suggestions that replace a `_` placeholder in an item
+//! signature.
+//!
+//! Inferred types may contain types that can't be written down, like closures, generators and
+//! opaque types. Printing those verbatim would suggest `[closure@src/lib.rs:2:5: 2:9]`, so
+//! this module renders them in a form that is valid in the position they are suggested for:
+//! function definitions become function pointers, closures become `impl Fn(..) -> ..`,
+//! `Box<dyn Fn(..) -> ..>` or function pointers, and opaque types stay `impl Trait`.
+
+use rustc_errors::Applicability;
+use rustc_hir as hir;
+use rustc_middle::ty::fold::{BottomUpFolder, TypeFoldable};
+use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_target::spec::abi::Abi;
+
+/// Where a rendered type is going to be written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum TyPosition {
+    /// The return type of a function, where `impl Trait` is allowed.
+    Return,
+    /// The type of a `const` or `static` item, which can neither be `impl Trait` nor a type
+    /// that has to be allocated, like `Box<dyn Fn()>`.
+    Item,
+}
+
+pub(super) struct NameableTy {
+    pub rendered: String,
+    /// `MachineApplicable` when the rendering denotes the inferred type itself (up to function
+    /// definitions becoming function pointers), `MaybeIncorrect` otherwise.
+    pub applicability: Applicability,
+    /// Whether some closure had to be rendered as `Box<dyn Fn(..) -> ..>`, which requires
+    /// boxing it with `Box::new` in the body as well.
+    pub boxed: bool,
+}
+
+/// Renders `ty` for a suggestion in `position`, or returns `None` when there is no way to
+/// write it down there.
+pub(super) fn nameable_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    position: TyPosition,
+) -> Option<NameableTy> {
+    let ty = ty.fold_with(&mut BottomUpFolder {
+        tcx,
+        ty_op: |ty| match *ty.kind() {
+            ty::FnDef(..) => tcx.mk_fn_ptr(ty.fn_sig(tcx)),
+            _ => ty,
+        },
+        lt_op: |r| r,
+        ct_op: |ct| ct,
+    });
+    let mut renderer = Renderer { tcx, position, exact: true, boxed: false };
+    let rendered = renderer.render(ty, position == TyPosition::Return)?;
+    let applicability = if renderer.exact {
+        Applicability::MachineApplicable
+    } else {
+        Applicability::MaybeIncorrect
+    };
+    Some(NameableTy { rendered, applicability, boxed: renderer.boxed })
+}
+
+/// Whether `ty` can be printed as is in a type annotation.
+pub(super) fn is_nameable(ty: Ty<'_>) -> bool {
+    !ty.walk().any(|arg| match arg.unpack() {
+        GenericArgKind::Type(ty) => matches!(
+            ty.kind(),
+            ty::Closure(..)
+                | ty::Generator(..)
+                | ty::GeneratorWitness(..)
+                | ty::FnDef(..)
+                | ty::Opaque(..)
+                | ty::Infer(_)
+                | ty::Bound(..)
+                | ty::Placeholder(_)
+                | ty::Error(_)
+        ),
+        GenericArgKind::Const(ct) => {
+            matches!(ct.val, ty::ConstKind::Infer(_) | ty::ConstKind::Error(_))
+        }
+        GenericArgKind::Lifetime(_) => false,
+    })
+}
+
+struct Renderer<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    position: TyPosition,
+    exact: bool,
+    boxed: bool,
+}
+
+impl<'tcx> Renderer<'tcx> {
+    /// Renders `ty`, using `impl Trait` for closures and opaque types if `impl_trait_allowed`.
+    fn render(&mut self, ty: Ty<'tcx>, impl_trait_allowed: bool) -> Option<String> {
+        if is_nameable(ty) {
+            return Some(ty.to_string());
+        }
+        Some(match *ty.kind() {
+            ty::Closure(_, substs) => self.render_closure(substs, impl_trait_allowed)?,
+            ty::Opaque(..) if impl_trait_allowed => {
+                self.exact = false;
+                ty.to_string()
+            }
+            ty::Adt(def, substs) => {
+                let path = self.tcx.def_path_str(def.did);
+                let args = self.render_substs(substs, impl_trait_allowed)?;
+                if args.is_empty() { path } else { format!("{}<{}>", path, args.join(", ")) }
+            }
+            ty::Tuple(tys) => {
+                let tys = tys
+                    .types()
+                    .map(|ty| self.render(ty, impl_trait_allowed))
+                    .collect::<Option<Vec<_>>>()?;
+                match &tys[..] {
+                    [ty] => format!("({},)", ty),
+                    tys => format!("({})", tys.join(", ")),
+                }
+            }
+            ty::Ref(r, ty, mutbl) => {
+                let r = match r {
+                    ty::ReErased => String::new(),
+                    r => format!("{} ", r),
+                };
+                format!("&{}{}{}", r, mutbl.prefix_str(), self.render(ty, impl_trait_allowed)?)
+            }
+            ty::RawPtr(ty::TypeAndMut { ty, mutbl }) => {
+                let mutbl = match mutbl {
+                    hir::Mutability::Mut => "mut",
+                    hir::Mutability::Not => "const",
+                };
+                format!("*{} {}", mutbl, self.render(ty, impl_trait_allowed)?)
+            }
+            ty::Array(ty, len) => format!("[{}; {}]", self.render(ty, impl_trait_allowed)?, len),
+            ty::Slice(ty) => format!("[{}]", self.render(ty, impl_trait_allowed)?),
+            ty::FnPtr(sig) => {
+                let sig = self.tcx.erase_late_bound_regions(sig);
+                if sig.unsafety != hir::Unsafety::Normal || sig.abi != Abi::Rust || sig.c_variadic {
+                    return None;
+                }
+                self.render_sig("fn", sig.inputs(), sig.output())?
+            }
+            _ => return None,
+        })
+    }
+
+    fn render_closure(
+        &mut self,
+        substs: SubstsRef<'tcx>,
+        impl_trait_allowed: bool,
+    ) -> Option<String> {
+        let closure = substs.as_closure();
+        let sig = self.tcx.erase_late_bound_regions(closure.sig());
+        let inputs = match sig.inputs()[0].kind() {
+            ty::Tuple(inputs) => inputs.types().collect::<Vec<_>>(),
+            _ => return None,
+        };
+        self.exact = false;
+        // Closures that don't capture anything coerce to function pointers, which is the only
+        // way to write them down outside of function signatures.
+        if self.position == TyPosition::Item {
+            if closure.upvar_tys().next().is_some() {
+                return None;
+            }
+            return self.render_sig("fn", &inputs, sig.output());
+        }
+        let trait_name = match closure.kind_ty().to_opt_closure_kind()? {
+            ty::ClosureKind::Fn => "Fn",
+            ty::ClosureKind::FnMut => "FnMut",
+            ty::ClosureKind::FnOnce => "FnOnce",
+        };
+        if impl_trait_allowed {
+            self.render_sig(&format!("impl {}", trait_name), &inputs, sig.output())
+        } else {
+            self.boxed = true;
+            let bound = self.render_sig(&format!("dyn {}", trait_name), &inputs, sig.output())?;
+            Some(format!("Box<{}>", bound))
+        }
+    }
+
+    /// Renders a signature like `fn(A, B) -> C` or `impl Fn(A, B) -> C`, depending on `prefix`.
+    fn render_sig(
+        &mut self,
+        prefix: &str,
+        inputs: &[Ty<'tcx>],
+        output: Ty<'tcx>,
+    ) -> Option<String> {
+        let inputs =
+            inputs.iter().map(|&ty| self.render(ty, false)).collect::<Option<Vec<_>>>()?;
+        let output = if output.is_unit() {
+            String::new()
+        } else {
+            format!(" -> {}", self.render(output, false)?)
+        };
+        Some(format!("{}({}){}", prefix, inputs.join(", "), output))
+    }
+
+    fn render_substs(
+        &mut self,
+        substs: SubstsRef<'tcx>,
+        impl_trait_allowed: bool,
+    ) -> Option<Vec<String>> {
+        substs
+            .iter()
+            .filter_map(|arg| match arg.unpack() {
+                GenericArgKind::Lifetime(ty::ReErased) => None,
+                GenericArgKind::Lifetime(r) => Some(Some(r.to_string())),
+                GenericArgKind::Type(ty) => Some(self.render(ty, impl_trait_allowed)),
+                GenericArgKind::Const(ct) => Some(Some(ct.to_string())),
+            })
+            .collect()
+    }
+}
@@ -9,10 +9,12 @@
 use rustc_middle::hir::map::Map;
 use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts};
 use rustc_middle::ty::util::IntTypeExt;
-use rustc_middle::ty::{self, DefIdTree, Ty, TyCtxt, TypeFoldable, TypeFolder};
+use rustc_middle::ty::{self, DefIdTree, Ty, TyCtxt, TypeFoldable};
 use rustc_span::symbol::Ident;
 use rustc_span::{Span, DUMMY_SP};
+use rustc_trait_selection::traits;
 
+use super::nameable::{nameable_ty, TyPosition};
 use super::ItemCtxt;
 use super::{bad_placeholder_type, is_suggestable_infer_ty};
 
@@ -167,6 +169,12 @@ pub(super) fn opt_const_param_of(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<
                     }
                 };
 
+                // The value of an associated const binding like `Trait<N = 3>` is not the
+                // argument of a const parameter.
+                if const_binding_of(path, hir_id).is_some() {
+                    return None;
+                }
+
                 // We've encountered an `AnonConst` in some path, so we need to
                 // figure out which generic parameter it corresponds to and return
                 // the relevant type.
@@ -240,6 +248,58 @@ pub(super) fn opt_const_param_of(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<
     }
 }
 
+/// Returns the associated const binding in `path` that `hir_id` is the value of, like the `3`
+/// of `Trait<N = 3>`.
+fn const_binding_of<'hir>(
+    path: &'hir hir::Path<'hir>,
+    hir_id: HirId,
+) -> Option<&'hir hir::TypeBinding<'hir>> {
+    path.segments.iter().filter_map(|segment| segment.args).flat_map(|args| args.bindings).find(
+        |binding| {
+            matches!(
+                binding.kind,
+                hir::TypeBindingKind::ConstEquality { ref value } if value.hir_id == hir_id
+            )
+        },
+    )
+}
+
+/// The type of the value of an associated const binding, which is the type of the associated
+/// constant it constrains.
+fn assoc_const_binding_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_ref: &hir::TraitRef<'_>,
+    binding: &hir::TypeBinding<'_>,
+) -> Ty<'tcx> {
+    let assoc_const = trait_ref.trait_def_id().and_then(|trait_def_id| {
+        traits::supertrait_def_ids(tcx, trait_def_id).find_map(|def_id| {
+            tcx.associated_items(def_id).find_by_name_and_kind(
+                tcx,
+                binding.ident,
+                ty::AssocKind::Const,
+                def_id,
+            )
+        })
+    });
+    match assoc_const {
+        Some(assoc_const) => {
+            let ty = tcx.type_of(assoc_const.def_id);
+            if ty.needs_subst() {
+                tcx.sess.span_err(
+                    binding.span,
+                    "associated const equality is not supported for constants whose type \
+                     depends on generic parameters",
+                );
+                tcx.ty_error()
+            } else {
+                ty
+            }
+        }
+        // The missing associated constant is reported when lowering the bound.
+        None => tcx.ty_error_with_message(binding.span, "associated constant of binding not found"),
+    }
+}
+
 fn get_path_containing_arg_in_pat<'hir>(
     pat: &'hir hir::Pat<'hir>,
     arg_id: HirId,
@@ -490,6 +550,11 @@ pub(super) fn type_of(tcx: TyCtxt<'_>, def_id: DefId) -> Ty<'_> {
                     ..
                 }) if ct.hir_id == hir_id => tcx.type_of(tcx.hir().local_def_id(param_hir_id)),
 
+                Node::TraitRef(trait_ref) if const_binding_of(trait_ref.path, hir_id).is_some() => {
+                    let binding = const_binding_of(trait_ref.path, hir_id).unwrap();
+                    assoc_const_binding_ty(tcx, trait_ref, binding)
+                }
+
                 x => tcx.ty_error_with_message(
                     DUMMY_SP,
                     &format!("unexpected const parent in type_of(): {:?}", x),
@@ -768,40 +833,6 @@ fn infer_placeholder_type<'a>(
     item_ident: Ident,
     kind: &'static str,
 ) -> Ty<'a> {
-    // Attempts to make the type nameable by turning FnDefs into FnPtrs.
-    struct MakeNameable<'tcx> {
-        success: bool,
-        tcx: TyCtxt<'tcx>,
-    }
-
-    impl<'tcx> MakeNameable<'tcx> {
-        fn new(tcx: TyCtxt<'tcx>) -> Self {
-            MakeNameable { success: true, tcx }
-        }
-    }
-
-    impl TypeFolder<'tcx> for MakeNameable<'tcx> {
-        fn tcx(&self) -> TyCtxt<'tcx> {
-            self.tcx
-        }
-
-        fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
-            if !self.success {
-                return ty;
-            }
-
-            match ty.kind() {
-                ty::FnDef(def_id, _) => self.tcx.mk_fn_ptr(self.tcx.fn_sig(*def_id)),
-                // FIXME: non-capturing closures should also suggest a function pointer
-                ty::Closure(..) | ty::Generator(..) => {
-                    self.success = false;
-                    ty
-                }
-                _ => ty.super_fold_with(self),
-            }
-        }
-    }
-
     let ty = tcx.diagnostic_only_typeck(def_id).node_type(body_id.hir_id);
 
     // If this came from a free `const` or `static mut?` item,
@@ -815,15 +846,12 @@ fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
             err.suggestions.clear();
 
             // Suggesting unnameable types won't help.
-            let mut mk_nameable = MakeNameable::new(tcx);
-            let ty = mk_nameable.fold_ty(ty);
-            let sugg_ty = if mk_nameable.success { Some(ty) } else { None };
-            if let Some(sugg_ty) = sugg_ty {
+            if let Some(sugg_ty) = nameable_ty(tcx, ty, TyPosition::Item) {
                 err.span_suggestion(
                     span,
                     &format!("provide a type for the {item}", item = kind),
-                    format!("{}: {}", item_ident, sugg_ty),
-                    Applicability::MachineApplicable,
+                    format!("{}: {}", item_ident, sugg_ty.rendered),
+                    sugg_ty.applicability,
                 );
             } else {
                 err.span_note(
@@ -838,14 +866,11 @@ fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
             let mut diag = bad_placeholder_type(tcx, vec![span], kind);
 
             if !ty.references_error() {
-                let mut mk_nameable = MakeNameable::new(tcx);
-                let ty = mk_nameable.fold_ty(ty);
-                let sugg_ty = if mk_nameable.success { Some(ty) } else { None };
-                if let Some(sugg_ty) = sugg_ty {
+                if let Some(sugg_ty) = nameable_ty(tcx, ty, TyPosition::Item) {
                     diag.span_suggestion(
                         span,
                         "replace with the correct type",
-                        sugg_ty.to_string(),
+                        sugg_ty.rendered,
                         Applicability::MaybeIncorrect,
                     );
                 } else {
@@ -595,13 +595,33 @@ fn walk_arm(&mut self, discr_place: &PlaceWithHirId<'tcx>, arm: &hir::Arm<'_>) {
         );
         self.walk_pat(discr_place, &arm.pat);
 
-        if let Some(hir::Guard::If(ref e)) = arm.guard {
-            self.consume_expr(e)
+        match arm.guard {
+            Some(hir::Guard::If(ref e)) => self.consume_expr(e),
+            Some(hir::Guard::IfLet(ref pat, ref e)) => self.walk_if_let_guard(pat, e),
+            None => {}
         }
 
         self.consume_expr(&arm.body);
     }
 
+    /// Walks an `if let` guard like a `match` on `scrutinee` with a single arm, whose scrutinee
+    /// is conservatively considered to be read.
+    fn walk_if_let_guard(&mut self, pat: &hir::Pat<'_>, scrutinee: &hir::Expr<'_>) {
+        self.borrow_expr(scrutinee, ty::ImmBorrow);
+
+        let scrutinee_place = return_if_err!(self.mc.cat_expr(scrutinee));
+        let closure_def_id = match scrutinee_place.place.base {
+            PlaceBase::Upvar(upvar_id) => Some(upvar_id.closure_expr_id.to_def_id()),
+            _ => None,
+        };
+        self.delegate.fake_read(
+            scrutinee_place.place.clone(),
+            FakeReadCause::ForMatchedPlace(closure_def_id),
+            scrutinee_place.hir_id,
+        );
+        self.walk_pat(&scrutinee_place, pat);
+    }
+
     /// Walks a pat that occurs in isolation (i.e., top-level of fn argument or
     /// let binding, and *not* a match arm or nested pat.)
     fn walk_irrefutable_pat(&mut self, discr_place: &PlaceWithHirId<'tcx>, pat: &hir::Pat<'_>) {
@@ -65,6 +65,7 @@
 #![feature(is_sorted)]
 #![feature(iter_zip)]
 #![feature(nll)]
+#![feature(once_cell)]
 #![feature(try_blocks)]
 #![feature(never_type)]
 #![feature(slice_partition_dedup)]
@@ -90,14 +91,17 @@
 mod errors;
 mod impl_wf_check;
 mod mem_categorization;
+mod method_resolution;
 mod outlives;
+mod place_categorization;
 mod structured_errors;
 mod variance;
 
-use rustc_errors::{struct_span_err, ErrorReported};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::{struct_span_err, Applicability, ErrorReported, FatalError, SuggestionStats};
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
-use rustc_hir::{Node, CRATE_HIR_ID};
+use rustc_hir::{LangItem, Node, CRATE_HIR_ID};
 use rustc_infer::infer::{InferOk, TyCtxtInferExt};
 use rustc_infer::traits::TraitEngineExt as _;
 use rustc_middle::middle;
@@ -105,6 +109,7 @@
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_middle::util;
 use rustc_session::config::EntryFnType;
+use rustc_session::InferVarStats;
 use rustc_span::{symbol::sym, Span, DUMMY_SP};
 use rustc_target::spec::abi::Abi;
 use rustc_trait_selection::traits::error_reporting::InferCtxtExt as _;
@@ -112,6 +117,7 @@
     self, ObligationCause, ObligationCauseCode, TraitEngine, TraitEngineExt as _,
 };
 
+use std::cmp::Reverse;
 use std::iter;
 
 use astconv::AstConv;
@@ -212,6 +218,52 @@ fn main_fn_where_clauses_span(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Span> {
         }
     }
 
+    fn main_fn_params_span(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Span> {
+        if !def_id.is_local() {
+            return None;
+        }
+        let hir_id = tcx.hir().local_def_id_to_hir_id(def_id.expect_local());
+        match tcx.hir().find(hir_id) {
+            Some(Node::Item(hir::Item { kind: hir::ItemKind::Fn(_, _, body_id), .. })) => {
+                match tcx.hir().body(*body_id).params {
+                    [] => None,
+                    [first, .., last] => Some(first.span.to(last.span)),
+                    [param] => Some(param.span),
+                }
+            }
+            _ => {
+                span_bug!(tcx.def_span(def_id), "main has a non-function type");
+            }
+        }
+    }
+
+    /// Returns the span from the generic parameters of `main` to the end of its signature,
+    /// along with the same signature without generic parameters, parameters or `where` clause.
+    fn main_fn_signature_suggestion(tcx: TyCtxt<'_>, def_id: DefId) -> Option<(Span, String)> {
+        if !def_id.is_local() {
+            return None;
+        }
+        let hir_id = tcx.hir().local_def_id_to_hir_id(def_id.expect_local());
+        match tcx.hir().find(hir_id) {
+            Some(Node::Item(hir::Item {
+                kind: hir::ItemKind::Fn(ref fn_sig, ref generics, _), ..
+            })) => {
+                let span = generics.span.with_hi(fn_sig.span.hi());
+                let sugg = match fn_sig.decl.output {
+                    hir::FnRetTy::DefaultReturn(_) => "()".to_string(),
+                    hir::FnRetTy::Return(ty) => {
+                        let ty = tcx.sess.source_map().span_to_snippet(ty.span).ok()?;
+                        format!("() -> {}", ty)
+                    }
+                };
+                Some((span, sugg))
+            }
+            _ => {
+                span_bug!(tcx.def_span(def_id), "main has a non-function type");
+            }
+        }
+    }
+
     fn main_fn_asyncness_span(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Span> {
         if !def_id.is_local() {
             return None;
@@ -246,29 +298,74 @@ fn main_fn_return_type_span(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Span> {
     let main_diagnostics_hir_id = main_fn_diagnostics_hir_id(tcx, main_def_id, main_span);
     let main_fn_generics = tcx.generics_of(main_def_id);
     let main_fn_predicates = tcx.predicates_of(main_def_id);
-    if main_fn_generics.count() != 0 || !main_fnsig.bound_vars().is_empty() {
-        let generics_param_span = main_fn_generics_params_span(tcx, main_def_id);
-        let msg = "`main` function is not allowed to have generic \
-            parameters";
-        let mut diag =
-            struct_span_err!(tcx.sess, generics_param_span.unwrap_or(main_span), E0131, "{}", msg);
-        if let Some(generics_param_span) = generics_param_span {
-            let label = "`main` cannot have generic parameters".to_string();
-            diag.span_label(generics_param_span, label);
+    let generics_param_span = main_fn_generics_params_span(tcx, main_def_id);
+    let generics_where_clauses_span = main_fn_where_clauses_span(tcx, main_def_id);
+    let params_span = main_fn_params_span(tcx, main_def_id);
+    let has_generics = main_fn_generics.count() != 0 || !main_fnsig.bound_vars().is_empty();
+    // generics may bring in implicit predicates, so we only look at the `where` clause itself
+    // if generics are present.
+    let has_where_clause = generics_where_clauses_span.is_some()
+        || (!has_generics && !main_fn_predicates.predicates.is_empty());
+    let has_params = !main_fnsig.inputs().skip_binder().is_empty();
+    if has_generics || has_where_clause || has_params {
+        // Report everything that's wrong with the signature of `main` in a single error, named
+        // after the first problem.
+        let mut diag = if has_generics {
+            struct_span_err!(
+                tcx.sess,
+                generics_param_span.unwrap_or(main_span),
+                E0131,
+                "`main` function is not allowed to have generic parameters"
+            )
+        } else if has_where_clause {
+            struct_span_err!(
+                tcx.sess,
+                generics_where_clauses_span.unwrap_or(main_span),
+                E0646,
+                "`main` function is not allowed to have a `where` clause"
+            )
+        } else {
+            struct_span_err!(
+                tcx.sess,
+                params_span.unwrap_or(main_span),
+                E0580,
+                "`main` function has wrong type"
+            )
+        };
+        let mut removed = vec![];
+        if has_generics {
+            if let Some(generics_param_span) = generics_param_span {
+                diag.span_label(generics_param_span, "`main` cannot have generic parameters");
+            }
+            removed.push("generic parameters");
         }
-        diag.emit();
-        error = true;
-    } else if !main_fn_predicates.predicates.is_empty() {
-        // generics may bring in implicit predicates, so we skip this check if generics is present.
-        let generics_where_clauses_span = main_fn_where_clauses_span(tcx, main_def_id);
-        let mut diag = struct_span_err!(
-            tcx.sess,
-            generics_where_clauses_span.unwrap_or(main_span),
-            E0646,
-            "`main` function is not allowed to have a `where` clause"
-        );
-        if let Some(generics_where_clauses_span) = generics_where_clauses_span {
-            diag.span_label(generics_where_clauses_span, "`main` cannot have a `where` clause");
+        if has_where_clause {
+            if let Some(generics_where_clauses_span) = generics_where_clauses_span {
+                diag.span_label(generics_where_clauses_span, "`main` cannot have a `where` clause");
+            }
+            removed.push("`where` clause");
+        }
+        if has_params {
+            if let Some(params_span) = params_span {
+                diag.span_label(params_span, "`main` cannot have parameters");
+            }
+            diag.help("command-line arguments can be read with `std::env::args()`");
+            removed.push("parameters");
+        }
+        traits::error_reporting::note_valid_main_signatures(&mut diag);
+        if let Some((span, sugg)) = main_fn_signature_suggestion(tcx, main_def_id) {
+            let removed = match removed.split_last() {
+                Some((last, rest)) if !rest.is_empty() => {
+                    format!("{} and {}", rest.join(", "), last)
+                }
+                _ => removed.concat(),
+            };
+            diag.span_suggestion_verbose(
+                span,
+                &format!("remove the {}", removed),
+                sugg,
+                Applicability::MaybeIncorrect,
+            );
         }
         diag.emit();
         error = true;
@@ -325,7 +422,7 @@ fn main_fn_return_type_span(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Span> {
                 main_diagnostics_hir_id,
                 ObligationCauseCode::MainFunctionType,
             );
-            let mut fulfillment_cx = traits::FulfillmentContext::new();
+            let mut fulfillment_cx = <dyn TraitEngine<'_>>::new(tcx);
             fulfillment_cx.register_bound(&infcx, ty::ParamEnv::empty(), return_ty, term_id, cause);
             if let Err(err) = fulfillment_cx.select_all_or_error(&infcx) {
                 infcx.report_fulfillment_errors(&err, None, false);
@@ -461,12 +558,51 @@ pub fn provide(providers: &mut Providers) {
     check::provide(providers);
     variance::provide(providers);
     outlives::provide(providers);
+    place_categorization::provide(providers);
+    method_resolution::provide(providers);
     impl_wf_check::provide(providers);
 }
 
 pub fn check_crate(tcx: TyCtxt<'_>) -> Result<(), ErrorReported> {
     let _prof_timer = tcx.sess.timer("type_check_crate");
 
+    let suggestion_stats = tcx.sess.opts.debugging_opts.suggestion_stats;
+    if suggestion_stats {
+        // Drop the suggestions emitted by earlier passes, only type checking is measured.
+        tcx.sess.diagnostic().take_suggestion_stats();
+    }
+    check_sized_lang_item(tcx);
+    let result = check_crate_items(tcx);
+    if suggestion_stats {
+        print_suggestion_stats(tcx.sess.diagnostic().take_suggestion_stats());
+    }
+    if tcx.sess.opts.debugging_opts.typeck_stats {
+        print_typeck_stats(tcx.sess.code_stats.take_infer_var_stats());
+    }
+    result
+}
+
+/// Type checking can't get far without `Sized`, which `#![no_core]` crates have to define
+/// themselves. Rather than failing wherever it is first needed, which is often a place without
+/// a span, report it missing once and point at the `#![no_core]` attribute.
+fn check_sized_lang_item(tcx: TyCtxt<'_>) {
+    if tcx.lang_items().sized_trait().is_some() {
+        return;
+    }
+    let krate_attrs = tcx.hir().krate_attrs();
+    if let Some(attr) = krate_attrs.iter().find(|attr| tcx.sess.check_name(attr, sym::no_core)) {
+        let mut err = tcx.sess.struct_span_fatal(attr.span, "requires `sized` lang_item");
+        err.span_label(attr.span, "this crate doesn't use the `sized` lang item of `core`");
+        err.note(
+            "type checking needs `Sized` to check that values have a size known at compile time",
+        );
+        tcx.suggest_defining_lang_item(&mut err, LangItem::Sized);
+        err.emit();
+        FatalError.raise();
+    }
+}
+
+fn check_crate_items(tcx: TyCtxt<'_>) -> Result<(), ErrorReported> {
     // this ensures that later parts of type checking can assume that items
     // have valid types and not error
     // FIXME(matthewjasper) We shouldn't need to use `track_errors`.
@@ -517,6 +653,86 @@ pub fn check_crate(tcx: TyCtxt<'_>) -> Result<(), ErrorReported> {
     if tcx.sess.err_count() == 0 { Ok(()) } else { Err(ErrorReported) }
 }
 
+/// Prints the `-Z suggestion-stats` summary: how many structured suggestions were emitted for
+/// each applicability, followed by a breakdown by suggestion message.
+fn print_suggestion_stats(stats: SuggestionStats) {
+    let mut by_applicability = FxHashMap::default();
+    for (&(_, applicability), &count) in &stats {
+        *by_applicability.entry(format!("{:?}", applicability)).or_insert(0) += count;
+    }
+    let mut by_applicability: Vec<_> = by_applicability.into_iter().collect();
+    by_applicability.sort_by_key(|(applicability, count)| (Reverse(*count), applicability.clone()));
+
+    eprintln!("\nSUGGESTION STATS\n");
+    eprintln!("{:<24}{:>10}", "Applicability", "Count");
+    eprintln!("----------------------------------");
+    for (applicability, count) in &by_applicability {
+        eprintln!("{:<24}{:>10}", applicability, count);
+    }
+    eprintln!("----------------------------------");
+    eprintln!("{:<24}{:>10}", "Total", stats.values().sum::<usize>());
+
+    let mut by_message: Vec<_> = stats.into_iter().collect();
+    by_message.sort_by_key(|((message, applicability), count)| {
+        (Reverse(*count), message.clone(), format!("{:?}", applicability))
+    });
+    eprintln!("\n{:<24}{:>10}  {}", "Applicability", "Count", "Message");
+    eprintln!("----------------------------------------------------------------");
+    for ((message, applicability), count) in by_message {
+        eprintln!("{:<24}{:>10}  {}", format!("{:?}", applicability), count, message);
+    }
+}
+
+/// Prints the `-Z typeck-stats` summary: how many inference variables of each kind were created
+/// over all the bodies of the crate, the bodies that needed the most, and a histogram of the
+/// number of type variables per body.
+fn print_typeck_stats(stats: Vec<(String, InferVarStats)>) {
+    let max_by = |f: fn(&InferVarStats) -> usize| {
+        stats
+            .iter()
+            .max_by_key(|(body, stats)| (f(stats), Reverse(body.clone())))
+            .map_or((0, ""), |(body, stats)| (f(stats), &body[..]))
+    };
+    let sum_by = |f: fn(&InferVarStats) -> usize| {
+        stats.iter().map(|(_, stats)| f(stats)).sum::<usize>().to_string()
+    };
+    let kinds: [(&str, fn(&InferVarStats) -> usize); 4] = [
+        ("Type variables", |stats| stats.type_vars),
+        ("Integer variables", |stats| stats.int_vars),
+        ("Float variables", |stats| stats.float_vars),
+        ("Region variables", |stats| stats.region_vars),
+    ];
+
+    eprintln!("\nTYPECK STATS\n");
+    eprintln!("Bodies checked: {}\n", stats.len());
+    eprintln!("{:<24}{:>10}{:>10}  {}", "Variables", "Total", "Max", "Body with max");
+    eprintln!("----------------------------------------------------------------");
+    for (name, f) in kinds {
+        let (max, body) = max_by(f);
+        eprintln!("{:<24}{:>10}{:>10}  {}", name, sum_by(f), max, body);
+    }
+    // Summing the largest unified set of every body wouldn't mean anything.
+    let (max, body) = max_by(|stats| stats.largest_unified_set);
+    eprintln!("{:<24}{:>10}{:>10}  {}", "Largest unified set", "-", max, body);
+
+    let mut histogram = [0; 5];
+    for (_, stats) in &stats {
+        let bucket = match stats.type_vars {
+            0 => 0,
+            1..=9 => 1,
+            10..=99 => 2,
+            100..=999 => 3,
+            _ => 4,
+        };
+        histogram[bucket] += 1;
+    }
+    eprintln!("\n{:<24}{:>10}", "Type variables per body", "Bodies");
+    eprintln!("----------------------------------");
+    for (range, count) in ["0", "1-9", "10-99", "100-999", "1000+"].iter().zip(histogram) {
+        eprintln!("{:<24}{:>10}", range, count);
+    }
+}
+
 /// A quasi-deprecated helper used in rustdoc and clippy to get
 /// the type from a HIR node.
 pub fn hir_ty_to_ty<'tcx>(tcx: TyCtxt<'tcx>, hir_ty: &hir::Ty<'_>) -> Ty<'tcx> {
@@ -0,0 +1,71 @@
+//! Resolution of the method calls of a body to the definitions that run for them.
+//!
+//! Type checking only records the method named by a call, which for a trait method is the
+//! method of the trait. This resolves those through trait selection, the way codegen does,
+//! except that calls whose implementation depends on the generic parameters of the body are
+//! left unresolved instead of being monomorphized.
+
+use rustc_hir as hir;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_middle::ty::method_resolution::{MethodResolution, MethodResolutions, MethodSource};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::subst::SubstsRef;
+use rustc_middle::ty::{self, TyCtxt, TypeFoldable};
+
+pub fn provide(providers: &mut Providers) {
+    *providers = Providers { method_resolutions, ..*providers };
+}
+
+fn method_resolutions<'tcx>(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> MethodResolutions<'tcx> {
+    let typeck_results = tcx.typeck(def_id);
+    let mut resolutions = MethodResolutions::default();
+    // Selection could fail in all sorts of ways on bodies that didn't typeck.
+    if typeck_results.tainted_by_errors.is_some() {
+        return resolutions;
+    }
+
+    let param_env = tcx.param_env(def_id);
+    for (&local_id, res) in typeck_results.type_dependent_defs().iter() {
+        let method = match *res {
+            Ok((DefKind::AssocFn, method)) => method,
+            _ => continue,
+        };
+        let hir_id = hir::HirId { owner: typeck_results.hir_owner, local_id };
+        let substs = typeck_results.node_substs(hir_id);
+        if let Some(source) = method_source(tcx, param_env, method, substs) {
+            resolutions.calls.insert(local_id, MethodResolution { method, substs, source });
+        }
+    }
+    resolutions
+}
+
+fn method_source<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    method: DefId,
+    substs: SubstsRef<'tcx>,
+) -> Option<MethodSource<'tcx>> {
+    if substs.references_error() {
+        return None;
+    }
+    let substs = tcx.normalize_erasing_regions(param_env, substs);
+    let instance = match ty::Instance::resolve(tcx, param_env, method, substs).ok()? {
+        Some(instance) => instance,
+        None => return Some(MethodSource::Generic),
+    };
+    Some(match instance.def {
+        ty::InstanceDef::Item(def) if tcx.def_kind(def.did) == DefKind::AssocFn => {
+            match tcx.impl_of_method(def.did) {
+                Some(impl_def_id) => {
+                    MethodSource::Impl { impl_def_id, item: def.did, substs: instance.substs }
+                }
+                None => MethodSource::TraitDefault { item: def.did, substs: instance.substs },
+            }
+        }
+        ty::InstanceDef::Virtual(..) => MethodSource::Virtual,
+        // Closure bodies, and the shims for function pointers, `FnOnce` on closures, `Clone`
+        // and the like.
+        _ => MethodSource::Builtin,
+    })
+}
@@ -9,7 +9,8 @@
 use super::explicit::ExplicitPredicatesMap;
 use super::utils::*;
 
-/// Infer predicates for the items in the crate.
+/// Infer predicates for the items in the crate, along with the field
+/// each of them was inferred from.
 ///
 /// `global_inferred_outlives`: this is initially the empty map that
 ///     was generated by walking the items in the crate. This will
@@ -17,12 +18,13 @@
 pub fn infer_predicates<'tcx>(
     tcx: TyCtxt<'tcx>,
     explicit_map: &mut ExplicitPredicatesMap<'tcx>,
-) -> FxHashMap<DefId, RequiredPredicates<'tcx>> {
+) -> (FxHashMap<DefId, RequiredPredicates<'tcx>>, FxHashMap<DefId, RequiredPredicateFields<'tcx>>) {
     debug!("infer_predicates");
 
     let mut predicates_added = true;
 
     let mut global_inferred_outlives = FxHashMap::default();
+    let mut global_inferred_fields = FxHashMap::default();
 
     // If new predicates were added then we need to re-calculate
     // all crates since there could be new implied predicates.
@@ -32,6 +34,7 @@ pub fn infer_predicates<'tcx>(
         let mut visitor = InferVisitor {
             tcx,
             global_inferred_outlives: &mut global_inferred_outlives,
+            global_inferred_fields: &mut global_inferred_fields,
             predicates_added: &mut predicates_added,
             explicit_map,
         };
@@ -40,12 +43,13 @@ pub fn infer_predicates<'tcx>(
         tcx.hir().krate().visit_all_item_likes(&mut visitor);
     }
 
-    global_inferred_outlives
+    (global_inferred_outlives, global_inferred_fields)
 }
 
 pub struct InferVisitor<'cx, 'tcx> {
     tcx: TyCtxt<'tcx>,
     global_inferred_outlives: &'cx mut FxHashMap<DefId, RequiredPredicates<'tcx>>,
+    global_inferred_fields: &'cx mut FxHashMap<DefId, RequiredPredicateFields<'tcx>>,
     predicates_added: &'cx mut bool,
     explicit_map: &'cx mut ExplicitPredicatesMap<'tcx>,
 }
@@ -57,6 +61,7 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
         debug!("InferVisitor::visit_item(item={:?})", item_did);
 
         let mut item_required_predicates = RequiredPredicates::default();
+        let mut item_predicate_fields = RequiredPredicateFields::default();
         match item.kind {
             hir::ItemKind::Union(..) | hir::ItemKind::Enum(..) | hir::ItemKind::Struct(..) => {
                 let adt_def = self.tcx.adt_def(item_did.to_def_id());
@@ -71,14 +76,22 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                     // requirements for adt_def.
                     let field_ty = self.tcx.type_of(field_def.did);
                     let field_span = self.tcx.def_span(field_def.did);
+                    let mut field_required_predicates = RequiredPredicates::default();
                     insert_required_predicates_to_be_wf(
                         self.tcx,
                         field_ty,
                         field_span,
                         self.global_inferred_outlives,
-                        &mut item_required_predicates,
+                        &mut field_required_predicates,
                         &mut self.explicit_map,
                     );
+
+                    // Point each predicate at the first field that requires it,
+                    // rather than at the definition of the type it came from.
+                    for &predicate in field_required_predicates.keys() {
+                        item_required_predicates.entry(predicate).or_insert(field_span);
+                        item_predicate_fields.entry(predicate).or_insert(field_def.did);
+                    }
                 }
             }
 
@@ -96,6 +109,7 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
         if item_required_predicates.len() > item_predicates_len {
             *self.predicates_added = true;
             self.global_inferred_outlives.insert(item_did.to_def_id(), item_required_predicates);
+            self.global_inferred_fields.insert(item_did.to_def_id(), item_predicate_fields);
         }
     }
 
@@ -1,8 +1,8 @@
 use hir::Node;
 use rustc_hir as hir;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_middle::ty::query::Providers;
-use rustc_middle::ty::subst::GenericArgKind;
+use rustc_middle::ty::subst::{GenericArg, GenericArgKind};
 use rustc_middle::ty::{self, CratePredicatesMap, ToPredicate, TyCtxt};
 use rustc_span::symbol::sym;
 use rustc_span::Span;
@@ -14,7 +14,12 @@
 mod utils;
 
 pub fn provide(providers: &mut Providers) {
-    *providers = Providers { inferred_outlives_of, inferred_outlives_crate, ..*providers };
+    *providers = Providers {
+        inferred_outlives_of,
+        inferred_outlives_fields_of,
+        inferred_outlives_crate,
+        ..*providers
+    };
 }
 
 fn inferred_outlives_of(tcx: TyCtxt<'_>, item_def_id: DefId) -> &[(ty::Predicate<'_>, Span)] {
@@ -58,6 +63,14 @@ fn inferred_outlives_of(tcx: TyCtxt<'_>, item_def_id: DefId) -> &[(ty::Predicate
     }
 }
 
+fn inferred_outlives_fields_of(
+    tcx: TyCtxt<'_>,
+    item_def_id: LocalDefId,
+) -> &[(ty::Predicate<'_>, DefId)] {
+    let crate_map = tcx.inferred_outlives_crate(());
+    crate_map.predicate_fields.get(&item_def_id.to_def_id()).copied().unwrap_or(&[])
+}
+
 fn inferred_outlives_crate(tcx: TyCtxt<'_>, (): ()) -> CratePredicatesMap<'_> {
     // Compute a map from each struct/enum/union S to the **explicit**
     // outlives predicates (`T: 'a`, `'a: 'b`) that the user wrote.
@@ -69,7 +82,8 @@ fn inferred_outlives_crate(tcx: TyCtxt<'_>, (): ()) -> CratePredicatesMap<'_> {
     // Compute the inferred predicates
     let mut exp_map = explicit::ExplicitPredicatesMap::new();
 
-    let global_inferred_outlives = implicit_infer::infer_predicates(tcx, &mut exp_map);
+    let (global_inferred_outlives, global_inferred_fields) =
+        implicit_infer::infer_predicates(tcx, &mut exp_map);
 
     // Convert the inferred predicates into the "collected" form the
     // global data structure expects.
@@ -79,31 +93,46 @@ fn inferred_outlives_crate(tcx: TyCtxt<'_>, (): ()) -> CratePredicatesMap<'_> {
     let predicates = global_inferred_outlives
         .iter()
         .map(|(&def_id, set)| {
-            let predicates = &*tcx.arena.alloc_from_iter(set.iter().filter_map(
-                |(ty::OutlivesPredicate(kind1, region2), &span)| {
-                    match kind1.unpack() {
-                        GenericArgKind::Type(ty1) => Some((
-                            ty::PredicateKind::TypeOutlives(ty::OutlivesPredicate(ty1, region2))
-                                .to_predicate(tcx),
-                            span,
-                        )),
-                        GenericArgKind::Lifetime(region1) => Some((
-                            ty::PredicateKind::RegionOutlives(ty::OutlivesPredicate(
-                                region1, region2,
-                            ))
-                            .to_predicate(tcx),
-                            span,
-                        )),
-                        GenericArgKind::Const(_) => {
-                            // Generic consts don't impose any constraints.
-                            None
-                        }
-                    }
-                },
-            ));
+            let predicates = &*tcx.arena.alloc_from_iter(
+                set.iter().filter_map(|(&predicate, &span)| {
+                    Some((outlives_predicate_to_predicate(tcx, predicate)?, span))
+                }),
+            );
             (def_id, predicates)
         })
         .collect();
 
-    ty::CratePredicatesMap { predicates }
+    let predicate_fields = global_inferred_fields
+        .iter()
+        .map(|(&def_id, fields)| {
+            let fields = &*tcx.arena.alloc_from_iter(
+                fields.iter().filter_map(|(&predicate, &field_def_id)| {
+                    Some((outlives_predicate_to_predicate(tcx, predicate)?, field_def_id))
+                }),
+            );
+            (def_id, fields)
+        })
+        .collect();
+
+    ty::CratePredicatesMap { predicates, predicate_fields }
+}
+
+fn outlives_predicate_to_predicate<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    predicate: ty::OutlivesPredicate<GenericArg<'tcx>, ty::Region<'tcx>>,
+) -> Option<ty::Predicate<'tcx>> {
+    let ty::OutlivesPredicate(kind1, region2) = predicate;
+    match kind1.unpack() {
+        GenericArgKind::Type(ty1) => Some(
+            ty::PredicateKind::TypeOutlives(ty::OutlivesPredicate(ty1, region2)).to_predicate(tcx),
+        ),
+        GenericArgKind::Lifetime(region1) => Some(
+            ty::PredicateKind::RegionOutlives(ty::OutlivesPredicate(region1, region2))
+                .to_predicate(tcx),
+        ),
+        GenericArgKind::Const(_) => {
+            // Generic consts don't impose any constraints.
+            None
+        }
+    }
 }
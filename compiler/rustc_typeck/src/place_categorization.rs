@@ -0,0 +1,244 @@
+//! Categorization of the place expressions of a body, along with the reason each immutable
+//! place can't be assigned to or mutably borrowed.
+//!
+//! Unlike `mem_categorization`, which builds the complete `Place` of an expression for the
+//! `ExprUseVisitor`, this only records the outermost operation of every place expression and
+//! whether the place is mutable. Overloaded dereferences and indexing are judged by whether the
+//! type implements `DerefMut` or `IndexMut`, not by the trait the expression ended up using, so
+//! that a place reached through a `MutexGuard` is mutable even where it's only read.
+
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_middle::hir::map::Map;
+use rustc_middle::hir::place::{
+    DerefKind, ImmutablePlaceReason, PlaceCategorization, PlaceCategorizations, PlaceCategory,
+};
+use rustc_middle::ty::adjustment::{Adjust, Adjustment, PointerCast};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::subst::GenericArg;
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeFoldable};
+
+pub fn provide(providers: &mut Providers) {
+    *providers = Providers { place_categorizations, ..*providers };
+}
+
+fn place_categorizations<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+) -> PlaceCategorizations<'tcx> {
+    let body_id = tcx.hir().body_owned_by(tcx.hir().local_def_id_to_hir_id(def_id));
+    let mut visitor = PlaceCategorizer {
+        tcx,
+        param_env: tcx.param_env(def_id),
+        typeck_results: tcx.typeck(def_id),
+        body_owner: def_id,
+        places: PlaceCategorizations::default(),
+    };
+    visitor.visit_body(tcx.hir().body(body_id));
+    visitor.places
+}
+
+type MutabilityResult<'tcx> = Result<(), ImmutablePlaceReason<'tcx>>;
+
+struct PlaceCategorizer<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    typeck_results: &'tcx ty::TypeckResults<'tcx>,
+    body_owner: LocalDefId,
+    places: PlaceCategorizations<'tcx>,
+}
+
+impl<'tcx> PlaceCategorizer<'tcx> {
+    fn category(&self, expr: &hir::Expr<'_>) -> Option<PlaceCategory> {
+        let typeck_results = self.typeck_results;
+        Some(match expr.kind {
+            hir::ExprKind::Path(ref qpath) => match typeck_results.qpath_res(qpath, expr.hir_id) {
+                Res::Local(var_id) if self.is_upvar(var_id) => PlaceCategory::Upvar(var_id),
+                Res::Local(var_id) => PlaceCategory::Local(var_id),
+                Res::Def(DefKind::Static, def_id) => PlaceCategory::StaticItem(def_id),
+                _ => return None,
+            },
+            hir::ExprKind::Unary(hir::UnOp::Deref, base) => {
+                PlaceCategory::Deref(if typeck_results.is_method_call(expr) {
+                    DerefKind::Overloaded
+                } else {
+                    match *typeck_results.expr_ty_adjusted(base).kind() {
+                        ty::Ref(_, _, mutbl) => DerefKind::Ref(mutbl),
+                        ty::RawPtr(ty::TypeAndMut { mutbl, .. }) => DerefKind::RawPtr(mutbl),
+                        _ => DerefKind::Box,
+                    }
+                })
+            }
+            hir::ExprKind::Field(base, _) if typeck_results.expr_ty_adjusted(base).is_union() => {
+                PlaceCategory::UnionField
+            }
+            hir::ExprKind::Field(..) => PlaceCategory::Field,
+            hir::ExprKind::Index(..) => {
+                PlaceCategory::Index { overloaded: typeck_results.is_method_call(expr) }
+            }
+            _ => return None,
+        })
+    }
+
+    fn is_upvar(&self, var_id: hir::HirId) -> bool {
+        self.tcx
+            .upvars_mentioned(self.body_owner.to_def_id())
+            .map_or(false, |upvars| upvars.contains_key(&var_id))
+    }
+
+    /// Whether `expr` is a mutable place once `adjustments`, a prefix of its adjustments, have
+    /// been applied to it.
+    fn mutability(
+        &self,
+        expr: &hir::Expr<'_>,
+        adjustments: &[Adjustment<'tcx>],
+    ) -> MutabilityResult<'tcx> {
+        let typeck_results = self.typeck_results;
+        if let Some((adjustment, previous)) = adjustments.split_last() {
+            let source_ty =
+                previous.last().map_or_else(|| typeck_results.expr_ty(expr), |adj| adj.target);
+            let inner = || self.mutability(expr, previous);
+            return match adjustment.kind {
+                Adjust::Deref(None) => self.deref_mutability(source_ty, expr.hir_id, inner),
+                Adjust::Deref(Some(_)) => {
+                    self.overloaded_deref_mutability(source_ty, expr.hir_id, inner)
+                }
+                // The other adjustments all produce temporaries.
+                Adjust::NeverToAny | Adjust::Borrow(_) | Adjust::Pointer(_) => Ok(()),
+            };
+        }
+
+        match expr.kind {
+            hir::ExprKind::Path(ref qpath) => match typeck_results.qpath_res(qpath, expr.hir_id) {
+                Res::Local(var_id) => match typeck_results.pat_binding_modes().get(var_id) {
+                    Some(ty::BindByValue(hir::Mutability::Mut)) | None => Ok(()),
+                    Some(_) => Err(ImmutablePlaceReason::BindingNotMut(var_id)),
+                },
+                Res::Def(DefKind::Static, def_id) if !self.tcx.is_mutable_static(def_id) => {
+                    Err(ImmutablePlaceReason::ImmutableStatic(def_id))
+                }
+                _ => Ok(()),
+            },
+            hir::ExprKind::Unary(hir::UnOp::Deref, base) if typeck_results.is_method_call(expr) => {
+                let adjustments = self.overloaded_operand_adjustments(base);
+                let self_ty = typeck_results.node_substs(expr.hir_id).type_at(0);
+                self.overloaded_deref_mutability(self_ty, base.hir_id, || {
+                    self.mutability(base, adjustments)
+                })
+            }
+            hir::ExprKind::Unary(hir::UnOp::Deref, base) => {
+                let base_ty = typeck_results.expr_ty_adjusted(base);
+                self.deref_mutability(base_ty, base.hir_id, || {
+                    self.mutability(base, typeck_results.expr_adjustments(base))
+                })
+            }
+            hir::ExprKind::Field(base, _) => {
+                self.mutability(base, typeck_results.expr_adjustments(base))
+            }
+            hir::ExprKind::Index(base, _) if typeck_results.is_method_call(expr) => {
+                let substs = typeck_results.node_substs(expr.hir_id);
+                let (self_ty, index_ty) = (substs.type_at(0), substs.type_at(1));
+                let index_mut_trait = self.tcx.lang_items().index_mut_trait();
+                if !self.implements_trait(index_mut_trait, self_ty, &[index_ty.into()]) {
+                    return Err(ImmutablePlaceReason::BehindIndex(self_ty, base.hir_id));
+                }
+                self.mutability(base, self.overloaded_operand_adjustments(base))
+            }
+            hir::ExprKind::Index(base, _) => {
+                self.mutability(base, typeck_results.expr_adjustments(base))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The adjustments of the operand of an overloaded dereference or indexing, without the
+    /// autoref (and unsizing) that passes it to the trait method.
+    fn overloaded_operand_adjustments(&self, base: &hir::Expr<'_>) -> &'tcx [Adjustment<'tcx>] {
+        let mut adjustments = self.typeck_results.expr_adjustments(base);
+        if let [previous @ .., Adjustment { kind: Adjust::Pointer(PointerCast::Unsize), .. }] =
+            adjustments
+        {
+            adjustments = previous;
+        }
+        if let [previous @ .., Adjustment { kind: Adjust::Borrow(_), .. }] = adjustments {
+            adjustments = previous;
+        }
+        adjustments
+    }
+
+    fn deref_mutability(
+        &self,
+        ty: Ty<'tcx>,
+        pointer: hir::HirId,
+        inner: impl FnOnce() -> MutabilityResult<'tcx>,
+    ) -> MutabilityResult<'tcx> {
+        match *ty.kind() {
+            ty::Ref(_, _, hir::Mutability::Not) => {
+                Err(ImmutablePlaceReason::BehindRef(ty, pointer))
+            }
+            // A `&mut` reference only has to be accessible uniquely, which doesn't require the
+            // binding holding it to be mutable.
+            ty::Ref(_, _, hir::Mutability::Mut) => match inner() {
+                Err(ImmutablePlaceReason::BindingNotMut(_)) => Ok(()),
+                mutability => mutability,
+            },
+            ty::RawPtr(ty::TypeAndMut { mutbl: hir::Mutability::Not, .. }) => {
+                Err(ImmutablePlaceReason::BehindConstPtr(ty, pointer))
+            }
+            ty::RawPtr(_) => Ok(()),
+            // `Box<T>` is mutable whenever the box is.
+            _ => inner(),
+        }
+    }
+
+    fn overloaded_deref_mutability(
+        &self,
+        ty: Ty<'tcx>,
+        pointer: hir::HirId,
+        inner: impl FnOnce() -> MutabilityResult<'tcx>,
+    ) -> MutabilityResult<'tcx> {
+        if self.implements_trait(self.tcx.lang_items().deref_mut_trait(), ty, &[]) {
+            // `DerefMut::deref_mut` takes `&mut self`.
+            inner()
+        } else {
+            Err(ImmutablePlaceReason::BehindDeref(ty, pointer))
+        }
+    }
+
+    fn implements_trait(
+        &self,
+        trait_def_id: Option<DefId>,
+        ty: Ty<'tcx>,
+        params: &[GenericArg<'tcx>],
+    ) -> bool {
+        // Don't blame the trait for types that didn't typeck.
+        if ty.references_error() {
+            return true;
+        }
+        trait_def_id.map_or(false, |trait_def_id| {
+            let ty = self.tcx.erase_regions(ty);
+            let params = self.tcx.erase_regions(self.tcx.intern_substs(params));
+            self.tcx.type_implements_trait((trait_def_id, ty, params, self.param_env))
+        })
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for PlaceCategorizer<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let Some(category) = self.category(expr) {
+            let mutability = self.mutability(expr, &[]);
+            self.places
+                .places
+                .insert(expr.hir_id.local_id, PlaceCategorization { category, mutability });
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
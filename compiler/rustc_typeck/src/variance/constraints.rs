@@ -5,6 +5,7 @@
 
 use hir::def_id::{DefId, LocalDefId};
 use rustc_hir as hir;
+use rustc_hir::def::DefKind;
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
 use rustc_middle::ty::{self, Ty, TyCtxt};
@@ -88,7 +89,7 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                 }
             }
 
-            hir::ItemKind::Fn(..) => {
+            hir::ItemKind::Fn(..) | hir::ItemKind::OpaqueTy(..) => {
                 self.visit_node_helper(item.hir_id());
             }
 
@@ -97,14 +98,23 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
     }
 
     fn visit_trait_item(&mut self, trait_item: &hir::TraitItem<'_>) {
-        if let hir::TraitItemKind::Fn(..) = trait_item.kind {
-            self.visit_node_helper(trait_item.hir_id());
+        match trait_item.kind {
+            hir::TraitItemKind::Fn(..) => self.visit_node_helper(trait_item.hir_id()),
+            // Generic associated types.
+            hir::TraitItemKind::Type(..) if !trait_item.generics.params.is_empty() => {
+                self.visit_node_helper(trait_item.hir_id())
+            }
+            _ => {}
         }
     }
 
     fn visit_impl_item(&mut self, impl_item: &hir::ImplItem<'_>) {
-        if let hir::ImplItemKind::Fn(..) = impl_item.kind {
-            self.visit_node_helper(impl_item.hir_id());
+        match impl_item.kind {
+            hir::ImplItemKind::Fn(..) => self.visit_node_helper(impl_item.hir_id()),
+            hir::ImplItemKind::TyAlias(..) if !impl_item.generics.params.is_empty() => {
+                self.visit_node_helper(impl_item.hir_id())
+            }
+            _ => {}
         }
     }
 
@@ -138,6 +148,14 @@ fn build_constraints_for_item(&mut self, def_id: LocalDefId) {
         let id = tcx.hir().local_def_id_to_hir_id(def_id);
         let inferred_start = self.terms_cx.inferred_starts[&id];
         let current_item = &CurrentItem { inferred_start };
+
+        // The `type_of` an opaque type or associated type is the type it stands
+        // for, which says nothing about how its parameters may be related.
+        if let DefKind::OpaqueTy | DefKind::AssocTy = tcx.def_kind(def_id) {
+            self.add_constraints_from_captures(current_item, def_id);
+            return;
+        }
+
         match tcx.type_of(def_id).kind() {
             ty::Adt(def, _) => {
                 // Not entirely obvious: constraints on structs/enums do not
@@ -169,6 +187,36 @@ fn build_constraints_for_item(&mut self, def_id: LocalDefId) {
         }
     }
 
+    /// Opaque types and generic associated types have no structure to infer
+    /// variances from, so every parameter they capture is invariant. The only
+    /// parameters an `impl Trait` in return position doesn't capture are the
+    /// lifetime parameters of its function which aren't named in its bounds,
+    /// and those are left bivariant, so that `impl Trait + 'a` only depends
+    /// on `'a` and not on the other lifetimes of the function.
+    fn add_constraints_from_captures(&mut self, current: &CurrentItem, def_id: LocalDefId) {
+        let tcx = self.tcx();
+        let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+        let uncaptured = match tcx.hir().get(hir_id) {
+            hir::Node::Item(hir::Item {
+                kind: hir::ItemKind::OpaqueTy(hir::OpaqueTy { impl_trait_fn: Some(fn_def_id), .. }),
+                ..
+            }) => tcx
+                .generics_of(*fn_def_id)
+                .params
+                .iter()
+                .filter(|param| matches!(param.kind, ty::GenericParamDefKind::Lifetime))
+                .map(|param| param.index)
+                .collect(),
+            _ => vec![],
+        };
+
+        for index in 0..tcx.generics_of(def_id).count() as u32 {
+            if !uncaptured.contains(&index) {
+                self.add_constraint(current, index, self.invariant);
+            }
+        }
+    }
+
     fn add_constraint(&mut self, current: &CurrentItem, index: u32, variance: VarianceTermPtr<'a>) {
         debug!("add_constraint(index={}, variance={:?})", index, variance);
         self.constraints.push(Constraint {
@@ -48,19 +48,20 @@ fn variances_of(tcx: TyCtxt<'_>, item_def_id: DefId) -> &[ty::Variance] {
             hir::ItemKind::Enum(..)
             | hir::ItemKind::Struct(..)
             | hir::ItemKind::Union(..)
-            | hir::ItemKind::Fn(..) => {}
+            | hir::ItemKind::Fn(..)
+            | hir::ItemKind::OpaqueTy(..) => {}
 
             _ => unsupported(),
         },
 
         Node::TraitItem(item) => match item.kind {
-            hir::TraitItemKind::Fn(..) => {}
+            hir::TraitItemKind::Fn(..) | hir::TraitItemKind::Type(..) => {}
 
             _ => unsupported(),
         },
 
         Node::ImplItem(item) => match item.kind {
-            hir::ImplItemKind::Fn(..) => {}
+            hir::ImplItemKind::Fn(..) | hir::ImplItemKind::TyAlias(..) => {}
 
             _ => unsupported(),
         },
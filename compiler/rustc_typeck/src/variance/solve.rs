@@ -6,6 +6,7 @@
 //! inferred is then written into the `variance_map` in the tcx.
 
 use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
 use rustc_middle::ty;
 
@@ -107,7 +108,8 @@ fn create_map(&self) -> FxHashMap<DefId, &'tcx [ty::Variance]> {
                 self.enforce_const_invariance(generics, variances);
 
                 // Functions are permitted to have unused generic parameters: make those invariant.
-                if let ty::FnDef(..) = tcx.type_of(def_id).kind() {
+                // Opaque types keep theirs bivariant, those are the lifetimes they don't capture.
+                if let DefKind::Fn | DefKind::AssocFn | DefKind::Ctor(..) = tcx.def_kind(def_id) {
                     for variance in variances.iter_mut() {
                         if *variance == ty::Bivariant {
                             *variance = ty::Invariant;
@@ -149,7 +149,7 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
                 }
             }
 
-            hir::ItemKind::Fn(..) => {
+            hir::ItemKind::Fn(..) | hir::ItemKind::OpaqueTy(..) => {
                 self.add_inferreds_for_item(item.hir_id());
             }
 
@@ -158,14 +158,23 @@ fn visit_item(&mut self, item: &hir::Item<'_>) {
     }
 
     fn visit_trait_item(&mut self, trait_item: &hir::TraitItem<'_>) {
-        if let hir::TraitItemKind::Fn(..) = trait_item.kind {
-            self.add_inferreds_for_item(trait_item.hir_id());
+        match trait_item.kind {
+            hir::TraitItemKind::Fn(..) => self.add_inferreds_for_item(trait_item.hir_id()),
+            // Generic associated types.
+            hir::TraitItemKind::Type(..) if !trait_item.generics.params.is_empty() => {
+                self.add_inferreds_for_item(trait_item.hir_id())
+            }
+            _ => {}
         }
     }
 
     fn visit_impl_item(&mut self, impl_item: &hir::ImplItem<'_>) {
-        if let hir::ImplItemKind::Fn(..) = impl_item.kind {
-            self.add_inferreds_for_item(impl_item.hir_id());
+        match impl_item.kind {
+            hir::ImplItemKind::Fn(..) => self.add_inferreds_for_item(impl_item.hir_id()),
+            hir::ImplItemKind::TyAlias(..) if !impl_item.generics.params.is_empty() => {
+                self.add_inferreds_for_item(impl_item.hir_id())
+            }
+            _ => {}
         }
     }
 
@@ -112,6 +112,7 @@ pub trait Default: Sized {
     ///     fn default() -> Self { Kind::A }
     /// }
     /// ```
+    #[cfg_attr(not(bootstrap), lang = "default_fn")]
     #[stable(feature = "rust1", since = "1.0.0")]
     fn default() -> Self;
 }
@@ -1,7 +1,11 @@
 use crate::future::Future;
 
 /// Conversion into a `Future`.
+///
+/// `.await` converts its operand with `into_future`, so any type implementing `IntoFuture` can
+/// be awaited.
 #[unstable(feature = "into_future", issue = "67644")]
+#[rustc_on_unimplemented(label = "`{Self}` is not a future", message = "`{Self}` is not a future")]
 pub trait IntoFuture {
     /// The output that the future will produce on completion.
     #[unstable(feature = "into_future", issue = "67644")]
@@ -13,6 +17,7 @@ pub trait IntoFuture {
 
     /// Creates a future from a value.
     #[unstable(feature = "into_future", issue = "67644")]
+    #[cfg_attr(not(bootstrap), lang = "into_future")]
     fn into_future(self) -> Self::Future;
 }
 
@@ -157,6 +157,7 @@
 /// [`HashSet`]: ../../std/collections/struct.HashSet.html
 /// [`hash`]: Hash::hash
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg_attr(not(test), rustc_diagnostic_item = "Hash")]
 pub trait Hash {
     /// Feeds this value into the given [`Hasher`].
     ///
@@ -187,6 +187,13 @@
 #[unstable(feature = "try_trait_v2", issue = "84277")]
 pub use self::try_trait::Try;
 
+#[unstable(feature = "try_trait_v2_yeet", issue = "none")]
+pub use self::try_trait::Yeet;
+
+#[unstable(feature = "yeet_desugar_details", issue = "none")]
+#[doc(hidden)]
+pub use self::try_trait::from_yeet;
+
 #[unstable(feature = "try_trait_transition", reason = "for bootstrap", issue = "none")]
 pub(crate) use self::try_trait::Try as TryV2;
 
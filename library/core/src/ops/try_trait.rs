@@ -311,6 +311,12 @@ pub trait Try: FromResidual {
         label = "cannot use the `?` operator in {ItemContext} that returns `{Self}`",
         enclosing_scope = "this function should return `Result` or `Option` to accept `?`"
     ),
+    on(
+        from_desugaring = "YeetExpr",
+        message = "`do yeet` can only be used in {ItemContext} that can absorb its residual",
+        label = "this `do yeet` produces `{R}`, which is incompatible with `{Self}`",
+        enclosing_scope = "this function returns `{Self}`"
+    ),
 )]
 #[unstable(feature = "try_trait_v2", issue = "84277")]
 pub trait FromResidual<R = <Self as Try>::Residual> {
@@ -338,3 +344,29 @@ pub trait FromResidual<R = <Self as Try>::Residual> {
     #[unstable(feature = "try_trait_v2", issue = "84277")]
     fn from_residual(residual: R) -> Self;
 }
+
+/// The residual of a `do yeet` expression.
+///
+/// Implement `FromResidual<Yeet<T>>` to allow `do yeet` with a value of type `T`
+/// in a function or `try` block that returns `Self`.
+///
+/// `do yeet x` desugars to `return FromResidual::from_residual(Yeet(x))` or,
+/// inside of a `try` block, to a `break` out of that block with the same value.
+#[unstable(feature = "try_trait_v2_yeet", issue = "none")]
+#[derive(Debug)]
+pub struct Yeet<T>(pub T);
+
+/// The function used to desugar `do yeet` expressions.
+///
+/// Having a `where` clause on a free function means that the obligation that
+/// fails when the return type can't absorb the yeeted value points at the
+/// `do yeet` expression, rather than at some unrelated `from_residual` call.
+#[cfg_attr(not(bootstrap), lang = "from_yeet")]
+#[unstable(feature = "yeet_desugar_details", issue = "none")]
+#[inline]
+pub fn from_yeet<T, Y>(yeeted: Y) -> T
+where
+    T: FromResidual<Yeet<Y>>,
+{
+    FromResidual::from_residual(Yeet(yeeted))
+}
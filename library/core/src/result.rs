@@ -1654,3 +1654,11 @@ fn from_residual(residual: Result<convert::Infallible, E>) -> Self {
         }
     }
 }
+
+#[unstable(feature = "try_trait_v2_yeet", issue = "none")]
+impl<T, E, F: From<E>> ops::FromResidual<ops::Yeet<E>> for Result<T, F> {
+    #[inline]
+    fn from_residual(ops::Yeet(e): ops::Yeet<E>) -> Self {
+        Err(From::from(e))
+    }
+}
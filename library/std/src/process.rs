@@ -1979,8 +1979,13 @@ pub fn id() -> u32 {
 #[cfg_attr(not(test), lang = "termination")]
 #[unstable(feature = "termination_trait_lib", issue = "43301")]
 #[rustc_on_unimplemented(
-    message = "`main` has invalid return type `{Self}`",
-    label = "`main` can only return types that implement `{Termination}`"
+    on(
+        cause = "MainFunctionType",
+        message = "`main` has invalid return type `{Self}`",
+        label = "`main` can only return types that implement `{Termination}`"
+    ),
+    message = "invalid return type `{Self}` for a test or entry point",
+    label = "tests and entry points can only return types that implement `{Termination}`"
 )]
 pub trait Termination {
     /// Is called to get the representation of the value as status code.
@@ -2228,7 +2228,13 @@ fn clean(&self, cx: &mut DocContext<'_>) -> TypeBindingKind {
             hir::TypeBindingKind::Equality { ref ty } => {
                 TypeBindingKind::Equality { ty: ty.clean(cx) }
             }
-            hir::TypeBindingKind::Constraint { ref bounds } => {
+            hir::TypeBindingKind::ConstEquality { ref value } => {
+                TypeBindingKind::ConstEquality { expr: print_const_expr(cx.tcx, value.body) }
+            }
+            // FIXME: rustdoc has no way to render `method()` yet, so these show up as plain
+            // associated type bounds.
+            hir::TypeBindingKind::Constraint { ref bounds }
+            | hir::TypeBindingKind::ReturnConstraint { ref bounds } => {
                 TypeBindingKind::Constraint { bounds: bounds.iter().map(|b| b.clean(cx)).collect() }
             }
         }
@@ -2209,6 +2209,7 @@ impl Import {
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
 crate enum TypeBindingKind {
     Equality { ty: Type },
+    ConstEquality { expr: String },
     Constraint { bounds: Vec<GenericBound> },
 }
 
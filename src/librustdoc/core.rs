@@ -296,6 +296,7 @@ impl<'tcx> DocContext<'tcx> {
         }),
         make_codegen_backend: None,
         registry: rustc_driver::diagnostics_registry(),
+        cancellation_flag: None,
     }
 }
 
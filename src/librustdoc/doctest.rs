@@ -103,6 +103,7 @@
         override_queries: None,
         make_codegen_backend: None,
         registry: rustc_driver::diagnostics_registry(),
+        cancellation_flag: None,
     };
 
     let mut test_args = options.test_args.clone();
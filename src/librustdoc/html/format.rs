@@ -1371,6 +1371,13 @@ impl clean::TypeBinding {
                         write!(f, " = {}", ty.print(cx))?;
                     }
                 }
+                clean::TypeBindingKind::ConstEquality { ref expr } => {
+                    if f.alternate() {
+                        write!(f, " = {}", expr)?;
+                    } else {
+                        write!(f, " = {}", Escape(expr))?;
+                    }
+                }
                 clean::TypeBindingKind::Constraint { ref bounds } => {
                     if !bounds.is_empty() {
                         if f.alternate() {
@@ -163,6 +163,7 @@ fn from_tcx(kind: clean::TypeBindingKind, tcx: TyCtxt<'_>) -> Self {
         use clean::TypeBindingKind::*;
         match kind {
             Equality { ty } => TypeBindingKind::Equality(ty.into_tcx(tcx)),
+            ConstEquality { expr } => TypeBindingKind::ConstEquality(expr),
             Constraint { bounds } => {
                 TypeBindingKind::Constraint(bounds.into_iter().map(|a| a.into_tcx(tcx)).collect())
             }
@@ -148,6 +148,7 @@ pub struct TypeBinding {
 #[serde(rename_all = "snake_case")]
 pub enum TypeBindingKind {
     Equality(Type),
+    ConstEquality(String),
     Constraint(Vec<GenericBound>),
 }
 
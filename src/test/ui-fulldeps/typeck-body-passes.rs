@@ -0,0 +1,82 @@
+// run-pass
+// Test that analysis passes run by overriding the queries that type-check bodies see every body.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+extern crate rustc_typeck;
+
+use rustc_driver::Compilation;
+use rustc_hir::Body;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty;
+use rustc_session::config::Input;
+use rustc_span::FileName;
+use rustc_typeck::check::{body_passes, FnCtxt};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const INPUT: &str = r#"
+#![feature(lang_items, no_core)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+pub const ONE: u8 = 1;
+
+pub fn id(x: u8) -> u8 {
+    x
+}
+"#;
+
+static BODIES: AtomicUsize = AtomicUsize::new(0);
+
+fn count_body(fcx: &FnCtxt<'_, '_>, body: &Body<'_>, results: &ty::TypeckResults<'_>) {
+    assert_eq!(results.hir_owner, fcx.tcx.hir().body_owner(body.id()).owner);
+    BODIES.fetch_add(1, Ordering::SeqCst);
+}
+
+struct OverrideTypeck;
+
+impl rustc_driver::Callbacks for OverrideTypeck {
+    fn config(&mut self, config: &mut interface::Config) {
+        let name = FileName::Custom("input".to_string());
+        config.input = Input::Str { name, input: INPUT.to_string() };
+        config.input_path = None;
+        config.override_queries = Some(|_, providers, _| {
+            providers.typeck_and_used_trait_imports = |tcx, def_id| {
+                body_passes::typeck_and_used_trait_imports(tcx, def_id, &[count_body])
+            };
+            providers.typeck_const_arg =
+                |tcx, key| body_passes::typeck_const_arg(tcx, key, &[count_body]);
+        });
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        _queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = ["rustc", "input.rs", "--crate-type=lib"].iter().map(|arg| arg.to_string());
+    let args = args.collect::<Vec<_>>();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut OverrideTypeck).run()
+    })
+    .unwrap()
+    .unwrap();
+    assert_eq!(BODIES.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,110 @@
+// run-pass
+// Test that `method_completion_candidates` lists the methods a call would pick at each autoderef
+// step of the receiver, along with the autoref applied to it.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_hir::Mutability;
+use rustc_interface::{interface, Queries};
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const INPUT: &str = r#"
+#![feature(lang_items, no_core)]
+#![no_core]
+#![allow(unused_variables)]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[lang = "receiver"]
+trait Receiver {}
+
+pub struct Inner;
+
+impl Inner {
+    pub fn by_value(self) {}
+    pub fn by_ref(&self) {}
+    pub fn by_mut(&mut self) {}
+}
+
+pub trait Tr {
+    fn tr(&self) {}
+}
+
+impl Tr for Inner {}
+
+pub fn receiver(inner: &Inner) {}
+"#;
+
+struct CheckMethodCompletions;
+
+impl rustc_driver::Callbacks for CheckMethodCompletions {
+    fn config(&mut self, config: &mut interface::Config) {
+        let name = FileName::Custom("input".to_string());
+        config.input = Input::Str { name, input: INPUT.to_string() };
+        config.input_path = None;
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            let def_id = tcx
+                .body_owners()
+                .find(|&def_id| tcx.def_path_str(def_id.to_def_id()) == "receiver")
+                .unwrap();
+            let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+            let body = tcx.hir().body(tcx.hir().body_owned_by(hir_id));
+            let self_ty = tcx.typeck(def_id).node_type(body.params[0].hir_id);
+
+            let steps = tcx
+                .method_completion_candidates((def_id, self_ty))
+                .steps
+                .iter()
+                .map(|step| {
+                    let mut methods = step
+                        .methods
+                        .iter()
+                        .map(|method| (tcx.item_name(method.def_id).to_string(), method.autoref))
+                        .collect::<Vec<_>>();
+                    methods.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    let methods = methods
+                        .into_iter()
+                        .map(|(name, autoref)| match autoref {
+                            None => name,
+                            Some(Mutability::Not) => format!("&{}", name),
+                            Some(Mutability::Mut) => format!("&mut {}", name),
+                        })
+                        .collect::<Vec<_>>();
+                    format!("{} at {}: {}", step.self_ty, step.autoderefs, methods.join(", "))
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(steps, ["&Inner at 0: by_ref, tr", "Inner at 1: &mut by_mut, by_value"]);
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = ["rustc", "input.rs", "--crate-type=lib"].iter().map(|arg| arg.to_string());
+    let args = args.collect::<Vec<_>>();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut CheckMethodCompletions).run()
+    })
+    .unwrap()
+    .unwrap();
+}
@@ -0,0 +1,121 @@
+// run-pass
+// Test that `method_resolutions` resolves the method calls of a body to the impl items or trait
+// defaults that run for them, and leaves those depending on generic parameters unresolved.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_hir::HirId;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::method_resolution::MethodSource;
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const INPUT: &str = r#"
+#![feature(lang_items, no_core)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[lang = "receiver"]
+trait Receiver {}
+
+pub struct S;
+
+impl S {
+    pub fn inherent(&self) {}
+}
+
+pub trait Tr {
+    fn overridden(&self);
+    fn defaulted(&self) {}
+}
+
+impl Tr for S {
+    fn overridden(&self) {}
+}
+
+pub fn concrete(s: &S) {
+    s.inherent();
+    s.overridden();
+    s.defaulted();
+}
+
+pub fn generic<T: Tr>(t: &T) {
+    t.overridden();
+}
+"#;
+
+struct CheckMethodResolutions;
+
+impl rustc_driver::Callbacks for CheckMethodResolutions {
+    fn config(&mut self, config: &mut interface::Config) {
+        let name = FileName::Custom("input".to_string());
+        config.input = Input::Str { name, input: INPUT.to_string() };
+        config.input_path = None;
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            let source_map = tcx.sess.source_map();
+            for def_id in tcx.body_owners() {
+                let owner = tcx.typeck(def_id).hir_owner;
+                let mut calls = tcx.method_resolutions(def_id).calls.iter().collect::<Vec<_>>();
+                calls.sort_by_key(|&(&local_id, _)| local_id);
+                let calls = calls
+                    .into_iter()
+                    .map(|(&local_id, resolution)| {
+                        let span = tcx.hir().span(HirId { owner, local_id });
+                        let source = match resolution.source {
+                            MethodSource::Impl { item, .. } => tcx.def_path_str(item),
+                            MethodSource::TraitDefault { item, .. } => {
+                                format!("default {}", tcx.def_path_str(item))
+                            }
+                            source => format!("{:?}", source),
+                        };
+                        format!("{} -> {}", source_map.span_to_snippet(span).unwrap(), source)
+                    })
+                    .collect::<Vec<_>>();
+                match &*tcx.def_path_str(def_id.to_def_id()) {
+                    "concrete" => assert_eq!(
+                        calls,
+                        [
+                            "s.inherent() -> S::inherent",
+                            "s.overridden() -> <S as Tr>::overridden",
+                            "s.defaulted() -> default Tr::defaulted",
+                        ]
+                    ),
+                    "generic" => assert_eq!(calls, ["t.overridden() -> Generic"]),
+                    _ => assert!(calls.is_empty()),
+                }
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = ["rustc", "input.rs", "--crate-type=lib"].iter().map(|arg| arg.to_string());
+    let args = args.collect::<Vec<_>>();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut CheckMethodResolutions).run()
+    })
+    .unwrap()
+    .unwrap();
+}
@@ -0,0 +1,117 @@
+// run-pass
+// Test that typeck records the rvalues it knows to be eligible for promotion, and why.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_hir::HirId;
+use rustc_interface::{interface, Queries};
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const INPUT: &str = r#"
+#![feature(lang_items, no_core)]
+#![no_core]
+#![allow(unused_variables)]
+
+#[lang = "sized"]
+trait Sized {}
+
+pub const ONE: u8 = 1;
+
+pub const fn identity(x: u8) -> u8 {
+    x
+}
+
+pub const PAIR: &(u8, u8) = &(ONE, identity(2));
+
+pub fn promotes(x: u8) {
+    let candidate = &(ONE, [2, 3]);
+    let local = &(x, 1);
+    let call = &identity(1);
+}
+"#;
+
+struct CheckPromotionCandidates;
+
+impl rustc_driver::Callbacks for CheckPromotionCandidates {
+    fn config(&mut self, config: &mut interface::Config) {
+        let name = FileName::Custom("input".to_string());
+        config.input = Input::Str { name, input: INPUT.to_string() };
+        config.input_path = None;
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            let source_map = tcx.sess.source_map();
+            for def_id in tcx.body_owners() {
+                let results = tcx.typeck(def_id);
+                let mut candidates = results.promotion_candidates().iter().collect::<Vec<_>>();
+                candidates.sort_by_key(|&(&local_id, _)| local_id);
+                let candidates = candidates
+                    .into_iter()
+                    .map(|(&local_id, candidacy)| {
+                        let span = tcx.hir().span(HirId { owner: results.hir_owner, local_id });
+                        format!("{}: {:?}", source_map.span_to_snippet(span).unwrap(), candidacy)
+                    })
+                    .collect::<Vec<_>>();
+                match &*tcx.def_path_str(def_id.to_def_id()) {
+                    "ONE" => assert_eq!(candidates, ["1: Literal"]),
+                    "identity" => assert!(candidates.is_empty()),
+                    // Calls to any `const fn` can be promoted in the initializer of a constant.
+                    "PAIR" => assert_eq!(
+                        candidates,
+                        [
+                            "ONE: ConstPath",
+                            "identity: ConstPath",
+                            "2: Literal",
+                            "identity(2): ConstFnCall",
+                            "(ONE, identity(2)): Aggregate",
+                            "&(ONE, identity(2)): Borrow",
+                        ]
+                    ),
+                    "promotes" => assert_eq!(
+                        candidates,
+                        [
+                            "ONE: ConstPath",
+                            "2: Literal",
+                            "3: Literal",
+                            "[2, 3]: Aggregate",
+                            "(ONE, [2, 3]): Aggregate",
+                            "&(ONE, [2, 3]): Borrow",
+                            "1: Literal",
+                            "identity: ConstPath",
+                            "1: Literal",
+                        ]
+                    ),
+                    path => panic!("unexpected body {}", path),
+                }
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = ["rustc", "input.rs", "--crate-type=lib"].iter().map(|arg| arg.to_string());
+    let args = args.collect::<Vec<_>>();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut CheckPromotionCandidates).run()
+    })
+    .unwrap()
+    .unwrap();
+}
@@ -0,0 +1,129 @@
+// run-pass
+// Test that `FnCtxt::resolve_field_path` resolves nested fields without auto-dereferencing and
+// reports private and nonexistent fields.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+extern crate rustc_typeck;
+
+use rustc_hir::Body;
+use rustc_interface::interface;
+use rustc_middle::ty;
+use rustc_session::config::Input;
+use rustc_session::DiagnosticOutput;
+use rustc_span::symbol::Ident;
+use rustc_span::FileName;
+use rustc_typeck::check::{body_passes, FnCtxt};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+const INPUT: &str = r#"
+#![feature(lang_items, no_core)]
+#![no_core]
+#![allow(dead_code, unused_variables)]
+
+#[lang = "sized"]
+trait Sized {}
+
+mod m {
+    pub struct Outer {
+        pub pair: (u8, u16),
+        secret: u8,
+    }
+}
+
+pub fn fields(outer: m::Outer, by_ref: &m::Outer, pair: (u8, u16)) {}
+"#;
+
+static RESOLVED: AtomicBool = AtomicBool::new(false);
+
+fn resolve_fields<'tcx>(
+    fcx: &FnCtxt<'_, 'tcx>,
+    body: &'tcx Body<'tcx>,
+    results: &'tcx ty::TypeckResults<'tcx>,
+) {
+    let param_ty = |index: usize| results.node_type(body.params[index].hir_id);
+    let resolve = |index: usize, path: &str| {
+        let fields = path.split('.').map(Ident::from_str).collect::<Vec<_>>();
+        fcx.resolve_field_path(body.value.hir_id, param_ty(index), &fields)
+    };
+
+    let resolved = resolve(0, "pair.1").unwrap();
+    assert_eq!(resolved.len(), 2);
+    assert_eq!(resolved[0].container, param_ty(0));
+    assert_eq!(resolved[0].index, 0);
+    assert_eq!(resolved[0].def.unwrap().ident.to_string(), "pair");
+    assert_eq!(resolved[0].ty, param_ty(2));
+    assert_eq!(resolved[1].container, param_ty(2));
+    assert_eq!(resolved[1].index, 1);
+    assert!(resolved[1].def.is_none());
+    assert_eq!(resolved[1].ty, fcx.tcx.types.u16);
+
+    assert!(resolve(0, "secret").is_err());
+    assert!(resolve(1, "pair").is_err());
+    assert!(resolve(2, "2").is_err());
+
+    RESOLVED.store(true, Ordering::SeqCst);
+}
+
+#[derive(Clone, Default)]
+struct Diagnostics(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Diagnostics {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct OverrideTypeck {
+    diagnostics: Diagnostics,
+}
+
+impl rustc_driver::Callbacks for OverrideTypeck {
+    fn config(&mut self, config: &mut interface::Config) {
+        let name = FileName::Custom("input".to_string());
+        config.input = Input::Str { name, input: INPUT.to_string() };
+        config.input_path = None;
+        config.diagnostic_output = DiagnosticOutput::Raw(Box::new(self.diagnostics.clone()));
+        config.override_queries = Some(|_, providers, _| {
+            providers.typeck_and_used_trait_imports = |tcx, def_id| {
+                body_passes::typeck_and_used_trait_imports(tcx, def_id, &[resolve_fields])
+            };
+        });
+    }
+}
+
+fn main() {
+    let args = ["rustc", "input.rs", "--crate-type=lib"].iter().map(|arg| arg.to_string());
+    let args = args.collect::<Vec<_>>();
+    let diagnostics = Diagnostics::default();
+    let mut callbacks = OverrideTypeck { diagnostics: diagnostics.clone() };
+    let result = rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut callbacks).run()
+    });
+    assert!(!matches!(result, Ok(Ok(()))));
+    assert!(RESOLVED.load(Ordering::SeqCst));
+
+    let diagnostics = String::from_utf8(diagnostics.0.lock().unwrap().clone()).unwrap();
+    assert!(diagnostics.contains("field `secret` of struct `m::Outer` is private"));
+    assert!(diagnostics.contains("no field `pair` on type `&m::Outer`"));
+    assert!(diagnostics.contains(
+        "`&m::Outer` is not dereferenced to look for fields of the type it points to"
+    ));
+    assert!(diagnostics.contains("no field `2` on type `(u8, u16)`"));
+}
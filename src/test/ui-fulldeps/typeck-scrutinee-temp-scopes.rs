@@ -0,0 +1,114 @@
+// run-pass
+// Test that typeck records where the temporaries of the scrutinees of `if let` expressions and
+// `if let` guards are dropped under each version of the rules.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_hir::HirId;
+use rustc_interface::{interface, Queries};
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const INPUT: &str = r#"
+#![feature(lang_items, no_core, if_let_guard)]
+#![no_core]
+#![allow(incomplete_features)]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[lang = "copy"]
+trait Copy {}
+
+pub enum Opt {
+    Some(u8),
+    None,
+}
+
+pub fn if_let(o: Opt) -> u8 {
+    if let Opt::Some(x) = o { x } else { 0 }
+}
+
+pub fn guard(o: Opt) -> u8 {
+    match o {
+        Opt::Some(n) if let Opt::Some(y) = Opt::Some(n) => y,
+        _ => 0,
+    }
+}
+"#;
+
+struct CheckScrutineeTempScopes;
+
+impl rustc_driver::Callbacks for CheckScrutineeTempScopes {
+    fn config(&mut self, config: &mut interface::Config) {
+        let name = FileName::Custom("input".to_string());
+        config.input = Input::Str { name, input: INPUT.to_string() };
+        config.input_path = None;
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            let source_map = tcx.sess.source_map();
+            for def_id in tcx.body_owners() {
+                let results = tcx.typeck(def_id);
+                let snippet = |local_id| {
+                    let span = tcx.hir().span(HirId { owner: results.hir_owner, local_id });
+                    source_map.span_to_snippet(span).unwrap()
+                };
+                let scopes = results
+                    .scrutinee_temp_scopes()
+                    .iter()
+                    .map(|(&local_id, scope)| {
+                        format!(
+                            "{}: {:?}, v1 until `{}`, v2 until `{}`",
+                            snippet(local_id),
+                            scope.version,
+                            snippet(scope.v1_scope),
+                            snippet(scope.v2_scope),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                match &*tcx.def_path_str(def_id.to_def_id()) {
+                    "if_let" => assert_eq!(
+                        scopes,
+                        ["o: V1, v1 until `if let Opt::Some(x) = o { x } else { 0 }`, \
+                         v2 until `{ x }`"]
+                    ),
+                    "guard" => assert_eq!(
+                        scopes,
+                        ["Opt::Some(n): V1, \
+                         v1 until `Opt::Some(n) if let Opt::Some(y) = Opt::Some(n) => y`, \
+                         v2 until `Opt::Some(n)`"]
+                    ),
+                    path => panic!("unexpected body {}", path),
+                }
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = ["rustc", "input.rs", "--crate-type=lib"].iter().map(|arg| arg.to_string());
+    let args = args.collect::<Vec<_>>();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut CheckScrutineeTempScopes).run()
+    })
+    .unwrap()
+    .unwrap();
+}
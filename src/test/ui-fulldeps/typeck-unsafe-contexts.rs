@@ -0,0 +1,133 @@
+// run-pass
+// Test that typeck records the unsafe context allowing each unsafe operation, and the unsafe
+// context already covering each nested unsafe block.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_hir::HirId;
+use rustc_interface::{interface, Queries};
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const INPUT: &str = r#"
+#![feature(lang_items, no_core)]
+#![no_core]
+#![allow(unused_unsafe)]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[lang = "copy"]
+trait Copy {}
+
+pub unsafe fn danger() {}
+
+pub static mut COUNT: u8 = 0;
+
+pub unsafe fn in_unsafe_fn(p: *const u8) -> u8 {
+    danger();
+    unsafe { *p }
+}
+
+pub fn in_blocks() -> u8 {
+    unsafe { danger(); unsafe { COUNT } }
+}
+"#;
+
+struct CheckUnsafeContexts;
+
+impl rustc_driver::Callbacks for CheckUnsafeContexts {
+    fn config(&mut self, config: &mut interface::Config) {
+        let name = FileName::Custom("input".to_string());
+        config.input = Input::Str { name, input: INPUT.to_string() };
+        config.input_path = None;
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            let source_map = tcx.sess.source_map();
+            for def_id in tcx.body_owners() {
+                let results = tcx.typeck(def_id);
+                let snippet = |local_id| {
+                    let span = tcx.hir().span(HirId { owner: results.hir_owner, local_id });
+                    source_map.span_to_snippet(span).unwrap()
+                };
+                let owner_hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+                let context = |context: HirId| {
+                    if context == owner_hir_id {
+                        "the function".to_string()
+                    } else {
+                        format!("`{}`", snippet(context.local_id))
+                    }
+                };
+
+                let mut ops = results.unsafe_ops().iter().collect::<Vec<_>>();
+                ops.sort_by_key(|&(&local_id, _)| local_id);
+                let ops = ops
+                    .into_iter()
+                    .map(|(&local_id, op)| {
+                        format!("`{}` in {}", snippet(local_id), context(op.context.unwrap()))
+                    })
+                    .collect::<Vec<_>>();
+                let nested = results
+                    .nested_unsafe_blocks()
+                    .iter()
+                    .map(|(&local_id, &covering)| {
+                        format!("`{}` in {}", snippet(local_id), context(covering))
+                    })
+                    .collect::<Vec<_>>();
+
+                match &*tcx.def_path_str(def_id.to_def_id()) {
+                    "danger" | "COUNT" => {
+                        assert!(ops.is_empty());
+                        assert!(nested.is_empty());
+                    }
+                    "in_unsafe_fn" => {
+                        assert_eq!(ops, ["`danger()` in the function", "`*p` in the function"]);
+                        assert_eq!(nested, ["`unsafe { *p }` in the function"]);
+                    }
+                    "in_blocks" => {
+                        assert_eq!(
+                            ops,
+                            [
+                                "`danger()` in `unsafe { danger(); unsafe { COUNT } }`",
+                                "`COUNT` in `unsafe { COUNT }`",
+                            ]
+                        );
+                        assert_eq!(
+                            nested,
+                            ["`unsafe { COUNT }` in `unsafe { danger(); unsafe { COUNT } }`"]
+                        );
+                    }
+                    path => panic!("unexpected body {}", path),
+                }
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = ["rustc", "input.rs", "--crate-type=lib"].iter().map(|arg| arg.to_string());
+    let args = args.collect::<Vec<_>>();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut CheckUnsafeContexts).run()
+    })
+    .unwrap()
+    .unwrap();
+}
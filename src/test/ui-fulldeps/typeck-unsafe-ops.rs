@@ -0,0 +1,123 @@
+// run-pass
+// Test that typeck records why each operation it finds requires `unsafe`.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_interface::{interface, Queries};
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const INPUT: &str = r#"
+#![feature(lang_items, no_core)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[lang = "copy"]
+trait Copy {}
+
+#[lang = "receiver"]
+trait Receiver {}
+
+pub struct Raw;
+
+impl Raw {
+    pub unsafe fn method(&self) {}
+}
+
+pub unsafe fn function() {}
+
+pub static mut COUNT: u8 = 0;
+
+extern "C" {
+    pub static EXTERN: u8;
+}
+
+pub union Bits {
+    pub int: u8,
+    pub flag: bool,
+}
+
+pub fn calls(raw: &Raw) {
+    unsafe {
+        function();
+        raw.method();
+    }
+}
+
+pub fn deref(p: *const u8) -> u8 {
+    unsafe { *p }
+}
+
+pub fn statics() -> (u8, u8) {
+    unsafe { (COUNT, EXTERN) }
+}
+
+// Ignoring a union field doesn't read it.
+pub fn union_fields(bits: Bits) -> u8 {
+    unsafe {
+        let Bits { flag: _ } = bits;
+        let Bits { int } = bits;
+        int
+    }
+}
+"#;
+
+struct CheckUnsafeOps;
+
+impl rustc_driver::Callbacks for CheckUnsafeOps {
+    fn config(&mut self, config: &mut interface::Config) {
+        let name = FileName::Custom("input".to_string());
+        config.input = Input::Str { name, input: INPUT.to_string() };
+        config.input_path = None;
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            for def_id in tcx.body_owners() {
+                let mut ops = tcx.typeck(def_id).unsafe_ops().iter().collect::<Vec<_>>();
+                ops.sort_by_key(|&(&local_id, _)| local_id);
+                let ops = ops
+                    .into_iter()
+                    .map(|(_, op)| op.details.description_and_note().0)
+                    .collect::<Vec<_>>();
+                match &*tcx.def_path_str(def_id.to_def_id()) {
+                    "Raw::method" | "function" | "COUNT" => assert!(ops.is_empty()),
+                    "calls" => {
+                        assert_eq!(ops, ["call to unsafe function", "call to unsafe function"])
+                    }
+                    "deref" => assert_eq!(ops, ["dereference of raw pointer"]),
+                    "statics" => assert_eq!(ops, ["use of mutable static", "use of extern static"]),
+                    "union_fields" => assert_eq!(ops, ["access to union field"]),
+                    path => panic!("unexpected body {}", path),
+                }
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = ["rustc", "input.rs", "--crate-type=lib"].iter().map(|arg| arg.to_string());
+    let args = args.collect::<Vec<_>>();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut CheckUnsafeOps).run()
+    })
+    .unwrap()
+    .unwrap();
+}
@@ -0,0 +1,29 @@
+// Test the suggestions for repeat expressions whose element isn't `Copy`.
+
+#![allow(dead_code)]
+
+#[derive(Clone)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Wrapper(Vec<u8>);
+
+const EMPTY: Wrapper = Wrapper(Vec::new());
+
+fn main() {
+    let a = Point { x: 0, y: 0 };
+    let _ = [a; 3];
+    //~^ ERROR the trait bound `Point: Copy` is not satisfied
+
+    let s = String::from("hello");
+    let _ = [s; 2];
+    //~^ ERROR the trait bound `String: Copy` is not satisfied
+
+    // Repeating a value at most once doesn't copy it, and constants are evaluated anew for
+    // every element.
+    let _ = [Wrapper(vec![1]); 1];
+    let _ = [Wrapper(vec![2]); 0];
+    let _ = [EMPTY; 4];
+}
@@ -0,0 +1,32 @@
+// only-x86_64
+// edition:2018
+#![feature(asm)]
+#![feature(naked_functions)]
+#![feature(unboxed_closures)]
+#![crate_type = "lib"]
+
+#[naked]
+pub async unsafe extern "C" fn asynchronous() {
+    //~^ ERROR naked functions cannot be `async`
+    asm!("", options(noreturn))
+}
+
+#[naked]
+pub unsafe extern "rust-call" fn rust_call(_: ()) {
+    //~^ ERROR the "rust-call" ABI is unsupported in naked functions
+    asm!("", options(noreturn))
+}
+
+#[naked]
+#[inline]
+//~^ WARN naked functions cannot be inlined
+//~| WARN this was previously accepted
+pub unsafe extern "C" fn inline() {
+    asm!("", options(noreturn))
+}
+
+#[naked]
+#[inline(never)]
+pub unsafe extern "C" fn inline_never() {
+    asm!("", options(noreturn))
+}
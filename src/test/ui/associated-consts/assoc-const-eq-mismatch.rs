@@ -0,0 +1,22 @@
+#![feature(associated_const_equality)]
+
+trait Trait {
+    const N: usize;
+}
+
+struct Four;
+impl Trait for Four {
+    const N: usize = 4;
+}
+
+fn three<T: Trait<N = 3>>() {}
+
+fn generic<T: Trait>() {
+    three::<T>();
+    //~^ ERROR type mismatch resolving `<T as Trait>::N == 3_usize`
+}
+
+fn main() {
+    three::<Four>();
+    //~^ ERROR type mismatch resolving `<Four as Trait>::N == 3_usize`
+}
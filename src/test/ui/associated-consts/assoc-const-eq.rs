@@ -0,0 +1,36 @@
+// run-pass
+#![feature(associated_const_equality)]
+
+trait Trait {
+    const N: usize;
+}
+trait Sub: Trait {}
+
+struct Three;
+impl Trait for Three {
+    const N: usize = 3;
+}
+impl Sub for Three {}
+
+fn literal<T: Trait<N = 3>>() -> usize {
+    T::N
+}
+
+fn block<T: Trait<N = { 1 + 2 }>>() -> usize {
+    T::N
+}
+
+fn supertrait<T: Sub<N = 3>>() -> usize {
+    T::N
+}
+
+fn forward<T: Trait<N = 3>>() -> usize {
+    literal::<T>()
+}
+
+fn main() {
+    assert_eq!(literal::<Three>(), 3);
+    assert_eq!(block::<Three>(), 3);
+    assert_eq!(supertrait::<Three>(), 3);
+    assert_eq!(forward::<Three>(), 3);
+}
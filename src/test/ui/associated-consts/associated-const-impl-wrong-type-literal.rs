@@ -0,0 +1,27 @@
+// Check that we suggest using the trait's type for an associated const when the value in the
+// impl would also be valid for it.
+
+trait Limits {
+    const MAX: u8;
+    const MIN: i8;
+    const LARGE: u8;
+    const RATIO: f64;
+}
+
+struct S;
+
+impl Limits for S {
+    const MAX: i32 = 200;
+    //~^ ERROR implemented const `MAX` has an incompatible type for trait
+    //~| HELP change the type to match the trait
+    const MIN: i64 = -128;
+    //~^ ERROR implemented const `MIN` has an incompatible type for trait
+    //~| HELP change the type to match the trait
+    const LARGE: u16 = 300;
+    //~^ ERROR implemented const `LARGE` has an incompatible type for trait
+    const RATIO: f32 = 0.5;
+    //~^ ERROR implemented const `RATIO` has an incompatible type for trait
+    //~| HELP change the type to match the trait
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+// edition:2018
+
+use std::future::Future;
+
+struct MyErr;
+fn err() -> Result<u8, MyErr> { Err(MyErr) }
+
+fn rethrow_makes_output_a_result() {
+    let block = async {
+        err()?;
+        Ok::<(), MyErr>(())
+    };
+    let _: &dyn Future<Output = ()> = &block;
+    //~^ ERROR type mismatch resolving `<impl Future as Future>::Output == ()`
+}
+
+fn main() {}
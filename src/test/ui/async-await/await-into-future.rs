@@ -0,0 +1,25 @@
+// check-pass
+// edition:2018
+
+#![feature(into_future)]
+
+use std::future::{ready, IntoFuture, Ready};
+
+struct AwaitMe;
+
+impl IntoFuture for AwaitMe {
+    type Output = i32;
+    type Future = Ready<i32>;
+
+    fn into_future(self) -> Self::Future {
+        ready(41)
+    }
+}
+
+async fn run() -> i32 {
+    AwaitMe.await + 1
+}
+
+fn main() {
+    let _ = run();
+}
@@ -0,0 +1,22 @@
+// Check that the requirements going through a recursive type more than once are condensed
+// to its innermost and outermost levels, pointing at the field that makes it recursive.
+
+#![feature(auto_traits, negative_impls)]
+#![allow(dead_code)]
+
+auto trait Checked {}
+
+struct Unchecked;
+impl !Checked for Unchecked {}
+
+struct Node<T> {
+    value: T,
+    next: Option<&'static Node<Unchecked>>,
+}
+
+fn is_checked<T: Checked>() {}
+
+fn main() {
+    is_checked::<Node<u8>>();
+    //~^ ERROR the trait bound `Unchecked: Checked` is not satisfied in `Node<u8>`
+}
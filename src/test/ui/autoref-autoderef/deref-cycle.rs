@@ -0,0 +1,35 @@
+// Check that a cycle of `Deref` impls is reported as such, instead of running into the
+// recursion limit.
+
+use std::ops::Deref;
+
+struct A;
+struct B;
+struct C;
+
+impl Deref for A {
+    type Target = B;
+    fn deref(&self) -> &B {
+        &B
+    }
+}
+
+impl Deref for B {
+    type Target = C;
+    fn deref(&self) -> &C {
+        &C
+    }
+}
+
+impl Deref for C {
+    type Target = A;
+    fn deref(&self) -> &A {
+        &A
+    }
+}
+
+fn main() {
+    A.missing();
+    //~^ ERROR cycle detected while auto-dereferencing `A`
+    //~| ERROR no method named `missing` found for struct `A` in the current scope
+}
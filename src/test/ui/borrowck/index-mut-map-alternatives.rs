@@ -0,0 +1,15 @@
+// Modifying the value of a map through indexing suggests the methods of the map that can do
+// so instead, as long as they accept the index as the key.
+
+use std::collections::{BTreeMap, HashMap};
+
+fn main() {
+    // `insert` and `entry` take a `String` key, not the `&str` index.
+    let mut names: BTreeMap<String, String> = BTreeMap::new();
+    names["peter"] = "Peter".to_string();
+    //~^ ERROR cannot assign to data in an index of `BTreeMap<String, String>`
+
+    let mut ages: HashMap<u32, u32> = HashMap::new();
+    ages[&1] += 1;
+    //~^ ERROR cannot assign to data in an index of `HashMap<u32, u32>`
+}
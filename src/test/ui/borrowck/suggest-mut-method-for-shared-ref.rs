@@ -0,0 +1,16 @@
+// When a place is behind a shared reference returned by a method that has a `_mut` variant,
+// suggest calling that variant instead.
+
+use std::collections::HashMap;
+
+fn main() {
+    let mut map = HashMap::new();
+    map.insert("a", 1);
+    *map.get("a").unwrap() = 2; //~ ERROR cannot assign to data in a `&` reference
+
+    let v = vec![1, 2, 3];
+    let first = &mut *v.first().expect("empty"); //~ ERROR cannot borrow data
+    *first = 0;
+
+    *v.iter().next().unwrap() = 5; //~ ERROR cannot assign to data in a `&` reference
+}
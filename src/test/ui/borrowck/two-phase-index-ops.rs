@@ -0,0 +1,44 @@
+// run-pass
+
+// Mutable overloaded indexing borrows its base with a two-phase borrow, like method receivers
+// and compound assignment operators, so the index expression may read the indexed value.
+
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+
+struct Grid {
+    cells: Vec<i32>,
+    width: usize,
+}
+
+impl Index<usize> for Grid {
+    type Output = i32;
+
+    fn index(&self, i: usize) -> &i32 {
+        &self.cells[i]
+    }
+}
+
+impl IndexMut<usize> for Grid {
+    fn index_mut(&mut self, i: usize) -> &mut i32 {
+        &mut self.cells[i]
+    }
+}
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    v[v.len() - 1] += 1;
+    v[v[0]] = 10;
+    let last = &mut v[v.len() - 1];
+    *last *= 2;
+    assert_eq!(v, [1, 10, 8]);
+
+    let mut d: VecDeque<usize> = (0..4).collect();
+    d[d.len() - 1] = d[0];
+    assert_eq!(d, [0, 1, 2, 0]);
+
+    let mut g = Grid { cells: vec![0; 4], width: 2 };
+    g[g.width + 1] = 5;
+    g[g[3] as usize - 4] += g.width as i32;
+    assert_eq!(g.cells, [0, 2, 0, 5]);
+}
@@ -135,13 +135,12 @@ fn index_mut(&mut self, _: i32) -> &mut i32 {
 
 fn coerce_index_op() {
     let mut i = I(10);
+    // Mutable indexing borrows `i` with a two-phase borrow, so the index may read `i`.
     i[i[3]] = 4;
-    //[nll]~^  ERROR cannot borrow `i` as immutable because it is also borrowed as mutable [E0502]
 
     i[3] = i[4];
 
     i[i[3]] = i[4];
-    //[nll]~^  ERROR cannot borrow `i` as immutable because it is also borrowed as mutable [E0502]
 }
 
 fn main() {
@@ -0,0 +1,15 @@
+// ignore-compare-mode-nll
+// The MIR borrow checker reports this as borrowed data escaping the function instead.
+
+fn spawn<F: FnOnce() + Send + 'static>(f: F) {
+    f()
+}
+
+fn print_later(name: &str) {
+    spawn(move || {
+        //~^ ERROR explicit lifetime required in the type of `name`
+        println!("{}", name);
+    });
+}
+
+fn main() {}
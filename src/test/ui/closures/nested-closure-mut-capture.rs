@@ -0,0 +1,42 @@
+// run-pass
+// Nested closures that mutably use a capture of an enclosing closure, including through
+// two-phase borrows, make the enclosing closure `FnMut` rather than erroring.
+
+fn call_mut(mut f: impl FnMut()) {
+    f();
+}
+
+fn main() {
+    let mut v = vec![1];
+    let mut outer = || {
+        let mut inner = || v.push(v.len());
+        inner();
+        inner();
+    };
+    outer();
+    outer();
+    assert_eq!(v, [1, 1, 2, 3, 4]);
+
+    let mut count = 0;
+    let mut inc = || count += 1;
+    let mut twice = || {
+        let mut go = || inc();
+        go();
+        go();
+    };
+    twice();
+    call_mut(&mut twice);
+    drop(twice);
+    assert_eq!(count, 4);
+
+    let mut total = 0;
+    let mut by_value = move || {
+        let mut add = |n| total += n;
+        add(1);
+        add(2);
+        total
+    };
+    assert_eq!(by_value(), 3);
+    assert_eq!(by_value(), 6);
+    assert_eq!(total, 0);
+}
@@ -0,0 +1,9 @@
+// aux-build:incoherent_inherent_impls_lib.rs
+
+extern crate incoherent_inherent_impls_lib;
+
+use incoherent_inherent_impls_lib::Split;
+
+impl Split {
+    pub fn ext(&self) {}
+}
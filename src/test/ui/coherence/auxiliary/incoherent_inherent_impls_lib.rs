@@ -0,0 +1,10 @@
+#![feature(rustc_attrs)]
+
+#[rustc_has_incoherent_inherent_impls]
+pub struct Split;
+
+pub struct Closed;
+
+impl Split {
+    pub fn own(&self) {}
+}
@@ -0,0 +1,20 @@
+// Test the notes explaining how `#[fundamental]` types affect whether a type is local.
+
+// compile-flags:--crate-name=test
+// aux-build:coherence_lib.rs
+
+extern crate coherence_lib as lib;
+use lib::*;
+use std::rc::Rc;
+
+struct Local;
+
+impl Remote for Box<Local> {} // OK
+
+impl Remote for Rc<Local> {}
+//~^ ERROR only traits defined in the current crate can be implemented for arbitrary types
+
+impl<T> Remote1<Box<Local>> for T {}
+//~^ ERROR type parameter `T` must be used as the type parameter for some local type
+
+fn main() {}
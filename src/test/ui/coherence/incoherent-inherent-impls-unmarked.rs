@@ -0,0 +1,12 @@
+// aux-build:incoherent_inherent_impls_lib.rs
+
+// Types that aren't marked with `#[rustc_has_incoherent_inherent_impls]` still can't have
+// inherent impls outside of their crate.
+
+extern crate incoherent_inherent_impls_lib;
+
+use incoherent_inherent_impls_lib::Closed;
+
+impl Closed {} //~ ERROR E0116
+
+fn main() {}
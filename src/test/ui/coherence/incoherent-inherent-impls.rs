@@ -0,0 +1,22 @@
+// check-pass
+// aux-build:incoherent_inherent_impls_lib.rs
+// aux-build:incoherent_inherent_impls_ext.rs
+
+// Inherent impls of a type marked with `#[rustc_has_incoherent_inherent_impls]` may be
+// defined in any crate, and method lookup finds the ones of all crates.
+
+extern crate incoherent_inherent_impls_ext;
+extern crate incoherent_inherent_impls_lib;
+
+use incoherent_inherent_impls_lib::Split;
+
+impl Split {
+    fn local(&self) {}
+}
+
+fn main() {
+    let split = Split;
+    split.own();
+    split.ext();
+    split.local();
+}
@@ -0,0 +1,11 @@
+// Inherent impls of types of other crates suggest moving the items into an extension trait.
+
+use std::collections::HashMap;
+
+impl<K> HashMap<K, u32> { pub fn total(&self) -> u32 { self.values().sum() } }
+//~^ ERROR cannot define inherent `impl` for a type outside of the crate
+
+impl<T: Clone, const N: usize> [T; N] { fn first_clone(&self) -> T { self[0].clone() } }
+//~^ ERROR only a single inherent implementation marked with `#[lang = "array"]` is allowed
+
+fn main() {}
@@ -0,0 +1,25 @@
+// Check that mismatches between the generic parameters of trait and impl methods are reported
+// along with the generics list the impl method should declare.
+
+trait Store {
+    fn put<K, const N: usize>(&self, key: K);
+    fn get<T>(&self) -> Option<T>;
+    fn fill<const N: usize>(&self);
+}
+
+struct S;
+
+impl Store for S {
+    fn put<const M: usize, Key>(&self, key: Key) {}
+    //~^ ERROR method `put` has an incompatible generic parameter for trait: `K`
+
+    fn get<T, U>(&self) -> Option<T> {
+    //~^ ERROR method `get` has 2 type parameters but its trait declaration has 1 type parameter
+        None
+    }
+
+    fn fill<const N: u8>(&self) {}
+    //~^ ERROR method `fill` has an incompatible generic parameter for trait: `N`
+}
+
+fn main() {}
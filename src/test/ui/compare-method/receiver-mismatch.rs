@@ -0,0 +1,42 @@
+// Check that methods whose signature only differs from the trait's in the form of the receiver
+// get a targeted error.
+
+trait Counter {
+    fn get(&self) -> u32;
+    fn bump(&mut self, by: u32);
+    fn finish(self) -> u32;
+}
+
+struct Simple(u32);
+
+impl Counter for Simple {
+    fn get(self) -> u32 {
+    //~^ ERROR method `get` takes `self` in the impl, but `&self` in the trait
+        self.0
+    }
+
+    fn bump(&self, by: u32) {}
+    //~^ ERROR method `bump` takes `&self` in the impl, but `&mut self` in the trait
+
+    fn finish(&mut self) -> u32 {
+    //~^ ERROR method `finish` takes `&mut self` in the impl, but `self` in the trait
+        self.0
+    }
+}
+
+struct Other;
+
+impl Counter for Other {
+    fn get(&self) -> u32 {
+        0
+    }
+
+    fn bump(&self, by: u64) {}
+    //~^ ERROR method `bump` has an incompatible type for trait
+
+    fn finish(self) -> u32 {
+        0
+    }
+}
+
+fn main() {}
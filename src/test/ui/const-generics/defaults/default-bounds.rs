@@ -0,0 +1,21 @@
+// revisions: full min
+#![cfg_attr(full, feature(const_generics))]
+#![feature(const_generics_defaults)]
+#![allow(incomplete_features)]
+
+struct Const<const N: usize>;
+trait IsSmall {}
+impl IsSmall for Const<1> {}
+impl IsSmall for Const<2> {}
+
+struct Literal<const N: usize = 3> where Const<N>: IsSmall;
+//~^ ERROR the trait bound `Const<3_usize>: IsSmall` is not satisfied
+
+struct Expr<const N: usize = { 1 + 2 }> where Const<N>: IsSmall;
+//~^ ERROR the trait bound `Const<3_usize>: IsSmall` is not satisfied
+
+struct Small<const N: usize = { 1 + 1 }> where Const<N>: IsSmall;
+
+trait Bounded<const N: usize = { 4 / 2 }> where Const<N>: IsSmall {}
+
+fn main() {}
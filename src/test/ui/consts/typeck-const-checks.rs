@@ -0,0 +1,32 @@
+// Test that `-Z typeck-const-checks` reports operations that can't be evaluated at compile
+// time while type checking const contexts.
+
+// compile-flags: -Z typeck-const-checks
+
+#![feature(box_syntax)]
+
+trait Answer {
+    fn answer() -> u8;
+}
+
+impl Answer for u8 {
+    fn answer() -> u8 {
+        42
+    }
+}
+
+const ALLOCATED: Box<u8> = box 1;
+//~^ ERROR allocations are not allowed in constants
+
+const fn call_trait_method() -> u8 {
+    u8::answer()
+    //~^ ERROR calls in constant functions are limited to constant functions
+}
+
+static READ: u8 = unsafe { *(&1 as *const u8) };
+//~^ ERROR dereferencing raw pointers in statics is unstable
+
+fn main() {
+    // Closures are evaluated at runtime, even when they are defined in a const context.
+    const _: fn() -> Box<u8> = || box 1;
+}
@@ -0,0 +1,16 @@
+// Check that `if` expressions without an `else` that are used as a value suggest an `else`
+// block evaluating to a placeholder of the expected type.
+
+fn option(cond: bool) -> Option<u8> {
+    let x: Option<u8> = if cond { Some(1) };
+    //~^ ERROR `if` may be missing an `else` clause
+    x
+}
+
+fn string(cond: bool) -> String {
+    let s: String = if cond { String::from("yes") };
+    //~^ ERROR `if` may be missing an `else` clause
+    s
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+trait Trait {
+    const N: usize;
+}
+
+fn foo<T: Trait<N = 3>>() {}
+//~^ ERROR associated const equality is incomplete
+
+fn main() {}
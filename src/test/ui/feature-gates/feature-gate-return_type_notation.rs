@@ -0,0 +1,8 @@
+trait Trait {
+    fn method(&self) -> u8;
+}
+
+fn foo<T: Trait<method(): Send>>() {}
+//~^ ERROR return type notation is experimental
+
+fn main() {}
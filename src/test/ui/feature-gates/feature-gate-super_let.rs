@@ -0,0 +1,8 @@
+fn main() {
+    let x = {
+        super let y = &String::new();
+        //~^ ERROR `super let` is experimental
+        y
+    };
+    let _ = x;
+}
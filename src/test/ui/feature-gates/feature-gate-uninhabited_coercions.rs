@@ -0,0 +1,7 @@
+enum Void {}
+
+fn absurd(v: Void) -> u32 {
+    v //~ ERROR mismatched types
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+pub fn demo() -> Option<i32> {
+    do yeet //~ ERROR `do yeet` expression is experimental
+}
+
+fn main() {}
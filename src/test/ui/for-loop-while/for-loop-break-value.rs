@@ -0,0 +1,37 @@
+// run-pass
+#![feature(for_loop_break_value)]
+
+fn find_even(v: &[u32]) -> Option<u32> {
+    for &x in v {
+        if x % 2 == 0 {
+            break Some(x);
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(find_even(&[1, 3, 4, 5]), Some(4));
+    assert_eq!(find_even(&[1, 3, 5]), None);
+
+    // Labeled breaks from inner loops target the outer `for` loop.
+    let first_long = 'outer: for word in ["a", "bb", "ccc"].iter() {
+        for _ in 0..1 {
+            if word.len() > 1 {
+                break 'outer word.len();
+            }
+        }
+    };
+    assert_eq!(first_long, 2);
+
+    // An exhausted iterator makes the loop evaluate to `Default::default()`.
+    let exhausted: u8 = for i in 0..3u8 {
+        if i > 10 {
+            break i;
+        }
+    };
+    assert_eq!(exhausted, 0);
+
+    // Loops without a `break` with a value keep evaluating to `()`.
+    let unit = for _ in 0..3 {};
+    assert_eq!(unit, ());
+}
@@ -0,0 +1,42 @@
+// Collections that only implement `IntoIterator` for references to themselves should get a
+// suggestion to iterate over a reference instead of a bare "is not an iterator" error.
+
+struct Bag {
+    items: Vec<u32>,
+}
+
+impl Bag {
+    fn iter(&self) -> std::slice::Iter<'_, u32> {
+        self.items.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Bag {
+    type Item = &'a u32;
+    type IntoIter = std::slice::Iter<'a, u32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+struct Slots {
+    items: Vec<u32>,
+}
+
+impl<'a> IntoIterator for &'a mut Slots {
+    type Item = &'a mut u32;
+    type IntoIter = std::slice::IterMut<'a, u32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter_mut()
+    }
+}
+
+fn main() {
+    let bag = Bag { items: vec![1, 2, 3] };
+    for x in bag {} //~ ERROR `Bag` is not an iterator
+
+    let mut slots = Slots { items: vec![1, 2, 3] };
+    for x in slots {} //~ ERROR `Slots` is not an iterator
+}
@@ -0,0 +1,17 @@
+#![feature(generators)]
+
+// normalize-stderr-test "std::pin::Unpin" -> "std::marker::Unpin"
+
+use std::marker::Unpin;
+
+fn assert_unpin<T: Unpin>(_: T) {}
+
+fn main() {
+    let generator = static || {
+        let x = 5;
+        let r = &x;
+        yield;
+        println!("{}", r);
+    };
+    assert_unpin(generator); //~ ERROR E0277
+}
@@ -0,0 +1,19 @@
+// Test that `yield` expressions evaluate to the resume type of the generator and that a `yield`
+// disagreeing with the first one points at it.
+
+#![feature(generators)]
+
+fn main() {
+    let _ = || {
+        yield 1u8;
+        yield "two";
+        //~^ ERROR `yield` expressions have incompatible types
+    };
+    let _ = |resume: String| {
+        yield resume.len();
+        let _: u32 = yield 0;
+        //~^ ERROR mismatched types
+        yield true;
+        //~^ ERROR `yield` expressions have incompatible types
+    };
+}
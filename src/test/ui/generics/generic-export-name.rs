@@ -0,0 +1,15 @@
+#![deny(no_mangle_generic_items)]
+
+#[export_name = "foo"]
+pub fn foo<T>() {}
+//~^ ERROR functions generic over types or consts must be mangled
+
+pub struct Bar<T>(T);
+
+impl<T> Bar<T> {
+    #[no_mangle]
+    pub fn bar() {}
+    //~^ ERROR functions generic over types or consts must be mangled
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+// Check that an `impl Trait` binding whose initializer doesn't determine the
+// hidden type is reported at the binding, rather than as an opaque type cycle.
+
+#![allow(incomplete_features)]
+#![feature(impl_trait_in_bindings)]
+
+fn main() {
+    let _a: impl Copy = Default::default();
+    //~^ ERROR type annotations needed for the hidden type of `impl Copy`
+    let _b: Option<impl Copy> = None;
+    //~^ ERROR type annotations needed for the hidden type of `Option<impl Copy>`
+    let _c: impl Copy = 1u8;
+}
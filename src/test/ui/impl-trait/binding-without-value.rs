@@ -3,7 +3,7 @@
 
 fn foo() {
     let _ : impl Copy;
-    //~^ ERROR cannot resolve opaque type
+    //~^ ERROR type annotations needed for the hidden type of `impl Copy`
 }
 
 fn main() {}
@@ -0,0 +1,19 @@
+use std::fmt::Debug;
+
+#[no_mangle]
+pub fn exported() -> impl Debug {
+    //~^ ERROR functions with `#[no_mangle]` cannot return `impl Trait`
+    0
+}
+
+pub struct S;
+
+impl S {
+    #[export_name = "s_method"]
+    pub fn method() -> Box<impl Debug> {
+        //~^ ERROR functions with `#[export_name]` cannot return `impl Trait`
+        Box::new(0)
+    }
+}
+
+fn main() {}
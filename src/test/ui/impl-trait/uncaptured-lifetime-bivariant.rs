@@ -0,0 +1,16 @@
+// check-pass
+
+// Lifetime parameters of a function that aren't named in the bounds of its
+// `impl Trait` return type are not captured by it, so values of the opaque
+// type can be related regardless of what those lifetimes were.
+
+fn make<'x: 'x>(_: &'x mut &'x ()) -> impl Sized {}
+
+fn reassign<'a, 'b>(x: &'a mut &'a (), y: &'b mut &'b ()) {
+    let mut opaque = make(x);
+    let _ = &opaque;
+    opaque = make(y);
+    let _ = &opaque;
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use std::fmt::Debug;
+
+fn two(a: impl Clone, b: impl Debug) {}
+
+fn main() {
+    two::<u8, u8>(1, 2);
+    //~^ ERROR cannot provide explicit generic arguments
+}
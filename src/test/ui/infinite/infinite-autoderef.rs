@@ -1,4 +1,4 @@
-// error-pattern: reached the recursion limit while auto-dereferencing
+// error-pattern: cycle detected while auto-dereferencing
 
 #![feature(box_syntax)]
 
@@ -0,0 +1,19 @@
+// Test that a lang item missing in a `#![no_core]` crate is reported at the operation that
+// requires it, along with the attribute that would define it.
+
+#![feature(lang_items, no_core)]
+#![no_core]
+#![crate_type = "lib"]
+
+#[lang = "sized"]
+trait Sized {}
+
+enum Option<T> {
+    Some(T),
+    None,
+}
+
+fn first(x: Option<u8>) -> Option<u8> {
+    let y = x?; //~ ERROR requires `branch` lang_item
+    Option::Some(y)
+}
@@ -0,0 +1,12 @@
+#![deny(future_impl_collision)]
+
+fn main() {
+    let boxed: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+    for _ in boxed.into_iter() {}
+    //~^ ERROR this method call will resolve differently once `Box<[i32]>` implements `IntoIterator`
+
+    // The pending impl doesn't change calls that don't go through `IntoIterator`, or that already
+    // pick an impl for the receiver by value.
+    for _ in boxed.iter() {}
+    for _ in Vec::from(boxed).into_iter() {}
+}
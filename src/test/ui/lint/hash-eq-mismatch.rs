@@ -0,0 +1,52 @@
+#![deny(hash_eq_mismatch)]
+#![allow(dead_code)]
+
+use std::hash::{Hash, Hasher};
+
+#[derive(Hash)]
+struct Name(String);
+
+impl PartialEq for Name { //~ ERROR `PartialEq` is implemented by hand for `Name`, but `Hash` is
+    fn eq(&self, other: &Name) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+#[derive(PartialEq)]
+struct Id(u32, u32);
+
+impl Hash for Id { //~ ERROR `Hash` is implemented by hand for `Id`, but `PartialEq` is derived
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+// Deriving both, or implementing both by hand, is fine.
+#[derive(PartialEq, Hash)]
+struct Derived(u32);
+
+struct Manual(u32);
+
+impl PartialEq for Manual {
+    fn eq(&self, other: &Manual) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Hash for Manual {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+// Comparisons with other types don't have to agree with `Hash`.
+#[derive(Hash)]
+struct Wrapper(u32);
+
+impl PartialEq<u32> for Wrapper {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
+
+fn main() {}
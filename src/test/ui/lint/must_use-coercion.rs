@@ -0,0 +1,19 @@
+#![deny(unused_must_use)]
+
+use std::any::Any;
+
+#[must_use]
+struct Loud;
+
+struct Quiet;
+
+fn main() {
+    let hidden = true;
+    if hidden { Box::new(Quiet) as Box<dyn Any> } else { Box::new(Loud) };
+    //~^ ERROR unused boxed `Loud` that must be used
+    match hidden {
+        true => Box::new(Quiet) as Box<dyn Any>,
+        false => Box::new(Loud), //~ ERROR unused boxed `Loud` that must be used
+    };
+    if hidden { Box::new(Loud) as Box<dyn Any> } else { Box::new(Quiet) };
+}
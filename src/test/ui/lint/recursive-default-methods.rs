@@ -0,0 +1,53 @@
+#![deny(recursive_default_methods)]
+
+trait Equal {
+    fn eq(&self, other: &Self) -> bool { !self.ne(other) }
+    fn ne(&self, other: &Self) -> bool { !Equal::eq(self, other) }
+}
+
+trait Three {
+    fn a(&self) { self.b() }
+    fn b(&self) { self.c() }
+    fn c(&self) { self.a() }
+}
+
+trait Conditional {
+    fn a(&self, n: u8) -> u8 { if n == 0 { 0 } else { self.b(n - 1) } }
+    fn b(&self, n: u8) -> u8 { self.a(n) }
+}
+
+trait EarlyReturn {
+    fn a(&self, n: u8) -> u8 {
+        if n == 0 {
+            return 0;
+        }
+        self.b(n - 1)
+    }
+    fn b(&self, n: u8) -> u8 { self.a(n) }
+}
+
+struct Overridden;
+
+impl Equal for Overridden {
+    fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl Three for Overridden {
+    fn b(&self) {}
+}
+
+struct Defaulted;
+
+impl Equal for Defaulted {}
+//~^ ERROR the default bodies of `eq` and `ne` call each other
+
+impl Three for Defaulted {}
+//~^ ERROR the default bodies of `a`, `b` and `c` call each other
+
+impl Conditional for Defaulted {}
+
+impl EarlyReturn for Defaulted {}
+
+fn main() {
+    let _ = (Overridden, Defaulted);
+}
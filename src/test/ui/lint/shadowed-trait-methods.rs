@@ -0,0 +1,50 @@
+#![deny(shadowed_trait_methods)]
+
+struct Meters(f64);
+
+impl Meters {
+    fn describe(self) -> String {
+        format!("{}", self.0)
+    }
+
+    fn halve(&self) -> f64 {
+        self.0 / 2.0
+    }
+
+    fn double(&self) -> f64 {
+        self.0 * 2.0
+    }
+}
+
+trait Describe {
+    fn describe(&self) -> String {
+        String::new()
+    }
+
+    fn halve(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Describe for Meters {}
+
+mod other {
+    // Not in scope in `main`, so calls there can't pick it.
+    pub trait Double {
+        fn double(&self) -> f64 {
+            0.0
+        }
+    }
+
+    impl Double for super::Meters {}
+}
+
+fn main() {
+    let meters = Meters(1.0);
+    meters.halve();
+    //~^ ERROR `Meters::halve` shadows the trait method `Describe::halve`
+    meters.double();
+    Describe::describe(&meters);
+    meters.describe();
+    //~^ ERROR `Meters::describe` shadows the trait method `Describe::describe`
+}
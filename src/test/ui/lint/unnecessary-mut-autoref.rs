@@ -0,0 +1,36 @@
+#![deny(unnecessary_mut_autoref)]
+
+use std::ops::Deref;
+
+struct Inner;
+
+impl Inner {
+    fn value(&self) -> u32 {
+        0
+    }
+}
+
+struct Outer(Inner);
+
+impl Outer {
+    fn value(&mut self) -> u32 {
+        1
+    }
+}
+
+impl Deref for Outer {
+    type Target = Inner;
+    fn deref(&self) -> &Inner {
+        &self.0
+    }
+}
+
+fn main() {
+    let mut outer = Outer(Inner);
+    outer.value(); //~ ERROR `Outer::value` mutably borrows its receiver
+    (*outer).value();
+
+    // The receiver already is a mutable reference, nothing is implicitly borrowed.
+    let outer = &mut outer;
+    outer.value();
+}
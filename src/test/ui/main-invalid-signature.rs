@@ -0,0 +1,7 @@
+// Every problem with the signature of `main` is reported in a single error, along with the
+// signatures `main` is allowed to have.
+
+fn main<T: Copy>(args: Vec<String>) -> Result<(), String> where T: Clone {
+    //~^ ERROR `main` function is not allowed to have generic parameters [E0131]
+    Ok(())
+}
@@ -0,0 +1,21 @@
+// Overlapping impls of a trait that could be a marker trait suggest making it one, and those of
+// a marker trait with associated items explain why they still can't overlap.
+
+#![feature(marker_trait_attr)]
+
+trait Unmarked {}
+
+impl<T: Copy> Unmarked for T {}
+impl<T: Clone> Unmarked for T {}
+//~^ ERROR conflicting implementations of trait `Unmarked`
+
+#[marker]
+trait MarkerWithItems {
+    const N: usize = 0;
+}
+
+impl<T: Copy> MarkerWithItems for T {}
+impl<T: Clone> MarkerWithItems for T {}
+//~^ ERROR conflicting implementations of trait `MarkerWithItems`
+
+fn main() {}
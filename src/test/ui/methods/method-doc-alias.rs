@@ -0,0 +1,30 @@
+// Methods called by one of their `#[doc(alias)]`es suggest the real method name.
+
+struct Queue;
+
+impl Queue {
+    #[doc(alias = "push_back")]
+    fn push(&mut self, _: u32) {}
+
+    #[doc(alias("pop_front", "dequeue"))]
+    fn pop(&mut self) -> Option<u32> { None }
+}
+
+trait Length {
+    #[doc(alias = "size")]
+    fn len2(&self) -> usize;
+}
+
+impl Length for Queue {
+    fn len2(&self) -> usize { 0 }
+}
+
+fn main() {
+    let mut queue = Queue;
+    queue.push_back(1); //~ ERROR no method named `push_back` found
+    queue.dequeue(); //~ ERROR no method named `dequeue` found
+    queue.size(); //~ ERROR no method named `size` found
+
+    let v = vec![1, 2, 3];
+    v.length(); //~ ERROR no method named `length` found
+}
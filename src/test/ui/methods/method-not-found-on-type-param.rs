@@ -0,0 +1,40 @@
+// When a method can't be found on a type parameter, point out the concrete types of this crate
+// that do have it.
+
+struct Meters(f64);
+struct Wrapper<T>(Vec<T>);
+
+impl Meters {
+    fn total(&self) -> f64 { self.0 }
+    fn zero() -> Self { Meters(0.0) }
+}
+
+impl<T> Wrapper<T> {
+    fn total(&self) -> usize { self.0.len() }
+}
+
+fn sum<T>(x: T) {
+    x.total(); //~ ERROR no method named `total` found for type parameter `T`
+}
+
+fn make<T>() {
+    T::zero(); //~ ERROR no function or associated item named `zero` found for type parameter `T`
+}
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for u8 { fn describe(&self) -> String { String::new() } }
+impl Describe for u16 { fn describe(&self) -> String { String::new() } }
+impl Describe for u32 { fn describe(&self) -> String { String::new() } }
+impl Describe for u64 { fn describe(&self) -> String { String::new() } }
+impl Describe for i8 { fn describe(&self) -> String { String::new() } }
+impl Describe for i16 { fn describe(&self) -> String { String::new() } }
+impl<T: Describe> Describe for Vec<T> { fn describe(&self) -> String { String::new() } }
+
+fn show<T>(x: T) {
+    x.describe(); //~ ERROR no method named `describe` found for type parameter `T`
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Checks that errors for where-clauses of the picked method that don't hold point at the
+// where-clause, and name the types of the call it was instantiated with.
+
+trait Describe {}
+
+impl Describe for u8 {}
+
+struct Wrapper<T>(T);
+
+impl<T> Wrapper<T> {
+    fn describe_with<U>(&self, _: U)
+    where
+        T: Describe,
+        U: Describe,
+    {
+    }
+}
+
+struct Opaque;
+
+fn main() {
+    Wrapper(Opaque).describe_with(0u8);
+    //~^ ERROR the trait bound `Opaque: Describe` is not satisfied
+    Wrapper(0u8).describe_with(Opaque);
+    //~^ ERROR the trait bound `Opaque: Describe` is not satisfied
+}
@@ -0,0 +1,28 @@
+// Checks the output of `-Z probe-trace`, which lists the candidates method resolution
+// considered for calls to a given method.
+
+// check-pass
+// compile-flags: -Z probe-trace=describe
+
+struct Foo;
+
+impl Foo {
+    fn describe(&self) -> &'static str {
+        "inherent"
+    }
+}
+
+trait Describe {
+    fn describe(&self) -> &'static str;
+}
+
+impl Describe for Foo {
+    fn describe(&self) -> &'static str {
+        "trait"
+    }
+}
+
+fn main() {
+    let foo = Foo;
+    foo.describe();
+}
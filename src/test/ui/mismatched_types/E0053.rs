@@ -9,7 +9,7 @@ impl Foo for Bar {
     fn foo(x: i16) { }
     //~^ ERROR method `foo` has an incompatible type for trait
     fn bar(&mut self) { }
-    //~^ ERROR method `bar` has an incompatible type for trait
+    //~^ ERROR method `bar` takes `&mut self` in the impl, but `&self` in the trait
 }
 
 fn main() {
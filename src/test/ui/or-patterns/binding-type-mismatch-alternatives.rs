@@ -0,0 +1,11 @@
+// Test that a binding with different types across the alternatives of an or-pattern is reported
+// once, listing the type and binding mode it has in every alternative.
+
+fn main() {
+    match (0u8, 1u16, 2u32) {
+        (x, _, _) | (_, x, _) | (_, _, x) => {} //~ ERROR mismatched types
+    }
+
+    let (((x,), _, _) | (_, (x,), _) | (_, _, (x,))) = (&(0u8,), (1u8,), (2u8,));
+    //~^ ERROR mismatched types
+}
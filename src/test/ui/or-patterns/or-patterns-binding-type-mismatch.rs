@@ -25,7 +25,6 @@ enum Blah {
         //~^ ERROR mismatched types
         //~| ERROR mismatched types
         //~| ERROR mismatched types
-        //~| ERROR mismatched types
         _ => {}
     }
 
@@ -46,7 +45,6 @@ enum Blah {
         //~^ ERROR mismatched types
         //~| ERROR mismatched types
         //~| ERROR mismatched types
-        //~| ERROR mismatched types
     = Some((0u8, Some((1u16, 2u32))))
     {}
 
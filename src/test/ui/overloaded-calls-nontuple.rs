@@ -7,14 +7,14 @@ struct S {
     y: isize,
 }
 
-impl FnMut<isize> for S {
+impl FnMut<isize> for S { //~ ERROR manual implementations of `FnMut` must take their arguments
     extern "rust-call" fn call_mut(&mut self, z: isize) -> isize {
         self.x + self.y + z
     }
     //~^^^ ERROR functions with the "rust-call" ABI must take a single non-self argument
 }
 
-impl FnOnce<isize> for S {
+impl FnOnce<isize> for S { //~ ERROR manual implementations of `FnOnce` must take their arguments
     type Output = isize;
     extern "rust-call" fn call_once(mut self, z: isize) -> isize { self.call_mut(z) }
     //~^ ERROR functions with the "rust-call" ABI must take a single non-self argument
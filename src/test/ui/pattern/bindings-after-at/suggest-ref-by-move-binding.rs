@@ -0,0 +1,15 @@
+// Test that a by-move binding conflicting with borrows in its subpattern suggests `ref`.
+
+// run-rustfix
+
+#![feature(bindings_after_at)]
+
+struct NotCopy;
+
+fn main() {
+    let a @ ref b = NotCopy; //~ ERROR borrow of moved value
+    match Some(NotCopy) {
+        c @ Some(ref d) => {} //~ ERROR borrow of moved value
+        None => {}
+    }
+}
@@ -0,0 +1,10 @@
+#![feature(box_patterns)]
+
+fn main() {
+    let box x = 1; //~ ERROR mismatched types
+
+    match Some(1) {
+        box Some(x) => {} //~ ERROR mismatched types
+        _ => {}
+    }
+}
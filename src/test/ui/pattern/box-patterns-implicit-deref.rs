@@ -0,0 +1,41 @@
+// run-pass
+// Test that with `box_patterns`, patterns other than `box` patterns dereference `Box`es
+// implicitly, like they do references, without changing the default binding mode.
+
+#![feature(box_patterns)]
+
+struct Foo {
+    a: String,
+    b: u8,
+}
+
+fn main() {
+    // The contents of the box are moved out of it.
+    match Box::new(Some(String::from("moved"))) {
+        Some(s) => assert_eq!(s, "moved"),
+        None => unreachable!(),
+    }
+
+    // Behind a reference, the default binding mode is still `ref`.
+    let nested = &Box::new((1u8, Box::new(Foo { a: String::from("a"), b: 2 })));
+    let (x, Foo { a, b }) = nested;
+    let (_, _, _): (&u8, &String, &u8) = (x, a, b);
+    assert_eq!((*x, a.as_str(), *b), (1, "a", 2));
+
+    let mut array = Box::new([1, 2, 3]);
+    if let [first, ..] = &mut array {
+        *first = 10;
+    }
+    assert_eq!(*array, [10, 2, 3]);
+
+    // `box` patterns nest through reference binding modes.
+    match &Box::new(Some(Box::new(5))) {
+        Some(box n) => assert_eq!(*n, 5),
+        None => unreachable!(),
+    }
+
+    match Box::new(4) {
+        0..=3 => unreachable!(),
+        n => assert_eq!(*n, 4),
+    }
+}
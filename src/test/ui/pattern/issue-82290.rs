@@ -1,7 +1,9 @@
+// check-pass
+
 #![feature(let_chains)] //~ WARN the feature `let_chains` is incomplete
 
 fn main() {
-    if true && let x = 1 { //~ ERROR `let` expressions are not supported here
+    if true && let x = 1 {
         let _ = x;
     }
 }
@@ -0,0 +1,21 @@
+// Check that we point out what is known about the value of a scrutinee when that is why the
+// match looks exhaustive but isn't.
+
+fn rem(x: u32) {
+    match x % 4 {
+        //~^ ERROR non-exhaustive patterns: `4_u32..=u32::MAX` not covered
+        0 => {}
+        1 => {}
+        2 => {}
+        3 => {}
+    }
+}
+
+fn slice(array: [u8; 2]) {
+    match &array[..] {
+        //~^ ERROR non-exhaustive patterns: `&[]`, `&[_]` and `&[_, _, _, ..]` not covered
+        [_, _] => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#![feature(return_type_notation)]
+#![allow(incomplete_features)]
+
+trait Foo {
+    fn method(&self) -> u8;
+}
+
+trait Generic {
+    fn generic<T>(&self) -> T;
+}
+
+fn missing<T: Foo<nonexistent(): Send>>() {}
+//~^ ERROR associated function `nonexistent` not found for `Foo`
+
+fn generic<T: Generic<generic(): Send>>() {}
+//~^ ERROR return type notation is not supported for functions with generic parameters
+
+fn in_dyn(_: &dyn Foo<method(): Send>) {}
+//~^ ERROR return type notation is not allowed in `impl Trait` or `dyn Trait` types
+
+fn main() {}
@@ -0,0 +1,26 @@
+// check-pass
+
+#![feature(return_type_notation)]
+#![allow(incomplete_features)]
+
+trait Foo {
+    type Output;
+    type Item;
+
+    fn method(&self) -> Self::Output;
+    fn get(&self) -> &Self::Item;
+}
+
+fn is_send<T: Send>(_: T) {}
+
+// Becomes `<T as Foo>::Output: Send`.
+fn owned<T: Foo<method(): Send>>(x: T) {
+    is_send(x.method());
+}
+
+// Becomes `for<'a> &'a <T as Foo>::Item: Send`, as `'a` is late-bound in `get`.
+fn borrowed<T: Foo<get(): Send>>(x: T) {
+    is_send(x.get());
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// run-pass
+// Tests that `main` can return any type implementing `Termination`, not just the ones that the
+// standard library provides.
+
+#![feature(termination_trait_lib)]
+
+use std::process::Termination;
+
+struct Success;
+
+impl Termination for Success {
+    fn report(self) -> i32 {
+        0
+    }
+}
+
+fn main() -> Success {
+    Success
+}
@@ -0,0 +1,13 @@
+// Check that a region error caused by an inferred outlives requirement
+// points at the field the requirement was inferred from (RFC 2093).
+
+struct Wrapper<'a, T> {
+    inner: &'a T,
+}
+
+struct Holder<U> {
+    wrapper: Wrapper<'static, U>,
+    //~^ ERROR the parameter type `U` may not live long enough [E0310]
+}
+
+fn main() {}
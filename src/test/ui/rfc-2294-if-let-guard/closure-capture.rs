@@ -0,0 +1,25 @@
+// run-pass
+// edition:2021
+
+#![feature(if_let_guard)]
+#![allow(incomplete_features)]
+
+// `values` is only used in the guard, which has to be taken into account to capture it.
+fn main() {
+    let values = vec![Some(1), None];
+    let classify = |i: usize| match i {
+        0 | 1 if let Some(v) = values[i] => v,
+        _ => 0,
+    };
+    assert_eq!(classify(0), 1);
+    assert_eq!(classify(1), 0);
+    assert_eq!(classify(2), 0);
+
+    let mut name = Some(String::from("guard"));
+    let mut take = || match () {
+        () if let Some(name) = name.take() => name,
+        () => String::new(),
+    };
+    assert_eq!(take(), "guard");
+    assert_eq!(take(), "");
+}
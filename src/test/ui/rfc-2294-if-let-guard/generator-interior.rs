@@ -0,0 +1,24 @@
+// check-pass
+// edition:2018
+
+#![feature(if_let_guard)]
+#![allow(incomplete_features)]
+
+async fn ready() {}
+
+// The bindings of the guard are held across the `await`.
+async fn first_even(values: Vec<u32>) -> u32 {
+    match values.len() {
+        n if let Some(&v) = values.iter().find(|v| *v % 2 == 0) => {
+            ready().await;
+            v + n as u32
+        }
+        _ => 0,
+    }
+}
+
+fn assert_send<T: Send>(_: T) {}
+
+fn main() {
+    assert_send(first_even(vec![1, 2]));
+}
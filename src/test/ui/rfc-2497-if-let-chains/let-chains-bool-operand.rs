@@ -0,0 +1,7 @@
+#![feature(let_chains)]
+#![allow(incomplete_features)]
+
+fn main() {
+    let opt = Some(1);
+    if let Some(x) = opt && x {} //~ ERROR mismatched types
+}
@@ -0,0 +1,40 @@
+// run-pass
+
+#![feature(let_chains)]
+#![allow(incomplete_features)]
+
+fn first_even(v: &[Option<u32>]) -> Option<u32> {
+    for x in v {
+        if let Some(n) = *x && n % 2 == 0 {
+            return Some(n);
+        }
+    }
+    None
+}
+
+fn describe(a: Option<u32>, b: Result<u32, ()>) -> &'static str {
+    if let Some(x) = a && x > 0 && let Ok(y) = b && y > x {
+        "increasing"
+    } else if let Some(_) = a {
+        "other"
+    } else {
+        "none"
+    }
+}
+
+fn sum_pairs(mut v: Vec<(u32, Option<u32>)>) -> u32 {
+    let mut sum = 0;
+    while let Some((a, b)) = v.pop() && let Some(b) = b {
+        sum += a + b;
+    }
+    sum
+}
+
+fn main() {
+    assert_eq!(first_even(&[None, Some(3), Some(4)]), Some(4));
+    assert_eq!(describe(Some(1), Ok(2)), "increasing");
+    assert_eq!(describe(Some(2), Ok(1)), "other");
+    assert_eq!(describe(Some(0), Ok(1)), "other");
+    assert_eq!(describe(None, Ok(1)), "none");
+    assert_eq!(sum_pairs(vec![(1, None), (2, Some(3)), (4, Some(5))]), 14);
+}
@@ -42,7 +42,7 @@ fn to(self) -> U {
 
 #[allow(dead_code)]
 fn foo<T: A, U: A>(x: T, y: U) -> U {
-    x.foo(y.to()).to() //~ ERROR overflow evaluating the requirement
+    x.foo(y.to()).to() //~ ERROR the requirement `T: FromA<U>` depends on itself
 }
 
 fn main() {
@@ -0,0 +1,25 @@
+// Check that `Self` paths in an impl for an enum are reported the same way in expressions and
+// patterns.
+
+enum E<T> {
+    A(T),
+}
+
+impl<T> E<T> {
+    fn f(self) {
+        let _ = Self {};
+        //~^ ERROR expected struct, variant or union type, found enum `E`
+        let _ = Self(0);
+        //~^ ERROR the `Self` constructor can only be used with tuple or unit structs
+        match self {
+            Self { .. } => {}
+            //~^ ERROR expected struct, variant or union type, found enum `E`
+        }
+        match self {
+            Self(_) => {}
+            //~^ ERROR the `Self` constructor can only be used with tuple or unit structs
+        }
+    }
+}
+
+fn main() {}
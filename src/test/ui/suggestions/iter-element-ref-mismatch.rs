@@ -0,0 +1,20 @@
+// Point at the call that created the iterator when an element only differs from the expected
+// type by a reference, and suggest switching between `iter` and `into_iter`.
+
+fn takes_u32(_: u32) {}
+fn takes_ref(_: &String) {}
+
+fn main() {
+    let v: Vec<u32> = vec![1, 2, 3];
+    for x in v.iter() {
+        takes_u32(x); //~ ERROR mismatched types
+    }
+
+    let names: Vec<String> = vec![];
+    for name in names.into_iter() {
+        takes_ref(name); //~ ERROR mismatched types
+    }
+
+    let w: Vec<u32> = vec![1, 2, 3];
+    w.iter().for_each(|x| takes_u32(x)); //~ ERROR mismatched types
+}
@@ -0,0 +1,21 @@
+// run-rustfix
+// Check that the bounds in the suggested signatures are grouped by type and listed in the order
+// they were declared in, instead of depending on the order of some hash map.
+
+trait Convert {
+    fn convert<A: Clone, B: Default, C>(_: A, _: B, _: C) -> Self where C: Copy, B: Copy;
+}
+
+trait Collect {
+    fn collect<T>(_: Vec<T>) -> Self where Vec<T>: Clone, T: Default;
+}
+
+struct S;
+
+impl Convert for S { //~ ERROR not all trait items implemented
+}
+
+impl Collect for S { //~ ERROR not all trait items implemented
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// Check that when some paths of a function `return` a value and others fall off the end of
+// the body, we suggest a placeholder tail expression synthesized from the return type.
+
+#[derive(Default)]
+struct Config;
+
+fn option(x: bool) -> Option<u32> { //~ ERROR mismatched types
+    if x {
+        return Some(42);
+    }
+}
+
+fn result(x: bool) -> Result<(), String> { //~ ERROR mismatched types
+    if x {
+        return Err(String::new());
+    }
+}
+
+fn config(x: bool) -> Config { //~ ERROR mismatched types
+    if x {
+        return Config;
+    }
+}
+
+fn main() {}
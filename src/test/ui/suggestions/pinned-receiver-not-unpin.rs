@@ -0,0 +1,17 @@
+// Check that we suggest pinning a receiver that isn't `Unpin` on the heap, as `Pin::new` can't
+// pin it.
+
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+struct Unmovable(PhantomPinned);
+
+impl Unmovable {
+    fn touch(self: Pin<&mut Self>) {}
+}
+
+fn main() {
+    let x = Unmovable(PhantomPinned);
+    x.touch();
+    //~^ ERROR no method named `touch` found for struct `Unmovable` in the current scope
+}
@@ -0,0 +1,48 @@
+// Check that placeholder values for ADTs without a `Default` impl are built from a constructor
+// that is accessible at the suggestion site.
+
+mod shapes {
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    pub struct Meters(pub f64);
+
+    pub enum Shape {
+        Circle(Point, f64),
+        Empty,
+    }
+
+    pub struct Secret {
+        hidden: u8,
+    }
+}
+
+use shapes::{Meters, Point, Secret, Shape};
+
+fn point(x: bool) -> Point { //~ ERROR mismatched types
+    if x {
+        return Point { x: 1, y: 2 };
+    }
+}
+
+fn meters(x: bool) -> Meters { //~ ERROR mismatched types
+    if x {
+        return Meters(1.0);
+    }
+}
+
+fn shape(x: bool) -> Shape { //~ ERROR mismatched types
+    if x {
+        return Shape::Circle(Point { x: 0, y: 0 }, 1.0);
+    }
+}
+
+fn secret(x: bool) -> Secret { //~ ERROR mismatched types
+    if x {
+        return secret(false);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+// Bounds for a type parameter are suggested next to the ones it already has.
+
+use std::fmt::Debug;
+
+trait Foo {
+    fn foo(&self) {}
+}
+
+fn one_predicate<T>(t: T) where T: Debug {
+    t.foo(); //~ ERROR no method named `foo` found
+}
+
+fn two_predicates<T>(t: T) where T: Debug, T: Clone {
+    t.foo(); //~ ERROR no method named `foo` found
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// A bound on a different trait with the same name doesn't make the missing one redundant.
+
+mod a {
+    pub trait Foo {}
+}
+
+mod b {
+    pub trait Foo {}
+}
+
+use b::Foo;
+
+fn needs_a_foo<T: a::Foo>(_: T) {}
+
+fn has_b_foo<T: Foo>(t: T) {
+    needs_a_foo(t); //~ ERROR the trait bound `T: a::Foo` is not satisfied
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+// compile-flags: -Z suggestion-stats
+
+fn main() {
+    let x: u32 = 20i32; //~ ERROR mismatched types
+}
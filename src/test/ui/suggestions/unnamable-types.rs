@@ -13,11 +13,10 @@
 //~| HELP: replace with the correct type
 
 
-// FIXME: this should also suggest a function pointer, as the closure is non-capturing
 const C: _ = || 42;
 //~^ ERROR: the type placeholder `_` is not allowed within types on item signatures for constants
 //~| NOTE: not allowed in type signatures
-//~| NOTE: however, the inferred type
+//~| HELP: replace with the correct type
 
 struct S<T> { t: T }
 const D = S { t: { let i = 0; move || -> i32 { i } } };
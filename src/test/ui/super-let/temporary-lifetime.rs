@@ -0,0 +1,41 @@
+// run-pass
+// Check that `super let` bindings, and the temporaries extended along with
+// them, live as long as a `let` written in place of the enclosing block.
+
+#![feature(super_let)]
+
+use std::cell::RefCell;
+
+struct Noisy<'a>(&'static str, &'a RefCell<Vec<&'static str>>);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(self.0);
+    }
+}
+
+fn main() {
+    let log = RefCell::new(Vec::new());
+
+    {
+        let x = {
+            super let a = &Noisy("a", &log);
+            let _b = Noisy("b", &log);
+            a
+        };
+        assert_eq!(*log.borrow(), ["b"]);
+        assert_eq!(x.0, "a");
+        log.borrow_mut().push("end of block");
+    }
+    assert_eq!(*log.borrow(), ["b", "end of block", "a"]);
+
+    // This is what `format_args!`-like macros need: an array of references to
+    // temporaries that can be returned out of the block that created them.
+    let args: &[&String] = {
+        super let args = [&String::from("hello"), &String::from("world")];
+        &args
+    };
+    assert_eq!(args.len(), 2);
+    assert_eq!(*args[0], "hello");
+    assert_eq!(*args[1], "world");
+}
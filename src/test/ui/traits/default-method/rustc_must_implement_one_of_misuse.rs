@@ -0,0 +1,42 @@
+#![feature(rustc_attrs)]
+
+#[rustc_must_implement_one_of(a)]
+//~^ ERROR the `#[rustc_must_implement_one_of]` attribute must list at least 2 methods
+trait Tr0 {
+    fn a() {}
+}
+
+#[rustc_must_implement_one_of(a, b)]
+//~^ ERROR method not found in this trait
+trait Tr1 {
+    fn a() {}
+}
+
+#[rustc_must_implement_one_of(a, A)]
+//~^ ERROR not a method of this trait
+trait Tr2 {
+    fn a() {}
+    const A: u8 = 1;
+}
+
+#[rustc_must_implement_one_of(a, b)]
+//~^ ERROR method doesn't have a default body
+trait Tr3 {
+    fn a() {}
+    fn b();
+}
+
+#[rustc_must_implement_one_of(a, a)]
+//~^ ERROR method is listed more than once
+trait Tr4 {
+    fn a() {}
+}
+
+#[rustc_must_implement_one_of(a, "b")]
+//~^ ERROR expected a method name
+trait Tr5 {
+    fn a() {}
+    fn b() {}
+}
+
+fn main() {}
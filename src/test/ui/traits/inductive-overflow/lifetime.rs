@@ -15,8 +15,9 @@ impl<'a> Y for C<'a> {
 struct C<'a>(&'a ());
 struct X<T: Y>(T::P);
 
-impl<T: NotAuto> NotAuto for Box<T> {}
+impl<T: NotAuto> NotAuto for Box<T> {} //~ NOTE: requires
 impl<T: Y> NotAuto for X<T> where T::P: NotAuto {} //~ NOTE: required
+//~^ NOTE: requires
 impl<'a> NotAuto for C<'a> {}
 
 fn is_send<S: NotAuto>() {}
@@ -25,7 +26,8 @@ fn is_send<S: NotAuto>() {}
 fn main() {
     // Should only be a few notes.
     is_send::<X<C<'static>>>();
-    //~^ ERROR overflow evaluating
+    //~^ ERROR depends on itself
     //~| 2 redundant requirements hidden
     //~| required because of
+    //~| NOTE: to break the cycle
 }
@@ -16,5 +16,5 @@ fn is_ee<T: Combo>(t: T) {
 
 fn main() {
     is_ee(4);
-    //~^ ERROR overflow evaluating the requirement `{integer}: Tweedle
+    //~^ ERROR the requirement `{integer}: Tweedledee` depends on itself
 }
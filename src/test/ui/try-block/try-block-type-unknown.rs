@@ -0,0 +1,14 @@
+// compile-flags: --edition 2018
+
+#![feature(try_blocks)]
+
+fn foo() -> Option<()> { Some(()) }
+
+fn main() {
+    let x = try {
+        foo()?;
+        42
+    };
+    x.is_some();
+    //~^ ERROR type annotations needed
+}
@@ -4,7 +4,7 @@
 
 fn result_to_result() -> Result<u64, u8> {
     Ok(Err(123_i32)?)
-    //~^ ERROR `?` couldn't convert the error to `u8`
+    //~^ ERROR `?` couldn't convert the error from `i32` to `u8`
 }
 
 fn option_to_result() -> Result<u64, String> {
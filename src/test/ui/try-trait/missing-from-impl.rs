@@ -0,0 +1,41 @@
+// Checks the suggestions for `?` when the error can't be converted with `From`.
+
+struct ParseError;
+
+struct IoError;
+
+struct LowLevelError;
+
+struct AppError;
+
+impl From<IoError> for AppError {
+    fn from(_: IoError) -> Self {
+        AppError
+    }
+}
+
+impl From<LowLevelError> for IoError {
+    fn from(_: LowLevelError) -> Self {
+        IoError
+    }
+}
+
+fn parse() -> Result<(), ParseError> {
+    Err(ParseError)
+}
+
+fn low_level() -> Result<(), LowLevelError> {
+    Err(LowLevelError)
+}
+
+fn run() -> Result<(), AppError> {
+    parse()?;
+    //~^ ERROR `?` couldn't convert the error from `ParseError` to `AppError`
+    low_level()?;
+    //~^ ERROR `?` couldn't convert the error from `LowLevelError` to `AppError`
+    Ok(())
+}
+
+fn main() {
+    let _ = run();
+}
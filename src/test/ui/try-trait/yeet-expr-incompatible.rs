@@ -0,0 +1,13 @@
+#![feature(yeet_expr)]
+
+fn yeet_in_unit_fn() {
+    do yeet 4
+    //~^ ERROR `do yeet` can only be used in a function that can absorb its residual
+}
+
+fn yeet_value_in_option_fn() -> Option<u8> {
+    do yeet "error"
+    //~^ ERROR `do yeet` can only be used in a function that can absorb its residual
+}
+
+fn main() {}
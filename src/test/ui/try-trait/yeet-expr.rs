@@ -0,0 +1,37 @@
+// run-pass
+// compile-flags: --edition 2018
+
+#![feature(try_blocks)]
+#![feature(yeet_expr)]
+
+fn yeet_no_expr() -> Option<String> {
+    do yeet
+}
+
+fn yeet_error(x: u8) -> Result<String, u64> {
+    if x > 10 {
+        do yeet x
+    }
+    Ok(x.to_string())
+}
+
+fn yeet_in_try_block(x: i32) -> i64 {
+    let r: Result<i32, i64> = try {
+        if x < 0 {
+            do yeet x
+        }
+        x * 2
+    };
+    match r {
+        Ok(v) => v as i64,
+        Err(e) => e - 1,
+    }
+}
+
+fn main() {
+    assert_eq!(yeet_no_expr(), None);
+    assert_eq!(yeet_error(3), Ok("3".to_string()));
+    assert_eq!(yeet_error(42), Err(42));
+    assert_eq!(yeet_in_try_block(4), 8);
+    assert_eq!(yeet_in_try_block(-4), -5);
+}
@@ -0,0 +1,6 @@
+// check-pass
+// compile-flags: -Zunpretty=typeck-coercions
+
+fn main() {
+    let _f: fn() = main;
+}
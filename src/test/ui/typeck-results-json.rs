@@ -0,0 +1,7 @@
+// check-pass
+// compile-flags: -Zunpretty=typeck-results-json
+
+fn main() {
+    let v: &[u8] = &[1];
+    v.len();
+}
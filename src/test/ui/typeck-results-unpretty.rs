@@ -0,0 +1,4 @@
+// check-pass
+// compile-flags: -Zunpretty=typeck-results
+
+fn main() {}
@@ -0,0 +1,27 @@
+// Check that assigning a value of the wrong type to a local points at the
+// type annotation that forced the local's type.
+
+fn param(mut count: u32) {
+    count = "three"; //~ ERROR mismatched types
+    let _ = count;
+}
+
+fn main() {
+    let mut name: String = String::new();
+    name = 42; //~ ERROR mismatched types
+
+    let closure = |mut flag: bool| {
+        flag = 1; //~ ERROR mismatched types
+        flag
+    };
+    closure(true);
+
+    // No annotation, so there is nothing to point at.
+    let mut inferred = 0u8;
+    inferred = 'c'; //~ ERROR mismatched types
+
+    // Not a simple binding either.
+    let (mut a, _b): (i32, i32) = (1, 2);
+    a = (); //~ ERROR mismatched types
+    let _ = (name, inferred, a);
+}
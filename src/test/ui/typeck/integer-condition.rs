@@ -0,0 +1,13 @@
+// Integers used as conditions suggest comparing them with zero, also when the condition is
+// buried in a macro expansion like `assert!` or `matches!`.
+
+fn main() {
+    let x = 3;
+    if x {} //~ ERROR mismatched types
+    while 1 {} //~ ERROR mismatched types
+    assert!(x); //~ ERROR mismatched types
+    assert!(x && true); //~ ERROR mismatched types
+    debug_assert!(1, "one"); //~ ERROR mismatched types
+    let _ = matches!(Some(2), Some(_) if x); //~ ERROR mismatched types
+    let _: bool = x; //~ ERROR mismatched types
+}
@@ -0,0 +1,8 @@
+// check-pass
+// compile-flags: -Z typeck-stats
+// normalize-stderr-test " +[0-9]+" -> " N"
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let _sum: i32 = v.iter().map(|x| x * 2).sum();
+}
@@ -0,0 +1,19 @@
+// Inferring the return type of functions that call each other, or themselves, must not
+// cause a query cycle; the callees are treated as returning an error type during recovery.
+
+fn ping(n: u32) -> _ {
+//~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+    if n == 0 { 0 } else { pong(n - 1) }
+}
+
+fn pong(n: u32) -> _ {
+//~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+    if n == 0 { 1 } else { ping(n - 1) }
+}
+
+fn countdown(n: u32) -> _ {
+//~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+    if n == 0 { true } else { countdown(n - 1) }
+}
+
+fn main() {}
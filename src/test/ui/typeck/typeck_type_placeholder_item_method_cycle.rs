@@ -0,0 +1,23 @@
+// Inferring the return type of methods that call each other, or themselves, must not cause a
+// query cycle either.
+
+struct Counter;
+
+impl Counter {
+    fn ping(&self, n: u32) -> _ {
+    //~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+        if n == 0 { 0 } else { self.pong(n - 1) }
+    }
+
+    fn pong(&self, n: u32) -> _ {
+    //~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+        if n == 0 { 1 } else { self.ping(n - 1) }
+    }
+
+    fn countdown(&self, n: u32) -> _ {
+    //~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+        if n == 0 { true } else { self.countdown(n - 1) }
+    }
+}
+
+fn main() {}
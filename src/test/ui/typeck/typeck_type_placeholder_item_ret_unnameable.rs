@@ -0,0 +1,32 @@
+// Check that inferred return types that can't be named directly are suggested as `impl Trait`,
+// or boxed where `impl Trait` isn't allowed.
+
+fn counter() -> _ {
+//~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+    let mut n = 0;
+    move || {
+        n += 1;
+        n
+    }
+}
+
+fn adder(x: u8) -> _ {
+//~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+    move |y: u8| x + y
+}
+
+fn all_numbers() -> _ {
+//~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+    numbers()
+}
+
+fn curried_adder() -> _ {
+//~^ ERROR the type placeholder `_` is not allowed within types on item signatures
+    |x: u8| move |y: u8| x + y
+}
+
+fn numbers() -> impl Iterator<Item = u32> {
+    0..10
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+// Checks the diagnostics for manual impls of the `Fn*` traits that don't agree with each other.
+
+#![feature(fn_traits, unboxed_closures)]
+
+struct MissingFnMut;
+
+impl FnOnce<(i32,)> for MissingFnMut {
+    type Output = i32;
+    extern "rust-call" fn call_once(self, (x,): (i32,)) -> i32 {
+        x
+    }
+}
+
+impl Fn<(i32,)> for MissingFnMut {
+    //~^ ERROR expected a `FnMut<(i32,)>` closure, found `MissingFnMut`
+    extern "rust-call" fn call(&self, (x,): (i32,)) -> i32 {
+        x
+    }
+}
+
+struct WrongOutput;
+
+impl FnOnce<()> for WrongOutput {
+    type Output = i32;
+    extern "rust-call" fn call_once(self, _: ()) -> i32 {
+        0
+    }
+}
+
+impl FnMut<()> for WrongOutput {
+    extern "rust-call" fn call_mut(&mut self, _: ()) -> i64 {
+        //~^ ERROR method `call_mut` has an incompatible type for trait
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// check-pass
+
+#![feature(uninhabited_coercions)]
+
+use std::convert::Infallible;
+
+enum Void {}
+
+fn absurd(v: Void) -> u32 {
+    v //~ WARN reading a value of the uninhabited type `Void`
+}
+
+fn unwrap<T>(r: Result<T, Infallible>) -> T {
+    match r {
+        Ok(t) => t,
+        Err(e) => e, //~ WARN reading a value of the uninhabited type `Infallible`
+    }
+}
+
+// `Void` still coerces to itself without an adjustment.
+fn identity(v: Void) -> Void {
+    v
+}
+
+fn main() {}
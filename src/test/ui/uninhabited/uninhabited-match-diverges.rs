@@ -0,0 +1,23 @@
+#![feature(exhaustive_patterns, never_type)]
+#![deny(unreachable_code)]
+
+enum Void {}
+
+// The `match` has type `!`, so `n` can be returned as a `String`.
+fn absurd(r: Result<Void, !>) -> String {
+    let n = match r {
+        Ok(_) => 0,
+        Err(_) => 1,
+    };
+    n //~ ERROR unreachable expression
+}
+
+// `Option<Void>` is inhabited, so the `match` has the type of its arms.
+fn inhabited(o: Option<Void>) -> u32 {
+    let n = match o {
+        None => 0,
+    };
+    n
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// Test that the parameters of generic associated types are invariant.
+
+#![feature(rustc_attrs, generic_associated_types)]
+#![allow(incomplete_features)]
+
+trait Lending {
+    #[rustc_variance]
+    type Item<'a, T>; //~ ERROR [o, o, o]
+}
+
+struct Slice;
+
+impl Lending for Slice {
+    #[rustc_variance]
+    type Item<'a, T> = &'a [T]; //~ ERROR [o, o]
+}
+
+fn main() {}
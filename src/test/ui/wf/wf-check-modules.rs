@@ -0,0 +1,21 @@
+// Check that items in nested modules and in function bodies are checked for well-formedness
+// exactly once when checking one module at a time.
+// compile-flags: -Z verify-wf-check-coverage
+
+#![allow(dead_code)]
+
+struct IsCopy<T: Copy> {
+    value: T,
+}
+
+mod inner {
+    pub struct InModule<A> {
+        data: super::IsCopy<A>, //~ ERROR E0277
+    }
+}
+
+fn main() {
+    struct InBody<B> {
+        data: IsCopy<B>, //~ ERROR E0277
+    }
+}
@@ -191,6 +191,13 @@ pub(crate) fn format_expr(
                 Some("yield".to_string())
             }
         }
+        ast::ExprKind::Yeet(ref opt_expr) => {
+            if let Some(ref expr) = *opt_expr {
+                rewrite_unary_prefix(context, "do yeet ", &**expr, shape)
+            } else {
+                Some("do yeet".to_string())
+            }
+        }
         ast::ExprKind::Closure(capture, ref is_async, movability, ref fn_decl, ref body, _) => {
             closures::rewrite_closure(
                 capture, is_async, movability, fn_decl, body, expr.span, context, shape,
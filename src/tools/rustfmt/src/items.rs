@@ -52,14 +52,19 @@ fn rewrite(&self, context: &RewriteContext<'_>, shape: Shape) -> Option<String>
             return None;
         }
 
+        let let_kw = if self.super_.is_some() {
+            "super let "
+        } else {
+            "let "
+        };
         let attrs_str = self.attrs.rewrite(context, shape)?;
         let mut result = if attrs_str.is_empty() {
-            "let ".to_owned()
+            let_kw.to_owned()
         } else {
             combine_strs_with_missing_comments(
                 context,
                 &attrs_str,
-                "let ",
+                let_kw,
                 mk_sp(
                     self.attrs.last().map(|a| a.span.hi()).unwrap(),
                     self.span.lo(),
@@ -69,8 +74,7 @@ fn rewrite(&self, context: &RewriteContext<'_>, shape: Shape) -> Option<String>
             )?
         };
 
-        // 4 = "let ".len()
-        let pat_shape = shape.offset_left(4)?;
+        let pat_shape = shape.offset_left(let_kw.len())?;
         // 1 = ;
         let pat_shape = pat_shape.sub_width(1)?;
         let pat_str = self.pat.rewrite(context, pat_shape)?;
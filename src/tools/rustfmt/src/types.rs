@@ -179,7 +179,8 @@ fn rewrite(&self, context: &RewriteContext<'_>, shape: Shape) -> Option<String>
                     ast::AssocTyConstraintKind::Bound { .. } => {
                         format!("{}: ", rewrite_ident(context, assoc_ty_constraint.ident))
                     }
-                    ast::AssocTyConstraintKind::Equality { .. } => {
+                    ast::AssocTyConstraintKind::Equality { .. }
+                    | ast::AssocTyConstraintKind::ConstEquality { .. } => {
                         match context.config.type_punctuation_density() {
                             TypeDensity::Wide => {
                                 format!("{} = ", rewrite_ident(context, assoc_ty_constraint.ident))
@@ -206,6 +207,7 @@ impl Rewrite for ast::AssocTyConstraintKind {
     fn rewrite(&self, context: &RewriteContext<'_>, shape: Shape) -> Option<String> {
         match self {
             ast::AssocTyConstraintKind::Equality { ty } => ty.rewrite(context, shape),
+            ast::AssocTyConstraintKind::ConstEquality { value } => value.rewrite(context, shape),
             ast::AssocTyConstraintKind::Bound { bounds } => bounds.rewrite(context, shape),
         }
     }